@@ -0,0 +1,323 @@
+//! Credential store backed by Docker and Podman auth files.
+//!
+//! Docker (`~/.docker/config.json`) and Podman (`~/.config/containers/auth.json`)
+//! share the same JSON format:
+//!
+//! ```json
+//! {
+//!   "auths": {
+//!     "https://index.docker.io/v1/": { "auth": "dXNlcjpwYXNz" },
+//!     "ghcr.io": { "identitytoken": "..." }
+//!   },
+//!   "credHelpers": { "gcr.io": "gcloud" },
+//!   "credsStore": "desktop"
+//! }
+//! ```
+//!
+//! Credential helpers are executed as `docker-credential-<name> get`, receiving
+//! the server URL on stdin and printing a JSON document on stdout.
+//!
+//! The store is read-only: credentials are managed with `docker login` or
+//! `podman login`.
+
+use crate::auth::{CredentialStore, Credentials};
+use crate::error::{Result, RexError};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Key under which Docker stores Docker Hub credentials.
+const DOCKER_HUB_AUTH_KEY: &str = "https://index.docker.io/v1/";
+
+/// Hostnames that all refer to Docker Hub.
+const DOCKER_HUB_HOSTS: &[&str] = &["docker.io", "index.docker.io", "registry-1.docker.io"];
+
+/// Username returned by credential helpers when the secret is an identity token.
+const IDENTITY_TOKEN_USERNAME: &str = "<token>";
+
+/// Contents of a Docker `config.json` or Podman `auth.json` file.
+#[derive(Debug, Default, Deserialize)]
+struct AuthFile {
+    #[serde(default)]
+    auths: HashMap<String, AuthEntry>,
+    #[serde(default, rename = "credHelpers")]
+    cred_helpers: HashMap<String, String>,
+    #[serde(default, rename = "credsStore")]
+    creds_store: Option<String>,
+}
+
+/// A single entry in the `auths` map.
+#[derive(Debug, Default, Deserialize)]
+struct AuthEntry {
+    /// base64("username:password")
+    #[serde(default)]
+    auth: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    /// OAuth2 refresh token issued by the registry's token service
+    #[serde(default)]
+    identitytoken: Option<String>,
+}
+
+/// Output of `docker-credential-<name> get`.
+#[derive(Debug, Deserialize)]
+struct HelperResponse {
+    #[serde(rename = "Username", default)]
+    username: String,
+    #[serde(rename = "Secret", default)]
+    secret: String,
+}
+
+/// Read-only credential store for Docker and Podman auth files.
+///
+/// Lookups follow Docker's precedence: a registry-specific `credHelpers` entry,
+/// then the global `credsStore`, then inline `auths` entries. Registry names are
+/// compared by host, so `https://ghcr.io`, `ghcr.io` and `ghcr.io/v2/` all match.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::auth::{CredentialStore, DockerConfigStore};
+/// use std::path::PathBuf;
+///
+/// # fn example() -> librex::error::Result<()> {
+/// let store = DockerConfigStore::new(PathBuf::from("/home/user/.docker/config.json"))?;
+/// if let Some(creds) = store.get("ghcr.io")? {
+///     println!("Found credentials for ghcr.io");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DockerConfigStore {
+    path: PathBuf,
+    file: AuthFile,
+    helper_dir: Option<PathBuf>,
+}
+
+impl DockerConfigStore {
+    /// Loads a Docker or Podman auth file.
+    ///
+    /// A missing file yields an empty store.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to `config.json` or `auth.json`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let file = if path.exists() {
+            let contents = fs::read_to_string(&path).map_err(|e| {
+                RexError::config_with_source("Failed to read auth file", path.to_str(), e)
+            })?;
+            serde_json::from_str(&contents).map_err(|e| {
+                RexError::config_with_source("Failed to parse auth file", path.to_str(), e)
+            })?
+        } else {
+            AuthFile::default()
+        };
+
+        Ok(Self {
+            path,
+            file,
+            helper_dir: None,
+        })
+    }
+
+    /// Looks up credential helpers in `dir` instead of on `PATH`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::auth::DockerConfigStore;
+    /// use std::path::PathBuf;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let store = DockerConfigStore::new(PathBuf::from("config.json"))?
+    ///     .with_helper_dir(PathBuf::from("/opt/docker/bin"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_helper_dir(mut self, dir: PathBuf) -> Self {
+        self.helper_dir = Some(dir);
+        self
+    }
+
+    /// Returns the path of the auth file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Reduces a registry name or URL to a lowercase host for comparison.
+    ///
+    /// All Docker Hub aliases map to `index.docker.io`.
+    fn normalize_host(registry: &str) -> String {
+        let host = registry
+            .trim()
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let host = host.split('/').next().unwrap_or(host).to_lowercase();
+
+        if DOCKER_HUB_HOSTS.contains(&host.as_str()) {
+            "index.docker.io".to_string()
+        } else {
+            host
+        }
+    }
+
+    /// Finds the value in `map` whose key refers to the same host as `host`.
+    fn find_by_host<'a, T>(map: &'a HashMap<String, T>, host: &str) -> Option<(&'a String, &'a T)> {
+        map.iter()
+            .find(|(key, _)| Self::normalize_host(key) == host)
+    }
+
+    /// Decodes an `auths` entry.
+    fn decode_entry(entry: &AuthEntry) -> Result<Option<Credentials>> {
+        if let Some(token) = entry.identitytoken.as_deref().filter(|t| !t.is_empty()) {
+            return Ok(Some(Credentials::identity_token(token)));
+        }
+
+        if let Some(auth) = entry.auth.as_deref().filter(|a| !a.is_empty()) {
+            use base64::{Engine as _, engine::general_purpose};
+            let decoded = general_purpose::STANDARD
+                .decode(auth)
+                .map_err(|e| RexError::validation_with_source("Failed to decode auth entry", e))?;
+            let decoded = String::from_utf8(decoded)
+                .map_err(|e| RexError::validation_with_source("Invalid auth entry encoding", e))?;
+            let (username, password) = decoded
+                .split_once(':')
+                .ok_or_else(|| RexError::validation("Auth entry is not in user:password form"))?;
+            return Ok(Some(Credentials::basic(username, password)));
+        }
+
+        if let (Some(username), Some(password)) = (&entry.username, &entry.password) {
+            return Ok(Some(Credentials::basic(username, password)));
+        }
+
+        Ok(None)
+    }
+
+    /// Runs `docker-credential-<helper> get` for a server URL.
+    ///
+    /// A helper that is not installed or reports no credentials yields `None`.
+    fn run_helper(&self, helper: &str, server_url: &str) -> Result<Option<Credentials>> {
+        let program = format!("docker-credential-{}", helper);
+        let program = match &self.helper_dir {
+            Some(dir) => dir.join(program),
+            None => PathBuf::from(program),
+        };
+
+        let mut child = match Command::new(&program)
+            .arg("get")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(RexError::config_with_source(
+                    "Failed to run credential helper",
+                    program.to_str(),
+                    e,
+                ));
+            }
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(server_url.as_bytes()).map_err(|e| {
+                RexError::config_with_source(
+                    "Failed to write to credential helper",
+                    program.to_str(),
+                    e,
+                )
+            })?;
+        }
+
+        let output = child.wait_with_output().map_err(|e| {
+            RexError::config_with_source("Credential helper failed", program.to_str(), e)
+        })?;
+
+        // Helpers exit non-zero when they hold no credentials for the server
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let response: HelperResponse = serde_json::from_slice(&output.stdout).map_err(|e| {
+            RexError::config_with_source(
+                "Failed to parse credential helper output",
+                program.to_str(),
+                e,
+            )
+        })?;
+
+        if response.secret.is_empty() {
+            Ok(None)
+        } else if response.username == IDENTITY_TOKEN_USERNAME {
+            Ok(Some(Credentials::identity_token(response.secret)))
+        } else {
+            Ok(Some(Credentials::basic(response.username, response.secret)))
+        }
+    }
+}
+
+impl CredentialStore for DockerConfigStore {
+    fn store(&mut self, _registry: &str, _credentials: &Credentials) -> Result<()> {
+        Err(RexError::config(
+            "Docker and Podman auth files are read-only; use 'docker login' or 'podman login'",
+            self.path.to_str(),
+        ))
+    }
+
+    fn get(&self, registry: &str) -> Result<Option<Credentials>> {
+        let host = Self::normalize_host(registry);
+
+        if let Some((server, helper)) = Self::find_by_host(&self.file.cred_helpers, &host) {
+            return self.run_helper(helper, server);
+        }
+
+        if let Some(ref helper) = self.file.creds_store {
+            let server = match Self::find_by_host(&self.file.auths, &host) {
+                Some((key, _)) => key.clone(),
+                None if host == "index.docker.io" => DOCKER_HUB_AUTH_KEY.to_string(),
+                None => host.clone(),
+            };
+            if let Some(creds) = self.run_helper(helper, &server)? {
+                return Ok(Some(creds));
+            }
+        }
+
+        match Self::find_by_host(&self.file.auths, &host) {
+            Some((_, entry)) => Self::decode_entry(entry),
+            None => Ok(None),
+        }
+    }
+
+    fn remove(&mut self, _registry: &str) -> Result<()> {
+        Err(RexError::config(
+            "Docker and Podman auth files are read-only; use 'docker logout' or 'podman logout'",
+            self.path.to_str(),
+        ))
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let mut registries: Vec<String> = self
+            .file
+            .auths
+            .keys()
+            .chain(self.file.cred_helpers.keys())
+            .cloned()
+            .collect();
+        registries.sort();
+        registries.dedup();
+        Ok(registries)
+    }
+}
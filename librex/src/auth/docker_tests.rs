@@ -0,0 +1,258 @@
+use super::{CredentialStore, Credentials, DockerConfigStore};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write_config(dir: &Path, contents: &str) -> std::path::PathBuf {
+    let path = dir.join("config.json");
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Writes a fake `docker-credential-<name>` helper that knows one server.
+#[cfg(unix)]
+fn write_helper(dir: &Path, name: &str, server: &str, username: &str, secret: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join(format!("docker-credential-{}", name));
+    let script = format!(
+        "#!/bin/sh\n\
+         [ \"$1\" = \"get\" ] || exit 2\n\
+         read server\n\
+         if [ \"$server\" = \"{server}\" ]; then\n\
+           echo '{{\"ServerURL\":\"{server}\",\"Username\":\"{username}\",\"Secret\":\"{secret}\"}}'\n\
+           exit 0\n\
+         fi\n\
+         echo 'credentials not found in native keychain'\n\
+         exit 1\n"
+    );
+    fs::write(&path, script).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn test_docker_store_missing_file_is_empty() {
+    let temp_dir = tempdir().unwrap();
+    let store = DockerConfigStore::new(temp_dir.path().join("config.json")).unwrap();
+
+    assert!(store.list().unwrap().is_empty());
+    assert_eq!(store.get("ghcr.io").unwrap(), None);
+}
+
+#[test]
+fn test_docker_store_invalid_json() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(temp_dir.path(), "{not json");
+
+    assert!(DockerConfigStore::new(path).is_err());
+}
+
+#[test]
+fn test_docker_store_decodes_auth_entry() {
+    let temp_dir = tempdir().unwrap();
+    // dXNlcjpwYXNz = base64("user:pass")
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"registry.example.com":{"auth":"dXNlcjpwYXNz"}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+}
+
+#[test]
+fn test_docker_store_password_with_colon() {
+    let temp_dir = tempdir().unwrap();
+    // base64("user:pa:ss")
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"registry.example.com":{"auth":"dXNlcjpwYTpzcw=="}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pa:ss"))
+    );
+}
+
+#[test]
+fn test_docker_store_username_password_fields() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"registry.example.com":{"username":"user","password":"pass"}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+}
+
+#[test]
+fn test_docker_store_identity_token() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"ghcr.io":{"auth":"","identitytoken":"refresh-token"}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(
+        store.get("ghcr.io").unwrap(),
+        Some(Credentials::identity_token("refresh-token"))
+    );
+}
+
+#[test]
+fn test_docker_store_matches_by_host() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"http://localhost:5000/v2/":{"auth":"dXNlcjpwYXNz"}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    let expected = Some(Credentials::basic("user", "pass"));
+    assert_eq!(store.get("localhost:5000").unwrap(), expected);
+    assert_eq!(store.get("http://localhost:5000").unwrap(), expected);
+    assert_eq!(store.get("https://LOCALHOST:5000/").unwrap(), expected);
+    assert_eq!(store.get("localhost:5001").unwrap(), None);
+}
+
+#[test]
+fn test_docker_store_docker_hub_aliases() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"https://index.docker.io/v1/":{"auth":"dXNlcjpwYXNz"}}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    let expected = Some(Credentials::basic("user", "pass"));
+    assert_eq!(store.get("docker.io").unwrap(), expected);
+    assert_eq!(store.get("https://registry-1.docker.io").unwrap(), expected);
+}
+
+#[test]
+fn test_docker_store_empty_entry_has_no_credentials() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(temp_dir.path(), r#"{"auths":{"ghcr.io":{}}}"#);
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(store.get("ghcr.io").unwrap(), None);
+}
+
+#[test]
+fn test_docker_store_is_read_only() {
+    let temp_dir = tempdir().unwrap();
+    let mut store = DockerConfigStore::new(temp_dir.path().join("config.json")).unwrap();
+
+    assert!(
+        store
+            .store("ghcr.io", &Credentials::basic("user", "pass"))
+            .is_err()
+    );
+    assert!(store.remove("ghcr.io").is_err());
+}
+
+#[test]
+fn test_docker_store_list() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"ghcr.io":{},"quay.io":{}},"credHelpers":{"gcr.io":"gcloud"}}"#,
+    );
+
+    let store = DockerConfigStore::new(path).unwrap();
+    assert_eq!(store.list().unwrap(), vec!["gcr.io", "ghcr.io", "quay.io"]);
+}
+
+#[test]
+fn test_docker_store_missing_helper_falls_back_to_auths() {
+    let temp_dir = tempdir().unwrap();
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"ghcr.io":{"auth":"dXNlcjpwYXNz"}},"credsStore":"does-not-exist"}"#,
+    );
+
+    let store = DockerConfigStore::new(path)
+        .unwrap()
+        .with_helper_dir(temp_dir.path().to_path_buf());
+    assert_eq!(
+        store.get("ghcr.io").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_docker_store_cred_helper() {
+    let temp_dir = tempdir().unwrap();
+    write_helper(
+        temp_dir.path(),
+        "test",
+        "registry.example.com",
+        "helper-user",
+        "helper-pass",
+    );
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"auths":{"registry.example.com":{"auth":"dXNlcjpwYXNz"}},"credHelpers":{"registry.example.com":"test"}}"#,
+    );
+
+    let store = DockerConfigStore::new(path)
+        .unwrap()
+        .with_helper_dir(temp_dir.path().to_path_buf());
+
+    // credHelpers take precedence over inline auths
+    assert_eq!(
+        store.get("https://registry.example.com").unwrap(),
+        Some(Credentials::basic("helper-user", "helper-pass"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_docker_store_creds_store_identity_token() {
+    let temp_dir = tempdir().unwrap();
+    write_helper(
+        temp_dir.path(),
+        "desktop",
+        "https://index.docker.io/v1/",
+        "<token>",
+        "refresh-token",
+    );
+    let path = write_config(temp_dir.path(), r#"{"credsStore":"desktop"}"#);
+
+    let store = DockerConfigStore::new(path)
+        .unwrap()
+        .with_helper_dir(temp_dir.path().to_path_buf());
+
+    assert_eq!(
+        store.get("docker.io").unwrap(),
+        Some(Credentials::identity_token("refresh-token"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_docker_store_helper_without_credentials() {
+    let temp_dir = tempdir().unwrap();
+    write_helper(temp_dir.path(), "test", "other.example.com", "u", "p");
+    let path = write_config(
+        temp_dir.path(),
+        r#"{"credHelpers":{"registry.example.com":"test"}}"#,
+    );
+
+    let store = DockerConfigStore::new(path)
+        .unwrap()
+        .with_helper_dir(temp_dir.path().to_path_buf());
+
+    assert_eq!(store.get("registry.example.com").unwrap(), None);
+}
@@ -9,13 +9,21 @@
 
 use crate::error::{Result, RexError};
 
+mod docker;
 mod store;
-pub use store::{CredentialStore, FileCredentialStore};
+mod token;
+pub use docker::DockerConfigStore;
+pub use store::{ChainedCredentialStore, CredentialStore, FileCredentialStore};
+pub use token::{TokenCache, TokenResponse};
 
+#[cfg(test)]
+mod docker_tests;
 #[cfg(test)]
 mod store_tests;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod token_tests;
 
 /// Credentials for registry authentication.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -36,6 +44,15 @@ pub enum Credentials {
         /// The bearer token
         token: String,
     },
+
+    /// OAuth2 refresh token (Docker "identity token")
+    ///
+    /// Identity tokens are never sent to the registry directly; they are
+    /// exchanged for an access token at the registry's token service.
+    IdentityToken {
+        /// The refresh token
+        token: String,
+    },
 }
 
 impl Credentials {
@@ -83,8 +100,26 @@ impl Credentials {
         }
     }
 
+    /// Creates identity token (OAuth2 refresh token) credentials.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::auth::Credentials;
+    ///
+    /// let creds = Credentials::identity_token("refresh123");
+    /// assert_eq!(creds.to_header_value(), None);
+    /// ```
+    pub fn identity_token(token: impl Into<String>) -> Self {
+        Self::IdentityToken {
+            token: token.into(),
+        }
+    }
+
     /// Returns the Authorization header value for these credentials.
     ///
+    /// Anonymous and identity token credentials have no header value.
+    ///
     /// # Examples
     ///
     /// ```
@@ -104,6 +139,7 @@ impl Credentials {
                 Some(format!("Basic {}", encoded))
             }
             Self::Bearer { token } => Some(format!("Bearer {}", token)),
+            Self::IdentityToken { .. } => None,
         }
     }
 }
@@ -151,18 +187,12 @@ impl AuthChallenge {
         let mut service = None;
         let mut scope = None;
 
-        for param in params.split(',') {
-            let param = param.trim();
-            if let Some((key, value)) = param.split_once('=') {
-                let key = key.trim();
-                let value = value.trim().trim_matches('"');
-
-                match key {
-                    "realm" => realm = Some(value.to_string()),
-                    "service" => service = Some(value.to_string()),
-                    "scope" => scope = Some(value.to_string()),
-                    _ => {} // Ignore unknown parameters
-                }
+        for (key, value) in Self::split_params(params) {
+            match key.as_str() {
+                "realm" => realm = Some(value),
+                "service" => service = Some(value),
+                "scope" => scope = Some(value),
+                _ => {} // Ignore unknown parameters
             }
         }
 
@@ -177,4 +207,49 @@ impl AuthChallenge {
             scope,
         })
     }
+
+    /// Returns true if this is a Bearer token challenge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::auth::AuthChallenge;
+    ///
+    /// let challenge = AuthChallenge::parse(r#"Bearer realm="https://auth.example.com/token""#).unwrap();
+    /// assert!(challenge.is_bearer());
+    /// ```
+    pub fn is_bearer(&self) -> bool {
+        self.scheme.eq_ignore_ascii_case("bearer")
+    }
+
+    /// Splits challenge parameters into key/value pairs.
+    ///
+    /// Commas inside quoted values are preserved, so scopes such as
+    /// `repository:alpine:pull,push` survive intact.
+    fn split_params(params: &str) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        let mut current = String::new();
+        let mut in_quotes = false;
+
+        for c in params.chars().chain(std::iter::once(',')) {
+            match c {
+                '"' => {
+                    in_quotes = !in_quotes;
+                    current.push(c);
+                }
+                ',' if !in_quotes => {
+                    if let Some((key, value)) = current.split_once('=') {
+                        pairs.push((
+                            key.trim().to_string(),
+                            value.trim().trim_matches('"').to_string(),
+                        ));
+                    }
+                    current.clear();
+                }
+                _ => current.push(c),
+            }
+        }
+
+        pairs
+    }
 }
@@ -4,6 +4,23 @@
 //! registry credentials. The file-based implementation stores credentials in
 //! a TOML file with restricted permissions (0600).
 //!
+//! Each entry is tagged with its kind:
+//!
+//! ```toml
+//! ["registry.example.com"]
+//! type = "basic"
+//! username = "user"
+//! password = "cGFzc3dvcmQ="  # base64 encoded
+//!
+//! ["ghcr.io"]
+//! type = "bearer"
+//! token = "dG9rZW4="         # base64 encoded
+//! expires_at = 1767225600    # optional, seconds since the Unix epoch
+//! ```
+//!
+//! Files written before entries were tagged (plain `username`/`password`
+//! tables) are migrated to the tagged format when loaded.
+//!
 //! Future implementations could include OS keyring integration.
 
 use crate::auth::Credentials;
@@ -12,6 +29,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Trait for storing and retrieving registry credentials.
 ///
@@ -69,18 +87,79 @@ pub trait CredentialStore {
 }
 
 /// Stored credential representation for serialization.
+///
+/// Secrets are base64 encoded. Token variants may carry an expiry; expired
+/// entries are treated as absent.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-struct StoredCredential {
-    /// Username for Basic authentication
-    username: String,
-    /// Password for Basic authentication (base64 encoded)
-    password: String,
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StoredCredential {
+    /// HTTP Basic authentication
+    Basic {
+        /// Username for Basic authentication
+        username: String,
+        /// Password for Basic authentication (base64 encoded)
+        password: String,
+    },
+    /// Static bearer token sent as-is
+    Bearer {
+        /// The bearer token (base64 encoded)
+        token: String,
+        /// Expiry in seconds since the Unix epoch
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+    },
+    /// OAuth2 refresh token (identity token) exchanged at the token service
+    IdentityToken {
+        /// The refresh token (base64 encoded)
+        token: String,
+        /// Expiry in seconds since the Unix epoch
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+    },
+}
+
+impl StoredCredential {
+    /// Returns true if the credential carries an expiry that has passed.
+    fn is_expired(&self) -> bool {
+        let expires_at = match self {
+            Self::Basic { .. } => None,
+            Self::Bearer { expires_at, .. } | Self::IdentityToken { expires_at, .. } => *expires_at,
+        };
+
+        match expires_at {
+            Some(expires_at) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                now >= expires_at
+            }
+            None => false,
+        }
+    }
+}
+
+/// A credentials file entry as found on disk, in either format.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StoredEntry {
+    /// Tagged entry (current format)
+    Current(StoredCredential),
+    /// Untagged `username`/`password` entry written by earlier versions
+    Legacy {
+        /// Username for Basic authentication
+        username: String,
+        /// Password for Basic authentication (base64 encoded)
+        password: String,
+    },
 }
 
 /// File-based credential store implementation.
 ///
 /// Stores credentials in a TOML file with restricted permissions (0600).
-/// Credentials are base64 encoded for basic obfuscation.
+/// Credentials are base64 encoded for basic obfuscation. Basic credentials,
+/// static bearer tokens and identity tokens are supported; files in the
+/// legacy untagged format are migrated on load.
 ///
 /// # Security Note
 ///
@@ -122,7 +201,8 @@ impl FileCredentialStore {
     /// Creates a new file-based credential store.
     ///
     /// If the file exists, it will be loaded. If not, an empty store is created.
-    /// The parent directory will be created if it doesn't exist.
+    /// The parent directory will be created if it doesn't exist. A file in the
+    /// legacy format is rewritten in the tagged format.
     ///
     /// # Arguments
     ///
@@ -133,6 +213,7 @@ impl FileCredentialStore {
     /// Returns an error if:
     /// - The parent directory cannot be created
     /// - The file exists but cannot be read or parsed
+    /// - A legacy file cannot be migrated
     /// - File permissions cannot be set
     ///
     /// # Examples
@@ -160,24 +241,107 @@ impl FileCredentialStore {
         }
 
         // Load existing credentials or create empty map
-        let credentials = if path.exists() {
+        let (credentials, migrated) = if path.exists() {
             Self::load_from_file(&path)?
         } else {
-            HashMap::new()
+            (HashMap::new(), false)
         };
 
-        Ok(Self { path, credentials })
+        let store = Self { path, credentials };
+
+        // Persist the migrated format so the file is only converted once
+        if migrated {
+            store.save_to_file()?;
+        }
+
+        Ok(store)
     }
 
     /// Loads credentials from the file.
-    fn load_from_file(path: &PathBuf) -> Result<HashMap<String, StoredCredential>> {
+    ///
+    /// Returns the credentials and whether any legacy entries were migrated.
+    fn load_from_file(path: &PathBuf) -> Result<(HashMap<String, StoredCredential>, bool)> {
         let contents = fs::read_to_string(path).map_err(|e| {
             RexError::config_with_source("Failed to read credentials file", path.to_str(), e)
         })?;
 
-        toml::from_str(&contents).map_err(|e| {
+        let entries: HashMap<String, StoredEntry> = toml::from_str(&contents).map_err(|e| {
             RexError::config_with_source("Failed to parse credentials file", path.to_str(), e)
-        })
+        })?;
+
+        let mut migrated = false;
+        let credentials = entries
+            .into_iter()
+            .map(|(registry, entry)| {
+                let stored = match entry {
+                    StoredEntry::Current(stored) => stored,
+                    StoredEntry::Legacy { username, password } => {
+                        migrated = true;
+                        StoredCredential::Basic { username, password }
+                    }
+                };
+                (registry, stored)
+            })
+            .collect();
+
+        Ok((credentials, migrated))
+    }
+
+    /// Stores credentials that stop being valid at `expires_at`.
+    ///
+    /// Once expired, the entry is treated as absent by [`CredentialStore::get`].
+    /// Expiry only applies to token credentials; it is ignored for Basic
+    /// credentials.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - The registry hostname or identifier
+    /// * `credentials` - The credentials to store
+    /// * `expires_at` - When the credentials expire
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the credentials cannot be encoded or written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::auth::{Credentials, FileCredentialStore};
+    /// use std::path::PathBuf;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let mut store = FileCredentialStore::new(PathBuf::from("credentials.toml"))?;
+    /// let expires_at = SystemTime::now() + Duration::from_secs(3600);
+    /// store.store_with_expiry("ghcr.io", &Credentials::bearer("token"), expires_at)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn store_with_expiry(
+        &mut self,
+        registry: &str,
+        credentials: &Credentials,
+        expires_at: SystemTime,
+    ) -> Result<()> {
+        let expires_at = expires_at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let stored = match Self::encode_credential(credentials)? {
+            StoredCredential::Bearer { token, .. } => StoredCredential::Bearer {
+                token,
+                expires_at: Some(expires_at),
+            },
+            StoredCredential::IdentityToken { token, .. } => StoredCredential::IdentityToken {
+                token,
+                expires_at: Some(expires_at),
+            },
+            basic => basic,
+        };
+
+        self.credentials.insert(registry.to_string(), stored);
+        self.save_to_file()
     }
 
     /// Saves credentials to the file with restricted permissions.
@@ -209,38 +373,52 @@ impl FileCredentialStore {
 
     /// Encodes a credential for storage.
     fn encode_credential(credentials: &Credentials) -> Result<StoredCredential> {
+        use base64::{Engine as _, engine::general_purpose};
+
         match credentials {
-            Credentials::Basic { username, password } => {
-                use base64::{Engine as _, engine::general_purpose};
-                let encoded_password = general_purpose::STANDARD.encode(password);
-                Ok(StoredCredential {
-                    username: username.clone(),
-                    password: encoded_password,
-                })
-            }
+            Credentials::Basic { username, password } => Ok(StoredCredential::Basic {
+                username: username.clone(),
+                password: general_purpose::STANDARD.encode(password),
+            }),
+            Credentials::Bearer { token } => Ok(StoredCredential::Bearer {
+                token: general_purpose::STANDARD.encode(token),
+                expires_at: None,
+            }),
+            Credentials::IdentityToken { token } => Ok(StoredCredential::IdentityToken {
+                token: general_purpose::STANDARD.encode(token),
+                expires_at: None,
+            }),
             Credentials::Anonymous => {
                 Err(RexError::validation("Cannot store anonymous credentials"))
             }
-            Credentials::Bearer { .. } => Err(RexError::validation(
-                "Bearer token storage not yet supported",
-            )),
         }
     }
 
     /// Decodes a stored credential.
     fn decode_credential(stored: &StoredCredential) -> Result<Credentials> {
-        use base64::{Engine as _, engine::general_purpose};
-        let decoded_password = general_purpose::STANDARD
-            .decode(&stored.password)
-            .map_err(|e| RexError::validation_with_source("Failed to decode password", e))?;
+        match stored {
+            StoredCredential::Basic { username, password } => Ok(Credentials::Basic {
+                username: username.clone(),
+                password: Self::decode_secret(password, "password")?,
+            }),
+            StoredCredential::Bearer { token, .. } => Ok(Credentials::Bearer {
+                token: Self::decode_secret(token, "token")?,
+            }),
+            StoredCredential::IdentityToken { token, .. } => Ok(Credentials::IdentityToken {
+                token: Self::decode_secret(token, "token")?,
+            }),
+        }
+    }
 
-        let password = String::from_utf8(decoded_password)
-            .map_err(|e| RexError::validation_with_source("Invalid password encoding", e))?;
+    /// Decodes a base64 encoded secret.
+    fn decode_secret(encoded: &str, what: &str) -> Result<String> {
+        use base64::{Engine as _, engine::general_purpose};
+        let decoded = general_purpose::STANDARD.decode(encoded).map_err(|e| {
+            RexError::validation_with_source(format!("Failed to decode {}", what), e)
+        })?;
 
-        Ok(Credentials::Basic {
-            username: stored.username.clone(),
-            password,
-        })
+        String::from_utf8(decoded)
+            .map_err(|e| RexError::validation_with_source(format!("Invalid {} encoding", what), e))
     }
 }
 
@@ -253,6 +431,7 @@ impl CredentialStore for FileCredentialStore {
 
     fn get(&self, registry: &str) -> Result<Option<Credentials>> {
         match self.credentials.get(registry) {
+            Some(stored) if stored.is_expired() => Ok(None),
             Some(stored) => Ok(Some(Self::decode_credential(stored)?)),
             None => Ok(None),
         }
@@ -267,3 +446,88 @@ impl CredentialStore for FileCredentialStore {
         Ok(self.credentials.keys().cloned().collect())
     }
 }
+
+/// A credential store that consults several stores in order.
+///
+/// Lookups return the first credentials found. Stores that fail (for example a
+/// malformed Docker config) are skipped so that one broken source does not
+/// hide credentials available elsewhere. Writes go to the first store.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::auth::{ChainedCredentialStore, CredentialStore, DockerConfigStore, FileCredentialStore};
+/// use std::path::PathBuf;
+///
+/// # fn example() -> librex::error::Result<()> {
+/// let store = ChainedCredentialStore::new()
+///     .with_store(FileCredentialStore::new(PathBuf::from("credentials.toml"))?)
+///     .with_store(DockerConfigStore::new(PathBuf::from("config.json"))?);
+///
+/// let creds = store.get("ghcr.io")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ChainedCredentialStore {
+    stores: Vec<Box<dyn CredentialStore>>,
+}
+
+impl ChainedCredentialStore {
+    /// Creates an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a store to the end of the chain.
+    pub fn with_store(mut self, store: impl CredentialStore + 'static) -> Self {
+        self.stores.push(Box::new(store));
+        self
+    }
+
+    /// Returns the number of stores in the chain.
+    pub fn len(&self) -> usize {
+        self.stores.len()
+    }
+
+    /// Returns true if the chain has no stores.
+    pub fn is_empty(&self) -> bool {
+        self.stores.is_empty()
+    }
+
+    /// Returns the first store, which receives all writes.
+    fn primary(&mut self) -> Result<&mut Box<dyn CredentialStore>> {
+        self.stores
+            .first_mut()
+            .ok_or_else(|| RexError::config("No credential store configured", None))
+    }
+}
+
+impl CredentialStore for ChainedCredentialStore {
+    fn store(&mut self, registry: &str, credentials: &Credentials) -> Result<()> {
+        self.primary()?.store(registry, credentials)
+    }
+
+    fn get(&self, registry: &str) -> Result<Option<Credentials>> {
+        Ok(self
+            .stores
+            .iter()
+            .find_map(|store| store.get(registry).ok().flatten()))
+    }
+
+    fn remove(&mut self, registry: &str) -> Result<()> {
+        self.primary()?.remove(registry)
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let mut registries: Vec<String> = self
+            .stores
+            .iter()
+            .filter_map(|store| store.list().ok())
+            .flatten()
+            .collect();
+        registries.sort();
+        registries.dedup();
+        Ok(registries)
+    }
+}
@@ -165,23 +165,163 @@ fn test_file_credential_store_store_anonymous_fails() {
 }
 
 #[test]
-fn test_file_credential_store_store_bearer_fails() {
+fn test_file_credential_store_store_and_get_bearer_token() {
     let temp_dir = tempdir().unwrap();
     let path = temp_dir.path().join("credentials.toml");
 
-    let mut store = FileCredentialStore::new(path).unwrap();
+    let mut store = FileCredentialStore::new(path.clone()).unwrap();
 
     let creds = Credentials::Bearer {
         token: "test_token".to_string(),
     };
-    let result = store.store("registry.example.com", &creds);
+    store.store("registry.example.com", &creds).unwrap();
 
-    assert!(result.is_err());
-    assert!(
-        result
-            .unwrap_err()
-            .to_string()
-            .contains("Bearer token storage not yet supported")
+    // Reload from disk to exercise serialization
+    let store = FileCredentialStore::new(path.clone()).unwrap();
+    assert_eq!(store.get("registry.example.com").unwrap(), Some(creds));
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("type = \"bearer\""));
+    assert!(!contents.contains("test_token"));
+}
+
+#[test]
+fn test_file_credential_store_store_and_get_identity_token() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    let mut store = FileCredentialStore::new(path.clone()).unwrap();
+
+    let creds = Credentials::identity_token("refresh_token");
+    store.store("ghcr.io", &creds).unwrap();
+
+    let store = FileCredentialStore::new(path.clone()).unwrap();
+    assert_eq!(store.get("ghcr.io").unwrap(), Some(creds));
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("type = \"identity_token\""));
+}
+
+#[test]
+fn test_file_credential_store_basic_entries_are_tagged() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    let mut store = FileCredentialStore::new(path.clone()).unwrap();
+    store
+        .store("registry.example.com", &Credentials::basic("user", "pass"))
+        .unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("type = \"basic\""));
+}
+
+#[test]
+fn test_file_credential_store_migrates_legacy_file() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    // Format written before entries were tagged; cGFzcw== = base64("pass")
+    std::fs::write(
+        &path,
+        "[\"registry.example.com\"]\nusername = \"user\"\npassword = \"cGFzcw==\"\n",
+    )
+    .unwrap();
+
+    let store = FileCredentialStore::new(path.clone()).unwrap();
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+
+    // The file is rewritten in the tagged format
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("type = \"basic\""));
+
+    let store = FileCredentialStore::new(path).unwrap();
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+}
+
+#[test]
+fn test_file_credential_store_migrates_mixed_file() {
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    // dG9rZW4= = base64("token")
+    std::fs::write(
+        &path,
+        "[\"old.example.com\"]\nusername = \"user\"\npassword = \"cGFzcw==\"\n\n\
+         [\"new.example.com\"]\ntype = \"bearer\"\ntoken = \"dG9rZW4=\"\n",
+    )
+    .unwrap();
+
+    let store = FileCredentialStore::new(path).unwrap();
+    assert_eq!(
+        store.get("old.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+    assert_eq!(
+        store.get("new.example.com").unwrap(),
+        Some(Credentials::bearer("token"))
+    );
+}
+
+#[test]
+fn test_file_credential_store_expired_token_is_absent() {
+    use std::time::{Duration, SystemTime};
+
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    let mut store = FileCredentialStore::new(path.clone()).unwrap();
+    store
+        .store_with_expiry(
+            "expired.example.com",
+            &Credentials::bearer("old"),
+            SystemTime::now() - Duration::from_secs(60),
+        )
+        .unwrap();
+    store
+        .store_with_expiry(
+            "valid.example.com",
+            &Credentials::identity_token("fresh"),
+            SystemTime::now() + Duration::from_secs(3600),
+        )
+        .unwrap();
+
+    let store = FileCredentialStore::new(path.clone()).unwrap();
+    assert_eq!(store.get("expired.example.com").unwrap(), None);
+    assert_eq!(
+        store.get("valid.example.com").unwrap(),
+        Some(Credentials::identity_token("fresh"))
+    );
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(contents.contains("expires_at"));
+}
+
+#[test]
+fn test_file_credential_store_expiry_ignored_for_basic() {
+    use std::time::{Duration, SystemTime};
+
+    let temp_dir = tempdir().unwrap();
+    let path = temp_dir.path().join("credentials.toml");
+
+    let mut store = FileCredentialStore::new(path).unwrap();
+    store
+        .store_with_expiry(
+            "registry.example.com",
+            &Credentials::basic("user", "pass"),
+            SystemTime::now() - Duration::from_secs(60),
+        )
+        .unwrap();
+
+    assert_eq!(
+        store.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
     );
 }
 
@@ -279,3 +419,129 @@ fn test_file_credential_store_list_empty_after_remove_all() {
 
     assert!(store.list().unwrap().is_empty());
 }
+
+#[test]
+fn test_chained_store_returns_first_match() {
+    let temp_dir = tempdir().unwrap();
+
+    let mut first = FileCredentialStore::new(temp_dir.path().join("first.toml")).unwrap();
+    first
+        .store("registry.example.com", &Credentials::basic("first", "pass"))
+        .unwrap();
+
+    let mut second = FileCredentialStore::new(temp_dir.path().join("second.toml")).unwrap();
+    second
+        .store(
+            "registry.example.com",
+            &Credentials::basic("second", "pass"),
+        )
+        .unwrap();
+    second
+        .store("other.example.com", &Credentials::basic("other", "pass"))
+        .unwrap();
+
+    let chain = ChainedCredentialStore::new()
+        .with_store(first)
+        .with_store(second);
+
+    assert_eq!(chain.len(), 2);
+    assert_eq!(
+        chain.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("first", "pass"))
+    );
+    assert_eq!(
+        chain.get("other.example.com").unwrap(),
+        Some(Credentials::basic("other", "pass"))
+    );
+    assert_eq!(chain.get("missing.example.com").unwrap(), None);
+}
+
+#[test]
+fn test_chained_store_skips_failing_stores() {
+    let temp_dir = tempdir().unwrap();
+
+    // A credential whose password is not valid base64 fails to decode
+    let broken_path = temp_dir.path().join("broken.toml");
+    std::fs::write(
+        &broken_path,
+        "[\"registry.example.com\"]\nusername = \"user\"\npassword = \"!!!\"\n",
+    )
+    .unwrap();
+    let broken = FileCredentialStore::new(broken_path).unwrap();
+    assert!(broken.get("registry.example.com").is_err());
+
+    let mut good = FileCredentialStore::new(temp_dir.path().join("good.toml")).unwrap();
+    good.store("registry.example.com", &Credentials::basic("user", "pass"))
+        .unwrap();
+
+    let chain = ChainedCredentialStore::new()
+        .with_store(broken)
+        .with_store(good);
+
+    assert_eq!(
+        chain.get("registry.example.com").unwrap(),
+        Some(Credentials::basic("user", "pass"))
+    );
+}
+
+#[test]
+fn test_chained_store_writes_to_first_store() {
+    let temp_dir = tempdir().unwrap();
+    let first_path = temp_dir.path().join("first.toml");
+    let second_path = temp_dir.path().join("second.toml");
+
+    let mut chain = ChainedCredentialStore::new()
+        .with_store(FileCredentialStore::new(first_path.clone()).unwrap())
+        .with_store(FileCredentialStore::new(second_path.clone()).unwrap());
+
+    chain
+        .store("registry.example.com", &Credentials::basic("user", "pass"))
+        .unwrap();
+
+    let first = FileCredentialStore::new(first_path).unwrap();
+    let second = FileCredentialStore::new(second_path).unwrap();
+    assert!(first.get("registry.example.com").unwrap().is_some());
+    assert!(second.get("registry.example.com").unwrap().is_none());
+
+    chain.remove("registry.example.com").unwrap();
+    assert_eq!(chain.get("registry.example.com").unwrap(), None);
+}
+
+#[test]
+fn test_chained_store_list_deduplicates() {
+    let temp_dir = tempdir().unwrap();
+
+    let mut first = FileCredentialStore::new(temp_dir.path().join("first.toml")).unwrap();
+    first
+        .store("a.example.com", &Credentials::basic("u", "p"))
+        .unwrap();
+    let mut second = FileCredentialStore::new(temp_dir.path().join("second.toml")).unwrap();
+    second
+        .store("a.example.com", &Credentials::basic("u", "p"))
+        .unwrap();
+    second
+        .store("b.example.com", &Credentials::basic("u", "p"))
+        .unwrap();
+
+    let chain = ChainedCredentialStore::new()
+        .with_store(first)
+        .with_store(second);
+
+    assert_eq!(
+        chain.list().unwrap(),
+        vec!["a.example.com", "b.example.com"]
+    );
+}
+
+#[test]
+fn test_chained_store_empty() {
+    let mut chain = ChainedCredentialStore::new();
+
+    assert!(chain.is_empty());
+    assert_eq!(chain.get("registry.example.com").unwrap(), None);
+    assert!(
+        chain
+            .store("registry.example.com", &Credentials::basic("u", "p"))
+            .is_err()
+    );
+}
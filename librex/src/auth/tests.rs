@@ -95,3 +95,25 @@ fn test_auth_challenge_parse_invalid_format() {
     let result = AuthChallenge::parse(header);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_auth_challenge_parse_scope_with_commas() {
+    let header = r#"Bearer realm="https://auth.example.com/token",service="registry",scope="repository:alpine:pull,push""#;
+
+    let challenge = AuthChallenge::parse(header).unwrap();
+    assert_eq!(challenge.realm, "https://auth.example.com/token");
+    assert_eq!(challenge.service, Some("registry".to_string()));
+    assert_eq!(
+        challenge.scope,
+        Some("repository:alpine:pull,push".to_string())
+    );
+}
+
+#[test]
+fn test_auth_challenge_is_bearer() {
+    let bearer = AuthChallenge::parse(r#"bearer realm="https://auth.example.com/token""#).unwrap();
+    assert!(bearer.is_bearer());
+
+    let basic = AuthChallenge::parse(r#"Basic realm="Registry Access""#).unwrap();
+    assert!(!basic.is_bearer());
+}
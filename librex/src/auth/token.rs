@@ -0,0 +1,141 @@
+//! Bearer token handling for registry token services.
+//!
+//! Registries such as Docker Hub, GHCR and Harbor delegate authentication to a
+//! separate token service. The client exchanges a `WWW-Authenticate` challenge
+//! for a short-lived token, which is cached here per scope until it expires.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Lifetime assumed when a token response omits `expires_in`.
+///
+/// The Docker token specification mandates 60 seconds as the default.
+const DEFAULT_TOKEN_LIFETIME_SECS: u64 = 60;
+
+/// Safety margin subtracted from the token lifetime so that a token is never
+/// sent right as it expires.
+const EXPIRY_MARGIN_SECS: u64 = 5;
+
+/// Response body returned by a registry token service.
+///
+/// Token services return the token in `token`, `access_token`, or both.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenResponse {
+    /// The bearer token (Docker registry token spec)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// The bearer token (OAuth2 compatible field)
+    #[serde(default)]
+    pub access_token: Option<String>,
+    /// Token lifetime in seconds
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+}
+
+impl TokenResponse {
+    /// Returns the bearer token, preferring `token` over `access_token`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::auth::TokenResponse;
+    ///
+    /// let response: TokenResponse = serde_json::from_str(r#"{"access_token":"abc"}"#).unwrap();
+    /// assert_eq!(response.bearer_token(), Some("abc"));
+    /// ```
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .or(self.access_token.as_deref())
+            .filter(|t| !t.is_empty())
+    }
+}
+
+/// A cached token and the instant after which it must not be used.
+#[derive(Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: Instant,
+}
+
+/// In-memory cache of bearer tokens keyed by scope.
+///
+/// # Examples
+///
+/// ```
+/// use librex::auth::TokenCache;
+///
+/// let mut cache = TokenCache::new();
+/// cache.insert("repository:alpine:pull", "token123", Some(300));
+/// assert_eq!(cache.get("repository:alpine:pull"), Some("token123".to_string()));
+/// ```
+#[derive(Clone, Default)]
+pub struct TokenCache {
+    tokens: HashMap<String, CachedToken>,
+}
+
+impl TokenCache {
+    /// Creates an empty token cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the token cached for a scope, if it has not expired.
+    pub fn get(&self, scope: &str) -> Option<String> {
+        self.tokens
+            .get(scope)
+            .filter(|cached| Instant::now() < cached.expires_at)
+            .map(|cached| cached.token.clone())
+    }
+
+    /// Caches a token for a scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope the token was issued for
+    /// * `token` - The bearer token
+    /// * `expires_in` - Token lifetime in seconds (defaults to 60 when absent)
+    pub fn insert(&mut self, scope: &str, token: impl Into<String>, expires_in: Option<u64>) {
+        let lifetime = expires_in
+            .unwrap_or(DEFAULT_TOKEN_LIFETIME_SECS)
+            .saturating_sub(EXPIRY_MARGIN_SECS);
+
+        self.tokens.insert(
+            scope.to_string(),
+            CachedToken {
+                token: token.into(),
+                expires_at: Instant::now() + Duration::from_secs(lifetime),
+            },
+        );
+    }
+
+    /// Removes the token cached for a scope.
+    pub fn remove(&mut self, scope: &str) {
+        self.tokens.remove(scope);
+    }
+
+    /// Removes all cached tokens.
+    pub fn clear(&mut self) {
+        self.tokens.clear();
+    }
+
+    /// Returns the number of cached tokens, including expired ones.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns true if no tokens are cached.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+impl std::fmt::Debug for TokenCache {
+    // Tokens are secrets, so only the scopes are shown.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenCache")
+            .field("scopes", &self.tokens.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
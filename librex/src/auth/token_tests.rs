@@ -0,0 +1,77 @@
+use super::token::*;
+
+#[test]
+fn test_token_response_prefers_token_field() {
+    let response: TokenResponse =
+        serde_json::from_str(r#"{"token":"primary","access_token":"secondary"}"#).unwrap();
+    assert_eq!(response.bearer_token(), Some("primary"));
+}
+
+#[test]
+fn test_token_response_falls_back_to_access_token() {
+    let response: TokenResponse =
+        serde_json::from_str(r#"{"access_token":"oauth","expires_in":120}"#).unwrap();
+    assert_eq!(response.bearer_token(), Some("oauth"));
+    assert_eq!(response.expires_in, Some(120));
+}
+
+#[test]
+fn test_token_response_without_token() {
+    let response: TokenResponse = serde_json::from_str(r#"{"expires_in":120}"#).unwrap();
+    assert_eq!(response.bearer_token(), None);
+
+    let response: TokenResponse = serde_json::from_str(r#"{"token":""}"#).unwrap();
+    assert_eq!(response.bearer_token(), None);
+}
+
+#[test]
+fn test_token_cache_insert_and_get() {
+    let mut cache = TokenCache::new();
+    assert!(cache.is_empty());
+
+    cache.insert("repository:alpine:pull", "abc", Some(300));
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.get("repository:alpine:pull"), Some("abc".to_string()));
+    assert_eq!(cache.get("repository:nginx:pull"), None);
+}
+
+#[test]
+fn test_token_cache_expired_token_not_returned() {
+    let mut cache = TokenCache::new();
+    cache.insert("repository:alpine:pull", "abc", Some(0));
+
+    assert_eq!(cache.get("repository:alpine:pull"), None);
+}
+
+#[test]
+fn test_token_cache_default_lifetime() {
+    let mut cache = TokenCache::new();
+    cache.insert("registry:catalog:*", "abc", None);
+
+    assert_eq!(cache.get("registry:catalog:*"), Some("abc".to_string()));
+}
+
+#[test]
+fn test_token_cache_remove_and_clear() {
+    let mut cache = TokenCache::new();
+    cache.insert("a", "1", Some(300));
+    cache.insert("b", "2", Some(300));
+
+    cache.remove("a");
+    assert_eq!(cache.get("a"), None);
+    assert_eq!(cache.len(), 1);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_token_cache_debug_hides_tokens() {
+    let mut cache = TokenCache::new();
+    cache.insert("repository:alpine:pull", "secret-token", Some(300));
+
+    let debug = format!("{:?}", cache);
+    assert!(debug.contains("repository:alpine:pull"));
+    assert!(!debug.contains("secret-token"));
+}
@@ -0,0 +1,526 @@
+//! Streaming blob access and content-addressed blob storage.
+//!
+//! Layers can be many gigabytes, so they must never be held in memory. This
+//! module provides:
+//!
+//! - [`BlobReader`] - a `Read` adapter that hashes content as it streams and
+//!   fails at end of stream if the content does not match the expected digest
+//! - [`BlobStore`] - an on-disk, content-addressed store (`<root>/sha256/<hex>`)
+//!   that only ever exposes fully downloaded and verified blobs
+
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// Name of the blob store directory inside a cache directory.
+///
+/// [`RexBuilder::with_cache`](crate::RexBuilder::with_cache) places the blob
+/// store here, and [`Cache::prune`](crate::cache::Cache::prune) leaves it alone
+/// since blobs never expire.
+pub const BLOB_STORE_DIR: &str = "blobstore";
+
+/// Suffix of the file a blob is downloaded to before it is verified.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// Size of the chunks blobs are streamed in.
+pub(crate) const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Returns an error unless the digest uses an algorithm we can verify.
+///
+/// Only sha256 is currently supported.
+pub(crate) fn ensure_supported(digest: &Digest) -> Result<()> {
+    if digest.algorithm() != "sha256" {
+        return Err(RexError::validation(format!(
+            "Unsupported digest algorithm: {}. Only sha256 is currently supported",
+            digest.algorithm()
+        )));
+    }
+    Ok(())
+}
+
+/// Converts an I/O error raised while streaming a blob into a `RexError`.
+///
+/// Digest mismatches detected by [`BlobReader`] are returned unchanged.
+pub(crate) fn read_error(error: io::Error) -> RexError {
+    if !error.get_ref().is_some_and(|inner| inner.is::<RexError>()) {
+        return RexError::network_with_source("Failed to read blob content", error);
+    }
+
+    match error.into_inner().map(|inner| inner.downcast::<RexError>()) {
+        Some(Ok(rex_error)) => *rex_error,
+        _ => RexError::network("Failed to read blob content"),
+    }
+}
+
+/// Copies a blob from a verifying reader into a writer in chunks.
+///
+/// `progress` is called after every chunk with the total number of bytes
+/// read so far (including any prefix) and the blob size, if known.
+///
+/// # Errors
+///
+/// Returns an error if reading or writing fails, or if the content does not
+/// match the expected digest.
+pub(crate) fn copy<R, W, F>(
+    reader: &mut BlobReader<R>,
+    writer: &mut W,
+    mut progress: F,
+) -> Result<u64>
+where
+    R: Read,
+    W: Write + ?Sized,
+    F: FnMut(u64, Option<u64>),
+{
+    let mut buf = vec![0u8; CHUNK_SIZE];
+
+    loop {
+        let n = reader.read(&mut buf).map_err(read_error)?;
+        if n == 0 {
+            break;
+        }
+
+        writer.write_all(&buf[..n]).map_err(|e| {
+            RexError::config_with_source("Failed to write blob content", None::<&str>, e)
+        })?;
+        progress(reader.bytes_read(), reader.total_size());
+    }
+
+    writer.flush().map_err(|e| {
+        RexError::config_with_source("Failed to write blob content", None::<&str>, e)
+    })?;
+
+    Ok(reader.bytes_read())
+}
+
+/// A `Read` adapter that verifies a blob's digest while it is read.
+///
+/// Every byte read is fed into a SHA-256 hasher. When the underlying reader
+/// reaches end of stream, the computed digest is compared with the expected
+/// one; a mismatch is reported as an `io::Error` of kind `InvalidData` that
+/// wraps a `RexError::Validation`. Consumers must therefore read to the end
+/// before trusting the content.
+///
+/// # Examples
+///
+/// ```
+/// use librex::blob::BlobReader;
+/// use std::io::Read;
+///
+/// let content = b"hello";
+/// let digest = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+///     .parse()
+///     .unwrap();
+///
+/// let mut reader = BlobReader::new(&content[..], digest, Some(5)).unwrap();
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+/// assert_eq!(reader.bytes_read(), 5);
+/// ```
+pub struct BlobReader<R> {
+    inner: R,
+    expected: Digest,
+    hasher: Sha256,
+    bytes_read: u64,
+    total_size: Option<u64>,
+    verified: bool,
+}
+
+impl<R: Read> BlobReader<R> {
+    /// Wraps a reader that yields the content of the blob `expected`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The source of the blob content
+    /// * `expected` - The digest the content must match
+    /// * `total_size` - The blob size, if known (used for progress reporting)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the digest algorithm is not supported.
+    pub fn new(inner: R, expected: Digest, total_size: Option<u64>) -> Result<Self> {
+        ensure_supported(&expected)?;
+
+        Ok(Self {
+            inner,
+            expected,
+            hasher: Sha256::new(),
+            bytes_read: 0,
+            total_size,
+            verified: false,
+        })
+    }
+
+    /// Feeds content obtained earlier, such as a partial download, into the
+    /// digest.
+    ///
+    /// The reader then continues where `prefix` ends: `inner` must yield the
+    /// rest of the blob. The prefix is counted in [`BlobReader::bytes_read`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::blob::BlobReader;
+    /// use std::io::Read;
+    ///
+    /// let digest = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let mut reader = BlobReader::new(&b"llo"[..], digest, Some(5))
+    ///     .unwrap()
+    ///     .with_prefix(&mut &b"he"[..])
+    ///     .unwrap();
+    /// assert_eq!(reader.bytes_read(), 2);
+    ///
+    /// reader.read_to_end(&mut Vec::new()).unwrap();
+    /// assert_eq!(reader.bytes_read(), 5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prefix cannot be read.
+    pub fn with_prefix<P: Read + ?Sized>(mut self, prefix: &mut P) -> Result<Self> {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+
+        loop {
+            let n = prefix.read(&mut buf).map_err(|e| {
+                RexError::config_with_source("Failed to read partial blob", None::<&str>, e)
+            })?;
+            if n == 0 {
+                break;
+            }
+            self.hasher.update(&buf[..n]);
+            self.bytes_read += n as u64;
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// Returns the total blob size, if known.
+    pub fn total_size(&self) -> Option<u64> {
+        self.total_size
+    }
+
+    /// Returns the digest the content is verified against.
+    pub fn digest(&self) -> &Digest {
+        &self.expected
+    }
+
+    /// Compares the digest of everything read so far with the expected digest.
+    ///
+    /// This happens automatically at end of stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RexError::Validation` if the digests differ.
+    pub fn verify(&self) -> Result<()> {
+        let computed = format!("{:x}", self.hasher.clone().finalize());
+
+        if computed != self.expected.hex() {
+            return Err(RexError::validation(format!(
+                "Blob digest mismatch: expected {}, computed sha256:{}",
+                self.expected, computed
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for BlobReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+
+        if n == 0 {
+            if !buf.is_empty() && !self.verified {
+                self.verify()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                self.verified = true;
+            }
+            return Ok(0);
+        }
+
+        self.hasher.update(&buf[..n]);
+        self.bytes_read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R> std::fmt::Debug for BlobReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("expected", &self.expected.to_string())
+            .field("bytes_read", &self.bytes_read)
+            .field("total_size", &self.total_size)
+            .finish()
+    }
+}
+
+/// On-disk, content-addressed blob storage.
+///
+/// Blobs are stored at `<root>/<algorithm>/<hex>`, the layout used by OCI
+/// image layouts. Content is first written to a `.partial` file next to its
+/// final location and only renamed into place once the writer reports
+/// success, so a blob path never refers to incomplete or unverified content.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::blob::BlobStore;
+/// use librex::client::Client;
+///
+/// # fn example() -> librex::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+/// let store = BlobStore::new("/tmp/rex-blobs");
+/// let digest = "sha256:abc123...".parse()?;
+///
+/// let path = store.insert_with(&digest, |file| {
+///     client.fetch_blob_to("alpine", &digest.to_string(), file, |_, _| {})
+/// })?;
+/// println!("Blob stored at {}", path.display());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    /// Creates a blob store rooted at `root`.
+    ///
+    /// The directory is created on the first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Returns the root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the path at which a blob is (or would be) stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::blob::BlobStore;
+    /// use std::path::Path;
+    ///
+    /// let store = BlobStore::new("/tmp/blobs");
+    /// let digest = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     store.path(&digest),
+    ///     Path::new("/tmp/blobs/sha256/2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+    /// );
+    /// ```
+    pub fn path(&self, digest: &Digest) -> PathBuf {
+        self.root.join(digest.algorithm()).join(digest.hex())
+    }
+
+    /// Returns the path a blob is downloaded to before it is verified.
+    pub fn partial_path(&self, digest: &Digest) -> PathBuf {
+        self.root
+            .join(digest.algorithm())
+            .join(format!("{}{}", digest.hex(), PARTIAL_SUFFIX))
+    }
+
+    /// Returns true if the blob is in the store.
+    pub fn contains(&self, digest: &Digest) -> bool {
+        self.path(digest).is_file()
+    }
+
+    /// Opens a stored blob for reading.
+    ///
+    /// Returns `None` if the blob is not in the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob exists but cannot be opened.
+    pub fn open(&self, digest: &Digest) -> Result<Option<File>> {
+        let path = self.path(digest);
+        match File::open(&path) {
+            Ok(file) => Ok(Some(file)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RexError::config_with_source(
+                "Failed to open stored blob",
+                path.to_str(),
+                e,
+            )),
+        }
+    }
+
+    /// Reads a stored blob into memory.
+    ///
+    /// Intended for small blobs such as image configs; stream large blobs
+    /// with [`BlobStore::open`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob exists but cannot be read.
+    pub fn read(&self, digest: &Digest) -> Result<Option<Vec<u8>>> {
+        match self.open(digest)? {
+            Some(mut file) => {
+                let mut content = Vec::new();
+                file.read_to_end(&mut content).map_err(|e| {
+                    RexError::config_with_source(
+                        "Failed to read stored blob",
+                        self.path(digest).to_str(),
+                        e,
+                    )
+                })?;
+                Ok(Some(content))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Adds a blob to the store, writing its content with `write`.
+    ///
+    /// `write` receives the partial file and must write the complete,
+    /// verified blob content to it, returning the number of bytes written.
+    /// On success the partial file is renamed to the blob path; on failure it
+    /// is removed and the error is returned.
+    ///
+    /// If the blob is already stored, `write` is not called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directory or partial file cannot be
+    /// created, if `write` fails, or if the file cannot be moved into place.
+    pub fn insert_with<F>(&self, digest: &Digest, write: F) -> Result<PathBuf>
+    where
+        F: FnOnce(&mut File) -> Result<u64>,
+    {
+        let path = self.path(digest);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let partial = self.partial_path(digest);
+        if let Some(parent) = partial.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                RexError::config_with_source("Failed to create blob directory", parent.to_str(), e)
+            })?;
+        }
+
+        let mut file = File::create(&partial).map_err(|e| {
+            RexError::config_with_source("Failed to create blob file", partial.to_str(), e)
+        })?;
+
+        let result = write(&mut file).and_then(|_| {
+            file.sync_all().map_err(|e| {
+                RexError::config_with_source("Failed to write blob file", partial.to_str(), e)
+            })
+        });
+        drop(file);
+
+        if let Err(e) = result {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+
+        fs::rename(&partial, &path)
+            .map_err(|e| RexError::config_with_source("Failed to store blob", path.to_str(), e))?;
+
+        Ok(path)
+    }
+
+    /// Adds a blob to the store, resuming an earlier partial download.
+    ///
+    /// Like [`BlobStore::insert_with`], but the partial file is opened without
+    /// truncating it, so `write` can continue after the bytes already there
+    /// (see [`Client::fetch_blob_resume`](crate::client::Client::fetch_blob_resume)).
+    /// If `write` fails, the partial file is kept for the next attempt unless
+    /// the failure is a validation error such as a digest mismatch, in which
+    /// case the content is unusable and the file is removed.
+    ///
+    /// If the blob is already stored, `write` is not called.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directory or partial file cannot be
+    /// opened, if `write` fails, or if the file cannot be moved into place.
+    pub fn resume_with<F>(&self, digest: &Digest, write: F) -> Result<PathBuf>
+    where
+        F: FnOnce(&mut File) -> Result<u64>,
+    {
+        let path = self.path(digest);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let partial = self.partial_path(digest);
+        if let Some(parent) = partial.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                RexError::config_with_source("Failed to create blob directory", parent.to_str(), e)
+            })?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&partial)
+            .map_err(|e| {
+                RexError::config_with_source(
+                    "Failed to open partial blob file",
+                    partial.to_str(),
+                    e,
+                )
+            })?;
+
+        let result = write(&mut file).and_then(|_| {
+            file.sync_all().map_err(|e| {
+                RexError::config_with_source("Failed to write blob file", partial.to_str(), e)
+            })
+        });
+        drop(file);
+
+        if let Err(e) = result {
+            if matches!(e, RexError::Validation { .. }) {
+                let _ = fs::remove_file(&partial);
+            }
+            return Err(e);
+        }
+
+        fs::rename(&partial, &path)
+            .map_err(|e| RexError::config_with_source("Failed to store blob", path.to_str(), e))?;
+
+        Ok(path)
+    }
+
+    /// Removes a blob from the store.
+    ///
+    /// Removing a blob that is not stored is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob file exists but cannot be removed.
+    pub fn remove(&self, digest: &Digest) -> Result<()> {
+        let path = self.path(digest);
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(RexError::config_with_source(
+                "Failed to remove stored blob",
+                path.to_str(),
+                e,
+            )),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use super::*;
+use crate::test_support::digest_of;
+use std::io::{Read, Write};
+use std::str::FromStr;
+use tempfile::TempDir;
+
+#[test]
+fn test_blob_reader_verifies_matching_content() {
+    let content = b"layer content".repeat(10_000);
+    let mut reader = BlobReader::new(&content[..], digest_of(&content), None).unwrap();
+
+    let mut read_back = Vec::new();
+    reader.read_to_end(&mut read_back).unwrap();
+
+    assert_eq!(read_back, content);
+    assert_eq!(reader.bytes_read(), content.len() as u64);
+}
+
+#[test]
+fn test_blob_reader_detects_mismatch_at_end_of_stream() {
+    let expected = digest_of(b"expected");
+    let mut reader = BlobReader::new(&b"tampered"[..], expected, Some(8)).unwrap();
+
+    let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(matches!(read_error(error), RexError::Validation { .. }));
+}
+
+#[test]
+fn test_blob_reader_reports_sizes() {
+    let content = b"hello";
+    let reader = BlobReader::new(&content[..], digest_of(content), Some(5)).unwrap();
+
+    assert_eq!(reader.bytes_read(), 0);
+    assert_eq!(reader.total_size(), Some(5));
+    assert_eq!(reader.digest(), &digest_of(content));
+}
+
+#[test]
+fn test_blob_reader_rejects_unsupported_algorithm() {
+    let digest = Digest::from_str(&format!("sha512:{}", "a".repeat(128))).unwrap();
+    let result = BlobReader::new(&b""[..], digest, None);
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_read_error_wraps_io_errors() {
+    let error = read_error(io::Error::new(io::ErrorKind::ConnectionReset, "reset"));
+    assert!(matches!(error, RexError::Network { .. }));
+}
+
+#[test]
+fn test_blob_store_paths() {
+    let store = BlobStore::new("/tmp/blobs");
+    let digest = digest_of(b"hello");
+
+    assert_eq!(
+        store.path(&digest),
+        Path::new("/tmp/blobs/sha256").join(digest.hex())
+    );
+    assert_eq!(
+        store.partial_path(&digest),
+        Path::new("/tmp/blobs/sha256").join(format!("{}.partial", digest.hex()))
+    );
+}
+
+#[test]
+fn test_blob_store_insert_and_read() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    assert!(!store.contains(&digest));
+    assert!(store.read(&digest).unwrap().is_none());
+
+    let path = store
+        .insert_with(&digest, |file| {
+            file.write_all(b"hello").unwrap();
+            Ok(5)
+        })
+        .unwrap();
+
+    assert_eq!(path, store.path(&digest));
+    assert!(store.contains(&digest));
+    assert!(!store.partial_path(&digest).exists());
+    assert_eq!(store.read(&digest).unwrap(), Some(b"hello".to_vec()));
+}
+
+#[test]
+fn test_blob_store_insert_skips_existing_blob() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    store
+        .insert_with(&digest, |file| {
+            file.write_all(b"hello").unwrap();
+            Ok(5)
+        })
+        .unwrap();
+
+    let path = store
+        .insert_with(&digest, |_| panic!("blob should not be written again"))
+        .unwrap();
+    assert_eq!(path, store.path(&digest));
+}
+
+#[test]
+fn test_blob_store_failed_insert_leaves_nothing_behind() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    let result = store.insert_with(&digest, |file| {
+        file.write_all(b"hel").unwrap();
+        Err(RexError::validation("Blob digest mismatch"))
+    });
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+    assert!(!store.contains(&digest));
+    assert!(!store.partial_path(&digest).exists());
+}
+
+#[test]
+fn test_blob_store_remove() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    store
+        .insert_with(&digest, |file| {
+            file.write_all(b"hello").unwrap();
+            Ok(5)
+        })
+        .unwrap();
+    store.remove(&digest).unwrap();
+
+    assert!(!store.contains(&digest));
+    // Removing a missing blob is not an error
+    store.remove(&digest).unwrap();
+}
+
+#[test]
+fn test_blob_reader_with_prefix() {
+    let content = b"hello, world";
+    let mut reader = BlobReader::new(&content[5..], digest_of(content), Some(12))
+        .unwrap()
+        .with_prefix(&mut &content[..5])
+        .unwrap();
+
+    assert_eq!(reader.bytes_read(), 5);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+
+    assert_eq!(rest, &content[5..]);
+    assert_eq!(reader.bytes_read(), 12);
+    assert!(reader.verify().is_ok());
+}
+
+#[test]
+fn test_blob_store_resume_keeps_partial_on_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    let result = store.resume_with(&digest, |file| {
+        file.write_all(b"hel").unwrap();
+        Err(RexError::network("Connection reset"))
+    });
+    assert!(matches!(result, Err(RexError::Network { .. })));
+    assert_eq!(fs::read(store.partial_path(&digest)).unwrap(), b"hel");
+
+    let path = store
+        .resume_with(&digest, |file| {
+            let mut existing = Vec::new();
+            file.read_to_end(&mut existing).unwrap();
+            assert_eq!(existing, b"hel");
+            file.write_all(b"lo").unwrap();
+            Ok(5)
+        })
+        .unwrap();
+
+    assert_eq!(fs::read(path).unwrap(), b"hello");
+    assert!(!store.partial_path(&digest).exists());
+}
+
+#[test]
+fn test_blob_store_resume_discards_invalid_partial() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest = digest_of(b"hello");
+
+    let result = store.resume_with(&digest, |file| {
+        file.write_all(b"HELLO").unwrap();
+        Err(RexError::validation("Blob digest mismatch"))
+    });
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+    assert!(!store.contains(&digest));
+    assert!(!store.partial_path(&digest).exists());
+}
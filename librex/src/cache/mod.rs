@@ -3,6 +3,7 @@
 //! This module provides a cache that serves as a fast local data source
 //! for the `registry` module, reducing network requests.
 
+use crate::blob::BLOB_STORE_DIR;
 use crate::error::{Result, RexError};
 use bincode::{Decode, Encode, config::standard};
 use lru::LruCache;
@@ -248,14 +249,19 @@ impl Cache {
     }
 
     /// Removes expired files from the on-disk cache.
+    ///
+    /// The blob store directory ([`BLOB_STORE_DIR`]) is skipped: blobs are
+    /// immutable and never expire.
     pub fn prune(&self) -> Result<PruneStats> {
         let mut stats = PruneStats::default();
         if !self.disk_path.exists() {
             return Ok(stats);
         }
 
+        let blob_store = self.disk_path.join(BLOB_STORE_DIR);
         for entry in WalkDir::new(&self.disk_path)
             .into_iter()
+            .filter_entry(|e| e.path() != blob_store)
             .filter_map(|e| e.ok())
         {
             if !entry.file_type().is_file() {
@@ -63,8 +63,10 @@ fn test_cache_l2_disk_hit() {
 #[test]
 fn test_cache_l2_disk_expired() {
     let temp_dir = tempdir().unwrap();
-    let mut ttl = CacheTtl::default();
-    ttl.tags = 1; // Set a 1-second TTL for tags
+    let ttl = CacheTtl {
+        tags: 1, // Set a 1-second TTL for tags
+        ..Default::default()
+    };
     let capacity = NonZeroUsize::new(100).unwrap();
     let mut cache = Cache::new(temp_dir.path().to_path_buf(), ttl, capacity);
 
@@ -92,9 +94,11 @@ fn test_cache_l2_disk_expired() {
 #[test]
 fn test_cache_prune() {
     let temp_dir = tempdir().unwrap();
-    let mut ttl = CacheTtl::default();
-    ttl.tags = 1; // 1 second TTL
-    ttl.catalog = 3600; // 1 hour TTL
+    let ttl = CacheTtl {
+        tags: 1,       // 1 second TTL
+        catalog: 3600, // 1 hour TTL
+        ..Default::default()
+    };
     let capacity = NonZeroUsize::new(100).unwrap();
     let mut cache = Cache::new(temp_dir.path().to_path_buf(), ttl, capacity);
 
@@ -240,3 +244,20 @@ fn test_cache_stats_empty() {
     assert_eq!(stats.disk_size, 0);
     assert_eq!(stats.memory_entries, 0);
 }
+
+#[test]
+fn test_cache_prune_skips_blob_store() {
+    let temp_dir = tempdir().unwrap();
+    let capacity = NonZeroUsize::new(100).unwrap();
+    let cache = Cache::new(temp_dir.path().to_path_buf(), CacheTtl::default(), capacity);
+
+    let blob_dir = temp_dir.path().join(BLOB_STORE_DIR).join("sha256");
+    std::fs::create_dir_all(&blob_dir).unwrap();
+    let blob_path = blob_dir.join("abc");
+    std::fs::write(&blob_path, b"raw layer bytes").unwrap();
+
+    let stats = cache.prune().unwrap();
+
+    assert_eq!(stats.removed_files, 0);
+    assert!(blob_path.exists());
+}
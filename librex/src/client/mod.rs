@@ -4,17 +4,52 @@
 //! with OCI-compliant container registries. It implements the OCI Distribution
 //! Specification v2 API.
 
+use crate::auth::{AuthChallenge, Credentials, TokenCache, TokenResponse};
+use crate::blob::BlobReader;
 use crate::digest::Digest;
 use crate::error::{Result, RexError};
-use reqwest::StatusCode;
-use reqwest::blocking::{Client as ReqwestClient, Response};
+use reqwest::blocking::{Client as ReqwestClient, RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
 use serde::Deserialize;
 use sha2::{Digest as Sha2Digest, Sha256};
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+mod referrers;
+mod retry;
+mod tls;
+mod upload;
+
+pub(crate) use referrers::referrers_tag;
+pub use referrers::referrers_tag_subject;
+pub use retry::RetryPolicy;
+pub use upload::{MountResult, UploadSession};
+
+#[cfg(test)]
+mod referrers_tests;
+#[cfg(test)]
+mod retry_tests;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod tls_tests;
+#[cfg(test)]
+mod upload_tests;
+
+/// Manifest media types requested when fetching or resolving manifests.
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.manifest.v1+json, \
+                               application/vnd.oci.image.index.v1+json, \
+                               application/vnd.docker.distribution.manifest.v2+json, \
+                               application/vnd.docker.distribution.manifest.list.v2+json";
+
+/// Builds the error for a failed operation on a local blob file.
+fn file_error(message: &str, error: std::io::Error) -> RexError {
+    RexError::config_with_source(message, None::<&str>, error)
+}
 
 /// Response from the catalog API endpoint.
 #[derive(Debug, Deserialize)]
@@ -40,10 +75,28 @@ pub struct RegistryVersion {
     pub api_version: Option<String>,
 }
 
+/// Metadata about a manifest or blob, read from the headers of a HEAD request.
+///
+/// HEAD requests transfer no content and do not count against Docker Hub's
+/// pull rate limit, which makes them the cheap way to check that something
+/// exists or to resolve a tag to a digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentInfo {
+    /// The content digest from the `Docker-Content-Digest` header.
+    ///
+    /// When the header is missing this is the requested digest, or `None` if
+    /// a manifest was requested by tag.
+    pub digest: Option<String>,
+    /// The content size from the `Content-Length` header, if present.
+    pub size: Option<u64>,
+    /// The media type from the `Content-Type` header, if present.
+    pub media_type: Option<String>,
+}
+
 /// Configuration for the HTTP client.
 ///
-/// This struct allows customization of HTTP client behavior such as timeouts
-/// and connection pooling. Use the builder pattern to configure:
+/// This struct allows customization of HTTP client behavior such as timeouts,
+/// connection pooling and TLS. Use the builder pattern to configure:
 ///
 /// # Examples
 ///
@@ -52,7 +105,8 @@ pub struct RegistryVersion {
 ///
 /// let config = ClientConfig::new()
 ///     .with_timeout(60)
-///     .with_max_idle_per_host(20);
+///     .with_max_idle_per_host(20)
+///     .with_ca_cert("/etc/ssl/internal-ca.pem");
 /// ```
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
@@ -63,6 +117,21 @@ pub struct ClientConfig {
     /// Enable Docker Hub compatibility mode (adds "library/" prefix for simple names)
     /// Default: false (works with Zot, GHCR, and most registries)
     pub dockerhub_compat: bool,
+    /// Additional trusted root CAs: PEM files, or directories of PEM files
+    /// (default: none, only the built-in web PKI roots are trusted)
+    pub ca_certs: Vec<PathBuf>,
+    /// Client certificate (PEM) for mutual TLS (default: none)
+    pub client_cert: Option<PathBuf>,
+    /// Client private key (PEM) for mutual TLS. May be omitted when the key is
+    /// bundled in `client_cert` (default: none)
+    pub client_key: Option<PathBuf>,
+    /// Disable TLS certificate verification (default: false)
+    ///
+    /// This makes connections vulnerable to interception; only use it for
+    /// test registries with self-signed certificates.
+    pub insecure_skip_verify: bool,
+    /// Retry policy for transient failures (default: no retries)
+    pub retry_policy: RetryPolicy,
 }
 
 impl Default for ClientConfig {
@@ -71,6 +140,11 @@ impl Default for ClientConfig {
             timeout_seconds: 30,
             max_idle_per_host: 10,
             dockerhub_compat: false,
+            ca_certs: Vec::new(),
+            client_cert: None,
+            client_key: None,
+            insecure_skip_verify: false,
+            retry_policy: RetryPolicy::none(),
         }
     }
 }
@@ -142,12 +216,96 @@ impl ClientConfig {
         self.dockerhub_compat = enabled;
         self
     }
+
+    /// Adds a trusted root CA certificate.
+    ///
+    /// The path may be a PEM file (which can hold several certificates) or a
+    /// directory, in which case every `.pem`, `.crt` and `.cer` file in it is
+    /// loaded. Can be called multiple times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::ClientConfig;
+    ///
+    /// let config = ClientConfig::new().with_ca_cert("/etc/ssl/internal-ca.pem");
+    /// assert_eq!(config.ca_certs.len(), 1);
+    /// ```
+    pub fn with_ca_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certs.push(path.into());
+        self
+    }
+
+    /// Sets the client certificate and private key for mutual TLS.
+    ///
+    /// Pass `None` for `key` when the certificate file also contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::ClientConfig;
+    ///
+    /// let config = ClientConfig::new()
+    ///     .with_client_cert("/etc/rex/client.pem", Some("/etc/rex/client-key.pem"));
+    /// assert!(config.client_cert.is_some());
+    /// ```
+    pub fn with_client_cert(
+        mut self,
+        cert: impl Into<PathBuf>,
+        key: Option<impl Into<PathBuf>>,
+    ) -> Self {
+        self.client_cert = Some(cert.into());
+        self.client_key = key.map(Into::into);
+        self
+    }
+
+    /// Enables or disables TLS certificate verification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::ClientConfig;
+    ///
+    /// let config = ClientConfig::new().with_insecure_skip_verify(true);
+    /// assert!(config.insecure_skip_verify);
+    /// ```
+    pub fn with_insecure_skip_verify(mut self, enabled: bool) -> Self {
+        self.insecure_skip_verify = enabled;
+        self
+    }
+
+    /// Sets the retry policy for transient failures.
+    ///
+    /// Retries are disabled unless a policy is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::{ClientConfig, RetryPolicy};
+    ///
+    /// let config = ClientConfig::new().with_retry_policy(RetryPolicy::new());
+    /// assert_eq!(config.retry_policy.max_attempts, 3);
+    /// ```
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
 }
 
 /// HTTP client for OCI registry operations.
 ///
 /// This client handles all HTTP communication with OCI registries, including
 /// connection pooling, timeouts, and TLS configuration.
+///
+/// Registries that delegate authentication to a token service (Docker Hub,
+/// GHCR, Harbor, ...) are supported transparently: when a request is rejected
+/// with a Bearer `WWW-Authenticate` challenge, the client exchanges its
+/// credentials (or nothing, for anonymous pulls) for a token at the challenge
+/// realm, caches the token per scope until it expires, and retries the request.
+/// Clones share the same token cache.
+///
+/// Transient failures (rate limits, 5xx responses, dropped connections) of
+/// idempotent requests are retried according to the configured [`RetryPolicy`].
 #[derive(Debug, Clone)]
 pub struct Client {
     /// The underlying HTTP client
@@ -156,6 +314,10 @@ pub struct Client {
     registry_url: String,
     /// Optional credentials for authenticated requests
     credentials: Option<crate::auth::Credentials>,
+    /// Bearer tokens obtained from the registry's token service, keyed by scope
+    tokens: Arc<Mutex<TokenCache>>,
+    /// Retry policy for transient failures
+    retry_policy: RetryPolicy,
 }
 
 impl Client {
@@ -194,9 +356,16 @@ impl Client {
     /// # Arguments
     ///
     /// * `registry_url` - The base URL of the OCI registry (e.g., "http://localhost:5000")
-    /// * `config` - Client configuration (timeout, connection pooling, etc.)
+    /// * `config` - Client configuration (timeout, connection pooling, TLS, etc.)
     /// * `credentials` - Optional credentials for authenticated requests
     ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry URL is empty
+    /// - A configured CA certificate, client certificate or key cannot be read or parsed
+    /// - The HTTP client cannot be created
+    ///
     /// # Examples
     ///
     /// ```
@@ -217,9 +386,11 @@ impl Client {
         let normalized_url = Self::normalize_url(registry_url)?;
 
         // Build the HTTP client with the provided configuration
-        let http_client = ReqwestClient::builder()
+        let builder = ReqwestClient::builder()
             .timeout(Duration::from_secs(config.timeout_seconds))
-            .pool_max_idle_per_host(config.max_idle_per_host)
+            .pool_max_idle_per_host(config.max_idle_per_host);
+
+        let http_client = tls::configure(builder, &config)?
             .build()
             .map_err(|e| RexError::network_with_source("Failed to create HTTP client", e))?;
 
@@ -227,6 +398,8 @@ impl Client {
             http_client,
             registry_url: normalized_url,
             credentials,
+            tokens: Arc::new(Mutex::new(TokenCache::new())),
+            retry_policy: config.retry_policy,
         })
     }
 
@@ -292,18 +465,7 @@ impl Client {
     pub fn check_version(&self) -> Result<RegistryVersion> {
         let url = format!("{}/v2/", self.registry_url);
 
-        let mut request = self.http_client.get(&url);
-
-        // Add Authorization header if credentials are present
-        if let Some(ref creds) = self.credentials
-            && let Some(auth_header) = creds.to_header_value()
-        {
-            request = request.header("Authorization", auth_header);
-        }
-
-        let response = request
-            .send()
-            .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+        let response = self.send("", || self.http_client.get(&url))?;
 
         // Extract version information from headers before consuming response
         let api_version = response
@@ -317,6 +479,52 @@ impl Client {
         Ok(RegistryVersion { api_version })
     }
 
+    /// Fetches a registry endpoint outside the repository API.
+    ///
+    /// This is used for extension endpoints such as `/v2/_oci/ext/discover`
+    /// or Zot's `/v2/_zot/ext/search`, whose responses are interpreted by the
+    /// caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path on the registry, starting with `/v2/`
+    /// * `query` - Query parameters to append
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let body = client.fetch_extension("/v2/_oci/ext/discover", &[])?;
+    /// println!("{}", String::from_utf8_lossy(&body));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The response body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry does not serve the endpoint (`NotFound`)
+    /// - The registry is unreachable
+    /// - Authentication is required but not provided
+    pub fn fetch_extension(&self, path: &str, query: &[(&str, &str)]) -> Result<Vec<u8>> {
+        let url = format!("{}{}", self.registry_url, path);
+
+        let response = self.send("", || self.http_client.get(&url).query(query))?;
+        let response = Self::check_response_status(response)?;
+
+        let bytes = response
+            .bytes()
+            .map_err(|e| RexError::network_with_source("Failed to read extension response", e))?;
+        Ok(bytes.to_vec())
+    }
+
     /// Fetches the catalog of repositories from the registry.
     ///
     /// This method performs a GET request to the `/v2/_catalog` endpoint to retrieve
@@ -384,18 +592,7 @@ impl Client {
         }
 
         loop {
-            let mut request = self.http_client.get(&url);
-
-            // Add Authorization header if credentials are present
-            if let Some(ref creds) = self.credentials
-                && let Some(auth_header) = creds.to_header_value()
-            {
-                request = request.header("Authorization", auth_header);
-            }
-
-            let response = request
-                .send()
-                .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+            let response = self.send("registry:catalog:*", || self.http_client.get(&url))?;
 
             // Extract Link header for pagination before consuming response
             let next_path = Self::extract_next_link(response.headers());
@@ -496,19 +693,10 @@ impl Client {
             url.push_str(&format!("?n={}", n));
         }
 
-        loop {
-            let mut request = self.http_client.get(&url);
-
-            // Add Authorization header if credentials are present
-            if let Some(ref creds) = self.credentials
-                && let Some(auth_header) = creds.to_header_value()
-            {
-                request = request.header("Authorization", auth_header);
-            }
+        let scope = Self::pull_scope(repository);
 
-            let response = request
-                .send()
-                .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+        loop {
+            let response = self.send(&scope, || self.http_client.get(&url))?;
 
             // Extract Link header for pagination before consuming response
             let next_path = Self::extract_next_link(response.headers());
@@ -592,28 +780,12 @@ impl Client {
             self.registry_url, repository, reference
         );
 
-        let mut request = self
-            .http_client
-            .get(&url)
-            // Add Accept headers for OCI and Docker manifest types
-            .header(
-                "Accept",
-                "application/vnd.oci.image.manifest.v1+json, \
-                 application/vnd.oci.image.index.v1+json, \
-                 application/vnd.docker.distribution.manifest.v2+json, \
-                 application/vnd.docker.distribution.manifest.list.v2+json",
-            );
-
-        // Add Authorization header if credentials are present
-        if let Some(ref creds) = self.credentials
-            && let Some(auth_header) = creds.to_header_value()
-        {
-            request = request.header("Authorization", auth_header);
-        }
-
-        let response = request
-            .send()
-            .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+        let response = self.send(&Self::pull_scope(repository), || {
+            self.http_client
+                .get(&url)
+                // Add Accept headers for OCI and Docker manifest types
+                .header("Accept", MANIFEST_ACCEPT)
+        })?;
 
         // Extract Docker-Content-Digest header before consuming response
         let digest_from_header = response
@@ -634,7 +806,6 @@ impl Client {
             d
         } else {
             // Compute digest from bytes using sha256
-            use sha2::{Digest as Sha2Digest, Sha256};
             let mut hasher = Sha256::new();
             hasher.update(&manifest_bytes);
             format!("sha256:{:x}", hasher.finalize())
@@ -643,6 +814,215 @@ impl Client {
         Ok((manifest_bytes.to_vec(), digest))
     }
 
+    /// Uploads a manifest under a tag or digest.
+    ///
+    /// This performs a PUT request to the `/v2/<name>/manifests/<reference>`
+    /// endpoint. The bytes are sent unchanged, so the manifest keeps its
+    /// digest; all blobs it references must already exist in the repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `reference` - The tag (e.g., "prod") or digest to store the manifest under
+    /// * `manifest` - The raw manifest bytes
+    /// * `media_type` - The manifest media type, sent as `Content-Type`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    /// use librex::oci::manifest_media_type;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    ///
+    /// let (manifest, _) = client.fetch_manifest("myapp", "sha-abc123")?;
+    /// let media_type = manifest_media_type(&manifest)?;
+    /// let digest = client.put_manifest("myapp", "prod", &manifest, &media_type)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The manifest digest, from the `Docker-Content-Digest` header or
+    /// computed from the bytes if the registry does not send one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry is unreachable
+    /// - Push access is denied
+    /// - The registry rejects the manifest (invalid, or referencing unknown blobs)
+    pub fn put_manifest(
+        &self,
+        repository: &str,
+        reference: &str,
+        manifest: &[u8],
+        media_type: &str,
+    ) -> Result<String> {
+        let url = format!(
+            "{}/v2/{}/manifests/{}",
+            self.registry_url, repository, reference
+        );
+
+        let response = self.send(&Self::push_scope(repository), || {
+            self.http_client
+                .put(&url)
+                .header(reqwest::header::CONTENT_TYPE, media_type)
+                .body(manifest.to_vec())
+        })?;
+
+        if response.status() == StatusCode::BAD_REQUEST {
+            let error_body = response.text().unwrap_or_default();
+            return Err(RexError::validation(format!(
+                "Registry rejected manifest {}:{}: {}",
+                repository, reference, error_body
+            )));
+        }
+        let response = Self::check_response_status(response)?;
+
+        let digest = match response
+            .headers()
+            .get("Docker-Content-Digest")
+            .and_then(|v| v.to_str().ok())
+        {
+            Some(digest) => digest.to_string(),
+            None => format!("sha256:{:x}", Sha256::digest(manifest)),
+        };
+
+        Ok(digest)
+    }
+
+    /// Checks a manifest with a HEAD request, without downloading it.
+    ///
+    /// This performs a HEAD request to the `/v2/<name>/manifests/<reference>`
+    /// endpoint and reads the digest, size and media type from the response
+    /// headers. Unlike [`Client::fetch_manifest`], it does not count as a pull
+    /// on Docker Hub.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `reference` - The tag name (e.g., "latest") or digest (e.g., "sha256:abc123...")
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    ///
+    /// let info = client.head_manifest("alpine", "latest")?;
+    /// if let Some(digest) = info.digest {
+    ///     println!("alpine:latest is {}", digest);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The manifest metadata. The digest is `None` only when the registry does
+    /// not send a `Docker-Content-Digest` header and `reference` is a tag; use
+    /// [`Client::fetch_manifest`] to compute it in that case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry is unreachable
+    /// - The repository or reference does not exist
+    /// - Authentication is required but not provided
+    pub fn head_manifest(&self, repository: &str, reference: &str) -> Result<ContentInfo> {
+        let url = format!(
+            "{}/v2/{}/manifests/{}",
+            self.registry_url, repository, reference
+        );
+
+        let response = self.send(&Self::pull_scope(repository), || {
+            self.http_client
+                .head(&url)
+                .header("Accept", MANIFEST_ACCEPT)
+        })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(RexError::not_found(
+                "manifest",
+                &format!("{} in {}", reference, repository),
+            ));
+        }
+        let response = Self::check_response_status(response)?;
+
+        let mut info = Self::content_info(response.headers());
+        if info.digest.is_none() && Digest::from_str(reference).is_ok() {
+            info.digest = Some(reference.to_string());
+        }
+
+        Ok(info)
+    }
+
+    /// Checks a blob with a HEAD request, without downloading it.
+    ///
+    /// This performs a HEAD request to the `/v2/<name>/blobs/<digest>` endpoint
+    /// and reads the digest, size and media type from the response headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `digest` - The content digest of the blob (e.g., "sha256:abc123...")
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    ///
+    /// let info = client.head_blob(
+    ///     "alpine",
+    ///     "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+    /// )?;
+    /// println!("Blob size: {:?}", info.size);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The blob metadata. The digest is always set: it falls back to the
+    /// requested digest when the registry does not send one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid
+    /// - The registry is unreachable
+    /// - The blob does not exist
+    /// - Authentication is required but not provided
+    pub fn head_blob(&self, repository: &str, digest: &str) -> Result<ContentInfo> {
+        Digest::from_str(digest)?;
+
+        let url = format!("{}/v2/{}/blobs/{}", self.registry_url, repository, digest);
+        let response = self.send(&Self::pull_scope(repository), || {
+            self.http_client.head(&url)
+        })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(RexError::not_found(
+                "blob",
+                &format!("{} in {}", digest, repository),
+            ));
+        }
+        let response = Self::check_response_status(response)?;
+
+        let mut info = Self::content_info(response.headers());
+        info.digest.get_or_insert_with(|| digest.to_string());
+
+        Ok(info)
+    }
+
     /// Fetches a blob (layer or config) from the registry.
     ///
     /// This method performs a GET request to the `/v2/<name>/blobs/<digest>` endpoint
@@ -686,52 +1066,319 @@ impl Client {
     /// - The downloaded content does not match the expected digest
     /// - The digest format is invalid
     pub fn fetch_blob(&self, repository: &str, digest: &str) -> Result<Vec<u8>> {
-        // Parse and validate the digest format
+        let mut blob_bytes = Vec::new();
+        self.fetch_blob_to(repository, digest, &mut blob_bytes, |_, _| {})?;
+        Ok(blob_bytes)
+    }
+
+    /// Opens a blob for streaming.
+    ///
+    /// Returns a [`BlobReader`] over the response body that verifies the
+    /// content against `digest` as it is read. The reader fails at end of
+    /// stream if the content does not match, so the data must only be trusted
+    /// once it has been read completely.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `digest` - The content digest of the blob (e.g., "sha256:abc123...")
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    /// use std::io::Read;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut reader = client.fetch_blob_reader(
+    ///     "alpine",
+    ///     "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+    /// )?;
+    ///
+    /// let mut buf = [0u8; 8192];
+    /// while reader.read(&mut buf)? > 0 {}
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid or its algorithm is unsupported
+    /// - The registry is unreachable
+    /// - The blob does not exist
+    /// - Authentication is required but not provided
+    pub fn fetch_blob_reader(
+        &self,
+        repository: &str,
+        digest: &str,
+    ) -> Result<BlobReader<Response>> {
         let expected_digest = Digest::from_str(digest)?;
+        crate::blob::ensure_supported(&expected_digest)?;
 
         let url = format!("{}/v2/{}/blobs/{}", self.registry_url, repository, digest);
 
-        let mut request = self.http_client.get(&url);
+        let response = self.send(&Self::pull_scope(repository), || self.http_client.get(&url))?;
+        let response = Self::check_response_status(response)?;
 
-        // Add Authorization header if credentials are present
-        if let Some(ref creds) = self.credentials
-            && let Some(auth_header) = creds.to_header_value()
-        {
-            request = request.header("Authorization", auth_header);
+        let total_size = response.content_length();
+        BlobReader::new(response, expected_digest, total_size)
+    }
+
+    /// Streams a blob into a writer, verifying its digest on the way.
+    ///
+    /// The blob is copied in chunks, so memory use does not depend on the blob
+    /// size. `progress` is called after every chunk with the number of bytes
+    /// written so far and the total size, if the registry reported one.
+    ///
+    /// When the digest does not match, an error is returned after the content
+    /// has been written; callers writing to a file should discard it (see
+    /// [`BlobStore::insert_with`](crate::blob::BlobStore::insert_with)).
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `digest` - The content digest of the blob (e.g., "sha256:abc123...")
+    /// * `writer` - Destination for the blob content
+    /// * `progress` - Callback receiving `(bytes_written, total_size)`
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    /// use std::fs::File;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut file = File::create("layer.tar.gz")?;
+    ///
+    /// client.fetch_blob_to(
+    ///     "alpine",
+    ///     "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+    ///     &mut file,
+    ///     |done, total| eprintln!("{} / {:?} bytes", done, total),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The blob cannot be fetched (see [`Client::fetch_blob_reader`])
+    /// - The connection fails while streaming
+    /// - The writer fails
+    /// - The downloaded content does not match the expected digest
+    pub fn fetch_blob_to<W, F>(
+        &self,
+        repository: &str,
+        digest: &str,
+        writer: &mut W,
+        progress: F,
+    ) -> Result<u64>
+    where
+        W: Write + ?Sized,
+        F: FnMut(u64, Option<u64>),
+    {
+        let mut reader = self.fetch_blob_reader(repository, digest)?;
+        crate::blob::copy(&mut reader, writer, progress)
+    }
+
+    /// Streams a blob into a partially downloaded file, resuming where it ends.
+    ///
+    /// If `file` already holds the first N bytes of the blob, only the rest is
+    /// requested with `Range: bytes=N-`. The registry's answer decides how to
+    /// continue:
+    /// - `206 Partial Content` with a `Content-Range` starting at N: the new
+    ///   bytes are appended
+    /// - `200 OK` (ranges not supported) or a `Content-Range` that does not
+    ///   start at N: the file is truncated and the blob downloaded in full
+    /// - `416 Range Not Satisfiable`: the file may already hold the whole
+    ///   blob; if it does not verify, it is truncated and downloaded again
+    ///
+    /// In every case the digest is computed over the complete file content, so
+    /// a corrupt partial file is detected.
+    ///
+    /// `file` must be opened for reading and writing. Use
+    /// [`BlobStore::resume_with`](crate::blob::BlobStore::resume_with) to manage
+    /// the partial file.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `digest` - The content digest of the blob (e.g., "sha256:abc123...")
+    /// * `file` - The partial download, possibly empty
+    /// * `progress` - Callback receiving `(bytes_in_file, total_size)`
+    ///
+    /// # Returns
+    ///
+    /// The size of the complete blob.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    /// use std::fs::OpenOptions;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut file = OpenOptions::new()
+    ///     .read(true)
+    ///     .write(true)
+    ///     .create(true)
+    ///     .truncate(false)
+    ///     .open("layer.tar.gz.partial")?;
+    ///
+    /// client.fetch_blob_resume(
+    ///     "alpine",
+    ///     "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+    ///     &mut file,
+    ///     |done, total| eprintln!("{} / {:?} bytes", done, total),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid or its algorithm is unsupported
+    /// - The blob cannot be fetched
+    /// - The file cannot be read, truncated or written
+    /// - The complete content does not match the expected digest
+    pub fn fetch_blob_resume<F>(
+        &self,
+        repository: &str,
+        digest: &str,
+        file: &mut File,
+        progress: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(u64, Option<u64>),
+    {
+        let expected_digest = Digest::from_str(digest)?;
+        crate::blob::ensure_supported(&expected_digest)?;
+
+        let offset = file
+            .metadata()
+            .map_err(|e| file_error("Failed to read partial blob", e))?
+            .len();
+
+        if offset == 0 {
+            return self.fetch_blob_to(repository, digest, file, progress);
         }
 
-        let response = request
-            .send()
-            .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+        let url = format!("{}/v2/{}/blobs/{}", self.registry_url, repository, digest);
+        let response = self.send(&Self::pull_scope(repository), || {
+            self.http_client
+                .get(&url)
+                .header(reqwest::header::RANGE, format!("bytes={}-", offset))
+        })?;
+
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                match Self::parse_content_range(response.headers()) {
+                    Some((start, total)) if start == offset => {
+                        file.seek(SeekFrom::Start(0))
+                            .map_err(|e| file_error("Failed to read partial blob", e))?;
+                        let mut reader =
+                            BlobReader::new(response, expected_digest, total)?.with_prefix(file)?;
+                        crate::blob::copy(&mut reader, file, progress)
+                    }
+                    // The registry sent a range we did not ask for
+                    _ => self.restart_blob_download(repository, digest, file, progress),
+                }
+            }
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                file.seek(SeekFrom::Start(0))
+                    .map_err(|e| file_error("Failed to read partial blob", e))?;
+                let reader =
+                    BlobReader::new(std::io::empty(), expected_digest, None)?.with_prefix(file)?;
+
+                if reader.verify().is_ok() {
+                    return Ok(reader.bytes_read());
+                }
+                self.restart_blob_download(repository, digest, file, progress)
+            }
+            _ => {
+                let response = Self::check_response_status(response)?;
+
+                // Ranges are not supported: the response holds the whole blob
+                Self::truncate(file)?;
+                let total_size = response.content_length();
+                let mut reader = BlobReader::new(response, expected_digest, total_size)?;
+                crate::blob::copy(&mut reader, file, progress)
+            }
+        }
+    }
 
-        let response = Self::check_response_status(response)?;
+    /// Discards a partial download and fetches the whole blob into `file`.
+    fn restart_blob_download<F>(
+        &self,
+        repository: &str,
+        digest: &str,
+        file: &mut File,
+        progress: F,
+    ) -> Result<u64>
+    where
+        F: FnMut(u64, Option<u64>),
+    {
+        Self::truncate(file)?;
+        self.fetch_blob_to(repository, digest, file, progress)
+    }
 
-        // Download the blob content
-        let blob_bytes = response
-            .bytes()
-            .map_err(|e| RexError::network_with_source("Failed to read blob response", e))?;
+    /// Empties a file and rewinds it.
+    fn truncate(file: &mut File) -> Result<()> {
+        file.set_len(0)
+            .and_then(|_| file.seek(SeekFrom::Start(0)))
+            .map(|_| ())
+            .map_err(|e| file_error("Failed to truncate partial blob", e))
+    }
 
-        // Verify the digest matches what we downloaded
-        // Currently only supporting SHA256
-        if expected_digest.algorithm() != "sha256" {
-            return Err(RexError::validation(format!(
-                "Unsupported digest algorithm: {}. Only sha256 is currently supported",
-                expected_digest.algorithm()
-            )));
+    /// Reads content metadata from response headers.
+    ///
+    /// The size comes from the `Content-Length` header rather than from the
+    /// response body, which is always empty for HEAD requests.
+    fn content_info(headers: &reqwest::header::HeaderMap) -> ContentInfo {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.trim().to_string())
+        };
+
+        ContentInfo {
+            digest: header("Docker-Content-Digest"),
+            size: header("Content-Length").and_then(|s| s.parse().ok()),
+            media_type: header("Content-Type"),
         }
+    }
 
-        let mut hasher = Sha256::new();
-        hasher.update(&blob_bytes);
-        let computed_hash = format!("{:x}", hasher.finalize());
+    /// Parses a `Content-Range: bytes <start>-<end>/<total>` header.
+    ///
+    /// Returns the start offset and the total size (`None` when given as `*`).
+    fn parse_content_range(headers: &reqwest::header::HeaderMap) -> Option<(u64, Option<u64>)> {
+        let value = headers.get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+        let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
 
-        if computed_hash != expected_digest.hex() {
-            return Err(RexError::validation(format!(
-                "Blob digest mismatch: expected {}, computed sha256:{}",
-                digest, computed_hash
-            )));
+        let start = start.trim().parse::<u64>().ok()?;
+        let end = end.trim().parse::<u64>().ok()?;
+        if end < start {
+            return None;
         }
 
-        Ok(blob_bytes.to_vec())
+        let total = match total.trim() {
+            "*" => None,
+            total => Some(total.parse::<u64>().ok()?),
+        };
+
+        Some((start, total))
     }
 
     /// Deletes a manifest from the registry by digest.
@@ -785,18 +1432,8 @@ impl Client {
             self.registry_url, repository, digest
         );
 
-        let mut request = self.http_client.delete(&url);
-
-        // Add Authorization header if credentials are present
-        if let Some(ref creds) = self.credentials
-            && let Some(auth_header) = creds.to_header_value()
-        {
-            request = request.header("Authorization", auth_header);
-        }
-
-        let response = request
-            .send()
-            .map_err(|e| Self::translate_reqwest_error(e, &self.registry_url))?;
+        let scope = format!("repository:{}:delete", repository);
+        let response = self.send(&scope, || self.http_client.delete(&url))?;
 
         let status = response.status();
 
@@ -846,6 +1483,233 @@ impl Client {
         }
     }
 
+    /// Returns the token scope used for read access to a repository.
+    fn pull_scope(repository: &str) -> String {
+        format!("repository:{}:pull", repository)
+    }
+
+    /// Returns the token scope used for write access to a repository.
+    fn push_scope(repository: &str) -> String {
+        format!("repository:{}:pull,push", repository)
+    }
+
+    /// Sends a request, handling the Bearer token challenge/exchange flow.
+    ///
+    /// `build` must produce the request without an Authorization header; it is
+    /// invoked again if the request needs to be retried with a fresh token.
+    /// `scope` identifies the access being requested and keys the token cache.
+    ///
+    /// The flow is:
+    /// 1. Send the request with a cached token for `scope`, or with the
+    ///    configured credentials if no token is cached.
+    /// 2. On a 401 carrying a Bearer `WWW-Authenticate` challenge, fetch a token
+    ///    from the challenge realm and cache it.
+    /// 3. Retry the request once with the new token.
+    ///
+    /// Any other response (including a second 401) is returned unchanged so
+    /// that callers can translate the status as usual. Each request is retried
+    /// on transient failures, see [`Client::execute`].
+    fn send<F>(&self, scope: &str, build: F) -> Result<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let cached_token = self.cached_token(scope);
+        let response = self.execute(&self.registry_url, || {
+            self.authorize(build(), cached_token.as_deref())
+        })?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        // A static bearer token cannot be exchanged for anything else
+        if matches!(self.credentials, Some(Credentials::Bearer { .. })) {
+            return Ok(response);
+        }
+
+        let challenge = match Self::parse_bearer_challenge(response.headers()) {
+            Some(challenge) => challenge,
+            None => return Ok(response),
+        };
+
+        let token = self.fetch_token(&challenge, scope)?;
+
+        self.execute(&self.registry_url, || self.authorize(build(), Some(&token)))
+    }
+
+    /// Sends a request, retrying transient failures according to the retry policy.
+    ///
+    /// Responses with a retryable status are retried after an exponential
+    /// backoff delay, or after the delay given in `Retry-After` for 429 and 503
+    /// responses. Connection failures and timeouts are retried the same way.
+    /// When the attempts are exhausted the last response or error is returned.
+    ///
+    /// Only idempotent requests (GET, HEAD, PUT, DELETE) are retried: a failed
+    /// POST or PATCH may still have been processed by the registry. Upload
+    /// chunks recover on their own, see [`Client::upload_chunk`].
+    ///
+    /// `target` names the server in error messages.
+    fn execute<F>(&self, target: &str, build: F) -> Result<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let policy = &self.retry_policy;
+        let mut attempt = 1;
+
+        loop {
+            let (client, request) = build().build_split();
+            let request = request.map_err(|e| Self::translate_reqwest_error(e, target))?;
+            let idempotent = matches!(
+                *request.method(),
+                Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+            );
+            let result = client.execute(request);
+
+            let delay = match &result {
+                _ if !idempotent => None,
+                Ok(response) if policy.is_retryable_status(response.status().as_u16()) => {
+                    let retry_after = match response.status() {
+                        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => {
+                            Self::parse_retry_after(response.headers()).map(Duration::from_secs)
+                        }
+                        _ => None,
+                    };
+                    policy.delay(attempt, retry_after)
+                }
+                Err(e) if policy.retry_network_errors && (e.is_connect() || e.is_timeout()) => {
+                    policy.delay(attempt, None)
+                }
+                _ => None,
+            };
+
+            match delay {
+                Some(delay) => std::thread::sleep(delay),
+                None => return result.map_err(|e| Self::translate_reqwest_error(e, target)),
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Adds the Authorization header to a request.
+    ///
+    /// A bearer token takes precedence over the configured credentials.
+    fn authorize(&self, request: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+        if let Some(token) = token {
+            return request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        if let Some(ref creds) = self.credentials
+            && let Some(auth_header) = creds.to_header_value()
+        {
+            return request.header("Authorization", auth_header);
+        }
+
+        request
+    }
+
+    /// Extracts a Bearer challenge from the WWW-Authenticate header, if any.
+    fn parse_bearer_challenge(headers: &reqwest::header::HeaderMap) -> Option<AuthChallenge> {
+        headers
+            .get_all(reqwest::header::WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| AuthChallenge::parse(value).ok())
+            .find(|challenge| challenge.is_bearer())
+    }
+
+    /// Returns the cached, unexpired token for a scope.
+    fn cached_token(&self, scope: &str) -> Option<String> {
+        self.tokens.lock().ok()?.get(scope)
+    }
+
+    /// Requests a bearer token from the token service named in a challenge.
+    ///
+    /// The request carries the challenge's `service` and `scope` parameters
+    /// (falling back to `scope` when the challenge has none). Basic credentials
+    /// are sent with a GET request; identity tokens are exchanged with an OAuth2
+    /// `refresh_token` grant (POST); otherwise the token is requested
+    /// anonymously. The token is cached under `scope` until it expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The token service is unreachable
+    /// - The token service rejects the credentials (401/403)
+    /// - The response does not contain a token
+    fn fetch_token(&self, challenge: &AuthChallenge, scope: &str) -> Result<String> {
+        let mut params: Vec<(&str, &str)> = Vec::new();
+
+        if let Some(ref service) = challenge.service {
+            params.push(("service", service));
+        }
+
+        let requested_scope = challenge
+            .scope
+            .as_deref()
+            .or(Some(scope).filter(|s| !s.is_empty()));
+
+        // Multiple scopes are space separated. The token GET takes them as
+        // repeated parameters, the OAuth2 form as one space-separated value
+        let scopes: Vec<&str> = requested_scope
+            .into_iter()
+            .flat_map(str::split_whitespace)
+            .collect();
+        let joined_scope = scopes.join(" ");
+
+        if let Some(Credentials::IdentityToken { ref token }) = self.credentials {
+            if !scopes.is_empty() {
+                params.push(("scope", &joined_scope));
+            }
+            params.extend([
+                ("grant_type", "refresh_token"),
+                ("refresh_token", token.as_str()),
+                ("client_id", "rex"),
+            ]);
+        } else {
+            params.extend(scopes.iter().map(|s| ("scope", *s)));
+        }
+
+        let response = self.execute(&challenge.realm, || match self.credentials {
+            Some(Credentials::IdentityToken { .. }) => {
+                self.http_client.post(&challenge.realm).form(&params)
+            }
+            Some(ref creds @ Credentials::Basic { .. }) => {
+                let request = self.http_client.get(&challenge.realm).query(&params);
+                match creds.to_header_value() {
+                    Some(auth_header) => request.header("Authorization", auth_header),
+                    None => request,
+                }
+            }
+            _ => self.http_client.get(&challenge.realm).query(&params),
+        })?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Err(RexError::authentication(
+                format!("Token service at {} rejected the request", challenge.realm),
+                Some(status.as_u16()),
+            ));
+        }
+
+        let response = Self::check_response_status(response)?;
+
+        let token_response: TokenResponse = response
+            .json()
+            .map_err(|e| RexError::validation_with_source("Failed to parse token response", e))?;
+
+        let token = token_response
+            .bearer_token()
+            .ok_or_else(|| RexError::validation("Token response did not contain a token"))?
+            .to_string();
+
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.insert(scope, token.clone(), token_response.expires_in);
+        }
+
+        Ok(token)
+    }
+
     /// Extracts the next page URL from the Link header.
     ///
     /// The OCI Distribution Specification uses the Link header for pagination:
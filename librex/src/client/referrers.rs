@@ -0,0 +1,181 @@
+//! Referrers (artifacts attached to a manifest).
+//!
+//! Signatures, SBOMs and attestations are stored as manifests whose `subject`
+//! points at the image they describe. Registries implementing OCI
+//! Distribution 1.1 list them at `GET /v2/<name>/referrers/<digest>`, as an
+//! image index that may be paginated with `Link` headers.
+//!
+//! Older registries answer that endpoint with `404`. Clients pushing to them
+//! maintain the same index by hand under the tag `<alg>-<hex>` (the "tag
+//! schema"), which is read instead.
+
+use super::Client;
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::oci::{Descriptor, ImageIndex};
+use reqwest::StatusCode;
+use std::str::FromStr;
+
+/// Media type of the index returned by the referrers API.
+const REFERRERS_ACCEPT: &str = "application/vnd.oci.image.index.v1+json";
+
+impl Client {
+    /// Lists the manifests that refer to a manifest digest.
+    ///
+    /// This performs a GET request to the `/v2/<name>/referrers/<digest>`
+    /// endpoint and follows `Link` pagination. If the registry does not
+    /// implement the endpoint (`404`), the tag-schema index is read instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository
+    /// * `digest` - The digest of the manifest the referrers point at
+    /// * `artifact_type` - Only return referrers of this artifact type
+    ///   (e.g., "application/vnd.dev.sigstore.bundle.v0.3+json")
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    ///
+    /// let referrers = client.fetch_referrers(
+    ///     "myapp",
+    ///     "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b",
+    ///     None,
+    /// )?;
+    /// for referrer in referrers {
+    ///     println!("{} {:?}", referrer.digest(), referrer.artifact_type());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// The referrer descriptors, empty if nothing refers to the digest. The
+    /// artifact type filter is applied here as well, since registries may
+    /// ignore it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid
+    /// - The registry is unreachable
+    /// - Authentication is required but not provided
+    /// - The registry returns an invalid index
+    pub fn fetch_referrers(
+        &self,
+        repository: &str,
+        digest: &str,
+        artifact_type: Option<&str>,
+    ) -> Result<Vec<Descriptor>> {
+        let parsed = Digest::from_str(digest)?;
+
+        let scope = Self::pull_scope(repository);
+        let mut url = format!(
+            "{}/v2/{}/referrers/{}",
+            self.registry_url, repository, digest
+        );
+        let mut query = artifact_type.map(|t| [("artifactType", t)]);
+        let mut referrers = Vec::new();
+
+        loop {
+            let response = self.send(&scope, || {
+                let request = self
+                    .http_client
+                    .get(&url)
+                    .header("Accept", REFERRERS_ACCEPT);
+                match &query {
+                    Some(query) => request.query(query),
+                    None => request,
+                }
+            })?;
+
+            if response.status() == StatusCode::NOT_FOUND && referrers.is_empty() {
+                return self.fetch_referrers_tag(repository, &parsed, artifact_type);
+            }
+
+            let next_path = Self::extract_next_link(response.headers());
+            let response = Self::check_response_status(response)?;
+            let bytes = response.bytes().map_err(|e| {
+                RexError::network_with_source("Failed to read referrers response", e)
+            })?;
+            referrers.extend(parse_referrers(&bytes)?);
+
+            match next_path {
+                Some(path) => {
+                    // The next link already carries any filter
+                    url = self.resolve_location(&path);
+                    query = None;
+                }
+                None => break,
+            }
+        }
+
+        Ok(filter_artifact_type(referrers, artifact_type))
+    }
+
+    /// Reads the referrers index kept under the tag schema (`sha256-<hex>`).
+    ///
+    /// A missing tag means there are no referrers.
+    fn fetch_referrers_tag(
+        &self,
+        repository: &str,
+        digest: &Digest,
+        artifact_type: Option<&str>,
+    ) -> Result<Vec<Descriptor>> {
+        match self.fetch_manifest(repository, &referrers_tag(digest)) {
+            Ok((bytes, _)) => Ok(filter_artifact_type(
+                parse_referrers(&bytes)?,
+                artifact_type,
+            )),
+            Err(RexError::NotFound { .. }) => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Returns the tag-schema tag for a digest: `<algorithm>-<hex>`.
+pub(crate) fn referrers_tag(digest: &Digest) -> String {
+    format!("{}-{}", digest.algorithm(), digest.hex())
+}
+
+/// Returns the subject digest named by a tag-schema tag: `<alg>-<hex>`,
+/// optionally followed by a suffix as in cosign's `sha256-<hex>.sig`.
+pub fn referrers_tag_subject(tag: &str) -> Option<String> {
+    let (algorithm, rest) = tag.split_once('-')?;
+    if !matches!(algorithm, "sha256" | "sha512") {
+        return None;
+    }
+    let hex = rest.split('.').next()?;
+    let digest = format!("{}:{}", algorithm, hex);
+    Digest::from_str(&digest).ok().map(|_| digest)
+}
+
+/// Parses a referrers index into its descriptors.
+fn parse_referrers(bytes: &[u8]) -> Result<Vec<Descriptor>> {
+    let index: ImageIndex = serde_json::from_slice(bytes)
+        .map_err(|e| RexError::validation_with_source("Failed to parse referrers index", e))?;
+    Ok(index.manifests().clone())
+}
+
+/// Keeps the referrers of the requested artifact type.
+fn filter_artifact_type(
+    referrers: Vec<Descriptor>,
+    artifact_type: Option<&str>,
+) -> Vec<Descriptor> {
+    match artifact_type {
+        Some(wanted) => referrers
+            .into_iter()
+            .filter(|d| {
+                d.artifact_type()
+                    .as_ref()
+                    .is_some_and(|t| t.to_string() == wanted)
+            })
+            .collect(),
+        None => referrers,
+    }
+}
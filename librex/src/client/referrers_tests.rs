@@ -0,0 +1,197 @@
+use super::*;
+use mockito::Matcher;
+
+const SUBJECT: &str = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+const SBOM_TYPE: &str = "application/spdx+json";
+const SIGNATURE_TYPE: &str = "application/vnd.dev.cosign.artifact.sig.v1+json";
+
+/// Builds a referrers index with one entry per (digest hex character, artifact type).
+fn referrers_index(entries: &[(char, &str)]) -> String {
+    let manifests: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|(c, artifact_type)| {
+            serde_json::json!({
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": format!("sha256:{}", c.to_string().repeat(64)),
+                "size": 512,
+                "artifactType": artifact_type,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.index.v1+json",
+        "manifests": manifests,
+    })
+    .to_string()
+}
+
+#[test]
+fn test_fetch_referrers() {
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", SUBJECT).as_str())
+        .match_header("Accept", "application/vnd.oci.image.index.v1+json")
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(referrers_index(&[('a', SBOM_TYPE), ('b', SIGNATURE_TYPE)]))
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let referrers = client.fetch_referrers("myapp", SUBJECT, None).unwrap();
+
+    mock.assert();
+    assert_eq!(referrers.len(), 2);
+    assert_eq!(
+        referrers[0].artifact_type().as_ref().unwrap().to_string(),
+        SBOM_TYPE
+    );
+}
+
+#[test]
+fn test_fetch_referrers_follows_pagination() {
+    let mut server = mockito::Server::new();
+    let path = format!("/v2/myapp/referrers/{}", SUBJECT);
+
+    let first = server
+        .mock("GET", path.as_str())
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_header("Link", &format!("<{}?last=a>; rel=\"next\"", path))
+        .with_body(referrers_index(&[('a', SBOM_TYPE)]))
+        .create();
+    let second = server
+        .mock("GET", path.as_str())
+        .match_query(Matcher::UrlEncoded("last".into(), "a".into()))
+        .with_status(200)
+        .with_body(referrers_index(&[('b', SIGNATURE_TYPE)]))
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let referrers = client.fetch_referrers("myapp", SUBJECT, None).unwrap();
+
+    first.assert();
+    second.assert();
+    assert_eq!(referrers.len(), 2);
+}
+
+#[test]
+fn test_fetch_referrers_filters_artifact_type() {
+    let mut server = mockito::Server::new();
+
+    // The registry ignores the filter; the client applies it
+    let mock = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", SUBJECT).as_str())
+        .match_query(Matcher::UrlEncoded("artifactType".into(), SBOM_TYPE.into()))
+        .with_status(200)
+        .with_body(referrers_index(&[('a', SBOM_TYPE), ('b', SIGNATURE_TYPE)]))
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let referrers = client
+        .fetch_referrers("myapp", SUBJECT, Some(SBOM_TYPE))
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(referrers.len(), 1);
+    assert_eq!(
+        referrers[0].digest().to_string(),
+        format!("sha256:{}", "a".repeat(64))
+    );
+}
+
+#[test]
+fn test_fetch_referrers_falls_back_to_tag_schema() {
+    let mut server = mockito::Server::new();
+    let tag = SUBJECT.replace(':', "-");
+
+    let api = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", SUBJECT).as_str())
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .create();
+    let fallback = server
+        .mock("GET", format!("/v2/myapp/manifests/{}", tag).as_str())
+        .with_status(200)
+        .with_body(referrers_index(&[('a', SBOM_TYPE), ('b', SIGNATURE_TYPE)]))
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let referrers = client
+        .fetch_referrers("myapp", SUBJECT, Some(SIGNATURE_TYPE))
+        .unwrap();
+
+    api.assert();
+    fallback.assert();
+    assert_eq!(referrers.len(), 1);
+}
+
+#[test]
+fn test_fetch_referrers_without_any() {
+    let mut server = mockito::Server::new();
+
+    let _api = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", SUBJECT).as_str())
+        .with_status(404)
+        .create();
+    let _fallback = server
+        .mock(
+            "GET",
+            Matcher::Regex("^/v2/myapp/manifests/sha256-".to_string()),
+        )
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let referrers = client.fetch_referrers("myapp", SUBJECT, None).unwrap();
+
+    assert!(referrers.is_empty());
+}
+
+#[test]
+fn test_fetch_referrers_invalid_digest() {
+    let client = Client::new("http://localhost:5000", None).unwrap();
+    let result = client.fetch_referrers("myapp", "latest", None);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_fetch_referrers_invalid_index() {
+    let mut server = mockito::Server::new();
+
+    let _mock = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", SUBJECT).as_str())
+        .with_status(200)
+        .with_body("not json")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.fetch_referrers("myapp", SUBJECT, None);
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_referrers_tag() {
+    let digest = Digest::from_str(SUBJECT).unwrap();
+    assert_eq!(referrers::referrers_tag(&digest), SUBJECT.replace(':', "-"));
+}
+
+#[test]
+fn test_referrers_tag_subject() {
+    let hex = "a".repeat(64);
+    assert_eq!(
+        referrers::referrers_tag_subject(&format!("sha256-{}", hex)),
+        Some(format!("sha256:{}", hex))
+    );
+    assert_eq!(
+        referrers::referrers_tag_subject(&format!("sha256-{}.sig", hex)),
+        Some(format!("sha256:{}", hex))
+    );
+    assert_eq!(referrers::referrers_tag_subject("v1.0-rc1"), None);
+    assert_eq!(referrers::referrers_tag_subject("sha256-abc"), None);
+}
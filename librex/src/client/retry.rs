@@ -0,0 +1,209 @@
+//! Retry policy for transient registry failures.
+//!
+//! Rate limits (429), overloaded or restarting registries (5xx) and dropped
+//! connections are usually transient. The policy decides which failures are
+//! retried and how long to wait in between: exponential backoff from
+//! `base_delay`, capped at `max_delay`, optionally jittered, or the delay the
+//! registry asked for in a `Retry-After` header.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+/// Status codes retried by the default policy.
+const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[429, 500, 502, 503, 504];
+
+/// Retry policy applied to the idempotent requests made by a [`Client`](super::Client).
+///
+/// # Examples
+///
+/// ```
+/// use librex::client::{ClientConfig, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_secs(1))
+///     .with_max_delay(Duration::from_secs(60));
+///
+/// let config = ClientConfig::new().with_retry_policy(policy);
+/// assert_eq!(config.retry_policy.max_attempts, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first request (default: 3)
+    ///
+    /// A value of 1 disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry (default: 500ms)
+    pub base_delay: Duration,
+    /// Upper bound for the backoff delay (default: 30 seconds)
+    pub max_delay: Duration,
+    /// Randomize each backoff delay between half and all of its value (default: true)
+    pub jitter: bool,
+    /// HTTP status codes that are retried (default: 429, 500, 502, 503, 504)
+    pub retryable_statuses: Vec<u16>,
+    /// Retry connection failures and timeouts (default: true)
+    pub retry_network_errors: bool,
+    /// Longest `Retry-After` the client waits for (default: 60 seconds)
+    ///
+    /// When a registry asks for a longer pause the request fails immediately
+    /// with the registry's response, rather than blocking for that long.
+    pub max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES.to_vec(),
+            retry_network_errors: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a retry policy with default values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new();
+    /// assert_eq!(policy.max_attempts, 3);
+    /// assert!(policy.is_retryable_status(429));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that never retries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::none();
+    /// assert_eq!(policy.max_attempts, 1);
+    /// ```
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first request.
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the upper bound for the backoff delay.
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Enables or disables jitter on backoff delays.
+    pub fn with_jitter(mut self, enabled: bool) -> Self {
+        self.jitter = enabled;
+        self
+    }
+
+    /// Sets the HTTP status codes that are retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new().with_retryable_statuses([429, 503]);
+    /// assert!(!policy.is_retryable_status(500));
+    /// ```
+    pub fn with_retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Enables or disables retrying connection failures and timeouts.
+    pub fn with_retry_network_errors(mut self, enabled: bool) -> Self {
+        self.retry_network_errors = enabled;
+        self
+    }
+
+    /// Sets the longest `Retry-After` the client waits for.
+    pub fn with_max_retry_after(mut self, delay: Duration) -> Self {
+        self.max_retry_after = delay;
+        self
+    }
+
+    /// Returns true if responses with this status code are retried.
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Returns the backoff delay before retry number `retry` (starting at 1),
+    /// before jitter is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::client::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::new()
+    ///     .with_base_delay(Duration::from_secs(1))
+    ///     .with_max_delay(Duration::from_secs(5));
+    ///
+    /// assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    /// assert_eq!(policy.backoff(3), Duration::from_secs(4));
+    /// assert_eq!(policy.backoff(4), Duration::from_secs(5));
+    /// ```
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(31);
+        self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay)
+    }
+
+    /// Returns how long to wait before the next attempt, or `None` to give up.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The number of the attempt that just failed (starting at 1)
+    /// * `retry_after` - The delay requested by the registry, if any
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_retry_after).then_some(retry_after);
+        }
+
+        let backoff = self.backoff(attempt);
+        if !self.jitter {
+            return Some(backoff);
+        }
+
+        let half = backoff / 2;
+        let spread = (backoff - half).as_millis() as u64;
+        let random = RandomState::new().hash_one(attempt);
+        Some(half + Duration::from_millis(random % (spread + 1)))
+    }
+}
@@ -0,0 +1,87 @@
+use super::retry::*;
+use std::time::Duration;
+
+#[test]
+fn test_retry_policy_defaults() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.max_attempts, 3);
+    assert_eq!(policy.base_delay, Duration::from_millis(500));
+    assert_eq!(policy.max_delay, Duration::from_secs(30));
+    assert!(policy.jitter);
+    assert!(policy.retry_network_errors);
+    for status in [429, 500, 502, 503, 504] {
+        assert!(policy.is_retryable_status(status));
+    }
+    assert!(!policy.is_retryable_status(401));
+    assert!(!policy.is_retryable_status(404));
+}
+
+#[test]
+fn test_retry_policy_none_never_retries() {
+    let policy = RetryPolicy::none();
+    assert_eq!(policy.delay(1, None), None);
+    assert_eq!(policy.delay(1, Some(Duration::ZERO)), None);
+}
+
+#[test]
+fn test_retry_policy_max_attempts_at_least_one() {
+    let policy = RetryPolicy::new().with_max_attempts(0);
+    assert_eq!(policy.max_attempts, 1);
+}
+
+#[test]
+fn test_retry_policy_backoff_is_exponential_and_capped() {
+    let policy = RetryPolicy::new()
+        .with_base_delay(Duration::from_millis(100))
+        .with_max_delay(Duration::from_millis(700));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(400));
+    assert_eq!(policy.backoff(4), Duration::from_millis(700));
+    assert_eq!(policy.backoff(100), Duration::from_millis(700));
+}
+
+#[test]
+fn test_retry_policy_delay_without_jitter() {
+    let policy = RetryPolicy::new()
+        .with_max_attempts(3)
+        .with_base_delay(Duration::from_millis(100))
+        .with_jitter(false);
+
+    assert_eq!(policy.delay(1, None), Some(Duration::from_millis(100)));
+    assert_eq!(policy.delay(2, None), Some(Duration::from_millis(200)));
+    assert_eq!(policy.delay(3, None), None);
+}
+
+#[test]
+fn test_retry_policy_delay_with_jitter_stays_in_range() {
+    let policy = RetryPolicy::new()
+        .with_max_attempts(10)
+        .with_base_delay(Duration::from_millis(100));
+
+    for attempt in 1..10 {
+        let backoff = policy.backoff(attempt);
+        let delay = policy.delay(attempt, None).unwrap();
+        assert!(delay >= backoff / 2);
+        assert!(delay <= backoff);
+    }
+}
+
+#[test]
+fn test_retry_policy_honors_retry_after() {
+    let policy = RetryPolicy::new().with_max_retry_after(Duration::from_secs(60));
+
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_secs(45))),
+        Some(Duration::from_secs(45))
+    );
+    assert_eq!(policy.delay(1, Some(Duration::from_secs(61))), None);
+}
+
+#[test]
+fn test_retry_policy_custom_statuses() {
+    let policy = RetryPolicy::new().with_retryable_statuses([429]);
+    assert!(policy.is_retryable_status(429));
+    assert!(!policy.is_retryable_status(503));
+}
@@ -183,7 +183,7 @@ fn test_client_config_dockerhub_compat_default() {
     use super::ClientConfig;
 
     let config = ClientConfig::default();
-    assert_eq!(config.dockerhub_compat, false);
+    assert!(!config.dockerhub_compat);
 }
 
 #[test]
@@ -191,10 +191,10 @@ fn test_client_config_with_dockerhub_compat() {
     use super::ClientConfig;
 
     let config = ClientConfig::new().with_dockerhub_compat(true);
-    assert_eq!(config.dockerhub_compat, true);
+    assert!(config.dockerhub_compat);
 
     let config2 = ClientConfig::new().with_dockerhub_compat(false);
-    assert_eq!(config2.dockerhub_compat, false);
+    assert!(!config2.dockerhub_compat);
 }
 
 #[test]
@@ -207,7 +207,7 @@ fn test_client_config_builder_chaining_with_dockerhub_compat() {
         .with_dockerhub_compat(true);
     assert_eq!(config.timeout_seconds, 120);
     assert_eq!(config.max_idle_per_host, 50);
-    assert_eq!(config.dockerhub_compat, true);
+    assert!(config.dockerhub_compat);
 }
 
 // Tests for catalog operations
@@ -1297,3 +1297,1268 @@ fn test_client_with_credentials() {
     mock.assert();
     assert!(result.is_ok());
 }
+
+/// Builds a Bearer challenge header pointing at a mock token server.
+fn bearer_challenge(token_server: &mockito::Server, scope: &str) -> String {
+    format!(
+        r#"Bearer realm="{}/token",service="registry.test",scope="{}""#,
+        token_server.url(),
+        scope
+    )
+}
+
+#[test]
+fn test_bearer_challenge_anonymous_token_exchange() {
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let challenge_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:pull"),
+        )
+        .create();
+
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_header("Authorization", Matcher::Missing)
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("service".into(), "registry.test".into()),
+            Matcher::UrlEncoded("scope".into(), "repository:alpine:pull".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"token":"anon-token","expires_in":300}"#)
+        .create();
+
+    let tags_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer anon-token")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    let tags = client.fetch_tags("alpine").unwrap();
+
+    challenge_mock.assert();
+    token_mock.assert();
+    tags_mock.assert();
+    assert_eq!(tags, vec!["latest"]);
+}
+
+#[test]
+fn test_bearer_challenge_with_basic_credentials() {
+    use crate::auth::Credentials;
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let challenge_mock = registry
+        .mock("GET", "/v2/_catalog")
+        .match_header("Authorization", "Basic dXNlcjpwYXNz")
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "registry:catalog:*"),
+        )
+        .create();
+
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_header("Authorization", "Basic dXNlcjpwYXNz")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("service".into(), "registry.test".into()),
+            Matcher::UrlEncoded("scope".into(), "registry:catalog:*".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"access_token":"user-token"}"#)
+        .create();
+
+    let catalog_mock = registry
+        .mock("GET", "/v2/_catalog")
+        .match_header("Authorization", "Bearer user-token")
+        .with_status(200)
+        .with_body(r#"{"repositories":["alpine","nginx"]}"#)
+        .create();
+
+    let client = Client::new(&registry.url(), Some(Credentials::basic("user", "pass"))).unwrap();
+    let repos = client.fetch_catalog().unwrap();
+
+    challenge_mock.assert();
+    token_mock.assert();
+    catalog_mock.assert();
+    assert_eq!(repos, vec!["alpine", "nginx"]);
+}
+
+#[test]
+fn test_bearer_token_is_cached_per_scope() {
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let challenge_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:pull"),
+        )
+        .expect(1)
+        .create();
+
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"token":"cached-token","expires_in":300}"#)
+        .expect(1)
+        .create();
+
+    let tags_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer cached-token")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .expect(3)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    client.fetch_tags("alpine").unwrap();
+    client.fetch_tags("alpine").unwrap();
+
+    // Clones share the token cache
+    client.clone().fetch_tags("alpine").unwrap();
+
+    challenge_mock.assert();
+    token_mock.assert();
+    tags_mock.assert();
+}
+
+#[test]
+fn test_bearer_token_refetched_after_expiry() {
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let challenge_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:pull"),
+        )
+        .expect(2)
+        .create();
+
+    // A zero lifetime means the token is expired as soon as it is cached
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"token":"short-token","expires_in":0}"#)
+        .expect(2)
+        .create();
+
+    let tags_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer short-token")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .expect(2)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    client.fetch_tags("alpine").unwrap();
+    client.fetch_tags("alpine").unwrap();
+
+    challenge_mock.assert();
+    token_mock.assert();
+    tags_mock.assert();
+}
+
+#[test]
+fn test_bearer_token_server_rejects_credentials() {
+    use crate::auth::Credentials;
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let _challenge_mock = registry
+        .mock("GET", "/v2/")
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &format!(
+                r#"Bearer realm="{}/token",service="registry.test""#,
+                token_server.url()
+            ),
+        )
+        .create();
+
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_query(Matcher::Any)
+        .with_status(401)
+        .with_body("invalid credentials")
+        .create();
+
+    let client = Client::new(&registry.url(), Some(Credentials::basic("user", "wrong"))).unwrap();
+    let err = client.check_version().unwrap_err();
+
+    token_mock.assert();
+    assert!(matches!(
+        err,
+        RexError::Authentication {
+            status_code: Some(401),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_bearer_token_response_without_token() {
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let _challenge_mock = registry
+        .mock("GET", "/v2/")
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &format!(r#"Bearer realm="{}/token""#, token_server.url()),
+        )
+        .create();
+
+    let _token_mock = token_server
+        .mock("GET", "/token")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"expires_in":300}"#)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    let err = client.check_version().unwrap_err();
+
+    assert!(matches!(err, RexError::Validation { .. }));
+}
+
+#[test]
+fn test_basic_challenge_is_not_exchanged() {
+    let mut registry = mockito::Server::new();
+
+    let mock = registry
+        .mock("GET", "/v2/")
+        .with_status(401)
+        .with_header("WWW-Authenticate", r#"Basic realm="Registry Access""#)
+        .expect(1)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    let err = client.check_version().unwrap_err();
+
+    mock.assert();
+    assert!(matches!(
+        err,
+        RexError::Authentication {
+            status_code: Some(401),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_bearer_challenge_on_delete_manifest() {
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+    let path = format!("/v2/alpine/manifests/{}", digest);
+
+    let _challenge_mock = registry
+        .mock("DELETE", path.as_str())
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:delete"),
+        )
+        .create();
+
+    let token_mock = token_server
+        .mock("GET", "/token")
+        .match_query(Matcher::UrlEncoded(
+            "scope".into(),
+            "repository:alpine:delete".into(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"token":"delete-token"}"#)
+        .create();
+
+    let delete_mock = registry
+        .mock("DELETE", path.as_str())
+        .match_header("Authorization", "Bearer delete-token")
+        .with_status(202)
+        .create();
+
+    let client = Client::new(&registry.url(), None).unwrap();
+    client.delete_manifest("alpine", digest).unwrap();
+
+    token_mock.assert();
+    delete_mock.assert();
+}
+
+#[test]
+fn test_bearer_challenge_with_identity_token() {
+    use crate::auth::Credentials;
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let _challenge_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:pull"),
+        )
+        .create();
+
+    let token_mock = token_server
+        .mock("POST", "/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("grant_type".into(), "refresh_token".into()),
+            Matcher::UrlEncoded("refresh_token".into(), "refresh-123".into()),
+            Matcher::UrlEncoded("service".into(), "registry.test".into()),
+            Matcher::UrlEncoded("scope".into(), "repository:alpine:pull".into()),
+        ]))
+        .with_status(200)
+        .with_body(r#"{"access_token":"oauth-token","expires_in":300}"#)
+        .create();
+
+    let tags_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer oauth-token")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .create();
+
+    let client = Client::new(
+        &registry.url(),
+        Some(Credentials::identity_token("refresh-123")),
+    )
+    .unwrap();
+    let tags = client.fetch_tags("alpine").unwrap();
+
+    token_mock.assert();
+    tags_mock.assert();
+    assert_eq!(tags, vec!["latest"]);
+}
+
+#[test]
+fn test_identity_token_sends_scopes_as_one_value() {
+    use crate::auth::Credentials;
+    use mockito::Matcher;
+
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let _challenge_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &bearer_challenge(&token_server, "repository:alpine:pull repository:base:pull"),
+        )
+        .create();
+
+    // OAuth2 takes a single space-separated scope, not repeated parameters
+    let token_mock = token_server
+        .mock("POST", "/token")
+        .match_body(Matcher::Regex(
+            r"(^|&)scope=repository%3Aalpine%3Apull(\+|%20)repository%3Abase%3Apull(&|$)"
+                .to_string(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"access_token":"oauth-token","expires_in":300}"#)
+        .create();
+
+    let _tags_mock = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer oauth-token")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .create();
+
+    let client = Client::new(
+        &registry.url(),
+        Some(Credentials::identity_token("refresh-123")),
+    )
+    .unwrap();
+    client.fetch_tags("alpine").unwrap();
+
+    token_mock.assert();
+}
+
+/// Retry policy with short, deterministic delays for tests.
+fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new()
+        .with_max_attempts(max_attempts)
+        .with_base_delay(Duration::from_millis(1))
+        .with_jitter(false)
+}
+
+pub(super) fn retrying_client(url: &str, max_attempts: u32) -> Client {
+    let config = ClientConfig::new().with_retry_policy(fast_retry_policy(max_attempts));
+    Client::with_config(url, config, None).unwrap()
+}
+
+#[test]
+fn test_client_config_retries_disabled_by_default() {
+    let config = ClientConfig::default();
+    assert_eq!(config.retry_policy, RetryPolicy::none());
+}
+
+#[test]
+fn test_retry_on_rate_limit_then_success() {
+    let mut server = mockito::Server::new();
+    let rate_limited = server
+        .mock("GET", "/v2/")
+        .with_status(429)
+        .with_header("Retry-After", "0")
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/v2/")
+        .with_status(200)
+        .with_header("Docker-Distribution-API-Version", "registry/2.0")
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let version = client.check_version().unwrap();
+
+    rate_limited.assert();
+    ok.assert();
+    assert_eq!(version.api_version, Some("registry/2.0".to_string()));
+}
+
+#[test]
+fn test_retry_on_server_error_then_success() {
+    let mut server = mockito::Server::new();
+    let unavailable = server
+        .mock("GET", "/v2/alpine/tags/list")
+        .with_status(502)
+        .expect(2)
+        .create();
+    let ok = server
+        .mock("GET", "/v2/alpine/tags/list")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let tags = client.fetch_tags("alpine").unwrap();
+
+    unavailable.assert();
+    ok.assert();
+    assert_eq!(tags, vec!["latest".to_string()]);
+}
+
+#[test]
+fn test_retry_gives_up_after_max_attempts() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/v2/")
+        .with_status(503)
+        .expect(3)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let result = client.check_version();
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::Server { .. })));
+}
+
+#[test]
+fn test_retry_waits_for_retry_after() {
+    let mut server = mockito::Server::new();
+    let rate_limited = server
+        .mock("GET", "/v2/")
+        .with_status(429)
+        .with_header("Retry-After", "1")
+        .expect(1)
+        .create();
+    let ok = server
+        .mock("GET", "/v2/")
+        .with_status(200)
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 2);
+    let start = std::time::Instant::now();
+    let result = client.check_version();
+
+    rate_limited.assert();
+    ok.assert();
+    assert!(result.is_ok());
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn test_retry_gives_up_when_retry_after_too_long() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/v2/")
+        .with_status(429)
+        .with_header("Retry-After", "3600")
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let result = client.check_version();
+
+    mock.assert();
+    match result {
+        Err(RexError::RateLimit { retry_after, .. }) => assert_eq!(retry_after, Some(3600)),
+        other => panic!("Expected RateLimit error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_retry_skips_non_retryable_status() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/v2/missing/tags/list")
+        .with_status(404)
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let result = client.fetch_tags("missing");
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
+
+#[test]
+fn test_retry_custom_statuses() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/v2/")
+        .with_status(500)
+        .expect(1)
+        .create();
+
+    let policy = fast_retry_policy(3).with_retryable_statuses([429]);
+    let config = ClientConfig::new().with_retry_policy(policy);
+    let client = Client::with_config(&server.url(), config, None).unwrap();
+    let result = client.check_version();
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::Server { .. })));
+}
+
+#[test]
+fn test_retry_network_error() {
+    // Reserve a port, then close it so connections are refused
+    let port = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    let url = format!("http://127.0.0.1:{}", port);
+
+    let client = retrying_client(&url, 3);
+    let start = std::time::Instant::now();
+    let result = client.check_version();
+
+    assert!(matches!(result, Err(RexError::Network { .. })));
+    // Two backoff delays of 1ms and 2ms were applied
+    assert!(start.elapsed() >= Duration::from_millis(3));
+}
+
+#[test]
+fn test_retry_skips_post() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(503)
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&server.url(), 3);
+    let result = client.start_upload("myapp");
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::Server { .. })));
+}
+
+#[test]
+fn test_retry_applies_to_token_service() {
+    let mut registry = mockito::Server::new();
+    let mut token_server = mockito::Server::new();
+
+    let challenge = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", mockito::Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &format!(
+                r#"Bearer realm="{}/token",service="registry",scope="repository:alpine:pull""#,
+                token_server.url()
+            ),
+        )
+        .create();
+    let tags = registry
+        .mock("GET", "/v2/alpine/tags/list")
+        .match_header("Authorization", "Bearer abc")
+        .with_status(200)
+        .with_body(r#"{"name":"alpine","tags":["latest"]}"#)
+        .create();
+    let token_unavailable = token_server
+        .mock("GET", "/token")
+        .match_query(mockito::Matcher::Any)
+        .with_status(503)
+        .expect(1)
+        .create();
+    let token_ok = token_server
+        .mock("GET", "/token")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"token":"abc"}"#)
+        .expect(1)
+        .create();
+
+    let client = retrying_client(&registry.url(), 3);
+    let result = client.fetch_tags("alpine").unwrap();
+
+    challenge.assert();
+    tags.assert();
+    token_unavailable.assert();
+    token_ok.assert();
+    assert_eq!(result, vec!["latest".to_string()]);
+}
+
+#[test]
+fn test_fetch_blob_to_streams_and_reports_progress() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = vec![7u8; 3 * crate::blob::CHUNK_SIZE + 10];
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body(&blob_content)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut output = Vec::new();
+    let mut updates = Vec::new();
+    let written = client
+        .fetch_blob_to("alpine", &digest, &mut output, |done, total| {
+            updates.push((done, total))
+        })
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(written, blob_content.len() as u64);
+    assert_eq!(output, blob_content);
+    assert!(!updates.is_empty());
+    assert!(updates.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(
+        updates.last(),
+        Some(&(blob_content.len() as u64, Some(blob_content.len() as u64)))
+    );
+}
+
+#[test]
+fn test_fetch_blob_to_digest_mismatch() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2";
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body("wrong content")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.fetch_blob_to("alpine", digest, &mut Vec::new(), |_, _| {});
+
+    mock.assert();
+    match result {
+        Err(RexError::Validation { message, .. }) => {
+            assert!(message.contains("Blob digest mismatch"))
+        }
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_fetch_blob_reader() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::io::Read;
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"streamed blob";
+    let digest = format!("sha256:{:x}", Sha256::digest(blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body(blob_content)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut reader = client.fetch_blob_reader("alpine", &digest).unwrap();
+    assert_eq!(reader.total_size(), Some(blob_content.len() as u64));
+
+    let mut output = Vec::new();
+    reader.read_to_end(&mut output).unwrap();
+
+    mock.assert();
+    assert_eq!(output, blob_content);
+}
+
+#[test]
+fn test_fetch_blob_reader_unsupported_algorithm_skips_request() {
+    let mut server = mockito::Server::new();
+    let digest = format!("sha512:{}", "a".repeat(128));
+
+    let mock = server.mock("GET", mockito::Matcher::Any).expect(0).create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.fetch_blob_reader("alpine", &digest);
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+/// Creates a partial blob file holding `content`, ready for resuming.
+fn partial_blob_file(dir: &tempfile::TempDir, content: &[u8]) -> std::fs::File {
+    let path = dir.path().join("blob.partial");
+    std::fs::write(&path, content).unwrap();
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .unwrap()
+}
+
+/// Reads back the whole content of a blob file.
+fn read_blob_file(file: &mut std::fs::File) -> Vec<u8> {
+    use std::io::Read;
+
+    let mut content = Vec::new();
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_end(&mut content).unwrap();
+    content
+}
+
+#[test]
+fn test_fetch_blob_resume_with_partial_content() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"hello, resumable world".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", "bytes=5-")
+        .with_status(206)
+        .with_header("Content-Range", "bytes 5-21/22")
+        .with_body(&blob_content[5..])
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, &blob_content[..5]);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut updates = Vec::new();
+    let size = client
+        .fetch_blob_resume("alpine", &digest, &mut file, |done, total| {
+            updates.push((done, total))
+        })
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(size, blob_content.len() as u64);
+    assert_eq!(read_blob_file(&mut file), blob_content);
+    assert_eq!(updates.last(), Some(&(22, Some(22))));
+}
+
+#[test]
+fn test_fetch_blob_resume_without_partial_content() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"fresh download".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body(&blob_content)
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, b"");
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let size = client
+        .fetch_blob_resume("alpine", &digest, &mut file, |_, _| {})
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(size, blob_content.len() as u64);
+    assert_eq!(read_blob_file(&mut file), blob_content);
+}
+
+#[test]
+fn test_fetch_blob_resume_range_ignored() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"hello, resumable world".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    // The registry does not support ranges and sends the whole blob
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", "bytes=5-")
+        .with_status(200)
+        .with_body(&blob_content)
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, &blob_content[..5]);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let size = client
+        .fetch_blob_resume("alpine", &digest, &mut file, |_, _| {})
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(size, blob_content.len() as u64);
+    assert_eq!(read_blob_file(&mut file), blob_content);
+}
+
+#[test]
+fn test_fetch_blob_resume_unexpected_content_range_restarts() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"hello, resumable world".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+    let path = format!("/v2/alpine/blobs/{}", digest);
+
+    let ranged = server
+        .mock("GET", path.as_str())
+        .match_header("Range", "bytes=5-")
+        .with_status(206)
+        .with_header("Content-Range", "bytes 0-21/22")
+        .with_body(&blob_content)
+        .create();
+    let full = server
+        .mock("GET", path.as_str())
+        .match_header("Range", mockito::Matcher::Missing)
+        .with_status(200)
+        .with_body(&blob_content)
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, &blob_content[..5]);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let size = client
+        .fetch_blob_resume("alpine", &digest, &mut file, |_, _| {})
+        .unwrap();
+
+    ranged.assert();
+    full.assert();
+    assert_eq!(size, blob_content.len() as u64);
+    assert_eq!(read_blob_file(&mut file), blob_content);
+}
+
+#[test]
+fn test_fetch_blob_resume_complete_partial_file() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"already complete".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", "bytes=16-")
+        .with_status(416)
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, &blob_content);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let size = client
+        .fetch_blob_resume("alpine", &digest, &mut file, |_, _| {})
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(size, blob_content.len() as u64);
+    assert_eq!(read_blob_file(&mut file), blob_content);
+}
+
+#[test]
+fn test_fetch_blob_resume_corrupt_partial_file() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"hello, resumable world".to_vec();
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", "bytes=5-")
+        .with_status(206)
+        .with_header("Content-Range", "bytes 5-21/22")
+        .with_body(&blob_content[5..])
+        .create();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let mut file = partial_blob_file(&temp_dir, b"HELLO");
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.fetch_blob_resume("alpine", &digest, &mut file, |_, _| {});
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_parse_content_range() {
+    let parse = |value: &str| {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::CONTENT_RANGE, value.parse().unwrap());
+        Client::parse_content_range(&headers)
+    };
+
+    assert_eq!(parse("bytes 5-21/22"), Some((5, Some(22))));
+    assert_eq!(parse("bytes 100-199/*"), Some((100, None)));
+    assert_eq!(parse("bytes 10-5/22"), None);
+    assert_eq!(parse("bytes */22"), None);
+    assert_eq!(parse("items 0-1/2"), None);
+    assert_eq!(
+        Client::parse_content_range(&reqwest::header::HeaderMap::new()),
+        None
+    );
+}
+
+#[test]
+fn test_head_manifest() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let mock = server
+        .mock("HEAD", "/v2/alpine/manifests/latest")
+        .match_header(
+            "Accept",
+            mockito::Matcher::Regex("application/vnd.oci.image.index.v1\\+json".to_string()),
+        )
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_header("Content-Length", "1638")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let info = client.head_manifest("alpine", "latest").unwrap();
+
+    mock.assert();
+    assert_eq!(
+        info,
+        ContentInfo {
+            digest: Some(digest.to_string()),
+            size: Some(1638),
+            media_type: Some("application/vnd.oci.image.index.v1+json".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_head_manifest_without_digest_header() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let _by_tag = server
+        .mock("HEAD", "/v2/alpine/manifests/latest")
+        .with_status(200)
+        .create();
+    let _by_digest = server
+        .mock("HEAD", format!("/v2/alpine/manifests/{}", digest).as_str())
+        .with_status(200)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+
+    let by_tag = client.head_manifest("alpine", "latest").unwrap();
+    assert_eq!(by_tag.digest, None);
+
+    let by_digest = client.head_manifest("alpine", digest).unwrap();
+    assert_eq!(by_digest.digest, Some(digest.to_string()));
+}
+
+#[test]
+fn test_head_manifest_not_found() {
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("HEAD", "/v2/alpine/manifests/missing")
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.head_manifest("alpine", "missing");
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
+
+#[test]
+fn test_head_manifest_with_bearer_challenge() {
+    let mut server = mockito::Server::new();
+    let url = server.url();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let challenge = server
+        .mock("HEAD", "/v2/alpine/manifests/latest")
+        .match_header("Authorization", mockito::Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &format!(
+                "Bearer realm=\"{}/token\",service=\"registry\",scope=\"repository:alpine:pull\"",
+                url
+            ),
+        )
+        .create();
+    let token = server
+        .mock("GET", "/token")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"token": "head-token"}"#)
+        .create();
+    let head = server
+        .mock("HEAD", "/v2/alpine/manifests/latest")
+        .match_header("Authorization", "Bearer head-token")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .create();
+
+    let client = Client::new(&url, None).unwrap();
+    let info = client.head_manifest("alpine", "latest").unwrap();
+
+    challenge.assert();
+    token.assert();
+    head.assert();
+    assert_eq!(info.digest, Some(digest.to_string()));
+}
+
+#[test]
+fn test_head_blob() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2";
+
+    let mock = server
+        .mock("HEAD", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_header("Content-Length", "3208942")
+        .with_header("Content-Type", "application/octet-stream")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let info = client.head_blob("alpine", digest).unwrap();
+
+    mock.assert();
+    assert_eq!(info.digest, Some(digest.to_string()));
+    assert_eq!(info.size, Some(3_208_942));
+    assert_eq!(
+        info.media_type,
+        Some("application/octet-stream".to_string())
+    );
+}
+
+#[test]
+fn test_head_blob_not_found() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2";
+
+    let mock = server
+        .mock("HEAD", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.head_blob("alpine", digest);
+
+    mock.assert();
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
+
+#[test]
+fn test_head_blob_invalid_digest() {
+    let client = Client::new("http://localhost:5000", None).unwrap();
+    let result = client.head_blob("alpine", "not-a-digest");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_put_manifest() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let manifest =
+        br#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json"}"#;
+    let digest = format!("sha256:{:x}", Sha256::digest(manifest));
+
+    let mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .match_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .match_body(manifest.to_vec())
+        .with_status(201)
+        .with_header("Docker-Content-Digest", &digest)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client
+        .put_manifest(
+            "myapp",
+            "prod",
+            manifest,
+            "application/vnd.oci.image.manifest.v1+json",
+        )
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(result, digest);
+}
+
+#[test]
+fn test_put_manifest_computes_digest_without_header() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+
+    let mut server = mockito::Server::new();
+    let manifest = b"{}";
+
+    let _mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .with_status(201)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client
+        .put_manifest("myapp", "prod", manifest, "application/json")
+        .unwrap();
+
+    assert_eq!(result, format!("sha256:{:x}", Sha256::digest(manifest)));
+}
+
+#[test]
+fn test_put_manifest_rejected() {
+    let mut server = mockito::Server::new();
+
+    let _mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .with_status(400)
+        .with_body(r#"{"errors":[{"code":"MANIFEST_BLOB_UNKNOWN"}]}"#)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.put_manifest("myapp", "prod", b"{}", "application/json");
+
+    match result {
+        Err(RexError::Validation { message, .. }) => {
+            assert!(message.contains("MANIFEST_BLOB_UNKNOWN"))
+        }
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_put_manifest_denied() {
+    let mut server = mockito::Server::new();
+
+    let _mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .with_status(403)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.put_manifest("myapp", "prod", b"{}", "application/json");
+
+    assert!(matches!(
+        result,
+        Err(RexError::Authentication {
+            status_code: Some(403),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_fetch_extension() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::UrlEncoded(
+            "query".into(),
+            "{ImageList}".into(),
+        ))
+        .with_status(200)
+        .with_body("{\"data\":{}}")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let body = client
+        .fetch_extension("/v2/_zot/ext/search", &[("query", "{ImageList}")])
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(body, b"{\"data\":{}}");
+}
+
+#[test]
+fn test_fetch_extension_not_found() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.fetch_extension("/v2/_oci/ext/discover", &[]);
+
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
@@ -0,0 +1,111 @@
+//! TLS configuration for the HTTP client.
+//!
+//! Loads extra root CAs and client identities from PEM files and applies them,
+//! together with the insecure mode switch, to a reqwest client builder.
+
+use super::ClientConfig;
+use crate::error::{Result, RexError};
+use reqwest::blocking::ClientBuilder;
+use reqwest::{Certificate, Identity};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extensions loaded when a CA path is a directory.
+const CA_FILE_EXTENSIONS: &[&str] = &["pem", "crt", "cer"];
+
+/// Applies the TLS settings from `config` to a client builder.
+///
+/// # Errors
+///
+/// Returns an error if a CA certificate, client certificate or key cannot be
+/// read or parsed.
+pub(super) fn configure(
+    mut builder: ClientBuilder,
+    config: &ClientConfig,
+) -> Result<ClientBuilder> {
+    for path in &config.ca_certs {
+        for cert in load_ca_certs(path)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    if let Some(ref cert_path) = config.client_cert {
+        let identity = load_identity(cert_path, config.client_key.as_deref())?;
+        builder = builder.identity(identity);
+    } else if config.client_key.is_some() {
+        return Err(RexError::validation(
+            "A client key was configured without a client certificate",
+        ));
+    }
+
+    if config.insecure_skip_verify {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder)
+}
+
+/// Loads all certificates from a PEM file or a directory of PEM files.
+fn load_ca_certs(path: &Path) -> Result<Vec<Certificate>> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| {
+                RexError::config_with_source(
+                    "Failed to read CA certificate directory",
+                    path.to_str(),
+                    e,
+                )
+            })?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.is_file()
+                    && p.extension()
+                        .and_then(|ext| ext.to_str())
+                        .is_some_and(|ext| CA_FILE_EXTENSIONS.contains(&ext))
+            })
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut certs = Vec::new();
+    for file in &files {
+        let pem = read_file(file, "Failed to read CA certificate")?;
+        let bundle = Certificate::from_pem_bundle(&pem).map_err(|e| {
+            RexError::config_with_source("Failed to parse CA certificate", file.to_str(), e)
+        })?;
+        certs.extend(bundle);
+    }
+
+    if certs.is_empty() {
+        return Err(RexError::config("No CA certificates found", path.to_str()));
+    }
+
+    Ok(certs)
+}
+
+/// Loads a client identity from a certificate and an optional separate key.
+fn load_identity(cert_path: &Path, key_path: Option<&Path>) -> Result<Identity> {
+    let mut pem = read_file(cert_path, "Failed to read client certificate")?;
+
+    if let Some(key_path) = key_path {
+        let key = read_file(key_path, "Failed to read client key")?;
+        pem.push(b'\n');
+        pem.extend(key);
+    }
+
+    Identity::from_pem(&pem).map_err(|e| {
+        RexError::config_with_source(
+            "Failed to parse client certificate or key",
+            cert_path.to_str(),
+            e,
+        )
+    })
+}
+
+/// Reads a file, mapping failures to a configuration error.
+fn read_file(path: &Path, message: &str) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| RexError::config_with_source(message, path.to_str(), e))
+}
@@ -0,0 +1,307 @@
+use super::*;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose,
+};
+use rustls::RootCertStore;
+use rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::server::WebPkiClientVerifier;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// Generates a self-signed CA certificate.
+fn generate_ca() -> (Certificate, KeyPair) {
+    let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params
+        .distinguished_name
+        .push(DnType::CommonName, "rex test CA");
+    params.key_usages = vec![
+        KeyUsagePurpose::KeyCertSign,
+        KeyUsagePurpose::CrlSign,
+        KeyUsagePurpose::DigitalSignature,
+    ];
+
+    let key = KeyPair::generate().unwrap();
+    let cert = params.self_signed(&key).unwrap();
+    (cert, key)
+}
+
+/// Issues a leaf certificate signed by the CA.
+fn issue_cert(
+    ca: &Certificate,
+    ca_key: &KeyPair,
+    name: &str,
+    purpose: ExtendedKeyUsagePurpose,
+) -> (Certificate, KeyPair) {
+    let mut params = CertificateParams::new(vec![name.to_string()]).unwrap();
+    params.distinguished_name.push(DnType::CommonName, name);
+    params.extended_key_usages = vec![purpose];
+
+    let key = KeyPair::generate().unwrap();
+    let cert = params.signed_by(&key, ca, ca_key).unwrap();
+    (cert, key)
+}
+
+/// Starts a minimal HTTPS registry that answers every request to `/v2/`
+/// with 200. When `client_ca` is set, clients must present a certificate
+/// signed by it.
+fn spawn_tls_registry(
+    cert: &Certificate,
+    key: &KeyPair,
+    client_ca: Option<&Certificate>,
+) -> String {
+    let certs = vec![cert.der().clone()];
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.serialize_der()));
+
+    let builder = rustls::ServerConfig::builder();
+    let config = match client_ca {
+        Some(ca) => {
+            let mut roots = RootCertStore::empty();
+            roots.add(ca.der().clone()).unwrap();
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .unwrap();
+            builder
+                .with_client_cert_verifier(verifier)
+                .with_single_cert(certs, key)
+        }
+        None => builder.with_no_client_auth().with_single_cert(certs, key),
+    }
+    .unwrap();
+    let config = Arc::new(config);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let Ok(conn) = rustls::ServerConnection::new(config.clone()) else {
+                continue;
+            };
+            let mut tls = rustls::StreamOwned::new(conn, stream);
+
+            // The handshake runs as part of the first read; failures just drop the connection
+            let mut buf = [0u8; 4096];
+            if tls.read(&mut buf).is_ok() {
+                let _ = tls.write_all(
+                    b"HTTP/1.1 200 OK\r\n\
+                      Docker-Distribution-API-Version: registry/2.0\r\n\
+                      Content-Length: 0\r\n\
+                      Connection: close\r\n\r\n",
+                );
+                tls.conn.send_close_notify();
+                let _ = tls.flush();
+            }
+        }
+    });
+
+    format!("https://127.0.0.1:{}", port)
+}
+
+/// A CA, a server certificate for 127.0.0.1 and a running HTTPS registry.
+struct TlsFixture {
+    ca: Certificate,
+    ca_key: KeyPair,
+    url: String,
+}
+
+fn tls_fixture(require_client_cert: bool) -> TlsFixture {
+    let (ca, ca_key) = generate_ca();
+    let (server_cert, server_key) = issue_cert(
+        &ca,
+        &ca_key,
+        "127.0.0.1",
+        ExtendedKeyUsagePurpose::ServerAuth,
+    );
+    let url = spawn_tls_registry(
+        &server_cert,
+        &server_key,
+        require_client_cert.then_some(&ca),
+    );
+
+    TlsFixture { ca, ca_key, url }
+}
+
+#[test]
+fn test_tls_untrusted_ca_fails() {
+    let fixture = tls_fixture(false);
+
+    let client = Client::new(&fixture.url, None).unwrap();
+    let result = client.check_version();
+
+    assert!(matches!(result, Err(RexError::Network { .. })));
+}
+
+#[test]
+fn test_tls_custom_ca_file() {
+    let fixture = tls_fixture(false);
+    let temp_dir = tempdir().unwrap();
+    let ca_path = temp_dir.path().join("ca.pem");
+    std::fs::write(&ca_path, fixture.ca.pem()).unwrap();
+
+    let config = ClientConfig::new().with_ca_cert(&ca_path);
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+    let version = client.check_version().unwrap();
+
+    assert_eq!(version.api_version, Some("registry/2.0".to_string()));
+}
+
+#[test]
+fn test_tls_custom_ca_directory() {
+    let fixture = tls_fixture(false);
+    let temp_dir = tempdir().unwrap();
+    let (other_ca, _) = generate_ca();
+    std::fs::write(temp_dir.path().join("other.crt"), other_ca.pem()).unwrap();
+    std::fs::write(temp_dir.path().join("internal.pem"), fixture.ca.pem()).unwrap();
+    std::fs::write(temp_dir.path().join("README"), "not a certificate").unwrap();
+
+    let config = ClientConfig::new().with_ca_cert(temp_dir.path());
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+
+    assert!(client.check_version().is_ok());
+}
+
+#[test]
+fn test_tls_insecure_skip_verify() {
+    let fixture = tls_fixture(false);
+
+    let config = ClientConfig::new().with_insecure_skip_verify(true);
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+
+    assert!(client.check_version().is_ok());
+}
+
+#[test]
+fn test_mtls_without_client_cert_fails() {
+    let fixture = tls_fixture(true);
+    let temp_dir = tempdir().unwrap();
+    let ca_path = temp_dir.path().join("ca.pem");
+    std::fs::write(&ca_path, fixture.ca.pem()).unwrap();
+
+    let config = ClientConfig::new().with_ca_cert(&ca_path);
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+
+    assert!(client.check_version().is_err());
+}
+
+#[test]
+fn test_mtls_with_client_cert_and_key() {
+    let fixture = tls_fixture(true);
+    let (client_cert, client_key) = issue_cert(
+        &fixture.ca,
+        &fixture.ca_key,
+        "rex-client",
+        ExtendedKeyUsagePurpose::ClientAuth,
+    );
+
+    let temp_dir = tempdir().unwrap();
+    let ca_path = temp_dir.path().join("ca.pem");
+    let cert_path = temp_dir.path().join("client.pem");
+    let key_path = temp_dir.path().join("client-key.pem");
+    std::fs::write(&ca_path, fixture.ca.pem()).unwrap();
+    std::fs::write(&cert_path, client_cert.pem()).unwrap();
+    std::fs::write(&key_path, client_key.serialize_pem()).unwrap();
+
+    let config = ClientConfig::new()
+        .with_ca_cert(&ca_path)
+        .with_client_cert(&cert_path, Some(&key_path));
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+
+    assert!(client.check_version().is_ok());
+}
+
+#[test]
+fn test_mtls_with_combined_cert_and_key_file() {
+    let fixture = tls_fixture(true);
+    let (client_cert, client_key) = issue_cert(
+        &fixture.ca,
+        &fixture.ca_key,
+        "rex-client",
+        ExtendedKeyUsagePurpose::ClientAuth,
+    );
+
+    let temp_dir = tempdir().unwrap();
+    let ca_path = temp_dir.path().join("ca.pem");
+    let bundle_path = temp_dir.path().join("client-bundle.pem");
+    std::fs::write(&ca_path, fixture.ca.pem()).unwrap();
+    std::fs::write(
+        &bundle_path,
+        format!("{}\n{}", client_cert.pem(), client_key.serialize_pem()),
+    )
+    .unwrap();
+
+    let config = ClientConfig::new()
+        .with_ca_cert(&ca_path)
+        .with_client_cert(&bundle_path, None::<&std::path::Path>);
+    let client = Client::with_config(&fixture.url, config, None).unwrap();
+
+    assert!(client.check_version().is_ok());
+}
+
+#[test]
+fn test_tls_missing_ca_file() {
+    let temp_dir = tempdir().unwrap();
+    let config = ClientConfig::new().with_ca_cert(temp_dir.path().join("missing.pem"));
+
+    let result = Client::with_config("https://localhost:5000", config, None);
+
+    assert!(matches!(result, Err(RexError::Config { .. })));
+}
+
+#[test]
+fn test_tls_invalid_ca_file() {
+    let temp_dir = tempdir().unwrap();
+    let ca_path = temp_dir.path().join("ca.pem");
+    std::fs::write(&ca_path, "not a certificate").unwrap();
+
+    let config = ClientConfig::new().with_ca_cert(&ca_path);
+    let result = Client::with_config("https://localhost:5000", config, None);
+
+    assert!(matches!(result, Err(RexError::Config { .. })));
+}
+
+#[test]
+fn test_tls_empty_ca_directory() {
+    let temp_dir = tempdir().unwrap();
+
+    let config = ClientConfig::new().with_ca_cert(temp_dir.path());
+    let result = Client::with_config("https://localhost:5000", config, None);
+
+    assert!(matches!(result, Err(RexError::Config { .. })));
+}
+
+#[test]
+fn test_tls_client_cert_without_key() {
+    let (ca, ca_key) = generate_ca();
+    let (client_cert, _) = issue_cert(
+        &ca,
+        &ca_key,
+        "rex-client",
+        ExtendedKeyUsagePurpose::ClientAuth,
+    );
+
+    let temp_dir = tempdir().unwrap();
+    let cert_path = temp_dir.path().join("client.pem");
+    std::fs::write(&cert_path, client_cert.pem()).unwrap();
+
+    let config = ClientConfig::new().with_client_cert(&cert_path, None::<&std::path::Path>);
+    let result = Client::with_config("https://localhost:5000", config, None);
+
+    assert!(matches!(result, Err(RexError::Config { .. })));
+}
+
+#[test]
+fn test_tls_client_key_without_cert() {
+    let config = ClientConfig {
+        client_key: Some("client-key.pem".into()),
+        ..ClientConfig::default()
+    };
+
+    let result = Client::with_config("https://localhost:5000", config, None);
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
@@ -0,0 +1,611 @@
+//! Blob uploads (the push side of the distribution API).
+//!
+//! An upload starts with `POST /v2/<name>/blobs/uploads/`, which returns a
+//! session URL in `Location`. Content is then sent either in one `PUT
+//! <location>?digest=<digest>` (monolithic) or as a series of `PATCH` requests
+//! with `Content-Range` followed by an empty closing `PUT` (chunked). Every
+//! response may move the session to a new `Location`, so the latest one is
+//! always used for the next request.
+//!
+//! A blob that already exists in another repository on the same registry can
+//! be mounted with `POST ...?mount=<digest>&from=<repository>` instead of being
+//! uploaded. Registries that cannot mount answer `202` and open a regular
+//! upload session instead.
+
+use super::Client;
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use reqwest::StatusCode;
+use reqwest::blocking::Response;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE};
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::io::Read;
+use std::str::FromStr;
+
+/// Header in which registries announce the smallest chunk they accept.
+const CHUNK_MIN_LENGTH_HEADER: &str = "OCI-Chunk-Min-Length";
+
+/// An open blob upload session.
+///
+/// Returned by [`Client::start_upload`] (or by [`Client::mount_blob`] when the
+/// registry cannot mount) and consumed by [`Client::finish_upload`],
+/// [`Client::finish_upload_chunked`] or [`Client::cancel_upload`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadSession {
+    /// The repository the blob is uploaded to
+    repository: String,
+    /// Absolute URL of the session, updated after every request
+    location: String,
+    /// Number of bytes the registry has accepted so far
+    offset: u64,
+    /// Smallest chunk size accepted by the registry, if announced
+    min_chunk_size: Option<u64>,
+}
+
+impl UploadSession {
+    /// Returns the repository the blob is uploaded to.
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    /// Returns the current session URL.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the number of bytes uploaded so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the smallest chunk size the registry accepts, if it announced one.
+    pub fn min_chunk_size(&self) -> Option<u64> {
+        self.min_chunk_size
+    }
+}
+
+/// The outcome of a cross-repository mount request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MountResult {
+    /// The blob was mounted; holds the digest reported by the registry
+    Mounted(String),
+    /// The registry did not mount the blob and opened an upload session instead
+    Upload(UploadSession),
+}
+
+impl Client {
+    /// Opens a blob upload session.
+    ///
+    /// This performs a POST request to the `/v2/<name>/blobs/uploads/` endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository to upload to
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let data = b"{}";
+    /// let digest = "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a";
+    ///
+    /// let session = client.start_upload("myapp")?;
+    /// client.finish_upload(session, digest, data)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry is unreachable
+    /// - Push access is denied
+    /// - The response does not contain a session location
+    pub fn start_upload(&self, repository: &str) -> Result<UploadSession> {
+        let url = format!("{}/v2/{}/blobs/uploads/", self.registry_url, repository);
+        let response = self.send(&Self::push_scope(repository), || {
+            self.http_client.post(&url).header(CONTENT_LENGTH, 0)
+        })?;
+
+        let response = Self::check_response_status(response)?;
+        self.upload_session(repository, &response)
+    }
+
+    /// Uploads a blob in a single request.
+    ///
+    /// The content is checked against `digest` before anything is sent, then
+    /// uploaded with a POST followed by one PUT carrying the whole blob.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository to upload to
+    /// * `digest` - The digest of `data` (e.g., "sha256:abc123...")
+    /// * `data` - The blob content
+    ///
+    /// # Returns
+    ///
+    /// The digest of the stored blob, as reported by the registry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let digest = client.upload_blob(
+    ///     "myapp",
+    ///     "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a",
+    ///     b"{}",
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid, or does not match `data`
+    /// - The registry is unreachable
+    /// - Push access is denied
+    /// - The registry rejects the upload
+    pub fn upload_blob(&self, repository: &str, digest: &str, data: &[u8]) -> Result<String> {
+        let expected = Digest::from_str(digest)?;
+        crate::blob::ensure_supported(&expected)?;
+
+        let actual = format!("{:x}", Sha256::digest(data));
+        if actual != expected.hex() {
+            return Err(RexError::validation(format!(
+                "Blob digest mismatch: expected {}, got sha256:{}",
+                digest, actual
+            )));
+        }
+
+        let session = self.start_upload(repository)?;
+        self.finish_upload(session, digest, data)
+    }
+
+    /// Uploads a blob from a reader in chunks.
+    ///
+    /// The content is streamed in `PATCH` requests of `chunk_size` bytes (or
+    /// the registry's announced minimum, if larger) and hashed on the way. If
+    /// the content does not match `digest`, the session is cancelled before it
+    /// is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The name of the repository to upload to
+    /// * `digest` - The expected digest of the content
+    /// * `reader` - The blob content
+    /// * `chunk_size` - The number of bytes sent per request
+    ///
+    /// # Returns
+    ///
+    /// The digest of the stored blob, as reported by the registry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::Client;
+    /// use std::fs::File;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut layer = File::open("layer.tar.gz")?;
+    ///
+    /// client.upload_blob_chunked(
+    ///     "myapp",
+    ///     "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+    ///     &mut layer,
+    ///     5 * 1024 * 1024,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid, or does not match the content
+    /// - The reader fails
+    /// - The registry is unreachable, denies push access or rejects a chunk
+    pub fn upload_blob_chunked<R: Read + ?Sized>(
+        &self,
+        repository: &str,
+        digest: &str,
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<String> {
+        let expected = Digest::from_str(digest)?;
+        crate::blob::ensure_supported(&expected)?;
+
+        let session = self.start_upload(repository)?;
+        self.finish_upload_chunked(session, digest, reader, chunk_size)
+    }
+
+    /// Streams a blob from a reader into an open session and completes it.
+    ///
+    /// This is the second half of [`Client::upload_blob_chunked`], for
+    /// sessions obtained elsewhere, such as the one a registry opens when it
+    /// cannot mount a blob (see [`Client::mount_blob`]). On failure the session
+    /// is cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid, or does not match the content
+    /// - The reader fails
+    /// - The registry is unreachable or rejects a chunk
+    pub fn finish_upload_chunked<R: Read + ?Sized>(
+        &self,
+        mut session: UploadSession,
+        digest: &str,
+        reader: &mut R,
+        chunk_size: usize,
+    ) -> Result<String> {
+        let expected = match Digest::from_str(digest)
+            .and_then(|d| crate::blob::ensure_supported(&d).map(|_| d))
+        {
+            Ok(expected) => expected,
+            Err(e) => {
+                let _ = self.cancel_upload(session);
+                return Err(e);
+            }
+        };
+
+        let chunk_size = match session.min_chunk_size {
+            Some(min) => chunk_size.max(min as usize),
+            None => chunk_size,
+        }
+        .max(1);
+
+        let mut hasher = Sha256::new();
+        let mut chunk = vec![0u8; chunk_size];
+
+        loop {
+            let len = match read_full(reader, &mut chunk) {
+                Ok(len) => len,
+                Err(e) => {
+                    let _ = self.cancel_upload(session);
+                    return Err(e);
+                }
+            };
+            if len == 0 {
+                break;
+            }
+
+            hasher.update(&chunk[..len]);
+            if let Err(e) = self.upload_chunk(&mut session, &chunk[..len]) {
+                let _ = self.cancel_upload(session);
+                return Err(e);
+            }
+
+            if len < chunk_size {
+                break;
+            }
+        }
+
+        let actual = format!("{:x}", hasher.finalize());
+        if actual != expected.hex() {
+            let _ = self.cancel_upload(session);
+            return Err(RexError::validation(format!(
+                "Blob digest mismatch: expected {}, got sha256:{}",
+                digest, actual
+            )));
+        }
+
+        self.finish_upload(session, digest, &[])
+    }
+
+    /// Sends one chunk of an upload.
+    ///
+    /// The chunk is sent with a `PATCH` request whose `Content-Range` starts at
+    /// the current session offset. The session location and offset are updated
+    /// from the registry's response; when its `Range` shows that only part of
+    /// the chunk was accepted, the rest is sent in a further `PATCH`.
+    ///
+    /// A `PATCH` is not retried blindly, since the registry may have stored
+    /// part of the chunk before failing. After a transient failure the session
+    /// status is read instead (see [`Client::upload_status`]) and only the
+    /// part the registry does not have yet is sent again, within the limits
+    /// of the retry policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry is unreachable or rejects the chunk
+    /// (for example `416` when the range does not continue the upload), or
+    /// if it stops accepting data before the end of the chunk.
+    pub fn upload_chunk(&self, session: &mut UploadSession, data: &[u8]) -> Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let start = session.offset;
+        let end = start + data.len() as u64 - 1;
+        let mut attempt = 1;
+
+        loop {
+            let from = session.offset;
+            let remaining = &data[(from - start) as usize..];
+            let result = self.send(&Self::push_scope(&session.repository), || {
+                self.http_client
+                    .patch(&session.location)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .header(CONTENT_RANGE, format!("{}-{}", from, end))
+                    .header(CONTENT_LENGTH, remaining.len())
+                    .body(remaining.to_vec())
+            });
+
+            let transient = match &result {
+                Ok(response) => self
+                    .retry_policy
+                    .is_retryable_status(response.status().as_u16()),
+                Err(RexError::Network { .. }) => self.retry_policy.retry_network_errors,
+                Err(_) => false,
+            };
+            if transient && let Some(delay) = self.retry_policy.delay(attempt, None) {
+                std::thread::sleep(delay);
+                self.upload_status(session)?;
+                if session.offset > end {
+                    return Ok(());
+                }
+                if session.offset < start {
+                    return Err(RexError::validation(format!(
+                        "Registry lost part of the upload: it has {} bytes, {} were sent",
+                        session.offset, start
+                    )));
+                }
+                attempt += 1;
+                continue;
+            }
+
+            let response = result?;
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                return Err(RexError::validation(format!(
+                    "Registry rejected upload chunk {}-{}: out of order",
+                    from, end
+                )));
+            }
+            let response = Self::check_response_status(response)?;
+
+            if let Some(location) = Self::header_str(&response, LOCATION) {
+                session.location = self.resolve_location(location);
+            }
+            session.offset = Self::header_str(&response, RANGE)
+                .and_then(parse_upload_range)
+                .unwrap_or(end + 1);
+
+            // A registry may accept only part of a chunk; send the rest
+            if session.offset > end {
+                return Ok(());
+            }
+            if session.offset <= from {
+                return Err(RexError::validation(format!(
+                    "Registry accepted no data of upload chunk {}-{}",
+                    from, end
+                )));
+            }
+        }
+    }
+
+    /// Reads how much of an upload the registry has stored.
+    ///
+    /// This performs a GET request on the session URL and updates the session
+    /// location and offset from the `Location` and `Range` headers of the
+    /// response. A session without a `Range` has no content yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry is unreachable, or if the session no
+    /// longer exists.
+    pub fn upload_status(&self, session: &mut UploadSession) -> Result<()> {
+        let response = self.send(&Self::push_scope(&session.repository), || {
+            self.http_client.get(&session.location)
+        })?;
+        let response = Self::check_response_status(response)?;
+
+        if let Some(location) = Self::header_str(&response, LOCATION) {
+            session.location = self.resolve_location(location);
+        }
+        session.offset = Self::header_str(&response, RANGE)
+            .and_then(parse_upload_range)
+            .unwrap_or(0);
+
+        Ok(())
+    }
+
+    /// Completes an upload with a `PUT <location>?digest=<digest>`.
+    ///
+    /// `data` is sent as the last (or, for a monolithic upload, the only)
+    /// piece of content and may be empty.
+    ///
+    /// # Returns
+    ///
+    /// The digest of the stored blob, from the `Docker-Content-Digest` header
+    /// or `digest` if the registry does not send one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry is unreachable or rejects the upload,
+    /// for example because the content does not match `digest`.
+    pub fn finish_upload(
+        &self,
+        session: UploadSession,
+        digest: &str,
+        data: &[u8],
+    ) -> Result<String> {
+        Digest::from_str(digest)?;
+
+        let separator = if session.location.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let url = format!("{}{}digest={}", session.location, separator, digest);
+
+        let response = self.send(&Self::push_scope(&session.repository), || {
+            self.http_client
+                .put(&url)
+                .header(CONTENT_TYPE, "application/octet-stream")
+                .header(CONTENT_LENGTH, data.len())
+                .body(data.to_vec())
+        })?;
+
+        if response.status() == StatusCode::BAD_REQUEST {
+            let body = response.text().unwrap_or_default();
+            return Err(RexError::validation(format!(
+                "Registry rejected blob {}: {}",
+                digest, body
+            )));
+        }
+        let response = Self::check_response_status(response)?;
+
+        Ok(Self::header_str(&response, "Docker-Content-Digest")
+            .unwrap_or(digest)
+            .to_string())
+    }
+
+    /// Cancels an upload session, discarding everything uploaded so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry is unreachable or refuses to cancel
+    /// the session. A session that no longer exists is not an error.
+    pub fn cancel_upload(&self, session: UploadSession) -> Result<()> {
+        let response = self.send(&Self::push_scope(&session.repository), || {
+            self.http_client.delete(&session.location)
+        })?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(());
+        }
+        Self::check_response_status(response).map(|_| ())
+    }
+
+    /// Mounts a blob from another repository on the same registry.
+    ///
+    /// This performs a POST request to
+    /// `/v2/<name>/blobs/uploads/?mount=<digest>&from=<from>`. A `201` means
+    /// the blob was mounted without transferring any data. Registries that
+    /// cannot mount (the source is missing, access to it is denied, or mounting
+    /// is not supported) answer `202` with a new upload session, which the
+    /// caller can use to upload the blob instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository to mount the blob into
+    /// * `digest` - The digest of the blob
+    /// * `from` - The repository that holds the blob
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::client::{Client, MountResult};
+    ///
+    /// # fn example() -> librex::error::Result<()> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let digest = "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2";
+    ///
+    /// match client.mount_blob("myapp", digest, "base/alpine")? {
+    ///     MountResult::Mounted(_) => println!("Mounted"),
+    ///     MountResult::Upload(session) => {
+    ///         let data = client.fetch_blob("base/alpine", digest)?;
+    ///         client.finish_upload(session, digest, &data)?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The digest format is invalid
+    /// - The registry is unreachable
+    /// - Push access to `repository` is denied
+    pub fn mount_blob(&self, repository: &str, digest: &str, from: &str) -> Result<MountResult> {
+        Digest::from_str(digest)?;
+
+        let url = format!("{}/v2/{}/blobs/uploads/", self.registry_url, repository);
+        let scope = format!(
+            "{} {}",
+            Self::push_scope(repository),
+            Self::pull_scope(from)
+        );
+        let response = self.send(&scope, || {
+            self.http_client
+                .post(&url)
+                .query(&[("mount", digest), ("from", from)])
+                .header(CONTENT_LENGTH, 0)
+        })?;
+
+        let response = Self::check_response_status(response)?;
+        if response.status() == StatusCode::CREATED {
+            return Ok(MountResult::Mounted(
+                Self::header_str(&response, "Docker-Content-Digest")
+                    .unwrap_or(digest)
+                    .to_string(),
+            ));
+        }
+
+        self.upload_session(repository, &response)
+            .map(MountResult::Upload)
+    }
+
+    /// Builds an upload session from the response that opened it.
+    fn upload_session(&self, repository: &str, response: &Response) -> Result<UploadSession> {
+        let location = Self::header_str(response, LOCATION)
+            .ok_or_else(|| RexError::validation("Registry did not return an upload location"))?;
+
+        Ok(UploadSession {
+            repository: repository.to_string(),
+            location: self.resolve_location(location),
+            offset: 0,
+            min_chunk_size: Self::header_str(response, CHUNK_MIN_LENGTH_HEADER)
+                .and_then(|s| s.parse().ok()),
+        })
+    }
+
+    /// Turns a `Location` header, which may be relative, into an absolute URL.
+    pub(super) fn resolve_location(&self, location: &str) -> String {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            location.to_string()
+        } else if location.starts_with('/') {
+            format!("{}{}", self.registry_url, location)
+        } else {
+            format!("{}/{}", self.registry_url, location)
+        }
+    }
+
+    /// Returns a response header as a string, if present and valid.
+    fn header_str<K: reqwest::header::AsHeaderName>(response: &Response, name: K) -> Option<&str> {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+    }
+}
+
+/// Parses an upload `Range: 0-<end>` header into the next offset.
+fn parse_upload_range(value: &str) -> Option<u64> {
+    let (_, end) = value.trim_start_matches("bytes=").split_once('-')?;
+    end.trim().parse::<u64>().ok()?.checked_add(1)
+}
+
+/// Reads until `buf` is full or the reader is exhausted.
+fn read_full<R: Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(crate::blob::read_error(e)),
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,559 @@
+use super::tests::retrying_client;
+use super::*;
+use crate::test_support::sha256_of;
+use mockito::Matcher;
+
+#[test]
+fn test_start_upload_resolves_relative_location() {
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/session-1")
+        .with_header("OCI-Chunk-Min-Length", "1024")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let session = client.start_upload("myapp").unwrap();
+
+    mock.assert();
+    assert_eq!(session.repository(), "myapp");
+    assert_eq!(
+        session.location(),
+        format!("{}/v2/myapp/blobs/uploads/session-1", server.url())
+    );
+    assert_eq!(session.offset(), 0);
+    assert_eq!(session.min_chunk_size(), Some(1024));
+}
+
+#[test]
+fn test_start_upload_without_location() {
+    let mut server = mockito::Server::new();
+
+    let _mock = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.start_upload("myapp");
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_start_upload_denied() {
+    let mut server = mockito::Server::new();
+
+    let _mock = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(403)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.start_upload("myapp");
+
+    assert!(matches!(
+        result,
+        Err(RexError::Authentication {
+            status_code: Some(403),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_upload_blob_monolithic() {
+    let mut server = mockito::Server::new();
+    let data = br#"{"architecture":"amd64"}"#;
+    let digest = sha256_of(data);
+
+    let post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/session-1?_state=abc")
+        .create();
+    let put = server
+        .mock("PUT", "/v2/myapp/blobs/uploads/session-1")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("_state".into(), "abc".into()),
+            Matcher::UrlEncoded("digest".into(), digest.clone()),
+        ]))
+        .match_header("Content-Type", "application/octet-stream")
+        .match_body(data.to_vec())
+        .with_status(201)
+        .with_header("Docker-Content-Digest", &digest)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let stored = client.upload_blob("myapp", &digest, data).unwrap();
+
+    post.assert();
+    put.assert();
+    assert_eq!(stored, digest);
+}
+
+#[test]
+fn test_upload_blob_digest_mismatch_sends_nothing() {
+    let mut server = mockito::Server::new();
+    let digest = sha256_of(b"expected");
+
+    let post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .expect(0)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.upload_blob("myapp", &digest, b"tampered");
+
+    post.assert();
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_upload_blob_rejected_by_registry() {
+    let mut server = mockito::Server::new();
+    let data = b"layer";
+    let digest = sha256_of(data);
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/session-1")
+        .create();
+    let _put = server
+        .mock("PUT", "/v2/myapp/blobs/uploads/session-1")
+        .match_query(Matcher::Any)
+        .with_status(400)
+        .with_body(r#"{"errors":[{"code":"DIGEST_INVALID"}]}"#)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.upload_blob("myapp", &digest, data);
+
+    match result {
+        Err(RexError::Validation { message, .. }) => assert!(message.contains("DIGEST_INVALID")),
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_upload_blob_chunked_tracks_session() {
+    let mut server = mockito::Server::new();
+    let data = b"0123456789";
+    let digest = sha256_of(data);
+
+    let post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let chunks = [
+        ("s0", "s1", "0-3", &data[0..4]),
+        ("s1", "s2", "4-7", &data[4..8]),
+        ("s2", "s3", "8-9", &data[8..]),
+    ];
+    let patches: Vec<_> = chunks
+        .iter()
+        .map(|(from, to, range, body)| {
+            server
+                .mock(
+                    "PATCH",
+                    format!("/v2/myapp/blobs/uploads/{}", from).as_str(),
+                )
+                .match_header("Content-Range", *range)
+                .match_header("Content-Type", "application/octet-stream")
+                .match_body(body.to_vec())
+                .with_status(202)
+                .with_header("Location", &format!("/v2/myapp/blobs/uploads/{}", to))
+                .with_header("Range", &format!("0-{}", range.split('-').nth(1).unwrap()))
+                .create()
+        })
+        .collect();
+    let put = server
+        .mock("PUT", "/v2/myapp/blobs/uploads/s3")
+        .match_query(Matcher::UrlEncoded("digest".into(), digest.clone()))
+        .match_header("Content-Length", "0")
+        .with_status(201)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let stored = client
+        .upload_blob_chunked("myapp", &digest, &mut &data[..], 4)
+        .unwrap();
+
+    post.assert();
+    for patch in &patches {
+        patch.assert();
+    }
+    put.assert();
+    assert_eq!(stored, digest);
+}
+
+#[test]
+fn test_upload_blob_chunked_honors_min_chunk_size() {
+    let mut server = mockito::Server::new();
+    let data = b"0123456789";
+    let digest = sha256_of(data);
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .with_header("OCI-Chunk-Min-Length", "16")
+        .create();
+    let patch = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .match_header("Content-Range", "0-9")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .expect(1)
+        .create();
+    let _put = server
+        .mock("PUT", "/v2/myapp/blobs/uploads/s0")
+        .match_query(Matcher::Any)
+        .with_status(201)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    client
+        .upload_blob_chunked("myapp", &digest, &mut &data[..], 4)
+        .unwrap();
+
+    patch.assert();
+}
+
+#[test]
+fn test_upload_blob_chunked_digest_mismatch_cancels() {
+    let mut server = mockito::Server::new();
+    let digest = sha256_of(b"expected");
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let _patch = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s1")
+        .create();
+    let cancel = server
+        .mock("DELETE", "/v2/myapp/blobs/uploads/s1")
+        .with_status(204)
+        .create();
+    let put = server.mock("PUT", Matcher::Any).expect(0).create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.upload_blob_chunked("myapp", &digest, &mut &b"tampered"[..], 64);
+
+    cancel.assert();
+    put.assert();
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_upload_chunk_out_of_order() {
+    let mut server = mockito::Server::new();
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let _patch = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .with_status(416)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut session = client.start_upload("myapp").unwrap();
+    let result = client.upload_chunk(&mut session, b"data");
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+    assert_eq!(session.offset(), 0);
+}
+
+/// An open session `s0` on a retrying client
+fn open_session(server: &mut mockito::ServerGuard) -> (Client, UploadSession) {
+    server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let client = retrying_client(&server.url(), 3);
+    let session = client.start_upload("myapp").unwrap();
+    (client, session)
+}
+
+#[test]
+fn test_upload_chunk_resumes_after_failure() {
+    let mut server = mockito::Server::new();
+    let (client, mut session) = open_session(&mut server);
+
+    // The registry stored 4 bytes before failing
+    let failed = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .match_header("Content-Range", "0-9")
+        .with_status(500)
+        .expect(1)
+        .create();
+    let status = server
+        .mock("GET", "/v2/myapp/blobs/uploads/s0")
+        .with_status(204)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s1")
+        .with_header("Range", "0-3")
+        .expect(1)
+        .create();
+    let resumed = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s1")
+        .match_header("Content-Range", "4-9")
+        .match_body(b"456789".to_vec())
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s2")
+        .with_header("Range", "0-9")
+        .expect(1)
+        .create();
+
+    client.upload_chunk(&mut session, b"0123456789").unwrap();
+
+    failed.assert();
+    status.assert();
+    resumed.assert();
+    assert_eq!(session.offset(), 10);
+    assert!(session.location().ends_with("/s2"));
+}
+
+#[test]
+fn test_upload_chunk_stored_despite_failure() {
+    let mut server = mockito::Server::new();
+    let (client, mut session) = open_session(&mut server);
+
+    let failed = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .with_status(503)
+        .expect(1)
+        .create();
+    let _status = server
+        .mock("GET", "/v2/myapp/blobs/uploads/s0")
+        .with_status(204)
+        .with_header("Range", "0-3")
+        .create();
+
+    client.upload_chunk(&mut session, b"0123").unwrap();
+
+    failed.assert();
+    assert_eq!(session.offset(), 4);
+}
+
+#[test]
+fn test_upload_status_without_range() {
+    let mut server = mockito::Server::new();
+    let (client, mut session) = open_session(&mut server);
+    let _status = server
+        .mock("GET", "/v2/myapp/blobs/uploads/s0")
+        .with_status(204)
+        .create();
+
+    client.upload_status(&mut session).unwrap();
+
+    assert_eq!(session.offset(), 0);
+}
+
+#[test]
+fn test_upload_chunk_sends_unaccepted_tail() {
+    let mut server = mockito::Server::new();
+    let (client, mut session) = open_session(&mut server);
+
+    let partial = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .match_header("Content-Range", "0-9")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s1")
+        .with_header("Range", "0-5")
+        .expect(1)
+        .create();
+    let tail = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s1")
+        .match_header("Content-Range", "6-9")
+        .match_body(b"6789".to_vec())
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s2")
+        .with_header("Range", "0-9")
+        .expect(1)
+        .create();
+
+    client.upload_chunk(&mut session, b"0123456789").unwrap();
+
+    partial.assert();
+    tail.assert();
+    assert_eq!(session.offset(), 10);
+}
+
+#[test]
+fn test_upload_chunk_fails_without_progress() {
+    let mut server = mockito::Server::new();
+    let (client, mut session) = open_session(&mut server);
+
+    let _partial = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .with_status(202)
+        .with_header("Range", "0-5")
+        .expect(2)
+        .create();
+
+    let result = client.upload_chunk(&mut session, b"0123456789");
+
+    match result {
+        Err(RexError::Validation { message, .. }) => {
+            assert!(message.contains("accepted no data of upload chunk 6-9"))
+        }
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+    assert_eq!(session.offset(), 6);
+}
+
+#[test]
+fn test_mount_blob_mounted() {
+    let mut server = mockito::Server::new();
+    let digest = sha256_of(b"layer");
+
+    let mock = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("mount".into(), digest.clone()),
+            Matcher::UrlEncoded("from".into(), "base/alpine".into()),
+        ]))
+        .with_status(201)
+        .with_header("Location", &format!("/v2/myapp/blobs/{}", digest))
+        .with_header("Docker-Content-Digest", &digest)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = client.mount_blob("myapp", &digest, "base/alpine").unwrap();
+
+    mock.assert();
+    assert_eq!(result, MountResult::Mounted(digest));
+}
+
+#[test]
+fn test_mount_blob_falls_back_to_upload() {
+    let mut server = mockito::Server::new();
+    let data = b"layer";
+    let digest = sha256_of(data);
+
+    let mount = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .match_query(Matcher::UrlEncoded("mount".into(), digest.clone()))
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let put = server
+        .mock("PUT", "/v2/myapp/blobs/uploads/s0")
+        .match_query(Matcher::UrlEncoded("digest".into(), digest.clone()))
+        .match_body(data.to_vec())
+        .with_status(201)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let session = match client.mount_blob("myapp", &digest, "base/alpine").unwrap() {
+        MountResult::Upload(session) => session,
+        other => panic!("Expected an upload session, got {:?}", other),
+    };
+    let stored = client.finish_upload(session, &digest, data).unwrap();
+
+    mount.assert();
+    put.assert();
+    assert_eq!(stored, digest);
+}
+
+#[test]
+fn test_mount_blob_requests_push_and_pull_scopes() {
+    let mut server = mockito::Server::new();
+    let url = server.url();
+    let digest = sha256_of(b"layer");
+
+    let challenge = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .match_query(Matcher::Any)
+        .match_header("Authorization", Matcher::Missing)
+        .with_status(401)
+        .with_header(
+            "WWW-Authenticate",
+            &format!("Bearer realm=\"{}/token\",service=\"registry\"", url),
+        )
+        .create();
+    let token = server
+        .mock("GET", "/token")
+        // Both scopes are requested as repeated parameters
+        .match_query(Matcher::Regex(
+            "scope=repository%3Amyapp%3Apull%2Cpush&scope=repository%3Abase%2Falpine%3Apull"
+                .to_string(),
+        ))
+        .with_status(200)
+        .with_body(r#"{"token": "push-token"}"#)
+        .create();
+    let mount = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .match_query(Matcher::Any)
+        .match_header("Authorization", "Bearer push-token")
+        .with_status(201)
+        .create();
+
+    let client = Client::new(&url, None).unwrap();
+    let result = client.mount_blob("myapp", &digest, "base/alpine").unwrap();
+
+    challenge.assert();
+    token.assert();
+    mount.assert();
+    assert_eq!(result, MountResult::Mounted(digest));
+}
+
+#[test]
+fn test_cancel_upload_ignores_missing_session() {
+    let mut server = mockito::Server::new();
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let delete = server
+        .mock("DELETE", "/v2/myapp/blobs/uploads/s0")
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let session = client.start_upload("myapp").unwrap();
+
+    assert!(client.cancel_upload(session).is_ok());
+    delete.assert();
+}
+
+#[test]
+fn test_finish_upload_chunked_cancels_on_invalid_digest() {
+    let mut server = mockito::Server::new();
+
+    let _post = server
+        .mock("POST", "/v2/myapp/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/myapp/blobs/uploads/s0")
+        .create();
+    let delete = server
+        .mock("DELETE", "/v2/myapp/blobs/uploads/s0")
+        .with_status(204)
+        .create();
+    let patch = server
+        .mock("PATCH", "/v2/myapp/blobs/uploads/s0")
+        .expect(0)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let session = client.start_upload("myapp").unwrap();
+    let result = client.finish_upload_chunked(session, "sha256:bad", &mut &b"data"[..], 4);
+
+    delete.assert();
+    patch.assert();
+    assert!(result.is_err());
+}
@@ -0,0 +1,456 @@
+//! Image copy between repositories and registries.
+//!
+//! The copy engine walks a source image top-down (index → child manifests →
+//! config and layers) and pushes it bottom-up, so every manifest only
+//! reaches the destination once the content it references is there.
+//! Blobs the destination already has are detected with `HEAD` and skipped;
+//! when source and destination are the same registry, blobs are mounted
+//! instead of transferred. Everything else is streamed: large blobs never
+//! sit in memory in full.
+
+use crate::client::{Client, MountResult, UploadSession};
+use crate::error::{Result, RexError};
+use crate::oci::{ManifestOrIndex, manifest_media_type};
+use oci_spec::image::{Descriptor, ImageManifest, Platform};
+
+#[cfg(test)]
+mod tests;
+
+/// Default size of blob chunks: 8 MiB.
+///
+/// Blobs up to this size are copied in a single request, larger ones are
+/// streamed in chunks of this size.
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// One side of a copy: a repository and reference on a registry.
+#[derive(Debug, Clone, Copy)]
+pub struct CopyEndpoint<'a> {
+    /// Client connected to the registry
+    pub client: &'a Client,
+    /// Repository name as the registry knows it (e.g., "library/alpine")
+    pub repository: &'a str,
+    /// Tag or digest
+    pub reference: &'a str,
+}
+
+impl<'a> CopyEndpoint<'a> {
+    /// Creates an endpoint.
+    pub fn new(client: &'a Client, repository: &'a str, reference: &'a str) -> Self {
+        Self {
+            client,
+            repository,
+            reference,
+        }
+    }
+}
+
+/// Options controlling an image copy.
+///
+/// # Examples
+///
+/// ```
+/// use librex::copy::CopyOptions;
+///
+/// let options = CopyOptions::new().with_platform("linux", "arm64", Some("v8"));
+/// assert!(options.platform.is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyOptions {
+    /// Copy only this platform (os, architecture, variant) of a multi-platform image
+    ///
+    /// The platform manifest is pushed under the destination reference in
+    /// place of the index. Without a filter every platform is copied.
+    pub platform: Option<(String, String, Option<String>)>,
+    /// Blob chunk size in bytes (default: 8 MiB)
+    pub chunk_size: usize,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self {
+            platform: None,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+}
+
+impl CopyOptions {
+    /// Creates copy options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies only the given platform of a multi-platform image.
+    ///
+    /// A platform without a variant matches any variant.
+    pub fn with_platform(mut self, os: &str, architecture: &str, variant: Option<&str>) -> Self {
+        self.platform = Some((
+            os.to_string(),
+            architecture.to_string(),
+            variant.map(str::to_string),
+        ));
+        self
+    }
+
+    /// Sets the blob chunk size. Values below 1 are treated as 1.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+}
+
+/// Progress of an image copy, reported once per blob and manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyEvent {
+    /// The destination already had this blob
+    BlobExists(String),
+    /// The blob was mounted from the source repository
+    BlobMounted(String),
+    /// The blob was transferred
+    BlobCopied {
+        /// Blob digest
+        digest: String,
+        /// Blob size in bytes
+        size: u64,
+    },
+    /// The destination already had this child manifest
+    ManifestExists(String),
+    /// A manifest was pushed
+    ManifestPushed {
+        /// Tag or digest it was pushed under
+        reference: String,
+        /// Manifest digest
+        digest: String,
+    },
+}
+
+/// Summary of a finished image copy.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CopyReport {
+    /// Digest of the manifest or index pushed under the destination reference
+    pub digest: String,
+    /// Number of manifests pushed
+    pub manifests: usize,
+    /// Number of blobs transferred
+    pub blobs_copied: usize,
+    /// Number of blobs mounted
+    pub blobs_mounted: usize,
+    /// Number of blobs the destination already had
+    pub blobs_skipped: usize,
+    /// Bytes transferred
+    pub bytes_copied: u64,
+}
+
+/// Copies an image from `source` to `destination`.
+///
+/// Blobs are copied before the manifests that reference them, and child
+/// manifests before their index. Blobs and child manifests the destination
+/// already has are skipped. Non-distributable (foreign) layers are never
+/// copied; registries are expected to fetch them from their own URLs.
+///
+/// # Arguments
+///
+/// * `source` - Where to read the image from
+/// * `destination` - Where to push it; `reference` is the tag or digest to push under
+/// * `options` - Platform filter and chunk size
+/// * `on_event` - Called as each blob and manifest is processed
+///
+/// # Returns
+///
+/// A [`CopyReport`] with the pushed digest and transfer counts.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::copy::{CopyEndpoint, CopyOptions, copy_image};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let source = Client::new("https://registry-1.docker.io", None)?;
+/// let destination = Client::new("http://localhost:5000", None)?;
+///
+/// let report = copy_image(
+///     &CopyEndpoint::new(&source, "library/alpine", "3.19"),
+///     &CopyEndpoint::new(&destination, "alpine", "3.19"),
+///     &CopyOptions::new(),
+///     |event| println!("{:?}", event),
+/// )?;
+/// println!("Pushed {} ({} bytes copied)", report.digest, report.bytes_copied);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The source image does not exist, or the platform filter matches nothing
+/// - Pull access to the source or push access to the destination is denied
+/// - A blob fails digest validation
+/// - The destination rejects a blob or manifest
+pub fn copy_image<F>(
+    source: &CopyEndpoint,
+    destination: &CopyEndpoint,
+    options: &CopyOptions,
+    on_event: F,
+) -> Result<CopyReport>
+where
+    F: FnMut(&CopyEvent),
+{
+    let mut copier = Copier {
+        source: *source,
+        destination: *destination,
+        options,
+        same_registry: source.client.registry_url() == destination.client.registry_url(),
+        on_event,
+        report: CopyReport::default(),
+    };
+
+    copier.report.digest = copier.copy_manifest(
+        source.reference,
+        destination.reference,
+        options.platform.as_ref(),
+    )?;
+    Ok(copier.report)
+}
+
+/// State of one copy operation.
+struct Copier<'a, F> {
+    source: CopyEndpoint<'a>,
+    destination: CopyEndpoint<'a>,
+    options: &'a CopyOptions,
+    same_registry: bool,
+    on_event: F,
+    report: CopyReport,
+}
+
+impl<F: FnMut(&CopyEvent)> Copier<'_, F> {
+    /// Copies the manifest at `reference` with everything it references and
+    /// pushes it under `target`. Returns the pushed digest.
+    fn copy_manifest(
+        &mut self,
+        reference: &str,
+        target: &str,
+        platform: Option<&(String, String, Option<String>)>,
+    ) -> Result<String> {
+        let (bytes, _) = self
+            .source
+            .client
+            .fetch_manifest(self.source.repository, reference)?;
+        let media_type = manifest_media_type(&bytes)?;
+
+        match ManifestOrIndex::from_bytes(&bytes)? {
+            ManifestOrIndex::Manifest(manifest) => self.copy_blobs(&manifest)?,
+            ManifestOrIndex::Index(index) => {
+                if let Some(platform) = platform {
+                    let descriptor = select_platform(index.manifests(), platform)?;
+                    return self.copy_manifest(descriptor.digest().as_ref(), target, None);
+                }
+
+                for descriptor in index.manifests() {
+                    self.copy_child(descriptor)?;
+                }
+            }
+        }
+
+        let digest = self.destination.client.put_manifest(
+            self.destination.repository,
+            target,
+            &bytes,
+            &media_type,
+        )?;
+        self.report.manifests += 1;
+        self.emit(CopyEvent::ManifestPushed {
+            reference: target.to_string(),
+            digest: digest.clone(),
+        });
+
+        Ok(digest)
+    }
+
+    /// Copies a child manifest of an index by digest, unless the destination
+    /// already has it.
+    fn copy_child(&mut self, descriptor: &Descriptor) -> Result<()> {
+        let digest = descriptor.digest().to_string();
+
+        match self
+            .destination
+            .client
+            .head_manifest(self.destination.repository, &digest)
+        {
+            Ok(_) => {
+                self.emit(CopyEvent::ManifestExists(digest));
+                Ok(())
+            }
+            Err(RexError::NotFound { .. }) => {
+                self.copy_manifest(&digest, &digest, None).map(|_| ())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Copies the config and layers of an image manifest.
+    fn copy_blobs(&mut self, manifest: &ImageManifest) -> Result<()> {
+        for descriptor in std::iter::once(manifest.config()).chain(manifest.layers()) {
+            if is_foreign(descriptor) {
+                continue;
+            }
+            self.copy_blob(descriptor.digest().as_ref(), descriptor.size())?;
+        }
+        Ok(())
+    }
+
+    /// Copies one blob: skipped if present, mounted within a registry,
+    /// transferred otherwise.
+    fn copy_blob(&mut self, digest: &str, size: u64) -> Result<()> {
+        match self
+            .destination
+            .client
+            .head_blob(self.destination.repository, digest)
+        {
+            Ok(_) => {
+                self.report.blobs_skipped += 1;
+                self.emit(CopyEvent::BlobExists(digest.to_string()));
+                return Ok(());
+            }
+            Err(RexError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+
+        if self.same_registry {
+            let mount = self.destination.client.mount_blob(
+                self.destination.repository,
+                digest,
+                self.source.repository,
+            )?;
+            match mount {
+                MountResult::Mounted(_) => {
+                    self.report.blobs_mounted += 1;
+                    self.emit(CopyEvent::BlobMounted(digest.to_string()));
+                    return Ok(());
+                }
+                // The registry declined to mount and opened a regular upload
+                MountResult::Upload(session) => self.transfer_blob(digest, size, Some(session))?,
+            }
+        } else {
+            self.transfer_blob(digest, size, None)?;
+        }
+
+        self.report.blobs_copied += 1;
+        self.report.bytes_copied += size;
+        self.emit(CopyEvent::BlobCopied {
+            digest: digest.to_string(),
+            size,
+        });
+        Ok(())
+    }
+
+    /// Transfers a blob from source to destination, into `session` if one is
+    /// already open.
+    fn transfer_blob(&self, digest: &str, size: u64, session: Option<UploadSession>) -> Result<()> {
+        let source = self.source.client;
+        let destination = self.destination.client;
+        let repository = self.destination.repository;
+
+        if size <= self.options.chunk_size as u64 {
+            let data = match source.fetch_blob(self.source.repository, digest) {
+                Ok(data) => data,
+                Err(e) => {
+                    if let Some(session) = session {
+                        let _ = destination.cancel_upload(session);
+                    }
+                    return Err(e);
+                }
+            };
+            return match session {
+                Some(session) => destination.finish_upload(session, digest, &data),
+                None => destination.upload_blob(repository, digest, &data),
+            }
+            .map(|_| ());
+        }
+
+        let mut reader = match source.fetch_blob_reader(self.source.repository, digest) {
+            Ok(reader) => reader,
+            Err(e) => {
+                if let Some(session) = session {
+                    let _ = destination.cancel_upload(session);
+                }
+                return Err(e);
+            }
+        };
+        match session {
+            Some(session) => destination.finish_upload_chunked(
+                session,
+                digest,
+                &mut reader,
+                self.options.chunk_size,
+            ),
+            None => destination.upload_blob_chunked(
+                repository,
+                digest,
+                &mut reader,
+                self.options.chunk_size,
+            ),
+        }
+        .map(|_| ())
+    }
+
+    fn emit(&mut self, event: CopyEvent) {
+        (self.on_event)(&event);
+    }
+}
+
+/// Finds the index entry for `platform`; a platform without a variant
+/// matches any variant.
+pub fn select_platform<'d>(
+    manifests: &'d [Descriptor],
+    (os, architecture, variant): &(String, String, Option<String>),
+) -> Result<&'d Descriptor> {
+    let matches = |p: &Platform| {
+        p.os().to_string() == *os
+            && p.architecture().to_string() == *architecture
+            && variant
+                .as_ref()
+                .is_none_or(|v| p.variant().as_deref() == Some(v.as_str()))
+    };
+
+    manifests
+        .iter()
+        .find(|desc| desc.platform().as_ref().is_some_and(matches))
+        .ok_or_else(|| {
+            let available: Vec<String> = manifests
+                .iter()
+                .filter_map(|desc| desc.platform().as_ref().map(format_platform))
+                .collect();
+            let requested = match variant {
+                Some(v) => format!("{}/{}/{}", os, architecture, v),
+                None => format!("{}/{}", os, architecture),
+            };
+            RexError::not_found(
+                "platform",
+                &format!(
+                    "{} (available: {})",
+                    requested,
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ),
+            )
+        })
+}
+
+/// Formats a platform as `os/arch[/variant]`.
+pub fn format_platform(platform: &Platform) -> String {
+    match platform.variant() {
+        Some(variant) => format!("{}/{}/{}", platform.os(), platform.architecture(), variant),
+        None => format!("{}/{}", platform.os(), platform.architecture()),
+    }
+}
+
+/// Returns true for non-distributable layers, which registries must not
+/// be asked to store.
+pub(crate) fn is_foreign(descriptor: &Descriptor) -> bool {
+    let media_type = descriptor.media_type().to_string();
+    media_type.contains(".nondistributable.") || media_type.contains(".foreign.")
+}
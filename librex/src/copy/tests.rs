@@ -0,0 +1,357 @@
+use super::*;
+use crate::test_support::{
+    LAYER, accept_manifest, accept_upload, head_blob, index_json, manifest_json, serve_blob,
+    serve_manifest, sha256_of,
+};
+use mockito::{Matcher, Server};
+
+#[test]
+fn test_copy_image_between_registries() {
+    let mut source = Server::new();
+    let mut destination = Server::new();
+    let config = br#"{"architecture":"amd64"}"#;
+    let layer = b"layer content";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+
+    let _manifest = serve_manifest(&mut source, "library/alpine", "3.19", &manifest);
+    let _layer = serve_blob(&mut source, "library/alpine", layer);
+    let config_get = source
+        .mock(
+            "GET",
+            format!("/v2/library/alpine/blobs/{}", sha256_of(config)).as_str(),
+        )
+        .expect(0)
+        .create();
+
+    let _config_head = head_blob(&mut destination, "alpine", config, 200);
+    let _layer_head = head_blob(&mut destination, "alpine", layer, 404);
+    let (post, put) = accept_upload(&mut destination, "alpine", layer);
+    let push = accept_manifest(&mut destination, "alpine", "3.19", &manifest);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let mut events = Vec::new();
+
+    let report = copy_image(
+        &CopyEndpoint::new(&source_client, "library/alpine", "3.19"),
+        &CopyEndpoint::new(&destination_client, "alpine", "3.19"),
+        &CopyOptions::new(),
+        |event| events.push(event.clone()),
+    )
+    .unwrap();
+
+    config_get.assert();
+    post.assert();
+    put.assert();
+    push.assert();
+    assert_eq!(
+        report,
+        CopyReport {
+            digest: sha256_of(&manifest),
+            manifests: 1,
+            blobs_copied: 1,
+            blobs_mounted: 0,
+            blobs_skipped: 1,
+            bytes_copied: layer.len() as u64,
+        }
+    );
+    assert_eq!(
+        events,
+        vec![
+            CopyEvent::BlobExists(sha256_of(config)),
+            CopyEvent::BlobCopied {
+                digest: sha256_of(layer),
+                size: layer.len() as u64,
+            },
+            CopyEvent::ManifestPushed {
+                reference: "3.19".to_string(),
+                digest: sha256_of(&manifest),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_copy_image_mounts_within_registry() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"amd64"}"#;
+    let layer = b"layer content";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+
+    let _manifest = serve_manifest(&mut server, "base", "v1", &manifest);
+    let _config_head = head_blob(&mut server, "app", config, 404);
+    let _layer_head = head_blob(&mut server, "app", layer, 404);
+    let mount = server
+        .mock("POST", "/v2/app/blobs/uploads/")
+        .match_query(Matcher::UrlEncoded("from".into(), "base".into()))
+        .with_status(201)
+        .expect(2)
+        .create();
+    let push = accept_manifest(&mut server, "app", "v1", &manifest);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let report = copy_image(
+        &CopyEndpoint::new(&client, "base", "v1"),
+        &CopyEndpoint::new(&client, "app", "v1"),
+        &CopyOptions::new(),
+        |_| {},
+    )
+    .unwrap();
+
+    mount.assert();
+    push.assert();
+    assert_eq!(report.blobs_mounted, 2);
+    assert_eq!(report.blobs_copied, 0);
+    assert_eq!(report.bytes_copied, 0);
+}
+
+#[test]
+fn test_copy_image_uploads_when_mount_declined() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"amd64"}"#;
+    let manifest = manifest_json(config, &[]);
+
+    let _manifest = serve_manifest(&mut server, "base", "v1", &manifest);
+    let _config = serve_blob(&mut server, "base", config);
+    let _config_head = head_blob(&mut server, "app", config, 404);
+    let mount = server
+        .mock("POST", "/v2/app/blobs/uploads/")
+        .match_query(Matcher::UrlEncoded("mount".into(), sha256_of(config)))
+        .with_status(202)
+        .with_header("Location", "/v2/app/blobs/uploads/s0")
+        .create();
+    let put = server
+        .mock("PUT", "/v2/app/blobs/uploads/s0")
+        .match_query(Matcher::UrlEncoded("digest".into(), sha256_of(config)))
+        .match_body(config.to_vec())
+        .with_status(201)
+        .create();
+    let _push = accept_manifest(&mut server, "app", "v1", &manifest);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let report = copy_image(
+        &CopyEndpoint::new(&client, "base", "v1"),
+        &CopyEndpoint::new(&client, "app", "v1"),
+        &CopyOptions::new(),
+        |_| {},
+    )
+    .unwrap();
+
+    mount.assert();
+    put.assert();
+    assert_eq!(report.blobs_copied, 1);
+    assert_eq!(report.blobs_mounted, 0);
+}
+
+#[test]
+fn test_copy_image_streams_large_blobs_in_chunks() {
+    let mut source = Server::new();
+    let mut destination = Server::new();
+    let config = b"{}";
+    let layer = b"0123456789";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+
+    let _manifest = serve_manifest(&mut source, "app", "v1", &manifest);
+    let _layer = serve_blob(&mut source, "app", layer);
+    let _config_head = head_blob(&mut destination, "app", config, 200);
+    let _layer_head = head_blob(&mut destination, "app", layer, 404);
+    let _post = destination
+        .mock("POST", "/v2/app/blobs/uploads/")
+        .with_status(202)
+        .with_header("Location", "/v2/app/blobs/uploads/s0")
+        .create();
+    let patch = destination
+        .mock("PATCH", "/v2/app/blobs/uploads/s0")
+        .with_status(202)
+        .with_header("Location", "/v2/app/blobs/uploads/s0")
+        .expect(3)
+        .create();
+    let put = destination
+        .mock("PUT", "/v2/app/blobs/uploads/s0")
+        .match_query(Matcher::UrlEncoded("digest".into(), sha256_of(layer)))
+        .with_status(201)
+        .create();
+    let _push = accept_manifest(&mut destination, "app", "v1", &manifest);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let report = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new().with_chunk_size(4),
+        |_| {},
+    )
+    .unwrap();
+
+    patch.assert();
+    put.assert();
+    assert_eq!(report.bytes_copied, layer.len() as u64);
+}
+
+#[test]
+fn test_copy_image_index_copies_missing_children() {
+    let mut source = Server::new();
+    let mut destination = Server::new();
+    let config = br#"{"architecture":"arm64"}"#;
+    let amd64 = manifest_json(b"{}", &[]);
+    let arm64 = manifest_json(config, &[]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+
+    let _index = serve_manifest(&mut source, "app", "v1", &index);
+    let _arm64 = serve_manifest(&mut source, "app", &sha256_of(&arm64), &arm64);
+    let _config = serve_blob(&mut source, "app", config);
+
+    let _amd64_head = destination
+        .mock(
+            "HEAD",
+            format!("/v2/app/manifests/{}", sha256_of(&amd64)).as_str(),
+        )
+        .with_status(200)
+        .create();
+    let _arm64_head = destination
+        .mock(
+            "HEAD",
+            format!("/v2/app/manifests/{}", sha256_of(&arm64)).as_str(),
+        )
+        .with_status(404)
+        .create();
+    let _config_head = head_blob(&mut destination, "app", config, 404);
+    let _upload = accept_upload(&mut destination, "app", config);
+    let child = accept_manifest(&mut destination, "app", &sha256_of(&arm64), &arm64);
+    let push = accept_manifest(&mut destination, "app", "v1", &index);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let mut events = Vec::new();
+    let report = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new(),
+        |event| events.push(event.clone()),
+    )
+    .unwrap();
+
+    child.assert();
+    push.assert();
+    assert_eq!(report.digest, sha256_of(&index));
+    assert_eq!(report.manifests, 2);
+    assert_eq!(events[0], CopyEvent::ManifestExists(sha256_of(&amd64)));
+}
+
+#[test]
+fn test_copy_image_with_platform_filter() {
+    let mut source = Server::new();
+    let mut destination = Server::new();
+    let config = br#"{"architecture":"arm64"}"#;
+    let amd64 = manifest_json(b"{}", &[]);
+    let arm64 = manifest_json(config, &[]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+
+    let _index = serve_manifest(&mut source, "app", "v1", &index);
+    let _arm64 = serve_manifest(&mut source, "app", &sha256_of(&arm64), &arm64);
+    let _config_head = head_blob(&mut destination, "app", config, 200);
+    let push = accept_manifest(&mut destination, "app", "v1", &arm64);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let report = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new().with_platform("linux", "arm64", None),
+        |_| {},
+    )
+    .unwrap();
+
+    push.assert();
+    assert_eq!(report.digest, sha256_of(&arm64));
+    assert_eq!(report.manifests, 1);
+}
+
+#[test]
+fn test_copy_image_unknown_platform() {
+    let mut source = Server::new();
+    let destination = Server::new();
+    let amd64 = manifest_json(b"{}", &[]);
+    let index = index_json(&[(&amd64, "linux", "amd64")]);
+
+    let _index = serve_manifest(&mut source, "app", "v1", &index);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let result = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new().with_platform("linux", "s390x", None),
+        |_| {},
+    );
+
+    match result {
+        Err(RexError::NotFound { name, .. }) => {
+            assert!(name.contains("linux/s390x"));
+            assert!(name.contains("linux/amd64"));
+        }
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_copy_image_skips_foreign_layers() {
+    let mut source = Server::new();
+    let mut destination = Server::new();
+    let config = b"{}";
+    let foreign = b"windows base layer";
+    let manifest = manifest_json(
+        config,
+        &[(
+            foreign,
+            "application/vnd.docker.image.rootfs.foreign.diff.tar.gzip",
+        )],
+    );
+
+    let _manifest = serve_manifest(&mut source, "app", "v1", &manifest);
+    let _config_head = head_blob(&mut destination, "app", config, 200);
+    let foreign_head = head_blob(&mut destination, "app", foreign, 404).expect(0);
+    let _push = accept_manifest(&mut destination, "app", "v1", &manifest);
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let report = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new(),
+        |_| {},
+    )
+    .unwrap();
+
+    foreign_head.assert();
+    assert_eq!(report.blobs_skipped, 1);
+    assert_eq!(report.blobs_copied, 0);
+}
+
+#[test]
+fn test_copy_image_missing_source() {
+    let mut source = Server::new();
+    let destination = Server::new();
+
+    let _mock = source
+        .mock("GET", "/v2/app/manifests/v1")
+        .with_status(404)
+        .create();
+
+    let source_client = Client::new(&source.url(), None).unwrap();
+    let destination_client = Client::new(&destination.url(), None).unwrap();
+    let result = copy_image(
+        &CopyEndpoint::new(&source_client, "app", "v1"),
+        &CopyEndpoint::new(&destination_client, "app", "v1"),
+        &CopyOptions::new(),
+        |_| {},
+    );
+
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
+
+#[test]
+fn test_copy_options_chunk_size_floor() {
+    assert_eq!(CopyOptions::new().chunk_size, DEFAULT_CHUNK_SIZE);
+    assert_eq!(CopyOptions::new().with_chunk_size(0).chunk_size, 1);
+}
@@ -0,0 +1,528 @@
+//! Image comparison.
+//!
+//! Two images are compared at three levels, from cheapest to most costly:
+//!
+//! - configuration: platform, user, working directory, entrypoint, command,
+//!   environment, labels, exposed ports, volumes and stop signal
+//! - layers: shared, added and removed, by digest
+//! - filesystem: the merged filesystems, file by file. Regular files are
+//!   compared by content (SHA-256), so every layer of both images is read;
+//!   a layer shared by both images is read once
+
+use crate::error::Result;
+use crate::layer::{EntryKind, FileEntry, layer_error, merge_layers, open_layer, walk_layer};
+use crate::oci::{Descriptor, ImageConfiguration, ImageManifest};
+use crate::registry::Registry;
+use ring::digest::{Context, SHA256};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io::Read;
+
+#[cfg(test)]
+mod tests;
+
+/// How an item differs between the old and the new image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Change {
+    /// Only in the new image
+    Added,
+    /// Only in the old image
+    Removed,
+    /// In both, with different values
+    Modified,
+}
+
+impl Change {
+    /// Classifies a pair of optional values; `None` if they are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::diff::Change;
+    ///
+    /// assert_eq!(Change::between(&None, &Some(1)), Some(Change::Added));
+    /// assert_eq!(Change::between(&Some(1), &Some(2)), Some(Change::Modified));
+    /// assert_eq!(Change::between(&Some(1), &Some(1)), None);
+    /// ```
+    pub fn between<T: PartialEq>(old: &Option<T>, new: &Option<T>) -> Option<Change> {
+        match (old, new) {
+            (None, Some(_)) => Some(Change::Added),
+            (Some(_), None) => Some(Change::Removed),
+            (Some(old), Some(new)) if old != new => Some(Change::Modified),
+            _ => None,
+        }
+    }
+
+    /// Marker used in listings: "+", "-" or "~".
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Change::Added => "+",
+            Change::Removed => "-",
+            Change::Modified => "~",
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Change::Added => "added",
+            Change::Removed => "removed",
+            Change::Modified => "modified",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A configuration field that differs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigChange {
+    /// Field name (e.g., "user", "env", "label")
+    pub field: String,
+    /// Key within the field, for environment variables and labels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Kind of change
+    pub change: Change,
+    /// Value in the old image
+    pub old: Option<String>,
+    /// Value in the new image
+    pub new: Option<String>,
+}
+
+/// Pushes a change for `field` if the values differ.
+fn compare_field(
+    changes: &mut Vec<ConfigChange>,
+    field: &str,
+    key: Option<&str>,
+    old: Option<String>,
+    new: Option<String>,
+) {
+    if let Some(change) = Change::between(&old, &new) {
+        changes.push(ConfigChange {
+            field: field.to_string(),
+            key: key.map(str::to_string),
+            change,
+            old,
+            new,
+        });
+    }
+}
+
+/// Compares keyed values (environment, labels) key by key, in key order.
+fn compare_map(
+    changes: &mut Vec<ConfigChange>,
+    field: &str,
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for key in keys {
+        compare_field(
+            changes,
+            field,
+            Some(key),
+            old.get(key).cloned(),
+            new.get(key).cloned(),
+        );
+    }
+}
+
+/// Compares sets of values (ports, volumes); each value is added or removed.
+fn compare_set(changes: &mut Vec<ConfigChange>, field: &str, old: &[String], new: &[String]) {
+    let old: BTreeSet<&String> = old.iter().collect();
+    let new: BTreeSet<&String> = new.iter().collect();
+    for value in old.difference(&new) {
+        compare_field(changes, field, None, Some(value.to_string()), None);
+    }
+    for value in new.difference(&old) {
+        compare_field(changes, field, None, None, Some(value.to_string()));
+    }
+}
+
+/// Splits "KEY=value" environment entries; an entry without "=" has an
+/// empty value.
+fn env_map(env: &[String]) -> BTreeMap<String, String> {
+    env.iter()
+        .map(|entry| match entry.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (entry.clone(), String::new()),
+        })
+        .collect()
+}
+
+/// Formats an exec-form command as JSON (`["nginx","-g","daemon off;"]`).
+fn command_string(command: &Option<Vec<String>>) -> Option<String> {
+    command
+        .as_ref()
+        .map(|args| serde_json::to_string(args).unwrap_or_default())
+}
+
+/// Formats the platform of an image configuration as "os/arch[/variant]".
+pub fn platform_string(config: &ImageConfiguration) -> String {
+    let mut platform = format!("{}/{}", config.os(), config.architecture());
+    if let Some(variant) = config.variant() {
+        platform.push('/');
+        platform.push_str(variant);
+    }
+    platform
+}
+
+/// Compares two image configurations.
+///
+/// Environment variables and labels are compared key by key, exposed ports
+/// and volumes as sets. Entrypoint and command are compared as a whole.
+///
+/// # Returns
+///
+/// The differing fields, in a fixed field order, keys sorted within a field.
+pub fn diff_configs(old: &ImageConfiguration, new: &ImageConfiguration) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    compare_field(
+        &mut changes,
+        "platform",
+        None,
+        Some(platform_string(old)),
+        Some(platform_string(new)),
+    );
+
+    let old_config = old.config().clone().unwrap_or_default();
+    let new_config = new.config().clone().unwrap_or_default();
+
+    compare_field(
+        &mut changes,
+        "user",
+        None,
+        old_config.user().clone(),
+        new_config.user().clone(),
+    );
+    compare_field(
+        &mut changes,
+        "working_dir",
+        None,
+        old_config.working_dir().clone(),
+        new_config.working_dir().clone(),
+    );
+    compare_field(
+        &mut changes,
+        "entrypoint",
+        None,
+        command_string(old_config.entrypoint()),
+        command_string(new_config.entrypoint()),
+    );
+    compare_field(
+        &mut changes,
+        "cmd",
+        None,
+        command_string(old_config.cmd()),
+        command_string(new_config.cmd()),
+    );
+    compare_map(
+        &mut changes,
+        "env",
+        &env_map(old_config.env().as_deref().unwrap_or_default()),
+        &env_map(new_config.env().as_deref().unwrap_or_default()),
+    );
+    compare_map(
+        &mut changes,
+        "label",
+        &old_config
+            .labels()
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        &new_config
+            .labels()
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+    );
+    compare_set(
+        &mut changes,
+        "port",
+        old_config.exposed_ports().as_deref().unwrap_or_default(),
+        new_config.exposed_ports().as_deref().unwrap_or_default(),
+    );
+    compare_set(
+        &mut changes,
+        "volume",
+        old_config.volumes().as_deref().unwrap_or_default(),
+        new_config.volumes().as_deref().unwrap_or_default(),
+    );
+    compare_field(
+        &mut changes,
+        "stop_signal",
+        None,
+        old_config.stop_signal().clone(),
+        new_config.stop_signal().clone(),
+    );
+
+    changes
+}
+
+/// A layer, by digest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LayerRef {
+    /// Layer digest
+    pub digest: String,
+    /// Compressed size in bytes
+    pub size: u64,
+}
+
+impl From<&Descriptor> for LayerRef {
+    fn from(descriptor: &Descriptor) -> Self {
+        LayerRef {
+            digest: descriptor.digest().to_string(),
+            size: descriptor.size(),
+        }
+    }
+}
+
+/// Layers of two images, split by digest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LayerDiff {
+    /// Layers of the new image also in the old one, in the new image's order
+    pub shared: Vec<LayerRef>,
+    /// Layers only in the new image, in its order
+    pub added: Vec<LayerRef>,
+    /// Layers only in the old image, in its order
+    pub removed: Vec<LayerRef>,
+}
+
+impl LayerDiff {
+    /// Whether both images have the same layers.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Compares the layer lists of two images by digest.
+///
+/// A digest listed twice in one image and once in the other counts as
+/// shared once, and added or removed once.
+pub fn diff_layers(old: &[Descriptor], new: &[Descriptor]) -> LayerDiff {
+    let mut remaining: HashMap<String, usize> = HashMap::new();
+    for descriptor in old {
+        *remaining
+            .entry(descriptor.digest().to_string())
+            .or_default() += 1;
+    }
+
+    let mut diff = LayerDiff::default();
+    for descriptor in new {
+        match remaining.get_mut(&descriptor.digest().to_string()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                diff.shared.push(LayerRef::from(descriptor));
+            }
+            _ => diff.added.push(LayerRef::from(descriptor)),
+        }
+    }
+
+    // Unmatched old layers, keeping their order
+    for descriptor in old.iter().rev() {
+        if let Some(count) = remaining.get_mut(&descriptor.digest().to_string())
+            && *count > 0
+        {
+            *count -= 1;
+            diff.removed.push(LayerRef::from(descriptor));
+        }
+    }
+    diff.removed.reverse();
+
+    diff
+}
+
+/// A file that differs between the merged filesystems.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChange {
+    /// Path, relative to the root
+    pub path: String,
+    /// Kind of change
+    pub change: Change,
+    /// Entry kind in the new image (in the old one if removed)
+    pub kind: EntryKind,
+    /// Size in the old image, for regular files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_size: Option<u64>,
+    /// Size in the new image, for regular files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_size: Option<u64>,
+}
+
+impl FileChange {
+    /// Size difference in bytes (new minus old).
+    pub fn size_delta(&self) -> i64 {
+        self.new_size.unwrap_or(0) as i64 - self.old_size.unwrap_or(0) as i64
+    }
+}
+
+/// A layer entry with the SHA-256 of its content (regular files only).
+type HashedEntry = (FileEntry, Option<Vec<u8>>);
+
+/// Merged filesystem, by path.
+type Snapshot = BTreeMap<String, HashedEntry>;
+
+/// Reads a layer's entries, hashing the content of regular files.
+fn read_hashed_layer<R: Read>(reader: R, layer: usize) -> Result<Vec<HashedEntry>> {
+    let mut entries = Vec::new();
+    walk_layer(reader, layer, |entry, content| {
+        let hash = if entry.kind == EntryKind::File {
+            let mut context = Context::new(&SHA256);
+            let mut buffer = [0u8; 64 * 1024];
+            loop {
+                let read = content.read(&mut buffer).map_err(layer_error)?;
+                if read == 0 {
+                    break;
+                }
+                context.update(&buffer[..read]);
+            }
+            Some(context.finish().as_ref().to_vec())
+        } else {
+            None
+        };
+        entries.push((entry, hash));
+        Ok(true)
+    })?;
+    Ok(entries)
+}
+
+/// Merges hashed layer listings, lowest first (see [`merge_layers`]).
+fn snapshot(layers: Vec<Vec<HashedEntry>>) -> Snapshot {
+    let mut hashes = HashMap::new();
+    let listings: Vec<Vec<FileEntry>> = layers
+        .into_iter()
+        .map(|entries| {
+            entries
+                .into_iter()
+                .map(|(entry, hash)| {
+                    if let Some(hash) = hash {
+                        hashes.insert((entry.layer, entry.path.clone()), hash);
+                    }
+                    entry
+                })
+                .collect()
+        })
+        .collect();
+
+    merge_layers(listings)
+        .into_iter()
+        .map(|entry| {
+            let hash = hashes.remove(&(entry.layer, entry.path.clone()));
+            (entry.path.clone(), (entry, hash))
+        })
+        .collect()
+}
+
+/// Whether two entries at the same path differ. Timestamps are ignored.
+fn entry_differs(old: &HashedEntry, new: &HashedEntry) -> bool {
+    let ((old, old_hash), (new, new_hash)) = (old, new);
+    old.kind != new.kind
+        || old.mode != new.mode
+        || old.uid != new.uid
+        || old.gid != new.gid
+        || old.link_target != new.link_target
+        || old.size != new.size
+        || old_hash != new_hash
+}
+
+/// Compares two merged filesystems, in path order.
+fn compare_snapshots(old: &Snapshot, new: &Snapshot) -> Vec<FileChange> {
+    let size = |entry: &FileEntry| (entry.kind == EntryKind::File).then_some(entry.size);
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let (change, kind) = match (old.get(path), new.get(path)) {
+                (None, Some((entry, _))) => (Change::Added, entry.kind),
+                (Some((entry, _)), None) => (Change::Removed, entry.kind),
+                (Some(before), Some(after)) if entry_differs(before, after) => {
+                    (Change::Modified, after.0.kind)
+                }
+                _ => return None,
+            };
+            Some(FileChange {
+                path: path.clone(),
+                change,
+                kind,
+                old_size: old.get(path).and_then(|(entry, _)| size(entry)),
+                new_size: new.get(path).and_then(|(entry, _)| size(entry)),
+            })
+        })
+        .collect()
+}
+
+/// Compares the merged filesystems of two images.
+///
+/// Every layer of both images is streamed; a layer present in both is read
+/// once. Both images must be in `registry`, possibly in different
+/// repositories.
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding both images
+/// * `old_repository` - Repository of the old image
+/// * `old` - Manifest of the old image (a single platform)
+/// * `new_repository` - Repository of the new image
+/// * `new` - Manifest of the new image (a single platform)
+///
+/// # Returns
+///
+/// The added, removed and modified entries, sorted by path. A file is
+/// modified if its type, content, size, permissions, owner or link target
+/// changed; timestamps are ignored.
+///
+/// # Errors
+///
+/// Returns an error if a layer cannot be fetched, or is not a (compressed)
+/// tar archive.
+pub fn diff_files(
+    registry: &Registry,
+    old_repository: &str,
+    old: &ImageManifest,
+    new_repository: &str,
+    new: &ImageManifest,
+) -> Result<Vec<FileChange>> {
+    let mut read: HashMap<String, Vec<HashedEntry>> = HashMap::new();
+    let mut snapshots = Vec::with_capacity(2);
+
+    for (repository, manifest) in [(old_repository, old), (new_repository, new)] {
+        let mut layers = Vec::with_capacity(manifest.layers().len());
+        for (index, descriptor) in manifest.layers().iter().enumerate() {
+            let digest = descriptor.digest().to_string();
+            let entries = match read.get(&digest) {
+                Some(entries) => entries.clone(),
+                None => {
+                    let entries =
+                        read_hashed_layer(open_layer(registry, repository, descriptor)?, index)?;
+                    read.insert(digest, entries.clone());
+                    entries
+                }
+            };
+            // The same layer may sit at another index in the other image
+            layers.push(
+                entries
+                    .into_iter()
+                    .map(|(entry, hash)| {
+                        (
+                            FileEntry {
+                                layer: index,
+                                ..entry
+                            },
+                            hash,
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        snapshots.push(snapshot(layers));
+    }
+
+    Ok(compare_snapshots(&snapshots[0], &snapshots[1]))
+}
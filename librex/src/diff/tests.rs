@@ -0,0 +1,179 @@
+use super::*;
+use crate::test_support::{TarEntry, tar};
+
+fn config(json: &str) -> ImageConfiguration {
+    serde_json::from_str(json).unwrap()
+}
+
+fn descriptor(digest_char: char, size: u64) -> Descriptor {
+    serde_json::from_str(&format!(
+        r#"{{"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","size":{},"digest":"sha256:{}"}}"#,
+        size,
+        digest_char.to_string().repeat(64)
+    ))
+    .unwrap()
+}
+
+fn snapshot_of(layers: &[Vec<u8>]) -> Snapshot {
+    snapshot(
+        layers
+            .iter()
+            .enumerate()
+            .map(|(index, bytes)| read_hashed_layer(&bytes[..], index).unwrap())
+            .collect(),
+    )
+}
+
+#[test]
+fn test_diff_configs() {
+    let old = config(
+        r#"{"architecture":"amd64","os":"linux","rootfs":{"type":"layers","diff_ids":[]},
+            "config":{"User":"root","Env":["PATH=/usr/bin","VERSION=1.4"],
+                      "Entrypoint":["/app/server"],"ExposedPorts":{"8080/tcp":{}},
+                      "Labels":{"org.opencontainers.image.version":"1.4","team":"core"}}}"#,
+    );
+    let new = config(
+        r#"{"architecture":"amd64","os":"linux","rootfs":{"type":"layers","diff_ids":[]},
+            "config":{"User":"app","Env":["PATH=/usr/bin","VERSION=1.5","DEBUG"],
+                      "Entrypoint":["/app/server","--serve"],"ExposedPorts":{"9090/tcp":{}},
+                      "Labels":{"org.opencontainers.image.version":"1.5"}}}"#,
+    );
+
+    let changes = diff_configs(&old, &new);
+    let summary: Vec<(&str, Option<&str>, Change)> = changes
+        .iter()
+        .map(|c| (c.field.as_str(), c.key.as_deref(), c.change))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("user", None, Change::Modified),
+            ("entrypoint", None, Change::Modified),
+            ("env", Some("DEBUG"), Change::Added),
+            ("env", Some("VERSION"), Change::Modified),
+            (
+                "label",
+                Some("org.opencontainers.image.version"),
+                Change::Modified
+            ),
+            ("label", Some("team"), Change::Removed),
+            ("port", None, Change::Removed),
+            ("port", None, Change::Added),
+        ]
+    );
+    assert_eq!(changes[1].old.as_deref(), Some(r#"["/app/server"]"#));
+    assert_eq!(changes[2].new.as_deref(), Some(""));
+    assert_eq!(changes[6].old.as_deref(), Some("8080/tcp"));
+
+    assert!(diff_configs(&old, &old).is_empty());
+}
+
+#[test]
+fn test_diff_configs_platform() {
+    let amd64 =
+        config(r#"{"architecture":"amd64","os":"linux","rootfs":{"type":"layers","diff_ids":[]}}"#);
+    let arm = config(
+        r#"{"architecture":"arm","os":"linux","variant":"v7","rootfs":{"type":"layers","diff_ids":[]}}"#,
+    );
+
+    let changes = diff_configs(&amd64, &arm);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].field, "platform");
+    assert_eq!(changes[0].new.as_deref(), Some("linux/arm/v7"));
+}
+
+#[test]
+fn test_diff_layers() {
+    let old = vec![
+        descriptor('a', 10),
+        descriptor('b', 20),
+        descriptor('c', 30),
+    ];
+    let new = vec![
+        descriptor('a', 10),
+        descriptor('b', 20),
+        descriptor('d', 40),
+        descriptor('b', 20),
+    ];
+
+    let diff = diff_layers(&old, &new);
+
+    let digests = |layers: &[LayerRef]| -> Vec<char> {
+        layers
+            .iter()
+            .map(|l| l.digest.chars().nth(7).unwrap())
+            .collect()
+    };
+    assert_eq!(digests(&diff.shared), vec!['a', 'b']);
+    assert_eq!(digests(&diff.added), vec!['d', 'b']);
+    assert_eq!(digests(&diff.removed), vec!['c']);
+    assert!(!diff.is_empty());
+    assert!(diff_layers(&old, &old).is_empty());
+}
+
+#[test]
+fn test_compare_snapshots() {
+    let base = tar(&[
+        TarEntry::File("etc/os-release", "ID=alpine\n"),
+        TarEntry::File("etc/motd", "Welcome!\n"),
+        TarEntry::File("app/server", "v1-binary"),
+        TarEntry::File("app/config.yaml", "port: 8080\n"),
+        TarEntry::Symlink("bin/sh", "/bin/busybox"),
+    ]);
+    let old = snapshot_of(std::slice::from_ref(&base));
+    let new = snapshot_of(&[
+        base,
+        tar(&[
+            TarEntry::File("etc/.wh.motd", ""),
+            // Same size, different content
+            TarEntry::File("app/server", "v2-binary"),
+            // Same content, rewritten by a later layer
+            TarEntry::File("app/config.yaml", "port: 8080\n"),
+            TarEntry::File("app/static/index.html", "<html></html>"),
+            TarEntry::Symlink("bin/sh", "/bin/dash"),
+        ]),
+    ]);
+
+    let changes = compare_snapshots(&old, &new);
+    let summary: Vec<(&str, Change)> = changes
+        .iter()
+        .map(|c| (c.path.as_str(), c.change))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("app/server", Change::Modified),
+            ("app/static/index.html", Change::Added),
+            ("bin/sh", Change::Modified),
+            ("etc/motd", Change::Removed),
+        ]
+    );
+    assert_eq!(changes[0].size_delta(), 0);
+    assert_eq!(changes[1].new_size, Some(13));
+    assert_eq!(changes[1].old_size, None);
+    assert_eq!(changes[3].size_delta(), -9);
+    assert_eq!(changes[2].kind, EntryKind::Symlink);
+    assert_eq!(changes[2].new_size, None);
+}
+
+#[test]
+fn test_change_serialization() {
+    let change = FileChange {
+        path: "etc/motd".to_string(),
+        change: Change::Removed,
+        kind: EntryKind::File,
+        old_size: Some(9),
+        new_size: None,
+    };
+
+    let json = serde_json::to_value(&change).unwrap();
+
+    assert_eq!(json["change"], "removed");
+    assert_eq!(json["old_size"], 9);
+    assert!(json.get("new_size").is_none());
+    assert_eq!(Change::Modified.symbol(), "~");
+    assert_eq!(Change::Added.to_string(), "added");
+}
@@ -50,6 +50,10 @@ pub enum RexError {
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
     },
 
+    /// A file was expected but the path is a directory
+    #[error("{path} is a directory")]
+    IsDirectory { path: String },
+
     /// Configuration errors (invalid config file, missing settings)
     #[error("Configuration error: {message}")]
     Config {
@@ -212,6 +216,20 @@ impl RexError {
         }
     }
 
+    /// Creates a new error for a directory found where a file was expected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::error::RexError;
+    ///
+    /// let err = RexError::is_directory("/etc");
+    /// assert!(matches!(err, RexError::IsDirectory { .. }));
+    /// ```
+    pub fn is_directory<S: Into<String>>(path: S) -> Self {
+        Self::IsDirectory { path: path.into() }
+    }
+
     /// Creates a new configuration error.
     ///
     /// # Examples
@@ -228,6 +228,13 @@ fn test_validation_with_source_helper_constructor() {
     assert!(err.source().is_some());
 }
 
+#[test]
+fn test_is_directory_helper_constructor() {
+    let err = RexError::is_directory("/etc");
+    assert!(matches!(err, RexError::IsDirectory { .. }));
+    assert_eq!(err.to_string(), "/etc is a directory");
+}
+
 #[test]
 fn test_config_helper_constructor() {
     let err = RexError::config("invalid config file", Some("/path/to/config.toml"));
@@ -0,0 +1,521 @@
+//! Garbage report: manifests no tag keeps alive, and broken links.
+//!
+//! The distribution API cannot list untagged manifests, but some can still
+//! be found: through the children of an index, through the referrers API,
+//! and through the `subject` of a referrer. This module walks every tag of
+//! a repository along those links and reports:
+//!
+//! - untagged manifests: found, but not reachable from any tag
+//! - indexes whose children are missing
+//! - referrers whose subject is gone
+//!
+//! Findings no tag reaches can then be deleted by digest with [`delete`];
+//! the others are only reported.
+
+use crate::client::{Client, referrers_tag_subject};
+use crate::error::{Result, RexError};
+use crate::oci::ManifestOrIndex;
+use crate::registry::Registry;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+mod tests;
+
+/// Why a manifest is in the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcReason {
+    /// Not reachable from any tag
+    Untagged,
+    /// A tagged index with children the registry no longer has; reported,
+    /// never deleted
+    MissingChildren,
+    /// A referrer whose subject the registry no longer has
+    DanglingReferrer,
+}
+
+impl std::fmt::Display for GcReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GcReason::Untagged => write!(f, "untagged"),
+            GcReason::MissingChildren => write!(f, "missing children"),
+            GcReason::DanglingReferrer => write!(f, "dangling referrer"),
+        }
+    }
+}
+
+/// A manifest reported for removal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GcFinding {
+    /// Repository holding the manifest
+    pub repository: String,
+    /// Manifest digest
+    pub digest: String,
+    /// Manifest media type
+    pub media_type: String,
+    /// Why it is reported
+    pub reason: GcReason,
+    /// Tags pointing at the manifest (a broken index, or a tag-schema
+    /// referrer such as cosign's `sha256-<hex>.sig`); deleting it removes them
+    pub tags: Vec<String>,
+    /// Reachable from a regular tag: reported, but left out of [`delete`]
+    pub live: bool,
+    /// Missing children, or the missing subject
+    pub missing: Vec<String>,
+    /// Manifests the finding was discovered through (parent indexes,
+    /// referrers, subjects)
+    pub found_via: Vec<String>,
+}
+
+/// Result of a garbage scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GcReport {
+    /// Number of repositories scanned
+    pub repositories: usize,
+    /// Number of manifests read
+    pub manifests: usize,
+    /// Findings, in the order [`delete`] removes them: referrers first,
+    /// then indexes, then image manifests
+    pub findings: Vec<GcFinding>,
+    /// Repositories and manifests that could not be read ("reference: error")
+    pub skipped: Vec<String>,
+}
+
+impl GcReport {
+    /// Findings with the given reason.
+    pub fn by_reason(&self, reason: GcReason) -> impl Iterator<Item = &GcFinding> {
+        self.findings.iter().filter(move |f| f.reason == reason)
+    }
+
+    /// Findings [`delete`] removes: those no regular tag reaches.
+    pub fn deletable(&self) -> impl Iterator<Item = &GcFinding> {
+        self.findings.iter().filter(|f| !f.live)
+    }
+}
+
+/// Result of deleting the findings of a report.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GcDeletion {
+    /// Deleted manifests ("repository@digest")
+    pub deleted: Vec<String>,
+    /// Manifests that could not be deleted ("repository@digest: error")
+    pub failed: Vec<String>,
+}
+
+/// Progress of a garbage scan or deletion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GcEvent {
+    /// The catalog was listed; holds the number of repositories to scan
+    Catalog(usize),
+    /// A repository was scanned
+    Repository {
+        /// Repository name
+        name: String,
+        /// Number of manifests read
+        manifests: usize,
+    },
+    /// A repository or manifest could not be read and was left out
+    Skipped {
+        /// Repository or manifest reference
+        reference: String,
+        /// Why it was skipped
+        error: String,
+    },
+    /// A finding was deleted
+    Deleted {
+        /// Repository name
+        repository: String,
+        /// Manifest digest
+        digest: String,
+    },
+    /// A finding could not be deleted
+    DeleteFailed {
+        /// Repository name
+        repository: String,
+        /// Manifest digest
+        digest: String,
+        /// Why the deletion failed
+        error: String,
+    },
+}
+
+/// A manifest read during the walk.
+#[derive(Debug)]
+struct Node {
+    media_type: String,
+    index: bool,
+    children: Vec<String>,
+    subject: Option<String>,
+    tags: Vec<String>,
+    found_via: BTreeSet<String>,
+}
+
+/// Walks one repository.
+struct Walk<'a, F> {
+    client: &'a Client,
+    repository: &'a str,
+    nodes: BTreeMap<String, Node>,
+    /// Digests the registry answered 404 for
+    missing: BTreeSet<String>,
+    /// Manifests behind regular (non tag-schema) tags
+    roots: BTreeSet<String>,
+    queue: Vec<String>,
+    skipped: Vec<String>,
+    on_event: &'a mut F,
+}
+
+impl<F: FnMut(&GcEvent)> Walk<'_, F> {
+    fn skip(&mut self, reference: String, error: &RexError) {
+        (self.on_event)(&GcEvent::Skipped {
+            reference: reference.clone(),
+            error: error.to_string(),
+        });
+        self.skipped.push(format!("{}: {}", reference, error));
+    }
+
+    /// Reads a manifest by tag or digest and queues it for the walk.
+    ///
+    /// Returns the digest, or `None` if it is missing or unreadable.
+    fn read(&mut self, reference: &str, via: Option<&str>) -> Option<String> {
+        if let Some(node) = self.nodes.get_mut(reference) {
+            node.found_via.extend(via.map(str::to_string));
+            return Some(reference.to_string());
+        }
+        if self.missing.contains(reference) {
+            return None;
+        }
+
+        let location = match reference.contains(':') {
+            true => format!("{}@{}", self.repository, reference),
+            false => format!("{}:{}", self.repository, reference),
+        };
+        let (bytes, digest) = match self.client.fetch_manifest(self.repository, reference) {
+            Ok(fetched) => fetched,
+            Err(RexError::NotFound { .. }) if reference.contains(':') => {
+                self.missing.insert(reference.to_string());
+                return None;
+            }
+            Err(e) => {
+                self.skip(location, &e);
+                return None;
+            }
+        };
+        if let Some(node) = self.nodes.get_mut(&digest) {
+            node.found_via.extend(via.map(str::to_string));
+            return Some(digest);
+        }
+
+        let parsed = match ManifestOrIndex::from_bytes(&bytes) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                self.skip(location, &e);
+                return None;
+            }
+        };
+        let (index, children, subject) = match &parsed {
+            ManifestOrIndex::Manifest(manifest) => (
+                false,
+                Vec::new(),
+                manifest.subject().as_ref().map(|s| s.digest().to_string()),
+            ),
+            ManifestOrIndex::Index(index) => (
+                true,
+                index
+                    .manifests()
+                    .iter()
+                    .map(|child| child.digest().to_string())
+                    .collect(),
+                index.subject().as_ref().map(|s| s.digest().to_string()),
+            ),
+        };
+        let media_type = crate::oci::manifest_media_type(&bytes).unwrap_or_default();
+
+        self.nodes.insert(
+            digest.clone(),
+            Node {
+                media_type,
+                index,
+                children,
+                subject,
+                tags: Vec::new(),
+                found_via: via.map(str::to_string).into_iter().collect(),
+            },
+        );
+        self.queue.push(digest.clone());
+        Some(digest)
+    }
+
+    /// Follows children, subject and referrers of every queued manifest.
+    fn follow(&mut self) {
+        while let Some(digest) = self.queue.pop() {
+            let node = &self.nodes[&digest];
+            let children = node.children.clone();
+            let subject = node.subject.clone();
+
+            for child in &children {
+                self.read(child, Some(&digest));
+            }
+            if let Some(subject) = subject {
+                self.read(&subject, Some(&digest));
+            }
+
+            match self.client.fetch_referrers(self.repository, &digest, None) {
+                Ok(referrers) => {
+                    for referrer in referrers {
+                        self.read(referrer.digest().as_ref(), Some(&digest));
+                    }
+                }
+                Err(e) => self.skip(format!("{}@{} referrers", self.repository, digest), &e),
+            }
+        }
+    }
+
+    /// Manifests reachable from a regular tag: roots, their children and
+    /// their referrers, transitively.
+    fn live(&self) -> BTreeSet<&str> {
+        let mut live: BTreeSet<&str> = self.roots.iter().map(String::as_str).collect();
+        loop {
+            let before = live.len();
+            for (digest, node) in &self.nodes {
+                if live.contains(digest.as_str()) {
+                    live.extend(node.children.iter().map(String::as_str));
+                } else if node
+                    .subject
+                    .as_deref()
+                    .is_some_and(|subject| live.contains(subject))
+                {
+                    live.insert(digest);
+                }
+            }
+            if live.len() == before {
+                return live;
+            }
+        }
+    }
+
+    fn findings(&self) -> Vec<GcFinding> {
+        let live = self.live();
+        let mut findings = Vec::new();
+
+        for (digest, node) in &self.nodes {
+            let is_live = live.contains(digest.as_str());
+            let missing_children: Vec<String> = node
+                .children
+                .iter()
+                .filter(|child| self.missing.contains(*child))
+                .cloned()
+                .collect();
+
+            let (reason, missing) = match &node.subject {
+                Some(subject) if self.missing.contains(subject) => {
+                    (GcReason::DanglingReferrer, vec![subject.clone()])
+                }
+                _ if !is_live => (GcReason::Untagged, missing_children),
+                _ if !missing_children.is_empty() => (GcReason::MissingChildren, missing_children),
+                _ => continue,
+            };
+
+            findings.push(GcFinding {
+                repository: self.repository.to_string(),
+                digest: digest.clone(),
+                media_type: node.media_type.clone(),
+                reason,
+                tags: node.tags.clone(),
+                live: is_live,
+                missing,
+                found_via: node.found_via.iter().cloned().collect(),
+            });
+        }
+
+        // Referrers, then indexes, then what they point at: a registry may
+        // refuse to delete a manifest something still refers to
+        findings.sort_by_key(|finding| {
+            let node = &self.nodes[&finding.digest];
+            match (node.subject.is_some(), node.index) {
+                (true, _) => 0,
+                (false, true) => 1,
+                (false, false) => 2,
+            }
+        });
+        findings
+    }
+}
+
+/// Scans one repository for garbage.
+fn scan_repository<F>(client: &Client, repository: &str, report: &mut GcReport, on_event: &mut F)
+where
+    F: FnMut(&GcEvent),
+{
+    let tags = match client.fetch_tags(repository) {
+        Ok(tags) => tags,
+        Err(e) => {
+            on_event(&GcEvent::Skipped {
+                reference: repository.to_string(),
+                error: e.to_string(),
+            });
+            report.skipped.push(format!("{}: {}", repository, e));
+            return;
+        }
+    };
+
+    let mut walk = Walk {
+        client,
+        repository,
+        nodes: BTreeMap::new(),
+        missing: BTreeSet::new(),
+        roots: BTreeSet::new(),
+        queue: Vec::new(),
+        skipped: Vec::new(),
+        on_event,
+    };
+
+    // Without every tag, what is untagged cannot be told apart from what
+    // an unread tag keeps alive
+    let mut all_tags_read = true;
+    for tag in &tags {
+        let Some(digest) = walk.read(tag, None) else {
+            all_tags_read = false;
+            continue;
+        };
+        let subject = referrers_tag_subject(tag);
+        let node = walk
+            .nodes
+            .get_mut(&digest)
+            .expect("read returns the digest of a walked node");
+        node.tags.push(tag.clone());
+        match subject {
+            // A tag-schema referrer: kept alive by its subject, not by the tag
+            Some(subject) => {
+                node.subject.get_or_insert(subject);
+            }
+            None => {
+                walk.roots.insert(digest);
+            }
+        }
+    }
+    walk.follow();
+
+    report.manifests += walk.nodes.len();
+    report.findings.extend(
+        walk.findings()
+            .into_iter()
+            .filter(|finding| all_tags_read || finding.reason != GcReason::Untagged),
+    );
+    report.skipped.append(&mut walk.skipped);
+    (walk.on_event)(&GcEvent::Repository {
+        name: repository.to_string(),
+        manifests: walk.nodes.len(),
+    });
+}
+
+/// Scans repositories for untagged manifests and broken links.
+///
+/// Repositories and manifests that cannot be read are recorded as skipped
+/// rather than failing the scan.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `repositories` - Repositories to scan
+/// * `on_event` - Called after each repository, and for each skipped reference
+///
+/// # Returns
+///
+/// A [`GcReport`] with the findings in deletion order.
+pub fn scan<F>(client: &Client, repositories: &[String], mut on_event: F) -> GcReport
+where
+    F: FnMut(&GcEvent),
+{
+    let mut report = GcReport {
+        repositories: repositories.len(),
+        ..Default::default()
+    };
+    for repository in repositories {
+        scan_repository(client, repository, &mut report, &mut on_event);
+    }
+    report
+}
+
+/// Scans a whole registry for untagged manifests and broken links.
+///
+/// Tags and manifests are read from the registry, not from the cache.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `on_event` - Called as the scan progresses
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::gc::{GcReason, analyze};
+///
+/// # fn example() -> librex::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+///
+/// let report = analyze(&client, |_| {})?;
+/// for finding in report.by_reason(GcReason::DanglingReferrer) {
+///     println!("{}@{} refers to {:?}", finding.repository, finding.digest, finding.missing);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the catalog cannot be listed.
+pub fn analyze<F>(client: &Client, mut on_event: F) -> Result<GcReport>
+where
+    F: FnMut(&GcEvent),
+{
+    let repositories = client.fetch_catalog()?;
+    on_event(&GcEvent::Catalog(repositories.len()));
+    Ok(scan(client, &repositories, on_event))
+}
+
+/// Deletes the findings of a report by digest, in report order.
+///
+/// Only [`GcReport::deletable`] findings are deleted: a manifest a regular
+/// tag reaches is kept even if it has broken links. A failed deletion is
+/// recorded and the rest go on. Tags of deleted manifests are dropped from
+/// the cache.
+///
+/// # Arguments
+///
+/// * `registry` - Registry the report was made for
+/// * `report` - Report whose findings are deleted
+/// * `on_event` - Called for each deletion
+pub fn delete<F>(registry: &mut Registry, report: &GcReport, mut on_event: F) -> GcDeletion
+where
+    F: FnMut(&GcEvent),
+{
+    let mut deletion = GcDeletion::default();
+
+    for finding in report.deletable() {
+        let location = format!("{}@{}", finding.repository, finding.digest);
+        match registry.delete_manifest(&finding.repository, &finding.digest) {
+            Ok(()) => {
+                for tag in &finding.tags {
+                    registry.invalidate_tag(&finding.repository, tag);
+                }
+                on_event(&GcEvent::Deleted {
+                    repository: finding.repository.clone(),
+                    digest: finding.digest.clone(),
+                });
+                deletion.deleted.push(location);
+            }
+            Err(e) => {
+                on_event(&GcEvent::DeleteFailed {
+                    repository: finding.repository.clone(),
+                    digest: finding.digest.clone(),
+                    error: e.to_string(),
+                });
+                deletion.failed.push(format!("{}: {}", location, e));
+            }
+        }
+    }
+
+    deletion
+}
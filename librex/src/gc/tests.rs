@@ -0,0 +1,289 @@
+use super::*;
+use crate::test_support::{index_json, manifest_json, serve_manifest, sha256_of};
+use mockito::{Mock, Server, ServerGuard};
+use serde_json::json;
+
+/// An image manifest referring to `subject`.
+fn referrer(config: &[u8], subject: &str) -> Vec<u8> {
+    let mut manifest: serde_json::Value =
+        serde_json::from_slice(&manifest_json(config, &[])).unwrap();
+    manifest["subject"] = json!({
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "digest": subject,
+        "size": 100,
+    });
+    serde_json::to_vec(&manifest).unwrap()
+}
+
+fn serve_referrers(server: &mut Server, digest: &str, referrers: &[&[u8]]) {
+    let manifests: Vec<_> = referrers
+        .iter()
+        .map(|body| {
+            json!({
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": sha256_of(body),
+                "size": body.len(),
+            })
+        })
+        .collect();
+    server
+        .mock("GET", format!("/v2/app/referrers/{}", digest).as_str())
+        .with_status(200)
+        .with_body(
+            json!({
+                "schemaVersion": 2,
+                "mediaType": "application/vnd.oci.image.index.v1+json",
+                "manifests": manifests,
+            })
+            .to_string(),
+        )
+        .create();
+}
+
+fn accept_delete(server: &mut Server, digest: &str, status: usize) -> Mock {
+    server
+        .mock("DELETE", format!("/v2/app/manifests/{}", digest).as_str())
+        .with_status(status)
+        .create()
+}
+
+/// Test registry with one repository, "app":
+///
+/// - `1.0`: an index with a present and a missing child
+/// - `2.0`: an image with a signature found through the referrers API
+/// - `sha256-<old>.sig`: a cosign signature of an untagged image
+/// - `sha256-ddd...d.sig`: a cosign signature of a deleted image
+struct Fixture {
+    server: ServerGuard,
+    broken_index: String,
+    missing_child: String,
+    old: String,
+    old_signature: String,
+    dead_signature: String,
+    dead_subject: String,
+}
+
+fn fixture() -> Fixture {
+    let mut server = Server::new();
+    let present_child = manifest_json(b"1", &[]);
+    let deleted_child = manifest_json(b"2", &[]);
+    let broken_index = index_json(&[
+        (&present_child, "linux", "amd64"),
+        (&deleted_child, "linux", "arm64"),
+    ]);
+    let missing_child = sha256_of(&deleted_child);
+    let current = manifest_json(b"3", &[]);
+    let signature = referrer(b"4", &sha256_of(&current));
+    let old = manifest_json(b"5", &[]);
+    let old_signature = manifest_json(b"6", &[]);
+    let dead_subject = format!("sha256:{}", "d".repeat(64));
+    let dead_signature = manifest_json(b"7", &[]);
+
+    let old_tag = format!("{}.sig", sha256_of(&old).replace(':', "-"));
+    let dead_tag = format!("{}.sig", dead_subject.replace(':', "-"));
+    server
+        .mock("GET", "/v2/_catalog")
+        .with_status(200)
+        .with_body(r#"{"repositories":["app"]}"#)
+        .create();
+    server
+        .mock("GET", "/v2/app/tags/list")
+        .with_status(200)
+        .with_body(json!({"name": "app", "tags": ["1.0", "2.0", old_tag, dead_tag]}).to_string())
+        .create();
+
+    serve_manifest(&mut server, "app", "1.0", &broken_index);
+    serve_manifest(&mut server, "app", "2.0", &current);
+    serve_manifest(&mut server, "app", &old_tag, &old_signature);
+    serve_manifest(&mut server, "app", &dead_tag, &dead_signature);
+    for body in [&present_child, &signature, &old] {
+        serve_manifest(&mut server, "app", &sha256_of(body), body);
+    }
+    for digest in [&missing_child, &dead_subject] {
+        server
+            .mock("GET", format!("/v2/app/manifests/{}", digest).as_str())
+            .with_status(404)
+            .create();
+    }
+
+    serve_referrers(&mut server, &sha256_of(&current), &[&signature]);
+    for body in [
+        &broken_index,
+        &present_child,
+        &signature,
+        &old,
+        &old_signature,
+        &dead_signature,
+    ] {
+        serve_referrers(&mut server, &sha256_of(body), &[]);
+    }
+
+    Fixture {
+        server,
+        broken_index: sha256_of(&broken_index),
+        missing_child,
+        old: sha256_of(&old),
+        old_signature: sha256_of(&old_signature),
+        dead_signature: sha256_of(&dead_signature),
+        dead_subject,
+    }
+}
+
+#[test]
+fn test_analyze_reports_garbage() {
+    let fixture = fixture();
+    let client = Client::new(&fixture.server.url(), None).unwrap();
+    let mut events = Vec::new();
+
+    let report = analyze(&client, |event| events.push(event.clone())).unwrap();
+
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    assert_eq!(report.repositories, 1);
+    // Index, child, image, signature, old image and two cosign signatures
+    assert_eq!(report.manifests, 7);
+    assert_eq!(report.findings.len(), 4);
+
+    let dangling: Vec<_> = report.by_reason(GcReason::DanglingReferrer).collect();
+    assert_eq!(dangling.len(), 1);
+    assert_eq!(dangling[0].digest, fixture.dead_signature);
+    assert_eq!(dangling[0].missing, vec![fixture.dead_subject.clone()]);
+    assert_eq!(dangling[0].tags.len(), 1);
+
+    let broken: Vec<_> = report.by_reason(GcReason::MissingChildren).collect();
+    assert_eq!(broken.len(), 1);
+    assert_eq!(broken[0].digest, fixture.broken_index);
+    assert_eq!(broken[0].tags, vec!["1.0"]);
+    assert_eq!(broken[0].missing, vec![fixture.missing_child.clone()]);
+    assert!(broken[0].live);
+    assert_eq!(report.deletable().count(), 3);
+
+    let untagged: Vec<_> = report.by_reason(GcReason::Untagged).collect();
+    assert_eq!(untagged.len(), 2);
+    // The signature first, then the image it was found through
+    assert_eq!(untagged[0].digest, fixture.old_signature);
+    assert_eq!(untagged[1].digest, fixture.old);
+    assert_eq!(untagged[1].found_via, vec![fixture.old_signature.clone()]);
+    assert!(untagged[1].tags.is_empty());
+    assert!(untagged.iter().all(|finding| !finding.live));
+
+    // Referrers, then the index, then the image manifest
+    assert_eq!(report.findings[2].digest, fixture.broken_index);
+    assert_eq!(report.findings[3].digest, fixture.old);
+
+    assert_eq!(
+        events,
+        vec![
+            GcEvent::Catalog(1),
+            GcEvent::Repository {
+                name: "app".to_string(),
+                manifests: 7,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_delete_removes_findings_by_digest() {
+    let mut fixture = fixture();
+    let client = Client::new(&fixture.server.url(), None).unwrap();
+    let report = analyze(&client, |_| {}).unwrap();
+
+    let deletes = [
+        accept_delete(&mut fixture.server, &fixture.dead_signature, 202),
+        accept_delete(&mut fixture.server, &fixture.old_signature, 202),
+    ];
+    // Tagged: reported, but deleting it would remove the tag
+    let kept = accept_delete(&mut fixture.server, &fixture.broken_index, 202).expect(0);
+    let refused = accept_delete(&mut fixture.server, &fixture.old, 405);
+    let mut registry = Registry::new(client, None, None, false);
+    let mut events = Vec::new();
+
+    let deletion = delete(&mut registry, &report, |event| events.push(event.clone()));
+
+    for mock in &deletes {
+        mock.assert();
+    }
+    kept.assert();
+    refused.assert();
+    assert_eq!(deletion.deleted.len(), 2);
+    assert!(
+        !deletion
+            .deleted
+            .contains(&format!("app@{}", fixture.broken_index))
+    );
+    assert_eq!(deletion.failed.len(), 1);
+    assert!(deletion.failed[0].starts_with(&format!("app@{}: ", fixture.old)));
+    assert!(matches!(
+        events.last(),
+        Some(GcEvent::DeleteFailed { digest, .. }) if *digest == fixture.old
+    ));
+}
+
+#[test]
+fn test_unreadable_tag_suppresses_untagged_findings() {
+    let mut server = Server::new();
+    // The signature's subject may be what "broken" points at
+    let image = manifest_json(b"1", &[]);
+    let signature = manifest_json(b"2", &[]);
+    let signature_tag = format!("{}.sig", sha256_of(&image).replace(':', "-"));
+    server
+        .mock("GET", "/v2/app/tags/list")
+        .with_status(200)
+        .with_body(json!({"name": "app", "tags": ["broken", signature_tag]}).to_string())
+        .create();
+    server
+        .mock("GET", "/v2/app/manifests/broken")
+        .with_status(500)
+        .create();
+    serve_manifest(&mut server, "app", &signature_tag, &signature);
+    serve_manifest(&mut server, "app", &sha256_of(&image), &image);
+    for body in [&image, &signature] {
+        serve_referrers(&mut server, &sha256_of(body), &[]);
+    }
+    let client = Client::new(&server.url(), None).unwrap();
+
+    let report = scan(&client, &["app".to_string()], |_| {});
+
+    assert_eq!(report.manifests, 2);
+    assert!(report.findings.is_empty());
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].starts_with("app:broken: "));
+}
+
+#[test]
+fn test_untagged_index_with_missing_child_is_deletable() {
+    let mut server = Server::new();
+    // The old index is only found through its signature
+    let deleted_child = manifest_json(b"1", &[]);
+    let old_index = index_json(&[(&deleted_child, "linux", "amd64")]);
+    let missing_child = sha256_of(&deleted_child);
+    let signature = manifest_json(b"2", &[]);
+    let signature_tag = format!("{}.sig", sha256_of(&old_index).replace(':', "-"));
+    server
+        .mock("GET", "/v2/app/tags/list")
+        .with_status(200)
+        .with_body(json!({"name": "app", "tags": [signature_tag]}).to_string())
+        .create();
+    serve_manifest(&mut server, "app", &signature_tag, &signature);
+    serve_manifest(&mut server, "app", &sha256_of(&old_index), &old_index);
+    server
+        .mock(
+            "GET",
+            format!("/v2/app/manifests/{}", missing_child).as_str(),
+        )
+        .with_status(404)
+        .create();
+    for body in [&old_index, &signature] {
+        serve_referrers(&mut server, &sha256_of(body), &[]);
+    }
+    let client = Client::new(&server.url(), None).unwrap();
+
+    let report = scan(&client, &["app".to_string()], |_| {});
+
+    assert_eq!(report.by_reason(GcReason::MissingChildren).count(), 0);
+    let untagged: Vec<_> = report.by_reason(GcReason::Untagged).collect();
+    assert_eq!(untagged.len(), 2);
+    assert_eq!(untagged[1].digest, sha256_of(&old_index));
+    assert_eq!(untagged[1].missing, vec![missing_child]);
+    assert_eq!(report.deletable().count(), 2);
+}
@@ -0,0 +1,533 @@
+//! Reading and extracting files from image layers.
+//!
+//! [`read_file`] looks for a path from the top layer down and stops at the
+//! first layer that decides it: one that has the file, deletes it, or hides
+//! it behind an opaque directory. Symbolic links, in the path itself or in
+//! one of its directories, are followed inside the image.
+//!
+//! [`extract`] applies the layers bottom-up to a directory, whiteouts
+//! included, the way a container runtime unpacks an image, but only for the
+//! entries at or below a path.
+
+use super::{EntryKind, FileEntry, normalize_path, open_layer, remove_below, walk_layer};
+use crate::error::{Result, RexError};
+use crate::oci::ImageManifest;
+use crate::registry::Registry;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Symbolic links followed before giving up (as Linux's ELOOP)
+const MAX_LINK_HOPS: usize = 40;
+
+/// What a layer says about the path being looked for.
+enum Lookup {
+    /// Nothing about the path
+    Absent,
+    /// The file, whose content has been written
+    Found(FileEntry),
+    /// The path goes through a link; look again for the new path
+    Redirect(String),
+    /// The path is a directory
+    Directory,
+    /// The path or one of its directories is deleted, or is not a directory
+    Missing,
+}
+
+/// Joins path components, resolving "." and ".." (never above the root).
+fn clean_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Whether `path` is `dir` or below it (both normalized).
+fn is_within(path: &str, dir: &str) -> bool {
+    dir.is_empty()
+        || path
+            .strip_prefix(dir)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Resolves the target of a symbolic link at `link` (relative or absolute).
+fn resolve_link(link: &str, target: &str) -> String {
+    if target.starts_with('/') {
+        return clean_path(target);
+    }
+    match link.rsplit_once('/') {
+        Some((parent, _)) => clean_path(&format!("{}/{}", parent, target)),
+        None => clean_path(target),
+    }
+}
+
+/// Reads a file from layers given by `open` (index to layer blob).
+///
+/// Split from [`read_file`] so that it can run on in-memory layers.
+pub(super) fn read_file_from_layers<O, W>(
+    mut open: O,
+    layer_count: usize,
+    path: &str,
+    writer: &mut W,
+) -> Result<FileEntry>
+where
+    O: FnMut(usize) -> Result<Box<dyn Read>>,
+    W: Write + ?Sized,
+{
+    let requested = clean_path(path);
+    if requested.is_empty() {
+        return Err(RexError::is_directory("/"));
+    }
+    let mut target = requested.clone();
+    let not_found = |path: &str| RexError::not_found("File", &format!("/{}", path));
+
+    for _ in 0..=MAX_LINK_HOPS {
+        let mut lookup = Lookup::Absent;
+
+        for index in (0..layer_count).rev() {
+            // An opaque ancestor only hides the layers below this one
+            let mut hidden_below = false;
+
+            walk_layer(open(index)?, index, |entry, content| {
+                if entry.path == target {
+                    lookup = match entry.kind {
+                        EntryKind::File => {
+                            io::copy(content, writer).map_err(|e| {
+                                RexError::validation_with_source("Failed to write file", e)
+                            })?;
+                            Lookup::Found(entry)
+                        }
+                        EntryKind::Symlink => Lookup::Redirect(resolve_link(
+                            &entry.path,
+                            entry.link_target.as_deref().unwrap_or_default(),
+                        )),
+                        EntryKind::Hardlink => Lookup::Redirect(normalize_path(
+                            entry.link_target.as_deref().unwrap_or_default(),
+                        )),
+                        EntryKind::Directory | EntryKind::OpaqueWhiteout => Lookup::Directory,
+                        EntryKind::Whiteout => Lookup::Missing,
+                        EntryKind::Other => {
+                            return Err(RexError::validation(format!(
+                                "/{} is not a regular file",
+                                target
+                            )));
+                        }
+                    };
+                    return Ok(false);
+                }
+
+                if let Some(rest) = target
+                    .strip_prefix(&entry.path)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .filter(|_| !entry.path.is_empty())
+                {
+                    match entry.kind {
+                        EntryKind::Directory => {}
+                        EntryKind::OpaqueWhiteout => hidden_below = true,
+                        EntryKind::Symlink => {
+                            let resolved = resolve_link(
+                                &entry.path,
+                                entry.link_target.as_deref().unwrap_or_default(),
+                            );
+                            lookup =
+                                Lookup::Redirect(clean_path(&format!("{}/{}", resolved, rest)));
+                            return Ok(false);
+                        }
+                        _ => {
+                            lookup = Lookup::Missing;
+                            return Ok(false);
+                        }
+                    }
+                }
+                Ok(true)
+            })?;
+
+            if !matches!(lookup, Lookup::Absent) {
+                break;
+            }
+            if hidden_below {
+                lookup = Lookup::Missing;
+                break;
+            }
+        }
+
+        match lookup {
+            Lookup::Found(entry) => return Ok(entry),
+            Lookup::Redirect(next) => target = next,
+            Lookup::Directory => {
+                return Err(RexError::is_directory(format!("/{}", requested)));
+            }
+            Lookup::Absent | Lookup::Missing => return Err(not_found(&requested)),
+        }
+    }
+
+    Err(RexError::validation(format!(
+        "Too many levels of symbolic links resolving /{}",
+        requested
+    )))
+}
+
+/// Writes the content of a file of an image.
+///
+/// The path is looked up from the top layer down, so only the layers above
+/// the one holding the file are read. Symbolic links are followed inside
+/// the image (e.g. `/etc/os-release` -> `/usr/lib/os-release`).
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding the image
+/// * `repository` - The image's repository
+/// * `manifest` - The image manifest (a single platform)
+/// * `path` - Path of the file in the image (e.g., "/etc/os-release")
+/// * `writer` - Destination for the file content
+///
+/// # Returns
+///
+/// The entry the content was read from, after following links.
+///
+/// # Errors
+///
+/// Returns a not-found error if the path does not exist in the image (or is
+/// deleted by a whiteout), an is-directory error for a directory, a
+/// validation error for a special file, or an error if a layer cannot be read.
+pub fn read_file<W: Write + ?Sized>(
+    registry: &Registry,
+    repository: &str,
+    manifest: &ImageManifest,
+    path: &str,
+    writer: &mut W,
+) -> Result<FileEntry> {
+    let layers = manifest.layers();
+    read_file_from_layers(
+        |index| open_layer(registry, repository, &layers[index]),
+        layers.len(),
+        path,
+        writer,
+    )
+}
+
+/// Summary of an extraction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ExtractReport {
+    /// Regular files written, hard links included
+    pub files: usize,
+    /// Directories created
+    pub directories: usize,
+    /// Symbolic links created
+    pub links: usize,
+    /// Total size of the files written in bytes
+    pub bytes: u64,
+    /// Entries not extracted, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Returns the path below `destination` for an image path, refusing paths
+/// that would land outside it (".." or a symbolic link on the way).
+fn destination_path(destination: &Path, path: &str) -> std::result::Result<PathBuf, String> {
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    let mut target = destination.to_path_buf();
+    for (index, part) in parts.iter().enumerate() {
+        if *part == ".." {
+            return Err("path leaves the destination".to_string());
+        }
+        target.push(part);
+        if index + 1 < parts.len()
+            && fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink())
+        {
+            return Err("path goes through a symbolic link".to_string());
+        }
+    }
+    Ok(target)
+}
+
+/// Removes whatever is at `path` (file, link or directory tree).
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Empties `dir`, sparing what the current layer wrote (`written`, image
+/// paths relative to the root).
+fn clear_directory(dir: &Path, image_dir: &str, written: &HashSet<String>) -> io::Result<()> {
+    if !fs::symlink_metadata(dir).is_ok_and(|m| m.is_dir()) {
+        return Ok(());
+    }
+    let entries = fs::read_dir(dir)?;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let image_path = if image_dir.is_empty() {
+            name
+        } else {
+            format!("{}/{}", image_dir, name)
+        };
+
+        if !written.contains(&image_path) {
+            remove_path(&entry.path())?;
+        } else if entry.file_type()?.is_dir() {
+            clear_directory(&entry.path(), &image_path, written)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &str, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, path)
+}
+
+#[cfg(not(unix))]
+fn create_symlink(_target: &str, _path: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "symbolic links are not supported on this platform",
+    ))
+}
+
+/// Writes one entry below `destination`.
+fn write_entry(
+    destination: &Path,
+    entry: &FileEntry,
+    content: &mut dyn Read,
+) -> std::result::Result<(), String> {
+    let target = destination_path(destination, &entry.path)?;
+    let io_error = |e: io::Error| e.to_string();
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+
+    match entry.kind {
+        EntryKind::Directory => {
+            if !fs::symlink_metadata(&target).is_ok_and(|m| m.is_dir()) {
+                remove_path(&target).map_err(io_error)?;
+                fs::create_dir(&target).map_err(io_error)?;
+            }
+            // Keep directories writable, later layers may add to them
+            set_mode(&target, entry.mode | 0o700).map_err(io_error)?;
+        }
+        EntryKind::File => {
+            remove_path(&target).map_err(io_error)?;
+            let mut file = fs::File::create(&target).map_err(io_error)?;
+            io::copy(content, &mut file).map_err(io_error)?;
+            set_mode(&target, entry.mode).map_err(io_error)?;
+        }
+        EntryKind::Symlink => {
+            remove_path(&target).map_err(io_error)?;
+            create_symlink(entry.link_target.as_deref().unwrap_or_default(), &target)
+                .map_err(io_error)?;
+        }
+        EntryKind::Hardlink => {
+            let source = destination_path(
+                destination,
+                &normalize_path(entry.link_target.as_deref().unwrap_or_default()),
+            )?;
+            remove_path(&target).map_err(io_error)?;
+            fs::hard_link(&source, &target).map_err(io_error)?;
+        }
+        EntryKind::Other => return Err("special files are not extracted".to_string()),
+        EntryKind::Whiteout | EntryKind::OpaqueWhiteout => {}
+    }
+    Ok(())
+}
+
+/// Counts the entries the extraction wrote that later layers left in place,
+/// and the directories created for them below `prefix` without an entry of
+/// their own.
+fn count_extracted(
+    extracted: &BTreeMap<String, FileEntry>,
+    prefix: &str,
+    report: &mut ExtractReport,
+) {
+    let mut parents = HashSet::new();
+    for path in extracted.keys() {
+        let mut parent = path.as_str();
+        while let Some((above, _)) = parent.rsplit_once('/') {
+            parent = above;
+            if !is_within(parent, prefix) || extracted.contains_key(parent) {
+                break;
+            }
+            parents.insert(parent);
+        }
+    }
+    report.directories += parents.len();
+
+    for entry in extracted.values() {
+        match entry.kind {
+            EntryKind::File => {
+                report.files += 1;
+                report.bytes += entry.size;
+            }
+            // On disk a hard link is one more name for a file
+            EntryKind::Hardlink => {
+                report.files += 1;
+                report.bytes += entry
+                    .link_target
+                    .as_deref()
+                    .and_then(|target| extracted.get(&normalize_path(target)))
+                    .map_or(0, |target| target.size);
+            }
+            EntryKind::Directory => report.directories += 1,
+            EntryKind::Symlink => report.links += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Extracts a path from layers given by `open` (index to layer blob).
+///
+/// Split from [`extract`] so that it can run on in-memory layers.
+pub(super) fn extract_from_layers<O>(
+    mut open: O,
+    layer_count: usize,
+    path: &str,
+    destination: &Path,
+) -> Result<ExtractReport>
+where
+    O: FnMut(usize) -> Result<Box<dyn Read>>,
+{
+    let prefix = clean_path(path);
+    let mut report = ExtractReport::default();
+    // What the layers wrote, less what later layers deleted: files already
+    // in the destination are not part of the report
+    let mut extracted: BTreeMap<String, FileEntry> = BTreeMap::new();
+
+    fs::create_dir_all(destination).map_err(|e| {
+        RexError::config_with_source(
+            "Failed to create destination directory",
+            destination.to_str(),
+            e,
+        )
+    })?;
+
+    for index in 0..layer_count {
+        let mut written = HashSet::new();
+
+        walk_layer(open(index)?, index, |entry, content| {
+            let affected = if entry.is_under(&prefix) {
+                entry.path.clone()
+            } else if entry.kind.is_whiteout() && is_within(&prefix, &entry.path) {
+                // Deleting a directory above the extracted path
+                prefix.clone()
+            } else {
+                return Ok(true);
+            };
+
+            // Only what this layer wrote survives below an opaque directory
+            let keeps_written = entry.kind == EntryKind::OpaqueWhiteout
+                && (affected == entry.path || written.contains(&affected));
+            let result = match entry.kind {
+                EntryKind::Whiteout => destination_path(destination, &affected)
+                    .and_then(|target| remove_path(&target).map_err(|e| e.to_string())),
+                EntryKind::OpaqueWhiteout => {
+                    destination_path(destination, &affected).and_then(|target| {
+                        if keeps_written {
+                            clear_directory(&target, &affected, &written)
+                        } else {
+                            remove_path(&target)
+                        }
+                        .map_err(|e| e.to_string())
+                    })
+                }
+                _ => write_entry(destination, &entry, content),
+            };
+
+            match result {
+                Ok(()) if keeps_written => {
+                    let kept: BTreeMap<String, FileEntry> = extracted
+                        .iter()
+                        .filter(|(path, _)| written.contains(*path))
+                        .map(|(path, entry)| (path.clone(), entry.clone()))
+                        .collect();
+                    remove_below(&mut extracted, &affected);
+                    extracted.extend(kept);
+                }
+                Ok(()) if entry.kind.is_whiteout() => {
+                    remove_below(&mut extracted, &affected);
+                    extracted.remove(&affected);
+                }
+                Ok(()) => {
+                    if entry.kind != EntryKind::Directory {
+                        remove_below(&mut extracted, &entry.path);
+                    }
+                    written.insert(entry.path.clone());
+                    extracted.insert(entry.path.clone(), entry.clone());
+                }
+                Err(reason) => report.skipped.push(format!("/{}: {}", entry.path, reason)),
+            }
+            Ok(true)
+        })?;
+    }
+
+    if !prefix.is_empty() && !extracted.values().any(|entry| entry.is_under(&prefix)) {
+        return Err(RexError::not_found("Path", &format!("/{}", prefix)));
+    }
+    count_extracted(&extracted, &prefix, &mut report);
+
+    Ok(report)
+}
+
+/// Extracts a file or directory of an image into a local directory.
+///
+/// The layers are applied bottom-up, whiteouts included, restricted to the
+/// entries at or below `path`. Entries keep their full image path below
+/// `destination` (extracting "/etc/nginx" to "out" writes "out/etc/nginx/...").
+/// Ownership is not restored; permission bits are, except that directories
+/// stay writable by the owner.
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding the image
+/// * `repository` - The image's repository
+/// * `manifest` - The image manifest (a single platform)
+/// * `path` - Path to extract (e.g., "/etc/nginx"; "/" for everything)
+/// * `destination` - Local directory, created if needed
+///
+/// # Returns
+///
+/// What was extracted. Entries that cannot be written (special files, paths
+/// through symbolic links) are listed in `skipped` rather than failing.
+///
+/// # Errors
+///
+/// Returns a not-found error if nothing exists at `path` in the image, or an
+/// error if a layer cannot be read or the destination cannot be created.
+pub fn extract(
+    registry: &Registry,
+    repository: &str,
+    manifest: &ImageManifest,
+    path: &str,
+    destination: &Path,
+) -> Result<ExtractReport> {
+    let layers = manifest.layers();
+    extract_from_layers(
+        |index| open_layer(registry, repository, &layers[index]),
+        layers.len(),
+        path,
+        destination,
+    )
+}
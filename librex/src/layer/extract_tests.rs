@@ -0,0 +1,206 @@
+use super::extract::{extract_from_layers, read_file_from_layers};
+use super::*;
+use crate::test_support::{TarEntry, tar};
+use std::fs;
+use std::path::Path;
+
+/// An alpine-like base, then a layer deleting, hiding and adding files
+fn image() -> Vec<Vec<u8>> {
+    vec![
+        tar(&[
+            TarEntry::Dir("etc/"),
+            TarEntry::Symlink("etc/os-release", "../usr/lib/os-release"),
+            TarEntry::File("etc/motd", "Welcome!\n"),
+            TarEntry::Dir("usr/lib/"),
+            TarEntry::File("usr/lib/os-release", "ID=alpine\n"),
+            TarEntry::Dir("var/cache/"),
+            TarEntry::File("var/cache/apk.idx", "index"),
+            TarEntry::Symlink("lib", "usr/lib"),
+        ]),
+        tar(&[
+            TarEntry::File("etc/.wh.motd", ""),
+            TarEntry::File("var/cache/.wh..wh..opq", ""),
+            TarEntry::File("var/cache/new.idx", "new"),
+            TarEntry::Dir("app/"),
+            TarEntry::File("app/config.yaml", "port: 8080\n"),
+            TarEntry::Hardlink("app/config.bak", "app/config.yaml"),
+        ]),
+    ]
+}
+
+fn open(layers: &[Vec<u8>]) -> impl FnMut(usize) -> Result<Box<dyn Read>> + '_ {
+    |index| Ok(Box::new(io::Cursor::new(layers[index].clone())) as Box<dyn Read>)
+}
+
+fn cat(layers: &[Vec<u8>], path: &str) -> Result<(FileEntry, String)> {
+    let mut content = Vec::new();
+    let entry = read_file_from_layers(open(layers), layers.len(), path, &mut content)?;
+    Ok((entry, String::from_utf8(content).unwrap()))
+}
+
+#[test]
+fn test_read_file_follows_links() {
+    let layers = image();
+
+    let (entry, content) = cat(&layers, "/etc/os-release").unwrap();
+    assert_eq!(content, "ID=alpine\n");
+    assert_eq!(entry.path, "usr/lib/os-release");
+    assert_eq!(entry.layer, 0);
+
+    // Through a symlinked directory, and through a hard link
+    assert_eq!(cat(&layers, "lib/os-release").unwrap().1, "ID=alpine\n");
+    let (entry, content) = cat(&layers, "/app/config.bak").unwrap();
+    assert_eq!(content, "port: 8080\n");
+    assert_eq!(entry.layer, 1);
+}
+
+#[test]
+fn test_read_file_respects_whiteouts() {
+    let layers = image();
+
+    assert!(matches!(
+        cat(&layers, "/etc/motd"),
+        Err(RexError::NotFound { .. })
+    ));
+    // Hidden by the opaque directory, while the new file is visible
+    assert!(matches!(
+        cat(&layers, "/var/cache/apk.idx"),
+        Err(RexError::NotFound { .. })
+    ));
+    assert_eq!(cat(&layers, "/var/cache/new.idx").unwrap().1, "new");
+    assert!(matches!(
+        cat(&layers, "/nope"),
+        Err(RexError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_read_file_rejects_directories_and_loops() {
+    let layers = vec![tar(&[
+        TarEntry::Dir("etc/"),
+        TarEntry::Symlink("loop", "loop"),
+    ])];
+
+    let err = cat(&layers, "/etc").unwrap_err();
+    assert!(matches!(err, RexError::IsDirectory { .. }));
+    assert!(err.to_string().contains("/etc is a directory"));
+    let err = cat(&layers, "/loop").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Too many levels of symbolic links")
+    );
+}
+
+#[test]
+fn test_extract_directory() {
+    let layers = image();
+    let dir = tempfile::tempdir().unwrap();
+
+    let report =
+        extract_from_layers(open(&layers), layers.len(), "/var/cache", dir.path()).unwrap();
+
+    assert!(!dir.path().join("var/cache/apk.idx").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("var/cache/new.idx")).unwrap(),
+        "new"
+    );
+    assert!(!dir.path().join("etc").exists());
+    assert_eq!(report.files, 1);
+    assert_eq!(report.directories, 1);
+    assert_eq!(report.bytes, 3);
+    assert!(report.skipped.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_everything() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let layers = image();
+    let dir = tempfile::tempdir().unwrap();
+
+    let report = extract_from_layers(open(&layers), layers.len(), "/", dir.path()).unwrap();
+
+    let root = dir.path();
+    assert!(!root.join("etc/motd").exists());
+    assert_eq!(
+        fs::read_link(root.join("etc/os-release")).unwrap(),
+        Path::new("../usr/lib/os-release")
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("etc/os-release")).unwrap(),
+        "ID=alpine\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("app/config.bak")).unwrap(),
+        "port: 8080\n"
+    );
+    let mode = fs::metadata(root.join("app/config.yaml"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o640);
+
+    // usr/lib/os-release, var/cache/new.idx and app/config.{yaml,bak}
+    assert_eq!(report.files, 4);
+    assert_eq!(report.links, 2);
+    assert_eq!(report.directories, 6);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_extract_skips_paths_through_links() {
+    let layers = vec![
+        tar(&[TarEntry::Symlink("lib", "/tmp")]),
+        tar(&[TarEntry::File("lib/evil", "x")]),
+    ];
+    let dir = tempfile::tempdir().unwrap();
+
+    let report = extract_from_layers(open(&layers), layers.len(), "/", dir.path()).unwrap();
+
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].starts_with("/lib/evil: "));
+    assert!(!Path::new("/tmp/evil").exists());
+}
+
+#[test]
+fn test_extract_counts_only_what_it_wrote() {
+    let layers = image();
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("srv/www")).unwrap();
+    fs::write(dir.path().join("srv/www/index.html"), "mine").unwrap();
+    fs::write(dir.path().join("notes.txt"), "mine").unwrap();
+
+    let report = extract_from_layers(open(&layers), layers.len(), "/", dir.path()).unwrap();
+
+    // As in an empty destination
+    assert_eq!(report.files, 4);
+    assert_eq!(report.links, 2);
+    assert_eq!(report.directories, 6);
+    assert_eq!(report.bytes, 10 + 3 + 11 + 11);
+    assert!(dir.path().join("notes.txt").exists());
+}
+
+#[test]
+fn test_extract_missing_path_already_in_destination() {
+    let layers = image();
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("etc")).unwrap();
+    fs::write(dir.path().join("etc/motd"), "local").unwrap();
+
+    let err =
+        extract_from_layers(open(&layers), layers.len(), "/etc/motd", dir.path()).unwrap_err();
+
+    assert!(matches!(err, RexError::NotFound { .. }));
+}
+
+#[test]
+fn test_extract_missing_path() {
+    let layers = image();
+    let dir = tempfile::tempdir().unwrap();
+
+    let err =
+        extract_from_layers(open(&layers), layers.len(), "/etc/motd", dir.path()).unwrap_err();
+
+    assert!(matches!(err, RexError::NotFound { .. }));
+}
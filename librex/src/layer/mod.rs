@@ -0,0 +1,487 @@
+//! Layer filesystem listing.
+//!
+//! Image layers are tar archives, usually gzip- or zstd-compressed. This
+//! module streams a layer, decompresses it and walks its tar entries, and
+//! merges the layers of an image in order to get the final root filesystem.
+//!
+//! Deletions are recorded as whiteouts: an entry `dir/.wh.name` removes
+//! `dir/name` from the layers below, and `dir/.wh..wh..opq` hides everything
+//! the layers below had in `dir`.
+
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::oci::{Descriptor, ImageManifest};
+use crate::registry::Registry;
+use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+
+mod extract;
+
+pub use extract::{ExtractReport, extract, read_file};
+
+#[cfg(test)]
+mod extract_tests;
+#[cfg(test)]
+mod tests;
+
+/// Prefix of whiteout entries
+pub const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// Name of the opaque whiteout entry
+pub const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+
+/// Compression of a layer blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Plain tar
+    None,
+    /// gzip
+    Gzip,
+    /// Zstandard
+    Zstd,
+}
+
+impl Compression {
+    /// Detects the compression from the first bytes of a blob.
+    ///
+    /// Media types are not trusted: Docker's `rootfs.diff.tar.gzip` and
+    /// artifact layers do not always say what they hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::layer::Compression;
+    ///
+    /// assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+    /// assert_eq!(Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd]), Compression::Zstd);
+    /// assert_eq!(Compression::detect(b"etc/"), Compression::None);
+    /// ```
+    pub fn detect(magic: &[u8]) -> Self {
+        if magic.starts_with(&[0x1f, 0x8b]) {
+            Compression::Gzip
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// Kind of a layer entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntryKind {
+    /// Regular file
+    File,
+    /// Directory
+    Directory,
+    /// Symbolic link
+    Symlink,
+    /// Hard link to another entry
+    Hardlink,
+    /// Device, FIFO or other special file
+    Other,
+    /// Deletion of a path from lower layers
+    Whiteout,
+    /// Deletion of a directory's content from lower layers
+    OpaqueWhiteout,
+}
+
+impl EntryKind {
+    /// Returns true for whiteouts, which delete rather than add.
+    pub fn is_whiteout(self) -> bool {
+        matches!(self, EntryKind::Whiteout | EntryKind::OpaqueWhiteout)
+    }
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EntryKind::File => "file",
+            EntryKind::Directory => "directory",
+            EntryKind::Symlink => "symlink",
+            EntryKind::Hardlink => "hardlink",
+            EntryKind::Other => "other",
+            EntryKind::Whiteout => "whiteout",
+            EntryKind::OpaqueWhiteout => "opaque whiteout",
+        })
+    }
+}
+
+/// An entry of a layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileEntry {
+    /// Path without leading "./" or "/" (e.g., "etc/os-release"). For
+    /// whiteouts, the deleted path; for opaque whiteouts, the directory.
+    pub path: String,
+    /// Entry kind
+    pub kind: EntryKind,
+    /// Size in bytes (0 for anything but regular files)
+    pub size: u64,
+    /// Permission bits (e.g., 0o755)
+    pub mode: u32,
+    /// Owner user ID
+    pub uid: u64,
+    /// Owner group ID
+    pub gid: u64,
+    /// Target of symbolic and hard links
+    pub link_target: Option<String>,
+    /// Index of the layer the entry comes from (0-based)
+    pub layer: usize,
+}
+
+impl FileEntry {
+    /// Returns true if the entry is `prefix` or lies below it.
+    ///
+    /// Leading and trailing slashes in `prefix` are ignored; an empty prefix
+    /// matches everything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::layer::{EntryKind, FileEntry};
+    ///
+    /// let entry = FileEntry {
+    ///     path: "etc/ssl/certs".to_string(),
+    ///     kind: EntryKind::Directory,
+    ///     size: 0,
+    ///     mode: 0o755,
+    ///     uid: 0,
+    ///     gid: 0,
+    ///     link_target: None,
+    ///     layer: 0,
+    /// };
+    ///
+    /// assert!(entry.is_under("/etc/ssl/"));
+    /// assert!(!entry.is_under("etc/ss"));
+    /// ```
+    pub fn is_under(&self, prefix: &str) -> bool {
+        let prefix = prefix.trim_matches('/');
+        prefix.is_empty()
+            || self
+                .path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+}
+
+/// Normalizes a tar path: no leading "./" or "/", no trailing "/".
+fn normalize_path(path: &str) -> String {
+    let path = path.trim_start_matches("./").trim_matches('/');
+    if path == "." {
+        String::new()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Wraps an I/O error met while reading a layer.
+///
+/// Transport failures are network errors; anything else, a truncated blob
+/// included, means the content is not what a layer should be.
+pub(crate) fn layer_error(error: io::Error) -> RexError {
+    let transport = matches!(
+        error.kind(),
+        io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted | io::ErrorKind::TimedOut
+    ) || error
+        .get_ref()
+        .is_some_and(|inner| inner.is::<reqwest::Error>());
+
+    if transport {
+        RexError::network_with_source("Failed to read layer", error)
+    } else if error.kind() == io::ErrorKind::UnexpectedEof {
+        RexError::validation_with_source("Layer is truncated or corrupt", error)
+    } else {
+        RexError::validation_with_source("Failed to read layer", error)
+    }
+}
+
+/// Decompresses a Zstandard stream, including multi-frame streams and
+/// skippable frames (used by zstd:chunked).
+struct ZstdReader<R> {
+    source: R,
+    frame: FrameDecoder,
+    in_frame: bool,
+}
+
+impl<R: BufRead> ZstdReader<R> {
+    fn new(source: R) -> Self {
+        Self {
+            source,
+            frame: FrameDecoder::new(),
+            in_frame: false,
+        }
+    }
+}
+
+impl<R: BufRead> Read for ZstdReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if !self.in_frame {
+                if self.source.fill_buf()?.is_empty() {
+                    return Ok(0);
+                }
+                match self.frame.reset(&mut self.source) {
+                    Ok(()) => self.in_frame = true,
+                    Err(FrameDecoderError::ReadFrameHeaderError(
+                        ReadFrameHeaderError::SkipFrame { length, .. },
+                    )) => {
+                        io::copy(
+                            &mut (&mut self.source).take(u64::from(length)),
+                            &mut io::sink(),
+                        )?;
+                        continue;
+                    }
+                    Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+                }
+            }
+
+            while self.frame.can_collect() < buf.len() && !self.frame.is_finished() {
+                let needed = buf.len() - self.frame.can_collect();
+                self.frame
+                    .decode_blocks(&mut self.source, BlockDecodingStrategy::UptoBytes(needed))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            }
+
+            let n = self.frame.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                return Ok(n);
+            }
+            // Frame done; the stream may hold another one
+            self.in_frame = false;
+        }
+    }
+}
+
+/// Returns a reader for the tar archive inside a (compressed) layer blob.
+fn decompress<'a, R: Read + 'a>(reader: R) -> Result<Box<dyn Read + 'a>> {
+    let mut reader = BufReader::new(reader);
+    let compression = Compression::detect(reader.fill_buf().map_err(layer_error)?);
+
+    Ok(match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(ZstdReader::new(reader)),
+    })
+}
+
+/// Walks the entries of a layer blob.
+///
+/// `visit` receives each entry and a reader for its content, and returns
+/// whether to go on. When every entry is visited, the rest of the blob is
+/// read too, so that a streamed blob gets its digest verified.
+///
+/// # Arguments
+///
+/// * `reader` - The layer blob (plain, gzip or zstd tar)
+/// * `layer` - Index stored in the entries
+/// * `visit` - Called for every entry; returns `Ok(false)` to stop
+///
+/// # Errors
+///
+/// Returns an error if the blob is not a (compressed) tar archive, if it
+/// cannot be read, or if `visit` fails.
+pub fn walk_layer<R, F>(reader: R, layer: usize, mut visit: F) -> Result<()>
+where
+    R: Read,
+    F: FnMut(FileEntry, &mut dyn Read) -> Result<bool>,
+{
+    let mut archive = tar::Archive::new(decompress(reader)?);
+
+    for entry in archive.entries().map_err(layer_error)? {
+        let mut entry = entry.map_err(layer_error)?;
+        let header = entry.header();
+
+        let kind = match header.entry_type() {
+            tar::EntryType::Regular | tar::EntryType::Continuous => EntryKind::File,
+            tar::EntryType::Directory => EntryKind::Directory,
+            tar::EntryType::Symlink => EntryKind::Symlink,
+            tar::EntryType::Link => EntryKind::Hardlink,
+            tar::EntryType::XGlobalHeader => continue,
+            _ => EntryKind::Other,
+        };
+        // Some tools leave numeric fields blank; that is no reason to give
+        // up on the listing
+        let mode = header.mode().unwrap_or(0) & 0o7777;
+        let uid = header.uid().unwrap_or(0);
+        let gid = header.gid().unwrap_or(0);
+        let size = if kind == EntryKind::File {
+            entry.size()
+        } else {
+            0
+        };
+        let link_target = entry
+            .link_name()
+            .map_err(layer_error)?
+            .map(|target| target.to_string_lossy().into_owned());
+
+        let raw_path = entry.path().map_err(layer_error)?;
+        let mut path = normalize_path(&raw_path.to_string_lossy());
+        if path.is_empty() {
+            continue;
+        }
+
+        let (parent, name) = match path.rsplit_once('/') {
+            Some((parent, name)) => (parent.to_string(), name.to_string()),
+            None => (String::new(), path.clone()),
+        };
+        let kind = if name == OPAQUE_WHITEOUT {
+            path = parent;
+            EntryKind::OpaqueWhiteout
+        } else if let Some(deleted) = name.strip_prefix(WHITEOUT_PREFIX) {
+            path = if parent.is_empty() {
+                deleted.to_string()
+            } else {
+                format!("{}/{}", parent, deleted)
+            };
+            EntryKind::Whiteout
+        } else {
+            kind
+        };
+
+        let file = FileEntry {
+            path,
+            kind,
+            size,
+            mode,
+            uid,
+            gid,
+            link_target,
+            layer,
+        };
+        if !visit(file, &mut entry)? {
+            return Ok(());
+        }
+    }
+
+    io::copy(&mut archive.into_inner(), &mut io::sink()).map_err(layer_error)?;
+    Ok(())
+}
+
+/// Lists the entries of a layer blob, sorted by path.
+///
+/// # Errors
+///
+/// See [`walk_layer`].
+pub fn read_layer<R: Read>(reader: R, layer: usize) -> Result<Vec<FileEntry>> {
+    let mut entries = Vec::new();
+    walk_layer(reader, layer, |entry, _| {
+        entries.push(entry);
+        Ok(true)
+    })?;
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Merges layer listings, lowest layer first, into the final filesystem.
+///
+/// A layer's whiteouts apply to the layers below it only; its own entries
+/// then replace whatever was at their paths. A directory replaced by a file
+/// or symlink loses its content. The result holds no whiteouts and is sorted
+/// by path.
+pub fn merge_layers<I>(layers: I) -> Vec<FileEntry>
+where
+    I: IntoIterator<Item = Vec<FileEntry>>,
+{
+    let mut files: BTreeMap<String, FileEntry> = BTreeMap::new();
+
+    for entries in layers {
+        for entry in entries.iter().filter(|e| e.kind.is_whiteout()) {
+            remove_below(&mut files, &entry.path);
+            if entry.kind == EntryKind::Whiteout {
+                files.remove(&entry.path);
+            }
+        }
+
+        for entry in entries.into_iter().filter(|e| !e.kind.is_whiteout()) {
+            if entry.kind != EntryKind::Directory {
+                remove_below(&mut files, &entry.path);
+            }
+            files.insert(entry.path.clone(), entry);
+        }
+    }
+
+    files.into_values().collect()
+}
+
+/// Removes the entries under a directory; `""` is the layer root.
+fn remove_below(files: &mut BTreeMap<String, FileEntry>, directory: &str) {
+    let below = match directory {
+        "" => String::new(),
+        directory => format!("{}/", directory),
+    };
+    let hidden: Vec<String> = files
+        .range(below.clone()..)
+        .take_while(|(path, _)| path.starts_with(&below))
+        .map(|(path, _)| path.clone())
+        .collect();
+    for path in hidden {
+        files.remove(&path);
+    }
+}
+
+/// Lists the files of an image, or of one of its layers.
+///
+/// Layers are streamed one at a time, so memory use depends on the number of
+/// entries rather than on the layer sizes.
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding the image
+/// * `repository` - The image's repository
+/// * `manifest` - The image manifest (a single platform)
+/// * `layer` - Index of a single layer to list (0-based); `None` merges all
+///
+/// # Returns
+///
+/// For a single layer its entries, whiteouts included; otherwise the merged
+/// filesystem. Sorted by path either way.
+///
+/// # Errors
+///
+/// Returns a validation error if `layer` is out of range, or an error if a
+/// layer cannot be fetched or read.
+pub fn list_files(
+    registry: &Registry,
+    repository: &str,
+    manifest: &ImageManifest,
+    layer: Option<usize>,
+) -> Result<Vec<FileEntry>> {
+    let layers = manifest.layers();
+
+    if let Some(index) = layer {
+        let descriptor = layers.get(index).ok_or_else(|| {
+            RexError::validation(format!(
+                "Layer {} does not exist (the image has {} layers)",
+                index + 1,
+                layers.len()
+            ))
+        })?;
+        return read_layer(open_layer(registry, repository, descriptor)?, index);
+    }
+
+    let mut listings = Vec::with_capacity(layers.len());
+    for (index, descriptor) in layers.iter().enumerate() {
+        listings.push(read_layer(
+            open_layer(registry, repository, descriptor)?,
+            index,
+        )?);
+    }
+    Ok(merge_layers(listings))
+}
+
+/// Opens the blob of a layer descriptor for streaming.
+pub(crate) fn open_layer(
+    registry: &Registry,
+    repository: &str,
+    descriptor: &Descriptor,
+) -> Result<Box<dyn Read>> {
+    let digest = Digest::from_str(descriptor.digest().as_ref())?;
+    registry.open_blob(repository, &digest)
+}
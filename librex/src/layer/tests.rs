@@ -0,0 +1,235 @@
+use super::*;
+use crate::test_support::{TarEntry, tar};
+use flate2::Compression as GzipLevel;
+use flate2::write::GzEncoder;
+use std::io::Write;
+
+fn gzip(bytes: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), GzipLevel::fast());
+    encoder.write_all(bytes).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn zstd(bytes: &[u8]) -> Vec<u8> {
+    ruzstd::encoding::compress_to_vec(bytes, ruzstd::encoding::CompressionLevel::Fastest)
+}
+
+fn paths(entries: &[FileEntry]) -> Vec<&str> {
+    entries.iter().map(|e| e.path.as_str()).collect()
+}
+
+fn base_layer() -> Vec<u8> {
+    tar(&[
+        TarEntry::Dir("./etc/"),
+        TarEntry::File("./etc/os-release", "ID=alpine\n"),
+        TarEntry::Dir("./var/cache/"),
+        TarEntry::File("./var/cache/apk.idx", "index"),
+        TarEntry::Symlink("./bin/sh", "/bin/busybox"),
+    ])
+}
+
+#[test]
+fn test_read_layer_entries() {
+    let entries = read_layer(&gzip(&base_layer())[..], 0).unwrap();
+
+    assert_eq!(
+        paths(&entries),
+        vec![
+            "bin/sh",
+            "etc",
+            "etc/os-release",
+            "var/cache",
+            "var/cache/apk.idx"
+        ]
+    );
+    let release = &entries[2];
+    assert_eq!(release.kind, EntryKind::File);
+    assert_eq!(release.size, 10);
+    assert_eq!(release.mode, 0o640);
+    assert_eq!((release.uid, release.gid), (1000, 1000));
+    assert_eq!(entries[0].kind, EntryKind::Symlink);
+    assert_eq!(entries[0].link_target.as_deref(), Some("/bin/busybox"));
+    assert_eq!(entries[1].kind, EntryKind::Directory);
+    assert_eq!(entries[1].size, 0);
+}
+
+#[test]
+fn test_read_layer_compressions() {
+    let plain = base_layer();
+    let expected = read_layer(&plain[..], 0).unwrap();
+
+    assert_eq!(read_layer(&zstd(&plain)[..], 0).unwrap(), expected);
+
+    // Two zstd frames, separated by a skippable frame
+    let half = plain.len() / 2;
+    let mut frames = zstd(&plain[..half]);
+    frames.extend_from_slice(&[0x50, 0x2a, 0x4d, 0x18, 3, 0, 0, 0, 1, 2, 3]);
+    frames.extend(zstd(&plain[half..]));
+    assert_eq!(read_layer(&frames[..], 0).unwrap(), expected);
+
+    assert_eq!(Compression::detect(&gzip(b"x")), Compression::Gzip);
+}
+
+#[test]
+fn test_read_layer_whiteouts() {
+    let layer = tar(&[
+        TarEntry::File("etc/.wh.os-release", ""),
+        TarEntry::File("var/cache/.wh..wh..opq", ""),
+        TarEntry::File(".wh.tmp", ""),
+    ]);
+
+    let entries = read_layer(&layer[..], 1).unwrap();
+
+    assert_eq!(paths(&entries), vec!["etc/os-release", "tmp", "var/cache"]);
+    assert_eq!(entries[0].kind, EntryKind::Whiteout);
+    assert_eq!(entries[2].kind, EntryKind::OpaqueWhiteout);
+    assert!(entries.iter().all(|e| e.layer == 1));
+}
+
+#[test]
+fn test_read_layer_rejects_garbage() {
+    let garbage = gzip(&[0xffu8; 1024]);
+    assert!(read_layer(&garbage[..], 0).is_err());
+    assert!(read_layer(&[0x1f, 0x8b, 0x00][..], 0).is_err());
+}
+
+#[test]
+fn test_walk_layer_reads_content_and_stops() {
+    let layer = gzip(&base_layer());
+    let mut content = String::new();
+    let mut visited = 0;
+
+    walk_layer(&layer[..], 0, |entry, reader| {
+        visited += 1;
+        if entry.path == "etc/os-release" {
+            reader.read_to_string(&mut content).unwrap();
+            return Ok(false);
+        }
+        Ok(true)
+    })
+    .unwrap();
+
+    assert_eq!(content, "ID=alpine\n");
+    assert_eq!(visited, 2);
+}
+
+#[test]
+fn test_merge_layers() {
+    let base = read_layer(&base_layer()[..], 0).unwrap();
+    let upper = read_layer(
+        &tar(&[
+            TarEntry::File("etc/.wh.os-release", ""),
+            TarEntry::File("var/cache/.wh..wh..opq", ""),
+            TarEntry::File("var/cache/new.idx", "new"),
+            TarEntry::File("etc/hostname", "rex"),
+            TarEntry::File("bin/sh", "#!/bin/dash"),
+        ])[..],
+        1,
+    )
+    .unwrap();
+
+    let merged = merge_layers(vec![base, upper]);
+
+    assert_eq!(
+        paths(&merged),
+        vec![
+            "bin/sh",
+            "etc",
+            "etc/hostname",
+            "var/cache",
+            "var/cache/new.idx"
+        ]
+    );
+    // Replaced by the upper layer
+    assert_eq!(merged[0].kind, EntryKind::File);
+    assert_eq!(merged[0].layer, 1);
+    // The opaque directory itself stays
+    assert_eq!(merged[3].layer, 0);
+}
+
+#[test]
+fn test_merge_whiteout_removes_subtree_only() {
+    let base = read_layer(
+        &tar(&[
+            TarEntry::Dir("opt/app"),
+            TarEntry::File("opt/app/bin", "x"),
+            TarEntry::File("opt/app-data", "y"),
+        ])[..],
+        0,
+    )
+    .unwrap();
+    let upper = read_layer(&tar(&[TarEntry::File("opt/.wh.app", "")])[..], 1).unwrap();
+
+    let merged = merge_layers(vec![base, upper]);
+
+    assert_eq!(paths(&merged), vec!["opt/app-data"]);
+}
+
+#[test]
+fn test_merge_file_replacing_directory_drops_its_content() {
+    let base = read_layer(
+        &tar(&[
+            TarEntry::Dir("opt/app"),
+            TarEntry::File("opt/app/bin", "x"),
+            TarEntry::Dir("srv"),
+            TarEntry::File("srv/index.html", "y"),
+        ])[..],
+        0,
+    )
+    .unwrap();
+    let upper = read_layer(
+        &tar(&[
+            TarEntry::File("opt/app", "now a file"),
+            TarEntry::Symlink("srv", "/var/www"),
+        ])[..],
+        1,
+    )
+    .unwrap();
+
+    let merged = merge_layers(vec![base, upper]);
+
+    assert_eq!(paths(&merged), vec!["opt/app", "srv"]);
+    assert_eq!(merged[0].kind, EntryKind::File);
+    assert_eq!(merged[1].kind, EntryKind::Symlink);
+}
+
+#[test]
+fn test_merge_root_opaque_whiteout_hides_everything_below() {
+    let base = read_layer(&base_layer()[..], 0).unwrap();
+    let upper = read_layer(
+        &tar(&[
+            TarEntry::File(".wh..wh..opq", ""),
+            TarEntry::File("app", "fresh"),
+        ])[..],
+        1,
+    )
+    .unwrap();
+
+    let merged = merge_layers(vec![base, upper]);
+
+    assert_eq!(paths(&merged), vec!["app"]);
+}
+
+#[test]
+fn test_read_truncated_layer_is_corrupt() {
+    let layer = gzip(&base_layer());
+    let truncated = &layer[..layer.len() / 2];
+
+    let error = read_layer(truncated, 0).unwrap_err();
+
+    assert!(matches!(error, RexError::Validation { .. }), "{:?}", error);
+    assert!(error.to_string().contains("truncated"), "{}", error);
+}
+
+#[test]
+fn test_is_under() {
+    let entries = read_layer(&base_layer()[..], 0).unwrap();
+
+    let under: Vec<&str> = entries
+        .iter()
+        .filter(|e| e.is_under("/var/"))
+        .map(|e| e.path.as_str())
+        .collect();
+    assert_eq!(under, vec!["var/cache", "var/cache/apk.idx"]);
+    assert!(entries.iter().all(|e| e.is_under("")));
+}
@@ -83,26 +83,50 @@ pub use error::{Result, RexError};
 pub use oci::ManifestOrIndex;
 pub use reference::Reference;
 pub use search::SearchResult;
+pub use zot::RegistryCapabilities;
 
 // Low-level implementation modules (hidden from docs but still public)
 // These are available for advanced users who need fine-grained control
 #[doc(hidden)]
 pub mod auth;
 #[doc(hidden)]
+pub mod blob;
+#[doc(hidden)]
 pub mod cache;
 #[doc(hidden)]
 pub mod client;
 #[doc(hidden)]
+pub mod copy;
+#[doc(hidden)]
+pub mod diff;
+#[doc(hidden)]
 pub mod digest;
 #[doc(hidden)]
 pub mod error;
 #[doc(hidden)]
 pub mod format;
 #[doc(hidden)]
+pub mod gc;
+#[doc(hidden)]
+pub mod layer;
+#[doc(hidden)]
 pub mod oci;
 #[doc(hidden)]
+pub mod oci_layout;
+#[doc(hidden)]
 pub mod reference;
 #[doc(hidden)]
 pub mod registry;
 #[doc(hidden)]
+pub mod sbom;
+#[doc(hidden)]
 pub mod search;
+#[doc(hidden)]
+pub mod signature;
+#[doc(hidden)]
+pub mod usage;
+#[doc(hidden)]
+pub mod zot;
+
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,196 @@
+//! Classification of OCI artifact manifests.
+//!
+//! Image manifests are also used to store things that are not container
+//! images: Helm charts, signatures, attestations, SBOMs, WASM modules. They
+//! are told apart by `artifactType`, `config.mediaType` and the layer media
+//! types.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Descriptor, ImageManifest};
+
+/// Media type of an OCI image configuration.
+pub const OCI_CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.image.config.v1+json";
+
+/// Media type of a Docker image configuration.
+pub const DOCKER_CONFIG_MEDIA_TYPE: &str = "application/vnd.docker.container.image.v1+json";
+
+/// Media type of the empty descriptor used as config by artifacts without one.
+pub const OCI_EMPTY_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+
+/// Known kinds of content stored in an image manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// A container image with an image configuration
+    Image,
+    /// A Helm chart
+    HelmChart,
+    /// A cosign or sigstore signature
+    CosignSignature,
+    /// An in-toto attestation, usually wrapped in a DSSE envelope
+    InTotoAttestation,
+    /// An SPDX software bill of materials
+    SpdxSbom,
+    /// A CycloneDX software bill of materials
+    CycloneDxSbom,
+    /// A WebAssembly module
+    Wasm,
+    /// Any other artifact
+    Generic,
+}
+
+impl ArtifactKind {
+    /// Returns the kind a media type identifies, if it is a known artifact type.
+    ///
+    /// Image configuration media types are not artifact types and return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::oci::ArtifactKind;
+    ///
+    /// assert_eq!(
+    ///     ArtifactKind::from_media_type("application/vnd.cncf.helm.config.v1+json"),
+    ///     Some(ArtifactKind::HelmChart)
+    /// );
+    /// assert_eq!(ArtifactKind::from_media_type("application/json"), None);
+    /// ```
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type.to_ascii_lowercase();
+
+        if media_type.starts_with("application/vnd.cncf.helm.") {
+            Some(ArtifactKind::HelmChart)
+        } else if media_type.starts_with("application/vnd.dev.cosign.artifact.sig")
+            || media_type.starts_with("application/vnd.dev.cosign.simplesigning")
+            || media_type.starts_with("application/vnd.dev.sigstore.bundle")
+        {
+            Some(ArtifactKind::CosignSignature)
+        } else if media_type.starts_with("application/vnd.in-toto")
+            || media_type.starts_with("application/vnd.dsse.envelope")
+        {
+            Some(ArtifactKind::InTotoAttestation)
+        } else if media_type.contains("spdx") {
+            Some(ArtifactKind::SpdxSbom)
+        } else if media_type.contains("cyclonedx") {
+            Some(ArtifactKind::CycloneDxSbom)
+        } else if media_type.contains("wasm") {
+            Some(ArtifactKind::Wasm)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if this is a regular container image.
+    pub fn is_image(&self) -> bool {
+        matches!(self, ArtifactKind::Image)
+    }
+
+    /// Human-readable name of the kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArtifactKind::Image => "Container image",
+            ArtifactKind::HelmChart => "Helm chart",
+            ArtifactKind::CosignSignature => "Cosign signature",
+            ArtifactKind::InTotoAttestation => "In-toto attestation",
+            ArtifactKind::SpdxSbom => "SPDX SBOM",
+            ArtifactKind::CycloneDxSbom => "CycloneDX SBOM",
+            ArtifactKind::Wasm => "WebAssembly module",
+            ArtifactKind::Generic => "Artifact",
+        }
+    }
+}
+
+impl fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What an image manifest stores, with the artifact fields of the manifest.
+///
+/// # Examples
+///
+/// ```
+/// use librex::oci::{Artifact, ArtifactKind, ImageManifest};
+///
+/// let manifest: ImageManifest = serde_json::from_str(r#"{
+///     "schemaVersion": 2,
+///     "mediaType": "application/vnd.oci.image.manifest.v1+json",
+///     "config": {
+///         "mediaType": "application/vnd.cncf.helm.config.v1+json",
+///         "size": 117,
+///         "digest": "sha256:8ec7c0f2f6860037c19b54c3cfbab48d9b4b21b485a93d87b64690fdb68c2111"
+///     },
+///     "layers": []
+/// }"#).unwrap();
+///
+/// let artifact = Artifact::from_manifest(&manifest);
+/// assert_eq!(artifact.kind, ArtifactKind::HelmChart);
+/// assert_eq!(artifact.artifact_type, "application/vnd.cncf.helm.config.v1+json");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    /// Classified kind
+    pub kind: ArtifactKind,
+    /// The manifest's `artifactType`, or its config media type when absent
+    pub artifact_type: String,
+    /// Media type of the config descriptor
+    pub config_media_type: String,
+    /// The manifest this artifact refers to, if any
+    pub subject: Option<Descriptor>,
+    /// Manifest annotations
+    pub annotations: HashMap<String, String>,
+    /// Layer (blob) descriptors
+    pub layers: Vec<Descriptor>,
+}
+
+impl Artifact {
+    /// Classifies an image manifest.
+    ///
+    /// `artifactType` is checked first, then `config.mediaType`, then the
+    /// layer media types (legacy cosign and SBOM attachments keep an image
+    /// config and only mark their layers). A manifest with an image config
+    /// and no `artifactType` is an image; anything unrecognised is generic.
+    pub fn from_manifest(manifest: &ImageManifest) -> Self {
+        let config_media_type = manifest.config().media_type().to_string();
+        let artifact_type = manifest.artifact_type().as_ref().map(|t| t.to_string());
+
+        let kind = artifact_type
+            .as_deref()
+            .and_then(ArtifactKind::from_media_type)
+            .or_else(|| ArtifactKind::from_media_type(&config_media_type))
+            .or_else(|| {
+                manifest
+                    .layers()
+                    .iter()
+                    .find_map(|layer| ArtifactKind::from_media_type(layer.media_type().as_ref()))
+            })
+            .unwrap_or_else(|| {
+                if artifact_type.is_none() && is_image_config(&config_media_type) {
+                    ArtifactKind::Image
+                } else {
+                    ArtifactKind::Generic
+                }
+            });
+
+        Artifact {
+            kind,
+            artifact_type: artifact_type.unwrap_or_else(|| config_media_type.clone()),
+            config_media_type,
+            subject: manifest.subject().clone(),
+            annotations: manifest.annotations().clone().unwrap_or_default(),
+            layers: manifest.layers().clone(),
+        }
+    }
+
+    /// Returns true if the manifest is a regular container image.
+    pub fn is_image(&self) -> bool {
+        self.kind.is_image()
+    }
+}
+
+/// Returns true if the media type is an OCI or Docker image configuration.
+pub fn is_image_config(media_type: &str) -> bool {
+    media_type == OCI_CONFIG_MEDIA_TYPE || media_type == DOCKER_CONFIG_MEDIA_TYPE
+}
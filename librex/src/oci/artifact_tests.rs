@@ -0,0 +1,199 @@
+use super::*;
+
+/// Builds a manifest with the given config media type, layer media types and
+/// optional `artifactType`.
+fn manifest(config_type: &str, layer_types: &[&str], artifact_type: Option<&str>) -> ImageManifest {
+    let layers: Vec<serde_json::Value> = layer_types
+        .iter()
+        .map(|media_type| {
+            serde_json::json!({
+                "mediaType": media_type,
+                "size": 1024,
+                "digest": format!("sha256:{}", "b".repeat(64)),
+            })
+        })
+        .collect();
+
+    let mut value = serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": config_type,
+            "size": 2,
+            "digest": format!("sha256:{}", "a".repeat(64)),
+        },
+        "layers": layers,
+    });
+    if let Some(artifact_type) = artifact_type {
+        value["artifactType"] = artifact_type.into();
+    }
+
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_artifact_container_image() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_CONFIG_MEDIA_TYPE,
+        &["application/vnd.oci.image.layer.v1.tar+gzip"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::Image);
+    assert!(artifact.is_image());
+    assert_eq!(artifact.artifact_type, OCI_CONFIG_MEDIA_TYPE);
+}
+
+#[test]
+fn test_artifact_docker_image() {
+    let artifact = Artifact::from_manifest(&manifest(
+        DOCKER_CONFIG_MEDIA_TYPE,
+        &["application/vnd.docker.image.rootfs.diff.tar.gzip"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::Image);
+}
+
+#[test]
+fn test_artifact_helm_chart() {
+    let artifact = Artifact::from_manifest(&manifest(
+        "application/vnd.cncf.helm.config.v1+json",
+        &["application/vnd.cncf.helm.chart.content.v1.tar+gzip"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::HelmChart);
+    assert_eq!(artifact.kind.to_string(), "Helm chart");
+}
+
+#[test]
+fn test_artifact_type_takes_precedence() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_EMPTY_MEDIA_TYPE,
+        &["application/json"],
+        Some("application/vnd.cyclonedx+json"),
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::CycloneDxSbom);
+    assert_eq!(artifact.artifact_type, "application/vnd.cyclonedx+json");
+    assert_eq!(artifact.config_media_type, OCI_EMPTY_MEDIA_TYPE);
+}
+
+#[test]
+fn test_artifact_legacy_cosign_signature() {
+    // cosign's tag-based signatures keep an image config and mark the layers
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_CONFIG_MEDIA_TYPE,
+        &["application/vnd.dev.cosign.simplesigning.v1+json"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::CosignSignature);
+}
+
+#[test]
+fn test_artifact_in_toto_attestation() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_CONFIG_MEDIA_TYPE,
+        &["application/vnd.dsse.envelope.v1+json"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::InTotoAttestation);
+}
+
+#[test]
+fn test_artifact_spdx_sbom() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_EMPTY_MEDIA_TYPE,
+        &["application/spdx+json"],
+        Some("application/spdx+json"),
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::SpdxSbom);
+}
+
+#[test]
+fn test_artifact_wasm() {
+    let artifact = Artifact::from_manifest(&manifest(
+        "application/vnd.wasm.config.v0+json",
+        &["application/wasm"],
+        None,
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::Wasm);
+}
+
+#[test]
+fn test_artifact_generic() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_EMPTY_MEDIA_TYPE,
+        &["text/plain"],
+        Some("application/vnd.example.notes+json"),
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::Generic);
+    assert_eq!(artifact.artifact_type, "application/vnd.example.notes+json");
+}
+
+#[test]
+fn test_artifact_unknown_type_on_image_config_is_generic() {
+    let artifact = Artifact::from_manifest(&manifest(
+        OCI_CONFIG_MEDIA_TYPE,
+        &[],
+        Some("application/vnd.example.notes+json"),
+    ));
+
+    assert_eq!(artifact.kind, ArtifactKind::Generic);
+}
+
+#[test]
+fn test_artifact_subject_and_annotations() {
+    let bytes = br#"{
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "artifactType": "application/vnd.dev.sigstore.bundle.v0.3+json",
+        "config": {
+            "mediaType": "application/vnd.oci.empty.v1+json",
+            "size": 2,
+            "digest": "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        },
+        "layers": [],
+        "subject": {
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "size": 1024,
+            "digest": "sha256:cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc"
+        },
+        "annotations": {
+            "org.opencontainers.image.created": "2024-05-01T10:00:00Z"
+        }
+    }"#;
+
+    let artifact = ManifestOrIndex::from_bytes(bytes)
+        .unwrap()
+        .artifact()
+        .unwrap();
+
+    assert_eq!(artifact.kind, ArtifactKind::CosignSignature);
+    assert_eq!(
+        artifact.subject.unwrap().digest().to_string(),
+        format!("sha256:{}", "c".repeat(64))
+    );
+    assert_eq!(
+        artifact.annotations["org.opencontainers.image.created"],
+        "2024-05-01T10:00:00Z"
+    );
+}
+
+#[test]
+fn test_artifact_of_index_is_none() {
+    let bytes = br#"{"schemaVersion": 2, "manifests": []}"#;
+
+    assert!(
+        ManifestOrIndex::from_bytes(bytes)
+            .unwrap()
+            .artifact()
+            .is_none()
+    );
+}
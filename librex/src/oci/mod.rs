@@ -5,6 +5,12 @@
 
 pub use oci_spec::image::{Descriptor, ImageConfiguration, ImageIndex, ImageManifest, Platform};
 
+mod artifact;
+pub use artifact::{
+    Artifact, ArtifactKind, DOCKER_CONFIG_MEDIA_TYPE, OCI_CONFIG_MEDIA_TYPE, OCI_EMPTY_MEDIA_TYPE,
+    is_image_config,
+};
+
 use crate::error::{Result, RexError};
 
 /// Represents either a single-platform image manifest or a multi-platform image index.
@@ -137,6 +143,13 @@ impl ManifestOrIndex {
         }
     }
 
+    /// Classifies the manifest as an image or an artifact.
+    ///
+    /// Returns `None` for an index, which is classified per child manifest.
+    pub fn artifact(&self) -> Option<Artifact> {
+        self.as_manifest().map(Artifact::from_manifest)
+    }
+
     /// Get available platforms if this is an image index.
     ///
     /// Returns a vector of platform descriptors with their corresponding manifests.
@@ -173,5 +186,49 @@ impl ManifestOrIndex {
     }
 }
 
+/// Media type of an OCI image manifest.
+pub const OCI_MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+
+/// Media type of an OCI image index.
+pub const OCI_INDEX_MEDIA_TYPE: &str = "application/vnd.oci.image.index.v1+json";
+
+/// Returns the media type of raw manifest bytes.
+///
+/// This is the `mediaType` field when present. Manifests without one (allowed
+/// by the OCI spec) are OCI manifests or indexes, told apart by their
+/// structure like in [`ManifestOrIndex::from_bytes`].
+///
+/// # Examples
+///
+/// ```
+/// use librex::oci::manifest_media_type;
+///
+/// let bytes = br#"{"schemaVersion": 2, "manifests": []}"#;
+/// assert_eq!(
+///     manifest_media_type(bytes).unwrap(),
+///     "application/vnd.oci.image.index.v1+json"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a JSON manifest or index.
+pub fn manifest_media_type(bytes: &[u8]) -> Result<String> {
+    let value: serde_json::Value = serde_json::from_slice(bytes)
+        .map_err(|e| RexError::validation_with_source("Failed to parse manifest JSON", e))?;
+
+    if let Some(media_type) = value.get("mediaType").and_then(|v| v.as_str()) {
+        return Ok(media_type.to_string());
+    }
+
+    match ManifestOrIndex::from_bytes(bytes)? {
+        ManifestOrIndex::Manifest(_) => Ok(OCI_MANIFEST_MEDIA_TYPE.to_string()),
+        ManifestOrIndex::Index(_) => Ok(OCI_INDEX_MEDIA_TYPE.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests;
+
+#[cfg(test)]
+mod artifact_tests;
@@ -136,3 +136,42 @@ fn test_manifest_or_index_into_index() {
     let index = manifest_or_index.into_index();
     assert!(index.is_some());
 }
+
+#[test]
+fn test_manifest_media_type_from_field() {
+    assert_eq!(
+        manifest_media_type(TEST_MANIFEST.as_bytes()).unwrap(),
+        OCI_MANIFEST_MEDIA_TYPE
+    );
+
+    let docker = br#"{
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.docker.distribution.manifest.list.v2+json",
+        "manifests": []
+    }"#;
+    assert_eq!(
+        manifest_media_type(docker).unwrap(),
+        "application/vnd.docker.distribution.manifest.list.v2+json"
+    );
+}
+
+#[test]
+fn test_manifest_media_type_inferred_from_structure() {
+    let index = br#"{"schemaVersion": 2, "manifests": []}"#;
+    assert_eq!(manifest_media_type(index).unwrap(), OCI_INDEX_MEDIA_TYPE);
+
+    let manifest = TEST_MANIFEST.replace(
+        r#""mediaType": "application/vnd.oci.image.manifest.v1+json","#,
+        "",
+    );
+    assert_eq!(
+        manifest_media_type(manifest.as_bytes()).unwrap(),
+        OCI_MANIFEST_MEDIA_TYPE
+    );
+}
+
+#[test]
+fn test_manifest_media_type_rejects_invalid_content() {
+    assert!(manifest_media_type(b"not json").is_err());
+    assert!(manifest_media_type(br#"{"schemaVersion": 2}"#).is_err());
+}
@@ -0,0 +1,597 @@
+//! Pushing images from layouts and archives to a registry.
+//!
+//! The source is an OCI image layout, as a directory or a tarball, or a
+//! `docker save` archive. Layouts (and Docker 25+ archives, which are
+//! layouts too) are pushed as stored, so digests are preserved. Older Docker
+//! archives only hold a config and uncompressed layers: an OCI manifest is
+//! built for them.
+
+use super::{
+    BLOBS_DIR, DOCKER_MANIFEST_FILE, IMAGE_LAYOUT_VERSION, IMAGE_NAME_ANNOTATION, INDEX_FILE,
+    OCI_LAYOUT_FILE, REF_NAME_ANNOTATION, digest_of,
+};
+use crate::client::Client;
+use crate::copy::{DEFAULT_CHUNK_SIZE, is_foreign};
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::layer::Compression;
+use crate::oci::{Descriptor, ImageIndex, ManifestOrIndex, manifest_media_type};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Options controlling an image load.
+///
+/// # Examples
+///
+/// ```
+/// use librex::oci_layout::LoadOptions;
+///
+/// let options = LoadOptions::new().with_name("myapp:1.5");
+/// assert_eq!(options.name.as_deref(), Some("myapp:1.5"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadOptions {
+    /// Image to load when the source holds several: a full name
+    /// (`repository:tag`) or a layout ref name (the tag)
+    pub name: Option<String>,
+}
+
+impl LoadOptions {
+    /// Creates load options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects the image to load by name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+/// Progress of an image load, reported once per blob and manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadEvent {
+    /// The registry already had this blob
+    BlobExists(String),
+    /// The blob was uploaded
+    BlobUploaded {
+        /// Blob digest
+        digest: String,
+        /// Blob size in bytes
+        size: u64,
+    },
+    /// The registry already had this child manifest
+    ManifestExists(String),
+    /// A manifest was pushed
+    ManifestPushed {
+        /// Tag or digest it was pushed under
+        reference: String,
+        /// Manifest digest
+        digest: String,
+    },
+}
+
+/// Summary of a finished image load.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LoadReport {
+    /// Digest of the manifest or index pushed under the reference
+    pub digest: String,
+    /// Number of manifests and indexes pushed
+    pub manifests: usize,
+    /// Number of blobs uploaded
+    pub blobs_uploaded: usize,
+    /// Number of blobs the registry already had
+    pub blobs_skipped: usize,
+    /// Bytes uploaded
+    pub bytes_uploaded: u64,
+}
+
+/// One image of Docker's `manifest.json`.
+#[derive(Debug, Clone, Deserialize)]
+struct DockerImage {
+    #[serde(rename = "Config")]
+    config: String,
+    #[serde(rename = "RepoTags", default)]
+    repo_tags: Option<Vec<String>>,
+    #[serde(rename = "Layers")]
+    layers: Vec<String>,
+}
+
+/// Files of a layout directory or archive, by path relative to its root.
+trait LayoutSource {
+    /// Opens a file; `None` if there is no such file
+    fn open(&mut self, path: &str) -> Result<Option<(Box<dyn Read + '_>, u64)>>;
+
+    /// Reads a (small) file into memory
+    fn read(&mut self, path: &str) -> Result<Option<Vec<u8>>> {
+        match self.open(path)? {
+            Some((mut reader, size)) => {
+                let mut content = Vec::with_capacity(size as usize);
+                reader
+                    .read_to_end(&mut content)
+                    .map_err(|e| source_error(path, e))?;
+                Ok(Some(content))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+fn source_error(path: &str, error: io::Error) -> RexError {
+    RexError::config_with_source("Failed to read image source", Some(path), error)
+}
+
+/// Normalizes a path inside a layout, refusing anything outside it.
+fn clean_path(path: &str) -> Result<String> {
+    let mut parts = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::CurDir => {}
+            _ => {
+                return Err(RexError::validation(format!(
+                    "Path {} leaves the image source",
+                    path
+                )));
+            }
+        }
+    }
+    Ok(parts.join("/"))
+}
+
+/// A layout (or extracted archive) in a directory.
+struct DirectorySource {
+    root: PathBuf,
+}
+
+impl LayoutSource for DirectorySource {
+    fn open(&mut self, path: &str) -> Result<Option<(Box<dyn Read + '_>, u64)>> {
+        let path = self.root.join(clean_path(path)?);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(source_error(&path.to_string_lossy(), e)),
+        };
+        let size = file
+            .metadata()
+            .map_err(|e| source_error(&path.to_string_lossy(), e))?
+            .len();
+        Ok(Some((Box::new(file), size)))
+    }
+}
+
+/// A layout or Docker archive in a tarball, read in place: the archive is
+/// indexed once, then files are read at their offsets.
+struct TarSource {
+    file: File,
+    entries: HashMap<String, (u64, u64)>,
+}
+
+impl TarSource {
+    fn new(path: &Path) -> Result<Self> {
+        let open_error =
+            |e| RexError::config_with_source("Failed to read archive", path.to_str(), e);
+        let file = File::open(path).map_err(open_error)?;
+
+        let mut entries = HashMap::new();
+        let mut archive = tar::Archive::new(&file);
+        for entry in archive.entries_with_seek().map_err(open_error)? {
+            let entry = entry.map_err(open_error)?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path().map_err(open_error)?;
+            // Entries escaping the archive root are left out
+            if let Ok(name) = clean_path(&name.to_string_lossy()) {
+                entries.insert(name, (entry.raw_file_position(), entry.size()));
+            }
+        }
+
+        Ok(Self { file, entries })
+    }
+}
+
+impl LayoutSource for TarSource {
+    fn open(&mut self, path: &str) -> Result<Option<(Box<dyn Read + '_>, u64)>> {
+        let Some(&(offset, size)) = self.entries.get(&clean_path(path)?) else {
+            return Ok(None);
+        };
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .map_err(|e| source_error(path, e))?;
+        Ok(Some((Box::new((&self.file).take(size)), size)))
+    }
+}
+
+/// Path of a blob in a layout.
+fn blob_path(digest: &Digest) -> String {
+    format!("{}/{}/{}", BLOBS_DIR, digest.algorithm(), digest.hex())
+}
+
+/// Names an index entry answers to.
+fn entry_names(descriptor: &Descriptor) -> Vec<String> {
+    let Some(annotations) = descriptor.annotations() else {
+        return Vec::new();
+    };
+    [IMAGE_NAME_ANNOTATION, REF_NAME_ANNOTATION]
+        .iter()
+        .filter_map(|key| annotations.get(*key).cloned())
+        .collect()
+}
+
+/// Picks the image to load: the one named `wanted`, or the only one.
+///
+/// A full name also matches with a registry prefix, so "alpine:3.19" finds
+/// "docker.io/library/alpine:3.19".
+fn select_image<'c, T>(
+    candidates: &'c [T],
+    names: impl Fn(&T) -> Vec<String>,
+    label: impl Fn(&T) -> String,
+    wanted: Option<&str>,
+) -> Result<&'c T> {
+    let available = || {
+        let labels: Vec<String> = candidates.iter().map(&label).collect();
+        if labels.is_empty() {
+            "none".to_string()
+        } else {
+            labels.join(", ")
+        }
+    };
+
+    match wanted {
+        Some(wanted) => candidates
+            .iter()
+            .find(|candidate| {
+                names(candidate)
+                    .iter()
+                    .any(|name| name == wanted || name.ends_with(&format!("/{}", wanted)))
+            })
+            .ok_or_else(|| {
+                RexError::not_found("image", &format!("{} (available: {})", wanted, available()))
+            }),
+        None => match candidates {
+            [only] => Ok(only),
+            [] => Err(RexError::validation("The image source holds no images")),
+            _ => Err(RexError::validation(format!(
+                "The image source holds {} images; choose one by name: {}",
+                candidates.len(),
+                available()
+            ))),
+        },
+    }
+}
+
+/// State of one load operation.
+struct Loader<'a, F> {
+    client: &'a Client,
+    repository: &'a str,
+    source: &'a mut dyn LayoutSource,
+    on_event: F,
+    report: LoadReport,
+}
+
+impl<F: FnMut(&LoadEvent)> Loader<'_, F> {
+    /// Pushes a manifest or index from the layout, after what it references.
+    fn push_manifest(&mut self, descriptor: &Descriptor, reference: &str) -> Result<String> {
+        let digest = Digest::from_str(descriptor.digest().as_ref())?;
+        let bytes = self
+            .source
+            .read(&blob_path(&digest))?
+            .ok_or_else(|| RexError::not_found("Manifest", &digest.to_string()))?;
+        let actual = digest_of(&bytes)?;
+        if actual != digest {
+            return Err(RexError::validation(format!(
+                "Manifest digest mismatch: expected {}, got {}",
+                digest, actual
+            )));
+        }
+
+        match ManifestOrIndex::from_bytes(&bytes)? {
+            ManifestOrIndex::Manifest(manifest) => {
+                for descriptor in std::iter::once(manifest.config()).chain(manifest.layers()) {
+                    if !is_foreign(descriptor) {
+                        let digest = Digest::from_str(descriptor.digest().as_ref())?;
+                        self.upload_blob(&blob_path(&digest), &digest, descriptor.size())?;
+                    }
+                }
+            }
+            ManifestOrIndex::Index(index) => {
+                for child in index.manifests() {
+                    let child_digest = child.digest().to_string();
+                    match self.client.head_manifest(self.repository, &child_digest) {
+                        Ok(_) => self.emit(LoadEvent::ManifestExists(child_digest)),
+                        Err(RexError::NotFound { .. }) => {
+                            self.push_manifest(child, &child_digest)?;
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+        }
+
+        self.put_manifest(reference, &bytes, &manifest_media_type(&bytes)?)
+    }
+
+    /// Pushes an image of a Docker archive without an OCI layout, building
+    /// its manifest.
+    fn push_docker_image(&mut self, image: &DockerImage, reference: &str) -> Result<String> {
+        let config = self
+            .source
+            .read(&image.config)?
+            .ok_or_else(|| RexError::not_found("Config", &image.config))?;
+        let config_digest = digest_of(&config)?;
+
+        let mut layers = Vec::new();
+        for path in &image.layers {
+            let (digest, size, compression) = self.hash_file(path)?;
+            let media_type = match compression {
+                Compression::None => "application/vnd.oci.image.layer.v1.tar",
+                Compression::Gzip => "application/vnd.oci.image.layer.v1.tar+gzip",
+                Compression::Zstd => "application/vnd.oci.image.layer.v1.tar+zstd",
+            };
+            layers.push((path, digest, size, media_type));
+        }
+
+        self.upload_blob(&image.config, &config_digest, config.len() as u64)?;
+        for (path, digest, size, _) in &layers {
+            self.upload_blob(path, digest, *size)?;
+        }
+
+        let manifest = json!({
+            "schemaVersion": 2,
+            "mediaType": crate::oci::OCI_MANIFEST_MEDIA_TYPE,
+            "config": {
+                "mediaType": "application/vnd.oci.image.config.v1+json",
+                "digest": config_digest.to_string(),
+                "size": config.len(),
+            },
+            "layers": layers
+                .iter()
+                .map(|(_, digest, size, media_type)| json!({
+                    "mediaType": media_type,
+                    "digest": digest.to_string(),
+                    "size": size,
+                }))
+                .collect::<Vec<_>>(),
+        });
+        let bytes = serde_json::to_vec(&manifest)
+            .map_err(|e| RexError::validation_with_source("Failed to build manifest", e))?;
+
+        self.put_manifest(reference, &bytes, crate::oci::OCI_MANIFEST_MEDIA_TYPE)
+    }
+
+    /// Computes the digest, size and compression of a file in the source.
+    fn hash_file(&mut self, path: &str) -> Result<(Digest, u64, Compression)> {
+        let (mut reader, _) = self
+            .source
+            .open(path)?
+            .ok_or_else(|| RexError::not_found("Layer", path))?;
+
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        let mut size = 0u64;
+        let mut compression = None;
+        loop {
+            let len = reader
+                .read(&mut buffer)
+                .map_err(|e| source_error(path, e))?;
+            if len == 0 {
+                break;
+            }
+            compression.get_or_insert_with(|| Compression::detect(&buffer[..len]));
+            hasher.update(&buffer[..len]);
+            size += len as u64;
+        }
+
+        let digest = Digest::from_str(&format!("sha256:{:x}", hasher.finalize()))?;
+        Ok((digest, size, compression.unwrap_or(Compression::None)))
+    }
+
+    /// Uploads a blob from the source, unless the registry has it.
+    fn upload_blob(&mut self, path: &str, digest: &Digest, size: u64) -> Result<()> {
+        let digest_str = digest.to_string();
+        match self.client.head_blob(self.repository, &digest_str) {
+            Ok(_) => {
+                self.report.blobs_skipped += 1;
+                self.emit(LoadEvent::BlobExists(digest_str));
+                return Ok(());
+            }
+            Err(RexError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+
+        {
+            let (mut reader, _) = self
+                .source
+                .open(path)?
+                .ok_or_else(|| RexError::not_found("Blob", &digest_str))?;
+            if size <= DEFAULT_CHUNK_SIZE as u64 {
+                let mut data = Vec::with_capacity(size as usize);
+                reader
+                    .read_to_end(&mut data)
+                    .map_err(|e| source_error(path, e))?;
+                self.client
+                    .upload_blob(self.repository, &digest_str, &data)?;
+            } else {
+                self.client.upload_blob_chunked(
+                    self.repository,
+                    &digest_str,
+                    &mut reader,
+                    DEFAULT_CHUNK_SIZE,
+                )?;
+            }
+        }
+
+        self.report.blobs_uploaded += 1;
+        self.report.bytes_uploaded += size;
+        self.emit(LoadEvent::BlobUploaded {
+            digest: digest_str,
+            size,
+        });
+        Ok(())
+    }
+
+    fn put_manifest(&mut self, reference: &str, bytes: &[u8], media_type: &str) -> Result<String> {
+        let digest = self
+            .client
+            .put_manifest(self.repository, reference, bytes, media_type)?;
+        self.report.manifests += 1;
+        self.emit(LoadEvent::ManifestPushed {
+            reference: reference.to_string(),
+            digest: digest.clone(),
+        });
+        Ok(digest)
+    }
+
+    fn emit(&mut self, event: LoadEvent) {
+        (self.on_event)(&event);
+    }
+}
+
+/// Pushes an image from an OCI image layout or a Docker archive.
+///
+/// `source` is a layout directory, or a tarball holding a layout or the
+/// output of `docker save`. When it holds several images, `options.name`
+/// selects one. Blobs the registry already has are skipped; everything
+/// uploaded is checked against its digest.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `repository` - Repository name as the registry knows it (e.g., "library/alpine")
+/// * `reference` - Tag or digest to push the image under
+/// * `source` - Layout directory or tarball
+/// * `options` - Image selection
+/// * `on_event` - Called as each blob and manifest is processed
+///
+/// # Returns
+///
+/// A [`LoadReport`] with the pushed digest and transfer counts.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::oci_layout::{LoadOptions, load_image};
+/// use std::path::Path;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("http://localhost:5000", None)?;
+///
+/// let report = load_image(
+///     &client,
+///     "alpine",
+///     "3.19",
+///     Path::new("alpine.tar"),
+///     &LoadOptions::new(),
+///     |_| {},
+/// )?;
+/// println!("Pushed {} ({} blobs uploaded)", report.digest, report.blobs_uploaded);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - `source` is neither an OCI image layout nor a Docker archive
+/// - The source holds several images and none (or no matching one) was named
+/// - A manifest or blob is missing from the source, or fails digest validation
+/// - Push access is denied, or the registry rejects content
+pub fn load_image<F>(
+    client: &Client,
+    repository: &str,
+    reference: &str,
+    source: &Path,
+    options: &LoadOptions,
+    on_event: F,
+) -> Result<LoadReport>
+where
+    F: FnMut(&LoadEvent),
+{
+    let mut files: Box<dyn LayoutSource> = if source.is_dir() {
+        Box::new(DirectorySource {
+            root: source.to_path_buf(),
+        })
+    } else {
+        Box::new(TarSource::new(source)?)
+    };
+    let wanted = options.name.as_deref();
+
+    let marker = files.read(OCI_LAYOUT_FILE)?;
+    let index = match marker {
+        Some(_) => files.read(INDEX_FILE)?,
+        None => None,
+    };
+    let docker_images = match index {
+        Some(_) => None,
+        None => files.read(DOCKER_MANIFEST_FILE)?,
+    };
+
+    let mut loader = Loader {
+        client,
+        repository,
+        source: files.as_mut(),
+        on_event,
+        report: LoadReport::default(),
+    };
+
+    let digest = match (marker, index, docker_images) {
+        (Some(marker), Some(index), _) => {
+            let version = serde_json::from_slice::<serde_json::Value>(&marker)
+                .ok()
+                .and_then(|value| value["imageLayoutVersion"].as_str().map(str::to_string));
+            if version.as_deref() != Some(IMAGE_LAYOUT_VERSION) {
+                return Err(RexError::validation(format!(
+                    "Unsupported image layout version: {}",
+                    version.unwrap_or_else(|| "none".to_string())
+                )));
+            }
+            let index: ImageIndex = serde_json::from_slice(&index)
+                .map_err(|e| RexError::validation_with_source("Failed to parse layout index", e))?;
+            let descriptor = select_image(
+                index.manifests(),
+                entry_names,
+                |descriptor| {
+                    entry_names(descriptor)
+                        .into_iter()
+                        .next()
+                        .unwrap_or_else(|| descriptor.digest().to_string())
+                },
+                wanted,
+            )?;
+            loader.push_manifest(descriptor, reference)?
+        }
+        (_, _, Some(docker_images)) => {
+            let images: Vec<DockerImage> = serde_json::from_slice(&docker_images).map_err(|e| {
+                RexError::validation_with_source("Failed to parse Docker archive manifest", e)
+            })?;
+            let image = select_image(
+                &images,
+                |image| image.repo_tags.clone().unwrap_or_default(),
+                |image| match image.repo_tags.as_deref() {
+                    Some([tag, ..]) => tag.clone(),
+                    _ => image.config.clone(),
+                },
+                wanted,
+            )?;
+            loader.push_docker_image(image, reference)?
+        }
+        _ => {
+            return Err(RexError::validation(format!(
+                "{} is not an OCI image layout or Docker archive",
+                source.display()
+            )));
+        }
+    };
+
+    loader.report.digest = digest;
+    Ok(loader.report)
+}
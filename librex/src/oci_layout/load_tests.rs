@@ -0,0 +1,242 @@
+use super::load::*;
+use super::*;
+use crate::test_support::{
+    LAYER, accept_manifest, accept_upload, head_blob, index_json, manifest_json, sha256_of,
+};
+use mockito::Server;
+
+/// Writes blobs into a new layout and lists `entries` (manifest, name) in its index.
+fn layout(root: &Path, blobs: &[&[u8]], entries: &[(&[u8], Option<&str>)]) -> OciLayout {
+    let mut layout = OciLayout::create(root).unwrap();
+    for blob in blobs {
+        let digest = Digest::from_str(&sha256_of(blob)).unwrap();
+        layout.put_bytes(&digest, blob).unwrap();
+    }
+    for (manifest, name) in entries {
+        let digest = Digest::from_str(&sha256_of(manifest)).unwrap();
+        let media_type = manifest_media_type(manifest).unwrap();
+        layout
+            .add_to_index(index_entry(&digest, &media_type, manifest.len() as u64, *name).unwrap())
+            .unwrap();
+    }
+    layout
+}
+
+/// Packs a directory into a tarball.
+fn pack(dir: &Path, output: &Path) {
+    let mut builder = tar::Builder::new(File::create(output).unwrap());
+    builder.append_dir_all(".", dir).unwrap();
+    builder.finish().unwrap();
+}
+
+#[test]
+fn test_load_image_from_layout_directory() {
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let layer = b"layer content";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+    let dir = tempfile::tempdir().unwrap();
+    layout(
+        dir.path(),
+        &[config, layer, &manifest],
+        &[(&manifest, Some("alpine:3.19"))],
+    );
+
+    let mut server = Server::new();
+    let _config_head = head_blob(&mut server, "alpine", config, 200);
+    let _layer_head = head_blob(&mut server, "alpine", layer, 404);
+    let (_, upload) = accept_upload(&mut server, "alpine", layer);
+    let push = accept_manifest(&mut server, "alpine", "3.19", &manifest);
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut events = Vec::new();
+
+    let report = load_image(
+        &client,
+        "alpine",
+        "3.19",
+        dir.path(),
+        &LoadOptions::new(),
+        |event| events.push(event.clone()),
+    )
+    .unwrap();
+
+    upload.assert();
+    push.assert();
+    assert_eq!(
+        report,
+        LoadReport {
+            digest: sha256_of(&manifest),
+            manifests: 1,
+            blobs_uploaded: 1,
+            blobs_skipped: 1,
+            bytes_uploaded: layer.len() as u64,
+        }
+    );
+    assert_eq!(
+        events,
+        vec![
+            LoadEvent::BlobExists(sha256_of(config)),
+            LoadEvent::BlobUploaded {
+                digest: sha256_of(layer),
+                size: layer.len() as u64,
+            },
+            LoadEvent::ManifestPushed {
+                reference: "3.19".to_string(),
+                digest: sha256_of(&manifest),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_load_image_index_from_tarball_by_name() {
+    let layer = b"common layer";
+    let amd64_config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let arm64_config = br#"{"architecture":"arm64","os":"linux"}"#;
+    let amd64 = manifest_json(amd64_config, &[(layer, LAYER)]);
+    let arm64 = manifest_json(arm64_config, &[(layer, LAYER)]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+    let dir = tempfile::tempdir().unwrap();
+    layout(
+        &dir.path().join("layout"),
+        &[layer, amd64_config, arm64_config, &amd64, &arm64, &index],
+        &[
+            (&amd64, Some("docker.io/library/app:old")),
+            (&index, Some("docker.io/library/app:1.5")),
+        ],
+    );
+    let archive = dir.path().join("app.tar");
+    pack(&dir.path().join("layout"), &archive);
+
+    let mut server = Server::new();
+    for blob in [&layer[..], amd64_config, arm64_config] {
+        head_blob(&mut server, "app", blob, 200);
+    }
+    // One platform is already there
+    server
+        .mock(
+            "HEAD",
+            format!("/v2/app/manifests/{}", sha256_of(&amd64)).as_str(),
+        )
+        .with_status(200)
+        .with_header("Docker-Content-Digest", &sha256_of(&amd64))
+        .create();
+    server
+        .mock(
+            "HEAD",
+            format!("/v2/app/manifests/{}", sha256_of(&arm64)).as_str(),
+        )
+        .with_status(404)
+        .create();
+    let child = accept_manifest(&mut server, "app", &sha256_of(&arm64), &arm64);
+    let top = accept_manifest(&mut server, "app", "1.5", &index);
+    let client = Client::new(&server.url(), None).unwrap();
+
+    // Two images in the archive: a name is needed
+    let err = load_image(&client, "app", "1.5", &archive, &LoadOptions::new(), |_| {}).unwrap_err();
+    assert!(err.to_string().contains("holds 2 images"));
+    assert!(err.to_string().contains("docker.io/library/app:1.5"));
+
+    let report = load_image(
+        &client,
+        "app",
+        "1.5",
+        &archive,
+        &LoadOptions::new().with_name("app:1.5"),
+        |_| {},
+    )
+    .unwrap();
+
+    child.assert();
+    top.assert();
+    assert_eq!(report.digest, sha256_of(&index));
+    assert_eq!(report.manifests, 2);
+    assert_eq!(report.blobs_skipped, 2);
+
+    let err = load_image(
+        &client,
+        "app",
+        "1.5",
+        &archive,
+        &LoadOptions::new().with_name("app:2.0"),
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, RexError::NotFound { .. }));
+}
+
+#[test]
+fn test_load_legacy_docker_archive() {
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let layer = b"plain tar layer";
+    let dir = tempfile::tempdir().unwrap();
+    let content = dir.path().join("content");
+    fs::create_dir_all(content.join("f00d")).unwrap();
+    fs::write(content.join("c0ffee.json"), config).unwrap();
+    fs::write(content.join("f00d/layer.tar"), layer).unwrap();
+    fs::write(
+        content.join(DOCKER_MANIFEST_FILE),
+        r#"[{"Config":"c0ffee.json","RepoTags":["app:1.5"],"Layers":["f00d/layer.tar"]}]"#,
+    )
+    .unwrap();
+    let archive = dir.path().join("app.tar");
+    pack(&content, &archive);
+
+    let expected = manifest_json(config, &[(layer, "application/vnd.oci.image.layer.v1.tar")]);
+
+    let mut server = Server::new();
+    head_blob(&mut server, "app", config, 404);
+    head_blob(&mut server, "app", layer, 404);
+    let (_, config_upload) = accept_upload(&mut server, "app", config);
+    let (_, layer_upload) = accept_upload(&mut server, "app", layer);
+    let push = accept_manifest(&mut server, "app", "1.5", &expected);
+    let client = Client::new(&server.url(), None).unwrap();
+
+    let report = load_image(&client, "app", "1.5", &archive, &LoadOptions::new(), |_| {}).unwrap();
+
+    config_upload.assert();
+    layer_upload.assert();
+    push.assert();
+    assert_eq!(report.digest, sha256_of(&expected));
+    assert_eq!(report.blobs_uploaded, 2);
+}
+
+#[test]
+fn test_load_rejects_corrupt_and_foreign_sources() {
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let manifest = manifest_json(config, &[]);
+    let dir = tempfile::tempdir().unwrap();
+    let layout = layout(dir.path(), &[config], &[(&manifest, None)]);
+    // The manifest blob does not match its digest
+    fs::write(
+        layout
+            .blobs()
+            .path(&Digest::from_str(&sha256_of(&manifest)).unwrap()),
+        b"{}",
+    )
+    .unwrap();
+
+    let server = Server::new();
+    let client = Client::new(&server.url(), None).unwrap();
+    let err = load_image(
+        &client,
+        "app",
+        "1.0",
+        dir.path(),
+        &LoadOptions::new(),
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Manifest digest mismatch"));
+
+    let empty = tempfile::tempdir().unwrap();
+    let err = load_image(
+        &client,
+        "app",
+        "1.0",
+        empty.path(),
+        &LoadOptions::new(),
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("is not an OCI image layout"));
+}
@@ -0,0 +1,840 @@
+//! OCI image layouts and image archives.
+//!
+//! An [OCI image layout] is a directory holding an `oci-layout` marker file,
+//! an `index.json` listing the images it holds, and the content of those
+//! images under `blobs/<algorithm>/<hex>`. This module saves images from a
+//! registry into:
+//!
+//! - a layout directory, to which more images can be added later
+//! - a layout tarball (the same files in a tar archive)
+//! - a `docker load` archive: a layout tarball with Docker's `manifest.json`
+//!   next to it, the format `docker save` writes since Docker 25
+//!
+//! Every blob is verified against its digest while it is written.
+//!
+//! Loading goes the other way: an image from a layout, a layout tarball or a
+//! `docker save` archive is pushed to a registry (see [`load_image`]).
+//!
+//! [OCI image layout]: https://github.com/opencontainers/image-spec/blob/main/image-layout.md
+
+use crate::blob::{self, BlobStore};
+use crate::client::Client;
+use crate::copy::{is_foreign, select_platform};
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::oci::{Descriptor, ImageIndex, ImageManifest, ManifestOrIndex, manifest_media_type};
+use serde::Serialize;
+use serde_json::{Value, json};
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+mod load;
+
+pub use load::{LoadEvent, LoadOptions, LoadReport, load_image};
+
+#[cfg(test)]
+mod load_tests;
+#[cfg(test)]
+mod tests;
+
+/// Name of the file marking a directory as an OCI image layout
+pub const OCI_LAYOUT_FILE: &str = "oci-layout";
+
+/// Image layout version written to the `oci-layout` file
+pub const IMAGE_LAYOUT_VERSION: &str = "1.0.0";
+
+/// Name of the layout's index
+pub const INDEX_FILE: &str = "index.json";
+
+/// Name of the directory holding the blobs
+pub const BLOBS_DIR: &str = "blobs";
+
+/// Name of Docker's image list in a `docker load` archive
+pub const DOCKER_MANIFEST_FILE: &str = "manifest.json";
+
+/// Annotation holding the tag of an image in a layout's index
+pub const REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+
+/// Annotation holding the full image name (`repository:tag`), as written
+/// by containerd and Docker
+pub const IMAGE_NAME_ANNOTATION: &str = "io.containerd.image.name";
+
+/// Format of a saved image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArchiveFormat {
+    /// OCI image layout, as a directory or a tarball
+    Oci,
+    /// Tarball for `docker load` (a single platform)
+    DockerArchive,
+}
+
+impl FromStr for ArchiveFormat {
+    type Err = RexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "oci" => Ok(ArchiveFormat::Oci),
+            "docker-archive" | "docker" => Ok(ArchiveFormat::DockerArchive),
+            _ => Err(RexError::validation(format!(
+                "Unknown archive format: {} (expected oci or docker-archive)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ArchiveFormat::Oci => "oci",
+            ArchiveFormat::DockerArchive => "docker-archive",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Options controlling an image save.
+///
+/// # Examples
+///
+/// ```
+/// use librex::oci_layout::{ArchiveFormat, SaveOptions};
+///
+/// let options = SaveOptions::new(ArchiveFormat::DockerArchive)
+///     .with_platform("linux", "amd64", None)
+///     .with_name("myapp:1.5");
+/// assert!(options.platform.is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveOptions {
+    /// Output format
+    pub format: ArchiveFormat,
+    /// Save only this platform (os, architecture, variant) of a multi-platform image
+    pub platform: Option<(String, String, Option<String>)>,
+    /// Image name recorded in the archive (e.g., "myapp:1.5"); its tag
+    /// becomes the layout's ref name
+    pub name: Option<String>,
+}
+
+impl SaveOptions {
+    /// Creates options for a format, saving every platform under no name.
+    pub fn new(format: ArchiveFormat) -> Self {
+        Self {
+            format,
+            platform: None,
+            name: None,
+        }
+    }
+
+    /// Saves only one platform of a multi-platform image.
+    pub fn with_platform(mut self, os: &str, architecture: &str, variant: Option<&str>) -> Self {
+        self.platform = Some((
+            os.to_string(),
+            architecture.to_string(),
+            variant.map(str::to_string),
+        ));
+        self
+    }
+
+    /// Records the image under a name (`repository:tag`).
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+/// Progress of an image save, reported once per blob and manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveEvent {
+    /// The layout already had this blob
+    BlobExists(String),
+    /// The blob was downloaded and verified
+    BlobWritten {
+        /// Blob digest
+        digest: String,
+        /// Blob size in bytes
+        size: u64,
+    },
+    /// A manifest or index was written
+    ManifestWritten(String),
+}
+
+/// Summary of a finished image save.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SaveReport {
+    /// Digest of the manifest or index saved (the platform manifest when
+    /// one platform was selected)
+    pub digest: String,
+    /// Media type of that manifest or index
+    pub media_type: String,
+    /// Number of manifests and indexes written
+    pub manifests: usize,
+    /// Number of blobs downloaded
+    pub blobs_written: usize,
+    /// Number of blobs the layout already had
+    pub blobs_skipped: usize,
+    /// Bytes downloaded
+    pub bytes_written: u64,
+}
+
+/// Computes the sha256 digest of manifest bytes.
+fn digest_of(bytes: &[u8]) -> Result<Digest> {
+    Digest::from_str(&format!("sha256:{:x}", Sha256::digest(bytes)))
+}
+
+/// Builds an index entry for a saved manifest or index.
+fn index_entry(
+    digest: &Digest,
+    media_type: &str,
+    size: u64,
+    name: Option<&str>,
+) -> Result<Descriptor> {
+    let mut entry = json!({
+        "mediaType": media_type,
+        "digest": digest.to_string(),
+        "size": size,
+    });
+    if let Some(name) = name {
+        let mut annotations = serde_json::Map::new();
+        annotations.insert(IMAGE_NAME_ANNOTATION.to_string(), json!(name));
+        if let Some((_, tag)) = split_tag(name) {
+            annotations.insert(REF_NAME_ANNOTATION.to_string(), json!(tag));
+        }
+        entry["annotations"] = Value::Object(annotations);
+    }
+    serde_json::from_value(entry)
+        .map_err(|e| RexError::validation_with_source("Failed to build index entry", e))
+}
+
+/// Splits "repository:tag" (the repository may hold a registry port).
+fn split_tag(name: &str) -> Option<(&str, &str)> {
+    let (repository, tag) = name.rsplit_once(':')?;
+    (!tag.contains('/')).then_some((repository, tag))
+}
+
+/// Builds an index listing `manifests`.
+fn build_index(manifests: Vec<Descriptor>) -> Result<ImageIndex> {
+    serde_json::from_value(json!({
+        "schemaVersion": 2,
+        "mediaType": crate::oci::OCI_INDEX_MEDIA_TYPE,
+        "manifests": manifests,
+    }))
+    .map_err(|e| RexError::validation_with_source("Failed to build image index", e))
+}
+
+fn layout_marker() -> Vec<u8> {
+    json!({ "imageLayoutVersion": IMAGE_LAYOUT_VERSION })
+        .to_string()
+        .into_bytes()
+}
+
+/// Serializes JSON for a layout file.
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value)
+        .map_err(|e| RexError::validation_with_source("Failed to write JSON", e))
+}
+
+/// Docker's `manifest.json` entry for a saved image.
+fn docker_manifest(manifest: &ImageManifest, name: Option<&str>) -> Value {
+    let path = |descriptor: &Descriptor| -> String {
+        let digest = descriptor.digest();
+        format!("{}/{}/{}", BLOBS_DIR, digest.algorithm(), digest.digest())
+    };
+    json!([{
+        "Config": path(manifest.config()),
+        "RepoTags": name.map(|name| vec![name]).unwrap_or_default(),
+        "Layers": manifest.layers().iter().map(path).collect::<Vec<_>>(),
+    }])
+}
+
+/// Where a save writes blobs and layout files.
+trait LayoutSink {
+    /// Whether the blob is already there
+    fn has_blob(&self, digest: &Digest) -> bool;
+
+    /// Streams a blob from the registry, verifying its digest
+    fn put_blob(
+        &mut self,
+        client: &Client,
+        repository: &str,
+        digest: &Digest,
+        size: u64,
+    ) -> Result<()>;
+
+    /// Writes a blob held in memory (manifests)
+    fn put_bytes(&mut self, digest: &Digest, bytes: &[u8]) -> Result<()>;
+}
+
+/// An OCI image layout directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::oci_layout::OciLayout;
+///
+/// # fn example() -> librex::Result<()> {
+/// let layout = OciLayout::open("./alpine-layout")?;
+/// for entry in layout.index()?.manifests() {
+///     println!("{} {}", entry.digest(), entry.size());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OciLayout {
+    root: PathBuf,
+    blobs: BlobStore,
+}
+
+impl OciLayout {
+    /// Creates a layout at `root`, or opens the one already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created, or holds files
+    /// but no `oci-layout` marker.
+    pub fn create(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let marker = root.join(OCI_LAYOUT_FILE);
+
+        if !marker.is_file() {
+            let has_files = fs::read_dir(&root).is_ok_and(|mut entries| entries.next().is_some());
+            if has_files {
+                return Err(RexError::validation(format!(
+                    "{} is not empty and is not an OCI image layout",
+                    root.display()
+                )));
+            }
+            fs::create_dir_all(&root).map_err(|e| {
+                RexError::config_with_source("Failed to create layout directory", root.to_str(), e)
+            })?;
+            write_file(&marker, &layout_marker())?;
+            write_file(&root.join(INDEX_FILE), &to_json(&build_index(Vec::new())?)?)?;
+        }
+
+        Self::open(root)
+    }
+
+    /// Opens an existing layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` has no `oci-layout` marker, or one with an
+    /// unsupported version.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let marker = root.join(OCI_LAYOUT_FILE);
+
+        let content = fs::read(&marker).map_err(|e| {
+            RexError::config_with_source("Not an OCI image layout", marker.to_str(), e)
+        })?;
+        let version = serde_json::from_slice::<Value>(&content)
+            .ok()
+            .and_then(|value| value["imageLayoutVersion"].as_str().map(str::to_string));
+        if version.as_deref() != Some(IMAGE_LAYOUT_VERSION) {
+            return Err(RexError::validation(format!(
+                "Unsupported image layout version in {}: {}",
+                marker.display(),
+                version.unwrap_or_else(|| "none".to_string())
+            )));
+        }
+
+        let blobs = BlobStore::new(root.join(BLOBS_DIR));
+        Ok(Self { root, blobs })
+    }
+
+    /// Returns the layout directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the layout's blobs, a content-addressed store.
+    pub fn blobs(&self) -> &BlobStore {
+        &self.blobs
+    }
+
+    /// Reads the layout's index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index.json` cannot be read or parsed.
+    pub fn index(&self) -> Result<ImageIndex> {
+        let path = self.root.join(INDEX_FILE);
+        let content = fs::read(&path).map_err(|e| {
+            RexError::config_with_source("Failed to read layout index", path.to_str(), e)
+        })?;
+        serde_json::from_slice(&content)
+            .map_err(|e| RexError::validation_with_source("Failed to parse layout index", e))
+    }
+
+    /// Adds an entry to the index.
+    ///
+    /// An entry with the same image name replaces the previous one, so
+    /// saving a tag again moves it; entries without a name accumulate
+    /// (unless they have the same digest).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be read or written.
+    pub fn add_to_index(&self, entry: Descriptor) -> Result<()> {
+        let name = |descriptor: &Descriptor| {
+            descriptor
+                .annotations()
+                .as_ref()
+                .and_then(|annotations| annotations.get(IMAGE_NAME_ANNOTATION).cloned())
+        };
+        let new_name = name(&entry);
+
+        let mut manifests: Vec<Descriptor> = self
+            .index()?
+            .manifests()
+            .iter()
+            .filter(|existing| match &new_name {
+                Some(new_name) => name(existing).as_ref() != Some(new_name),
+                None => name(existing).is_some() || existing.digest() != entry.digest(),
+            })
+            .cloned()
+            .collect();
+        manifests.push(entry);
+
+        write_file(
+            &self.root.join(INDEX_FILE),
+            &to_json(&build_index(manifests)?)?,
+        )
+    }
+}
+
+impl LayoutSink for OciLayout {
+    fn has_blob(&self, digest: &Digest) -> bool {
+        self.blobs.contains(digest)
+    }
+
+    fn put_blob(
+        &mut self,
+        client: &Client,
+        repository: &str,
+        digest: &Digest,
+        _size: u64,
+    ) -> Result<()> {
+        self.blobs
+            .insert_with(digest, |file| {
+                client.fetch_blob_to(repository, &digest.to_string(), file, |_, _| {})
+            })
+            .map(|_| ())
+    }
+
+    fn put_bytes(&mut self, digest: &Digest, bytes: &[u8]) -> Result<()> {
+        let path = self.blobs.path(digest);
+        self.blobs
+            .insert_with(digest, |file| {
+                file.write_all(bytes)
+                    .map(|_| bytes.len() as u64)
+                    .map_err(|e| {
+                        RexError::config_with_source("Failed to write blob file", path.to_str(), e)
+                    })
+            })
+            .map(|_| ())
+    }
+}
+
+/// Writes a file in one go, replacing it.
+fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    fs::write(path, content)
+        .map_err(|e| RexError::config_with_source("Failed to write layout file", path.to_str(), e))
+}
+
+/// An image layout written as a tar archive.
+struct LayoutArchive<W: Write> {
+    builder: tar::Builder<W>,
+    written: HashSet<String>,
+}
+
+impl<W: Write> LayoutArchive<W> {
+    fn new(writer: W) -> Result<Self> {
+        let mut archive = Self {
+            builder: tar::Builder::new(writer),
+            written: HashSet::new(),
+        };
+        for dir in [BLOBS_DIR, &format!("{}/sha256", BLOBS_DIR)] {
+            let mut header = tar::Header::new_ustar();
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+            archive
+                .builder
+                .append_data(&mut header, format!("{}/", dir), io::empty())
+                .map_err(archive_error)?;
+        }
+        Ok(archive)
+    }
+
+    fn append(&mut self, path: &str, size: u64, content: &mut dyn io::Read) -> Result<()> {
+        let mut header = tar::Header::new_ustar();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_mode(0o644);
+        header.set_size(size);
+        self.builder
+            .append_data(&mut header, path, content)
+            .map_err(archive_error)
+    }
+
+    fn blob_path(digest: &Digest) -> String {
+        format!("{}/{}/{}", BLOBS_DIR, digest.algorithm(), digest.hex())
+    }
+
+    /// Writes the layout files and returns the underlying writer.
+    fn finish(mut self, index: &ImageIndex, docker: Option<&Value>) -> Result<W> {
+        let files = [
+            Some((OCI_LAYOUT_FILE, layout_marker())),
+            Some((INDEX_FILE, to_json(index)?)),
+            docker
+                .map(|manifest| to_json(manifest).map(|json| (DOCKER_MANIFEST_FILE, json)))
+                .transpose()?,
+        ];
+        for (name, content) in files.into_iter().flatten() {
+            self.append(name, content.len() as u64, &mut &content[..])?;
+        }
+        self.builder.into_inner().map_err(archive_error)
+    }
+}
+
+impl<W: Write> LayoutSink for LayoutArchive<W> {
+    fn has_blob(&self, digest: &Digest) -> bool {
+        self.written.contains(&digest.to_string())
+    }
+
+    fn put_blob(
+        &mut self,
+        client: &Client,
+        repository: &str,
+        digest: &Digest,
+        size: u64,
+    ) -> Result<()> {
+        let mut reader = client.fetch_blob_reader(repository, &digest.to_string())?;
+        self.append(&Self::blob_path(digest), size, &mut reader)?;
+        // The tar header promised `size` bytes
+        if reader.bytes_read() != size {
+            return Err(RexError::validation(format!(
+                "Blob {} has {} bytes, its descriptor says {}",
+                digest,
+                reader.bytes_read(),
+                size
+            )));
+        }
+        self.written.insert(digest.to_string());
+        Ok(())
+    }
+
+    fn put_bytes(&mut self, digest: &Digest, bytes: &[u8]) -> Result<()> {
+        self.append(
+            &Self::blob_path(digest),
+            bytes.len() as u64,
+            &mut &bytes[..],
+        )?;
+        self.written.insert(digest.to_string());
+        Ok(())
+    }
+}
+
+/// Converts an error writing an archive; digest mismatches met while
+/// streaming a blob into it are returned unchanged.
+fn archive_error(error: io::Error) -> RexError {
+    if error.get_ref().is_some_and(|inner| inner.is::<RexError>()) {
+        return blob::read_error(error);
+    }
+    RexError::config_with_source("Failed to write archive", None::<&str>, error)
+}
+
+/// A manifest or index written to a layout.
+struct Saved {
+    digest: Digest,
+    media_type: String,
+    size: u64,
+    content: ManifestOrIndex,
+}
+
+/// State of one save operation.
+struct Saver<'a, S, F> {
+    client: &'a Client,
+    repository: &'a str,
+    sink: &'a mut S,
+    on_event: F,
+    /// Refuse indexes left after platform selection (docker archives)
+    single_platform: bool,
+    report: SaveReport,
+}
+
+impl<S: LayoutSink, F: FnMut(&SaveEvent)> Saver<'_, S, F> {
+    /// Saves the manifest at `reference` with everything it references.
+    fn save_manifest(
+        &mut self,
+        reference: &str,
+        platform: Option<&(String, String, Option<String>)>,
+    ) -> Result<Saved> {
+        let (bytes, _) = self.client.fetch_manifest(self.repository, reference)?;
+        let digest = digest_of(&bytes)?;
+        if reference.contains(':') && reference != digest.to_string() {
+            return Err(RexError::validation(format!(
+                "Manifest digest mismatch: expected {}, computed {}",
+                reference, digest
+            )));
+        }
+        let content = ManifestOrIndex::from_bytes(&bytes)?;
+
+        match &content {
+            ManifestOrIndex::Manifest(manifest) => {
+                for descriptor in std::iter::once(manifest.config()).chain(manifest.layers()) {
+                    if !is_foreign(descriptor) {
+                        self.save_blob(descriptor)?;
+                    }
+                }
+            }
+            ManifestOrIndex::Index(index) => {
+                if let Some(platform) = platform {
+                    let descriptor = select_platform(index.manifests(), platform)?;
+                    return self.save_manifest(descriptor.digest().as_ref(), None);
+                }
+                if self.single_platform {
+                    let platforms: Vec<String> = content
+                        .platforms()
+                        .iter()
+                        .map(|(platform, _)| {
+                            format!("{}/{}", platform.os(), platform.architecture())
+                        })
+                        .collect();
+                    return Err(RexError::validation(format!(
+                        "A docker archive holds a single platform; choose one of: {}",
+                        platforms.join(", ")
+                    )));
+                }
+                for descriptor in index.manifests() {
+                    if !self
+                        .sink
+                        .has_blob(&Digest::from_str(descriptor.digest().as_ref())?)
+                    {
+                        self.save_manifest(descriptor.digest().as_ref(), None)?;
+                    }
+                }
+            }
+        }
+
+        if !self.sink.has_blob(&digest) {
+            self.sink.put_bytes(&digest, &bytes)?;
+        }
+        self.report.manifests += 1;
+        self.emit(SaveEvent::ManifestWritten(digest.to_string()));
+
+        Ok(Saved {
+            digest,
+            media_type: manifest_media_type(&bytes)?,
+            size: bytes.len() as u64,
+            content,
+        })
+    }
+
+    /// Saves a config or layer blob, unless the layout has it.
+    fn save_blob(&mut self, descriptor: &Descriptor) -> Result<()> {
+        let digest = Digest::from_str(descriptor.digest().as_ref())?;
+        if self.sink.has_blob(&digest) {
+            self.report.blobs_skipped += 1;
+            self.emit(SaveEvent::BlobExists(digest.to_string()));
+            return Ok(());
+        }
+
+        self.sink
+            .put_blob(self.client, self.repository, &digest, descriptor.size())?;
+        self.report.blobs_written += 1;
+        self.report.bytes_written += descriptor.size();
+        self.emit(SaveEvent::BlobWritten {
+            digest: digest.to_string(),
+            size: descriptor.size(),
+        });
+        Ok(())
+    }
+
+    fn emit(&mut self, event: SaveEvent) {
+        (self.on_event)(&event);
+    }
+}
+
+/// Saves an image to an OCI layout directory or tarball, or to a
+/// `docker load` archive.
+///
+/// With [`ArchiveFormat::Oci`], an `output` ending in `.tar` gets a layout
+/// tarball; anything else is a layout directory, created if needed, to
+/// which the image is added. [`ArchiveFormat::DockerArchive`] always writes
+/// a tarball and needs a single-platform image (or a platform filter).
+///
+/// Tarballs are written to a `.partial` file renamed into place at the end,
+/// so a failed save leaves no archive behind.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `repository` - Repository name as the registry knows it (e.g., "library/alpine")
+/// * `reference` - Tag or digest to save
+/// * `options` - Format, platform filter and image name
+/// * `output` - Directory or tarball path
+/// * `on_event` - Called as each blob and manifest is written
+///
+/// # Returns
+///
+/// A [`SaveReport`] with the saved digest and transfer counts.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::oci_layout::{ArchiveFormat, SaveOptions, save_image};
+/// use std::path::Path;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("http://localhost:5000", None)?;
+/// let options = SaveOptions::new(ArchiveFormat::Oci).with_name("alpine:3.19");
+///
+/// let report = save_image(&client, "alpine", "3.19", &options, Path::new("alpine.tar"), |_| {})?;
+/// println!("Saved {} ({} bytes)", report.digest, report.bytes_written);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The image does not exist, or the platform filter matches nothing
+/// - A docker archive is asked for a multi-platform image without a platform
+/// - A blob or manifest fails digest validation
+/// - The output cannot be written
+pub fn save_image<F>(
+    client: &Client,
+    repository: &str,
+    reference: &str,
+    options: &SaveOptions,
+    output: &Path,
+    on_event: F,
+) -> Result<SaveReport>
+where
+    F: FnMut(&SaveEvent),
+{
+    let to_directory = options.format == ArchiveFormat::Oci
+        && output
+            .extension()
+            .is_none_or(|extension| extension != "tar");
+
+    if to_directory {
+        let mut layout = OciLayout::create(output)?;
+        let (saved, report) = save_into(
+            client,
+            repository,
+            reference,
+            options,
+            &mut layout,
+            on_event,
+        )?;
+        layout.add_to_index(index_entry(
+            &saved.digest,
+            &saved.media_type,
+            saved.size,
+            options.name.as_deref(),
+        )?)?;
+        return Ok(report);
+    }
+
+    let partial = PathBuf::from(format!("{}.partial", output.display()));
+    let result = save_archive(client, repository, reference, options, &partial, on_event).and_then(
+        |report| {
+            fs::rename(&partial, output)
+                .map_err(|e| {
+                    RexError::config_with_source("Failed to write archive", output.to_str(), e)
+                })
+                .map(|_| report)
+        },
+    );
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result
+}
+
+/// Writes a layout tarball (with Docker's manifest for docker archives).
+fn save_archive<F>(
+    client: &Client,
+    repository: &str,
+    reference: &str,
+    options: &SaveOptions,
+    path: &Path,
+    on_event: F,
+) -> Result<SaveReport>
+where
+    F: FnMut(&SaveEvent),
+{
+    let file = File::create(path)
+        .map_err(|e| RexError::config_with_source("Failed to create archive", path.to_str(), e))?;
+    let mut archive = LayoutArchive::new(io::BufWriter::new(file))?;
+
+    let (saved, report) = save_into(
+        client,
+        repository,
+        reference,
+        options,
+        &mut archive,
+        on_event,
+    )?;
+
+    let docker = match (options.format, &saved.content) {
+        (ArchiveFormat::DockerArchive, ManifestOrIndex::Manifest(manifest)) => {
+            Some(docker_manifest(manifest, options.name.as_deref()))
+        }
+        _ => None,
+    };
+
+    let index = build_index(vec![index_entry(
+        &saved.digest,
+        &saved.media_type,
+        saved.size,
+        options.name.as_deref(),
+    )?])?;
+    let writer = archive.finish(&index, docker.as_ref())?;
+    writer
+        .into_inner()
+        .map_err(|e| {
+            RexError::config_with_source("Failed to write archive", path.to_str(), e.into_error())
+        })?
+        .sync_all()
+        .map_err(|e| RexError::config_with_source("Failed to write archive", path.to_str(), e))?;
+
+    Ok(report)
+}
+
+/// Saves an image into a sink.
+fn save_into<S, F>(
+    client: &Client,
+    repository: &str,
+    reference: &str,
+    options: &SaveOptions,
+    sink: &mut S,
+    on_event: F,
+) -> Result<(Saved, SaveReport)>
+where
+    S: LayoutSink,
+    F: FnMut(&SaveEvent),
+{
+    let mut saver = Saver {
+        client,
+        repository,
+        sink,
+        on_event,
+        single_platform: options.format == ArchiveFormat::DockerArchive,
+        report: SaveReport::default(),
+    };
+    let saved = saver.save_manifest(reference, options.platform.as_ref())?;
+    saver.report.digest = saved.digest.to_string();
+    saver.report.media_type = saved.media_type.clone();
+    Ok((saved, saver.report))
+}
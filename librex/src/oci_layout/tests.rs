@@ -0,0 +1,297 @@
+use super::*;
+use crate::test_support::{
+    LAYER, index_json, manifest_json, serve_blob, serve_manifest, sha256_of,
+};
+use mockito::Server;
+use std::io::Read;
+
+/// Reads every file of a tarball into (path, content) pairs.
+fn tar_entries(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let mut archive = tar::Archive::new(File::open(path).unwrap());
+    archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            (path, content)
+        })
+        .collect()
+}
+
+fn blob_path(data: &[u8]) -> String {
+    format!("blobs/sha256/{}", &sha256_of(data)[7..])
+}
+
+#[test]
+fn test_save_image_to_directory() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let layer = b"layer content";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+    let _manifest = serve_manifest(&mut server, "library/alpine", "3.19", &manifest);
+    let config_get = serve_blob(&mut server, "library/alpine", config);
+    let layer_get = serve_blob(&mut server, "library/alpine", layer);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("alpine");
+    let options = SaveOptions::new(ArchiveFormat::Oci).with_name("alpine:3.19");
+    let mut events = Vec::new();
+
+    let report = save_image(
+        &client,
+        "library/alpine",
+        "3.19",
+        &options,
+        &output,
+        |event| events.push(event.clone()),
+    )
+    .unwrap();
+
+    config_get.assert();
+    layer_get.assert();
+    assert_eq!(
+        report,
+        SaveReport {
+            digest: sha256_of(&manifest),
+            media_type: "application/vnd.oci.image.manifest.v1+json".to_string(),
+            manifests: 1,
+            blobs_written: 2,
+            blobs_skipped: 0,
+            bytes_written: (config.len() + layer.len()) as u64,
+        }
+    );
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[2], SaveEvent::ManifestWritten(sha256_of(&manifest)));
+
+    assert_eq!(
+        fs::read_to_string(output.join(OCI_LAYOUT_FILE)).unwrap(),
+        r#"{"imageLayoutVersion":"1.0.0"}"#
+    );
+    assert_eq!(fs::read(output.join(blob_path(layer))).unwrap(), layer);
+    assert_eq!(
+        fs::read(output.join(blob_path(&manifest))).unwrap(),
+        manifest
+    );
+
+    let layout = OciLayout::open(&output).unwrap();
+    let index = layout.index().unwrap();
+    assert_eq!(index.manifests().len(), 1);
+    let entry = &index.manifests()[0];
+    assert_eq!(entry.digest().to_string(), sha256_of(&manifest));
+    let annotations = entry.annotations().as_ref().unwrap();
+    assert_eq!(annotations[REF_NAME_ANNOTATION], "3.19");
+    assert_eq!(annotations[IMAGE_NAME_ANNOTATION], "alpine:3.19");
+}
+
+#[test]
+fn test_save_image_adds_to_existing_layout() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let layer = b"shared layer";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+    let _first = serve_manifest(&mut server, "app", "1.4", &manifest);
+    let _second = serve_manifest(&mut server, "app", "1.5", &manifest);
+    // Downloaded once, skipped the second time
+    let layer_get = serve_blob(&mut server, "app", layer);
+    let _config = serve_blob(&mut server, "app", config);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("layout");
+
+    for tag in ["1.4", "1.5"] {
+        let options = SaveOptions::new(ArchiveFormat::Oci).with_name(&format!("app:{}", tag));
+        save_image(&client, "app", tag, &options, &output, |_| {}).unwrap();
+    }
+    // Saving a tag again replaces its entry
+    let options = SaveOptions::new(ArchiveFormat::Oci).with_name("app:1.5");
+    let report = save_image(&client, "app", "1.5", &options, &output, |_| {}).unwrap();
+
+    layer_get.assert();
+    assert_eq!(report.blobs_written, 0);
+    assert_eq!(report.blobs_skipped, 2);
+    let index = OciLayout::open(&output).unwrap().index().unwrap();
+    assert_eq!(index.manifests().len(), 2);
+}
+
+#[test]
+fn test_save_multi_platform_tarball() {
+    let mut server = Server::new();
+    let layer = b"common layer";
+    let amd64_config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let arm64_config = br#"{"architecture":"arm64","os":"linux"}"#;
+    let amd64 = manifest_json(amd64_config, &[(layer, LAYER)]);
+    let arm64 = manifest_json(arm64_config, &[(layer, LAYER)]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+
+    let _index = serve_manifest(&mut server, "app", "latest", &index);
+    let _amd64 = serve_manifest(&mut server, "app", &sha256_of(&amd64), &amd64);
+    let _arm64 = serve_manifest(&mut server, "app", &sha256_of(&arm64), &arm64);
+    let _amd64_config = serve_blob(&mut server, "app", amd64_config);
+    let _arm64_config = serve_blob(&mut server, "app", arm64_config);
+    let layer_get = serve_blob(&mut server, "app", layer);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("app.tar");
+
+    let report = save_image(
+        &client,
+        "app",
+        "latest",
+        &SaveOptions::new(ArchiveFormat::Oci),
+        &output,
+        |_| {},
+    )
+    .unwrap();
+
+    layer_get.assert();
+    assert_eq!(report.digest, sha256_of(&index));
+    assert_eq!(report.manifests, 3);
+    assert!(!dir.path().join("app.tar.partial").exists());
+
+    let entries = tar_entries(&output);
+    let paths: Vec<&str> = entries.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(&paths[..2], ["blobs/", "blobs/sha256/"]);
+    assert!(paths.contains(&blob_path(layer).as_str()));
+    assert!(paths.contains(&blob_path(&index).as_str()));
+    assert!(!paths.contains(&DOCKER_MANIFEST_FILE));
+    assert_eq!(
+        paths
+            .iter()
+            .filter(|path| path.starts_with("blobs/sha256/"))
+            .count(),
+        7
+    );
+
+    let (_, index_file) = entries.iter().find(|(path, _)| path == INDEX_FILE).unwrap();
+    let saved: ImageIndex = serde_json::from_slice(index_file).unwrap();
+    assert_eq!(saved.manifests()[0].digest().to_string(), sha256_of(&index));
+}
+
+#[test]
+fn test_save_docker_archive() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"arm64","os":"linux"}"#;
+    let layer = b"arm layer";
+    let amd64 = manifest_json(
+        br#"{"architecture":"amd64","os":"linux"}"#,
+        &[(b"amd64 layer", LAYER)],
+    );
+    let arm64 = manifest_json(config, &[(layer, LAYER)]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+
+    let _index = serve_manifest(&mut server, "app", "1.5", &index);
+    let _arm64 = serve_manifest(&mut server, "app", &sha256_of(&arm64), &arm64);
+    let _config = serve_blob(&mut server, "app", config);
+    let _layer = serve_blob(&mut server, "app", layer);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("app.tar");
+
+    // A docker archive holds one platform
+    let err = save_image(
+        &client,
+        "app",
+        "1.5",
+        &SaveOptions::new(ArchiveFormat::DockerArchive),
+        &output,
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("linux/amd64, linux/arm64"));
+    assert!(!output.exists());
+    assert!(!dir.path().join("app.tar.partial").exists());
+
+    let options = SaveOptions::new(ArchiveFormat::DockerArchive)
+        .with_platform("linux", "arm64", None)
+        .with_name("app:1.5");
+    let report = save_image(&client, "app", "1.5", &options, &output, |_| {}).unwrap();
+
+    assert_eq!(report.digest, sha256_of(&arm64));
+    let entries = tar_entries(&output);
+    let (_, docker) = entries
+        .iter()
+        .find(|(path, _)| path == DOCKER_MANIFEST_FILE)
+        .unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Value>(docker).unwrap(),
+        json!([{
+            "Config": blob_path(config),
+            "RepoTags": ["app:1.5"],
+            "Layers": [blob_path(layer)],
+        }])
+    );
+    assert!(
+        entries
+            .iter()
+            .any(|(path, content)| *path == blob_path(layer) && content == layer)
+    );
+}
+
+#[test]
+fn test_save_rejects_corrupt_blob() {
+    let mut server = Server::new();
+    let config = br#"{"architecture":"amd64","os":"linux"}"#;
+    let layer = b"layer content";
+    let manifest = manifest_json(config, &[(layer, LAYER)]);
+    let _manifest = serve_manifest(&mut server, "app", "1.0", &manifest);
+    let _config = serve_blob(&mut server, "app", config);
+    let _layer = server
+        .mock(
+            "GET",
+            format!("/v2/app/blobs/{}", sha256_of(layer)).as_str(),
+        )
+        .with_status(200)
+        .with_body(b"layer CONTENT")
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let options = SaveOptions::new(ArchiveFormat::Oci);
+    let output = dir.path().join("app.tar");
+    let err = save_image(&client, "app", "1.0", &options, &output, |_| {}).unwrap_err();
+    assert!(matches!(err, RexError::Validation { .. }), "{}", err);
+    assert!(!output.exists());
+
+    let output = dir.path().join("layout");
+    save_image(&client, "app", "1.0", &options, &output, |_| {}).unwrap_err();
+    assert!(!output.join(blob_path(layer)).exists());
+}
+
+#[test]
+fn test_layout_open_and_create() {
+    let dir = tempfile::tempdir().unwrap();
+
+    assert!(OciLayout::open(dir.path()).is_err());
+    fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+    assert!(OciLayout::create(dir.path()).is_err());
+
+    let layout = OciLayout::create(dir.path().join("layout")).unwrap();
+    assert!(layout.index().unwrap().manifests().is_empty());
+
+    fs::write(
+        layout.root().join(OCI_LAYOUT_FILE),
+        r#"{"imageLayoutVersion":"2.0.0"}"#,
+    )
+    .unwrap();
+    let err = OciLayout::open(layout.root()).unwrap_err();
+    assert!(err.to_string().contains("2.0.0"));
+}
+
+#[test]
+fn test_archive_format_parsing() {
+    assert_eq!("oci".parse::<ArchiveFormat>().unwrap(), ArchiveFormat::Oci);
+    assert_eq!(
+        "docker-archive".parse::<ArchiveFormat>().unwrap(),
+        ArchiveFormat::DockerArchive
+    );
+    assert!("zip".parse::<ArchiveFormat>().is_err());
+    assert_eq!(ArchiveFormat::DockerArchive.to_string(), "docker-archive");
+}
@@ -6,11 +6,12 @@
 //! seamless API for registry interactions.
 
 use crate::auth::Credentials;
+use crate::blob::BlobStore;
 use crate::cache::{Cache, CacheType};
 use crate::client::Client;
 use crate::digest::Digest;
 use crate::error::Result;
-use crate::oci::ManifestOrIndex;
+use crate::oci::{Descriptor, ManifestOrIndex};
 use crate::reference::Reference;
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
@@ -44,6 +45,8 @@ pub struct Registry {
     credentials: Option<Credentials>,
     /// Docker Hub compatibility mode (strips auto-added "library/" prefix when false).
     dockerhub_compat: bool,
+    /// Optional content-addressed store for downloaded blobs.
+    blob_store: Option<BlobStore>,
 }
 
 impl Registry {
@@ -76,6 +79,57 @@ impl Registry {
             cache,
             credentials,
             dockerhub_compat,
+            blob_store: None,
+        }
+    }
+
+    /// Stores downloaded blobs in a content-addressed blob store.
+    ///
+    /// With a blob store, [`Registry::get_blob`] keeps blobs as plain files in
+    /// the store instead of serializing them into the cache, and
+    /// [`Registry::download_blob`] becomes available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::blob::BlobStore;
+    /// use librex::client::Client;
+    /// use librex::registry::Registry;
+    ///
+    /// let client = Client::new("http://localhost:5000", None).unwrap();
+    /// let registry = Registry::new(client, None, None, false)
+    ///     .with_blob_store(BlobStore::new("/tmp/rex-blobs"));
+    /// assert!(registry.blob_store().is_some());
+    /// ```
+    pub fn with_blob_store(mut self, store: BlobStore) -> Self {
+        self.blob_store = Some(store);
+        self
+    }
+
+    /// Returns the blob store, if one is configured.
+    pub fn blob_store(&self) -> Option<&BlobStore> {
+        self.blob_store.as_ref()
+    }
+
+    /// Returns the HTTP client used for registry communication.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Returns true if Docker Hub compatibility mode is enabled.
+    pub fn dockerhub_compat(&self) -> bool {
+        self.dockerhub_compat
+    }
+
+    /// Drops the cached manifest of a tag and the repository's tag list.
+    ///
+    /// Called whenever a tag is created, moved or deleted.
+    pub(crate) fn invalidate_tag(&mut self, repository: &str, tag: &str) {
+        if let Some(cache) = &mut self.cache {
+            let tag_cache_key = format!("{}/tags/{}/manifest", repository, tag);
+            let _ = cache.delete(&tag_cache_key);
+            let tags_cache_key = format!("{}/_tags", repository);
+            let _ = cache.delete(&tags_cache_key);
         }
     }
 
@@ -289,6 +343,183 @@ impl Registry {
         Ok((manifest_or_index, digest))
     }
 
+    /// Resolves a reference to its manifest digest.
+    ///
+    /// Digest references resolve to themselves without contacting the
+    /// registry. Tags are resolved with a HEAD request, which avoids
+    /// downloading the manifest and does not count against Docker Hub's pull
+    /// rate limit. If the registry does not return a `Docker-Content-Digest`
+    /// header, the manifest is fetched and its digest computed instead.
+    ///
+    /// Tag resolutions are not cached, since tags can move at any time.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The image reference (tag or digest)
+    ///
+    /// # Returns
+    ///
+    /// The manifest digest (e.g., "sha256:abc123...").
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use librex::client::Client;
+    /// # use librex::reference::Reference;
+    /// # use librex::registry::Registry;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut registry = Registry::new(client, None, None, false);
+    /// let reference = Reference::from_str("alpine:latest")?;
+    ///
+    /// let digest = registry.resolve_digest(&reference)?;
+    /// println!("alpine:latest is {}", digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest does not exist or cannot be fetched.
+    pub fn resolve_digest(&mut self, reference: &Reference) -> Result<String> {
+        if let Some(digest) = reference.digest() {
+            return Ok(digest.to_string());
+        }
+
+        let info = self.client.head_manifest(
+            reference.repository_for_registry(self.dockerhub_compat),
+            reference.tag().unwrap_or("latest"),
+        )?;
+
+        match info.digest {
+            Some(digest) => Ok(digest),
+            None => self.get_manifest(reference).map(|(_, digest)| digest),
+        }
+    }
+
+    /// Lists the artifacts (signatures, SBOMs, attestations) attached to an image.
+    ///
+    /// The reference is resolved to a digest first; referrers are always
+    /// fetched from the registry, since they can be attached at any time.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The image reference (tag or digest)
+    /// * `artifact_type` - Only return referrers of this artifact type
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use librex::client::Client;
+    /// # use librex::reference::Reference;
+    /// # use librex::registry::Registry;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut registry = Registry::new(client, None, None, false);
+    /// let reference = Reference::from_str("myapp:v1")?;
+    ///
+    /// for referrer in registry.list_referrers(&reference, None)? {
+    ///     println!("{}", referrer.digest());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image does not exist or the referrers cannot
+    /// be fetched.
+    pub fn list_referrers(
+        &mut self,
+        reference: &Reference,
+        artifact_type: Option<&str>,
+    ) -> Result<Vec<Descriptor>> {
+        let digest = self.resolve_digest(reference)?;
+        self.client.fetch_referrers(
+            reference.repository_for_registry(self.dockerhub_compat),
+            &digest,
+            artifact_type,
+        )
+    }
+
+    /// Tags an existing manifest without pulling its layers.
+    ///
+    /// The raw manifest bytes of `source` are fetched from the registry and
+    /// uploaded unchanged under `new_tag` in the same repository, so the new
+    /// tag points at exactly the same digest. Only the manifest is
+    /// transferred; the layers are already in the repository.
+    ///
+    /// The manifest is always fetched from the registry rather than the
+    /// cache, so a stale cached tag is never promoted.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The image to tag (tag or digest reference)
+    /// * `new_tag` - The tag to create or move
+    ///
+    /// # Returns
+    ///
+    /// The manifest digest the new tag points to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use librex::client::Client;
+    /// # use librex::reference::Reference;
+    /// # use librex::registry::Registry;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let mut registry = Registry::new(client, None, None, false);
+    /// let source = Reference::from_str("myapp:sha-abc123")?;
+    ///
+    /// let digest = registry.tag(&source, "prod")?;
+    /// println!("myapp:prod -> {}", digest);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `new_tag` is not a valid tag
+    /// - The source manifest does not exist or cannot be fetched
+    /// - Push access is denied or the registry rejects the manifest
+    pub fn tag(&mut self, source: &Reference, new_tag: &str) -> Result<String> {
+        use std::str::FromStr;
+
+        let repository = source.repository_for_registry(self.dockerhub_compat);
+
+        // Let the reference parser enforce the tag grammar
+        let valid_tag = Reference::from_str(&format!("{}:{}", source.repository(), new_tag))
+            .is_ok_and(|r| r.tag() == Some(new_tag) && r.digest().is_none());
+        if !valid_tag {
+            return Err(crate::error::RexError::validation(format!(
+                "Invalid tag: '{}'",
+                new_tag
+            )));
+        }
+
+        let source_reference = source.digest().or_else(|| source.tag()).unwrap_or("latest");
+        let (manifest_bytes, _) = self.client.fetch_manifest(repository, source_reference)?;
+        let media_type = crate::oci::manifest_media_type(&manifest_bytes)?;
+
+        let digest = self
+            .client
+            .put_manifest(repository, new_tag, &manifest_bytes, &media_type)?;
+
+        // The tag may have pointed elsewhere, and the tag list may have grown
+        let repository = repository.to_string();
+        self.invalidate_tag(&repository, new_tag);
+
+        Ok(digest)
+    }
+
     /// Retrieves a blob (layer or config) for a reference.
     ///
     /// This is a convenience method that extracts the repository name from a Reference
@@ -315,7 +546,12 @@ impl Registry {
     ///
     /// Blobs are immutable and content-addressed by digest. They are cached
     /// globally (independent of repository/registry) since the same digest
-    /// always represents the same content.
+    /// always represents the same content. With a blob store the blob is kept
+    /// as a file in the store; otherwise it goes through the cache.
+    ///
+    /// This loads the whole blob into memory and is meant for small blobs such
+    /// as image configs. Use [`Registry::download_blob`] or
+    /// [`Registry::fetch_blob_to`] for layers.
     ///
     /// # Arguments
     ///
@@ -345,6 +581,20 @@ impl Registry {
     /// # }
     /// ```
     pub fn get_blob(&mut self, repository: &str, digest: &Digest) -> Result<Vec<u8>> {
+        if let Some(store) = &self.blob_store {
+            if let Some(blob_bytes) = store.read(digest)? {
+                return Ok(blob_bytes);
+            }
+            let path = self.download_blob(repository, digest, |_, _| {})?;
+            return std::fs::read(&path).map_err(|e| {
+                crate::error::RexError::config_with_source(
+                    "Failed to read stored blob",
+                    path.to_str(),
+                    e,
+                )
+            });
+        }
+
         // Cache key is global (not repository-specific) since blobs are content-addressed
         let cache_key = format!("blobs/{}", digest);
 
@@ -366,6 +616,125 @@ impl Registry {
         Ok(blob_bytes)
     }
 
+    /// Streams a blob into a writer without buffering or caching it.
+    ///
+    /// See [`Client::fetch_blob_to`] for details on verification and progress
+    /// reporting.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest of the blob
+    /// * `writer` - Destination for the blob content
+    /// * `progress` - Callback receiving `(bytes_written, total_size)`
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    pub fn fetch_blob_to<W, F>(
+        &self,
+        repository: &str,
+        digest: &Digest,
+        writer: &mut W,
+        progress: F,
+    ) -> Result<u64>
+    where
+        W: std::io::Write + ?Sized,
+        F: FnMut(u64, Option<u64>),
+    {
+        self.client
+            .fetch_blob_to(repository, &digest.to_string(), writer, progress)
+    }
+
+    /// Downloads a blob into the blob store and returns its path.
+    ///
+    /// The blob is streamed to disk and verified while downloading. A blob that
+    /// is already in the store is not downloaded again. An interrupted download
+    /// leaves a `.partial` file behind, and the next call resumes from it with
+    /// an HTTP range request.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest of the blob
+    /// * `progress` - Callback receiving `(bytes_written, total_size)`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no blob store is configured, or if the blob cannot
+    /// be downloaded, verified or stored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use librex::blob::BlobStore;
+    /// # use librex::client::Client;
+    /// # use librex::digest::Digest;
+    /// # use librex::registry::Registry;
+    /// # use std::str::FromStr;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("http://localhost:5000", None)?;
+    /// let registry = Registry::new(client, None, None, false)
+    ///     .with_blob_store(BlobStore::new("/tmp/rex-blobs"));
+    /// let digest = Digest::from_str("sha256:abc123...")?;
+    ///
+    /// let path = registry.download_blob("alpine", &digest, |done, total| {
+    ///     eprintln!("{} / {:?} bytes", done, total);
+    /// })?;
+    /// println!("Layer stored at {}", path.display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_blob<F>(
+        &self,
+        repository: &str,
+        digest: &Digest,
+        progress: F,
+    ) -> Result<std::path::PathBuf>
+    where
+        F: FnMut(u64, Option<u64>),
+    {
+        let store = self
+            .blob_store
+            .as_ref()
+            .ok_or_else(|| crate::error::RexError::config("No blob store configured", None))?;
+
+        store.resume_with(digest, |file| {
+            self.client
+                .fetch_blob_resume(repository, &digest.to_string(), file, progress)
+        })
+    }
+
+    /// Opens a blob for streaming.
+    ///
+    /// A blob already in the blob store is read from there. Otherwise it is
+    /// streamed from the registry without being stored, so that browsing the
+    /// layers of many images does not fill the store, and its digest is
+    /// verified when the end of the stream is reached: callers must read to
+    /// the end to get that check.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest of the blob
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob cannot be fetched or opened.
+    pub fn open_blob(&self, repository: &str, digest: &Digest) -> Result<Box<dyn std::io::Read>> {
+        if let Some(store) = &self.blob_store
+            && let Some(file) = store.open(digest)?
+        {
+            return Ok(Box::new(file));
+        }
+
+        let reader = self
+            .client
+            .fetch_blob_reader(repository, &digest.to_string())?;
+        Ok(Box::new(reader))
+    }
+
     /// Checks if the registry is accessible and supports the OCI Distribution Specification.
     ///
     /// This performs a version check by calling the `/v2/` endpoint.
@@ -453,13 +822,13 @@ impl Registry {
     /// - The registry does not support manifest deletion
     /// - Network or server errors occur
     pub fn delete_tag(&mut self, repository: &str, tag: &str) -> Result<()> {
-        // First, get the manifest to retrieve its digest
+        // First, resolve the tag to its digest
         // Construct a reference from repository and tag
         use std::str::FromStr;
         let ref_str = format!("{}:{}", repository, tag);
         let reference = Reference::from_str(&ref_str)?;
 
-        let (_manifest, digest) = self.get_manifest(&reference)?;
+        let digest = self.resolve_digest(&reference)?;
 
         // Invalidate tag-based manifest cache entry
         if let Some(cache) = &mut self.cache {
@@ -902,3 +902,464 @@ fn test_get_manifest_by_digest_strips_library_when_dockerhub_compat_false() {
         }
     }
 }
+
+#[test]
+fn test_get_blob_with_blob_store() {
+    use crate::blob::BlobStore;
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    let mut server = mockito::Server::new();
+    let blob_content = b"test blob content";
+    let digest = format!("sha256:{:x}", Sha256::digest(blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body(blob_content)
+        .expect(1)
+        .create();
+
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false).with_blob_store(store.clone());
+
+    let digest_obj = Digest::from_str(&digest).unwrap();
+    assert_eq!(
+        registry.get_blob("alpine", &digest_obj).unwrap(),
+        blob_content
+    );
+    assert_eq!(
+        registry.get_blob("alpine", &digest_obj).unwrap(),
+        blob_content
+    );
+
+    mock.assert();
+    // The blob is a plain file in the store, not a cache entry
+    assert_eq!(
+        std::fs::read(store.path(&digest_obj)).unwrap(),
+        blob_content
+    );
+}
+
+#[test]
+fn test_open_blob_streams_without_storing() {
+    use crate::blob::BlobStore;
+    use crate::test_support::{digest_of, sha256_of};
+    use std::io::{Read, Write};
+    use tempfile::TempDir;
+
+    let mut server = mockito::Server::new();
+    let streamed = b"layer content";
+    let stored = b"stored layer content";
+
+    let mock = server
+        .mock(
+            "GET",
+            format!("/v2/alpine/blobs/{}", sha256_of(streamed)).as_str(),
+        )
+        .with_status(200)
+        .with_body(streamed)
+        .expect(1)
+        .create();
+
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    store
+        .insert_with(&digest_of(stored), |file| {
+            file.write_all(stored).unwrap();
+            Ok(stored.len() as u64)
+        })
+        .unwrap();
+    let client = Client::new(&server.url(), None).unwrap();
+    let registry = Registry::new(client, None, None, false).with_blob_store(store.clone());
+
+    let mut content = Vec::new();
+    registry
+        .open_blob("alpine", &digest_of(streamed))
+        .unwrap()
+        .read_to_end(&mut content)
+        .unwrap();
+    assert_eq!(content, streamed);
+    assert!(!store.contains(&digest_of(streamed)));
+
+    // Blobs already in the store are read from it
+    let mut content = Vec::new();
+    registry
+        .open_blob("alpine", &digest_of(stored))
+        .unwrap()
+        .read_to_end(&mut content)
+        .unwrap();
+    assert_eq!(content, stored);
+    mock.assert();
+}
+
+#[test]
+fn test_download_blob() {
+    use crate::blob::BlobStore;
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    let mut server = mockito::Server::new();
+    let blob_content = vec![1u8; 200_000];
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body(&blob_content)
+        .expect(1)
+        .create();
+
+    let temp_dir = TempDir::new().unwrap();
+    let client = Client::new(&server.url(), None).unwrap();
+    let registry =
+        Registry::new(client, None, None, false).with_blob_store(BlobStore::new(temp_dir.path()));
+
+    let digest_obj = Digest::from_str(&digest).unwrap();
+    let mut last_progress = 0;
+    let path = registry
+        .download_blob("alpine", &digest_obj, |done, _| last_progress = done)
+        .unwrap();
+
+    // Already stored: no second request and no progress
+    let again = registry
+        .download_blob("alpine", &digest_obj, |_, _| panic!("no download expected"))
+        .unwrap();
+
+    mock.assert();
+    assert_eq!(path, again);
+    assert_eq!(last_progress, blob_content.len() as u64);
+    assert_eq!(std::fs::read(&path).unwrap(), blob_content);
+}
+
+#[test]
+fn test_download_blob_digest_mismatch_discards_file() {
+    use crate::blob::BlobStore;
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    let mut server = mockito::Server::new();
+    let digest = "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2";
+
+    let _mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .with_status(200)
+        .with_body("wrong content")
+        .create();
+
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let client = Client::new(&server.url(), None).unwrap();
+    let registry = Registry::new(client, None, None, false).with_blob_store(store.clone());
+
+    let digest_obj = Digest::from_str(digest).unwrap();
+    let result = registry.download_blob("alpine", &digest_obj, |_, _| {});
+
+    assert!(matches!(
+        result,
+        Err(crate::error::RexError::Validation { .. })
+    ));
+    assert!(!store.contains(&digest_obj));
+    assert!(!store.partial_path(&digest_obj).exists());
+}
+
+#[test]
+fn test_download_blob_without_blob_store() {
+    use std::str::FromStr;
+
+    let client = Client::new("http://localhost:5000", None).unwrap();
+    let registry = Registry::new(client, None, None, false);
+    let digest =
+        Digest::from_str("sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2")
+            .unwrap();
+
+    let result = registry.download_blob("alpine", &digest, |_, _| {});
+    assert!(matches!(result, Err(crate::error::RexError::Config { .. })));
+}
+
+#[test]
+fn test_download_blob_resumes_partial_file() {
+    use crate::blob::BlobStore;
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::str::FromStr;
+    use tempfile::TempDir;
+
+    let mut server = mockito::Server::new();
+    let blob_content = vec![3u8; 100_000];
+    let digest = format!("sha256:{:x}", Sha256::digest(&blob_content));
+
+    let mock = server
+        .mock("GET", format!("/v2/alpine/blobs/{}", digest).as_str())
+        .match_header("Range", "bytes=40000-")
+        .with_status(206)
+        .with_header("Content-Range", "bytes 40000-99999/100000")
+        .with_body(&blob_content[40_000..])
+        .create();
+
+    let temp_dir = TempDir::new().unwrap();
+    let store = BlobStore::new(temp_dir.path());
+    let digest_obj = Digest::from_str(&digest).unwrap();
+
+    // Left behind by an interrupted pull
+    let partial = store.partial_path(&digest_obj);
+    std::fs::create_dir_all(partial.parent().unwrap()).unwrap();
+    std::fs::write(&partial, &blob_content[..40_000]).unwrap();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let registry = Registry::new(client, None, None, false).with_blob_store(store.clone());
+    let path = registry
+        .download_blob("alpine", &digest_obj, |_, _| {})
+        .unwrap();
+
+    mock.assert();
+    assert!(!partial.exists());
+    assert_eq!(std::fs::read(path).unwrap(), blob_content);
+}
+
+#[test]
+fn test_resolve_digest_uses_head_request() {
+    use std::str::FromStr;
+
+    let mut server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let head = server
+        .mock("HEAD", "/v2/alpine/manifests/3.19")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .create();
+    let get = server
+        .mock("GET", "/v2/alpine/manifests/3.19")
+        .expect(0)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let reference = Reference::from_str("alpine:3.19").unwrap();
+
+    assert_eq!(registry.resolve_digest(&reference).unwrap(), digest);
+    head.assert();
+    get.assert();
+}
+
+#[test]
+fn test_resolve_digest_falls_back_to_get() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::str::FromStr;
+
+    let mut server = mockito::Server::new();
+    let manifest = r#"{
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": "application/vnd.oci.image.config.v1+json",
+            "digest": "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+            "size": 2
+        },
+        "layers": []
+    }"#;
+    let expected = format!("sha256:{:x}", Sha256::digest(manifest.as_bytes()));
+
+    let _head = server
+        .mock("HEAD", "/v2/alpine/manifests/latest")
+        .with_status(200)
+        .create();
+    let get = server
+        .mock("GET", "/v2/alpine/manifests/latest")
+        .with_status(200)
+        .with_body(manifest)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let reference = Reference::from_str("alpine:latest").unwrap();
+
+    assert_eq!(registry.resolve_digest(&reference).unwrap(), expected);
+    get.assert();
+}
+
+#[test]
+fn test_resolve_digest_for_digest_reference() {
+    use std::str::FromStr;
+
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+    let client = Client::new("http://localhost:1", None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let reference = Reference::from_str(&format!("alpine@{}", digest)).unwrap();
+
+    // No request is made for digest references
+    assert_eq!(registry.resolve_digest(&reference).unwrap(), digest);
+}
+
+#[test]
+fn test_delete_tag_resolves_with_head() {
+    let mut server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let head = server
+        .mock("HEAD", "/v2/alpine/manifests/old")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .create();
+    let get = server
+        .mock("GET", "/v2/alpine/manifests/old")
+        .expect(0)
+        .create();
+    let delete = server
+        .mock(
+            "DELETE",
+            format!("/v2/alpine/manifests/{}", digest).as_str(),
+        )
+        .with_status(202)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+
+    registry.delete_tag("alpine", "old").unwrap();
+    head.assert();
+    get.assert();
+    delete.assert();
+}
+
+#[test]
+fn test_tag_pushes_identical_manifest_bytes() {
+    use sha2::{Digest as Sha2Digest, Sha256};
+    use std::str::FromStr;
+
+    let mut server = mockito::Server::new();
+    // Unusual formatting must survive untouched to keep the digest
+    let manifest = "{\n  \"schemaVersion\": 2,\n  \"mediaType\": \"application/vnd.docker.distribution.manifest.v2+json\",\n  \"config\": {\"mediaType\": \"application/vnd.docker.container.image.v1+json\", \"size\": 2, \"digest\": \"sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a\"},\n  \"layers\": []\n}";
+    let digest = format!("sha256:{:x}", Sha256::digest(manifest.as_bytes()));
+
+    let get = server
+        .mock("GET", "/v2/myapp/manifests/sha-abc")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", &digest)
+        .with_body(manifest)
+        .create();
+    let put = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .match_header(
+            "Content-Type",
+            "application/vnd.docker.distribution.manifest.v2+json",
+        )
+        .match_body(manifest)
+        .with_status(201)
+        .with_header("Docker-Content-Digest", &digest)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let source = Reference::from_str("myapp:sha-abc").unwrap();
+
+    let result = registry.tag(&source, "prod").unwrap();
+
+    get.assert();
+    put.assert();
+    assert_eq!(result, digest);
+}
+
+#[test]
+fn test_tag_invalidates_cached_tag_entries() {
+    use std::str::FromStr;
+
+    let mut server = mockito::Server::new();
+    let manifest = r#"{"schemaVersion": 2, "manifests": []}"#;
+
+    let _get = server
+        .mock("GET", "/v2/myapp/manifests/v1")
+        .with_status(200)
+        .with_body(manifest)
+        .create();
+    let _put = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .match_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_status(201)
+        .create();
+
+    let temp_dir = tempdir().unwrap();
+    let mut cache = Cache::new(
+        temp_dir.path().to_path_buf(),
+        CacheTtl::default(),
+        NonZeroUsize::new(100).unwrap(),
+    );
+    let tags_key = "myapp/_tags";
+    let tag_key = "myapp/tags/prod/manifest";
+    cache
+        .set(tags_key, &vec!["v1".to_string()], CacheType::Tags)
+        .unwrap();
+    cache
+        .set(tag_key, &b"old".to_vec(), CacheType::Manifest)
+        .unwrap();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, Some(cache), None, false);
+    let source = Reference::from_str("myapp:v1").unwrap();
+    registry.tag(&source, "prod").unwrap();
+
+    let cache = registry.cache.as_mut().unwrap();
+    assert!(cache.get::<Vec<String>>(tags_key).unwrap().is_none());
+    assert!(cache.get::<Vec<u8>>(tag_key).unwrap().is_none());
+}
+
+#[test]
+fn test_tag_rejects_invalid_tag() {
+    use std::str::FromStr;
+
+    let client = Client::new("http://localhost:1", None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let source = Reference::from_str("myapp:v1").unwrap();
+
+    for tag in ["", "-leading-dash", "has space", "with@digest", "a/b"] {
+        let result = registry.tag(&source, tag);
+        assert!(
+            matches!(result, Err(crate::error::RexError::Validation { .. })),
+            "tag {:?} should be rejected",
+            tag
+        );
+    }
+}
+
+#[test]
+fn test_list_referrers_resolves_tag() {
+    use std::str::FromStr;
+
+    let mut server = mockito::Server::new();
+    let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+    let _head = server
+        .mock("HEAD", "/v2/myapp/manifests/v1")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .create();
+    let referrers = server
+        .mock("GET", format!("/v2/myapp/referrers/{}", digest).as_str())
+        .with_status(200)
+        .with_body(
+            r#"{
+                "schemaVersion": 2,
+                "mediaType": "application/vnd.oci.image.index.v1+json",
+                "manifests": [{
+                    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                    "digest": "sha256:4abcf20661432fb2d719b4568d94db3b6cf9b44bf2a3e1c2c6d0c89fd9e6e0b2",
+                    "size": 512,
+                    "artifactType": "application/spdx+json"
+                }]
+            }"#,
+        )
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut registry = Registry::new(client, None, None, false);
+    let reference = Reference::from_str("myapp:v1").unwrap();
+
+    let result = registry.list_referrers(&reference, None).unwrap();
+
+    referrers.assert();
+    assert_eq!(result.len(), 1);
+}
@@ -36,17 +36,29 @@
 //! ```
 
 use crate::auth::Credentials;
+use crate::blob::BlobStore;
 use crate::cache::{Cache, CacheTtl};
-use crate::client::Client;
+use crate::client::{Client, ClientConfig};
+use crate::copy::{self, CopyEndpoint, CopyEvent, CopyOptions, CopyReport};
+use crate::diff::{self, FileChange};
 use crate::digest::Digest;
-use crate::error::Result;
-use crate::oci::ManifestOrIndex;
+use crate::error::{Result, RexError};
+use crate::gc::{self, GcDeletion, GcEvent, GcReport};
+use crate::layer::{self, ExtractReport, FileEntry};
+use crate::oci::{Descriptor, ImageManifest, ManifestOrIndex};
+use crate::oci_layout::{
+    self, LoadEvent, LoadOptions, LoadReport, SaveEvent, SaveOptions, SaveReport,
+};
 use crate::reference::Reference;
 use crate::registry::Registry;
+use crate::sbom::{self, Sbom};
 use crate::search::{SearchResult, search_images, search_repositories, search_tags};
+use crate::signature::{self, PublicKey, Signature, SignatureCheck};
+use crate::usage::{self, UsageEvent, UsageReport};
+use crate::zot::{self, CveReport, GlobalSearchResult, ImageSummary, RegistryCapabilities};
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 /// High-level interface for interacting with OCI registries.
@@ -117,6 +129,8 @@ pub struct Rex {
     cached_repositories: Option<Vec<String>>,
     /// Cached tags per repository for search operations.
     cached_tags: HashMap<String, Vec<String>>,
+    /// Extensions discovered on the registry.
+    capabilities: Option<RegistryCapabilities>,
 }
 
 impl Rex {
@@ -153,6 +167,7 @@ impl Rex {
             registry_url: registry_url.to_string(),
             cached_repositories: None,
             cached_tags: HashMap::new(),
+            capabilities: None,
         })
     }
 
@@ -201,6 +216,45 @@ impl Rex {
         self.registry.check_version()
     }
 
+    /// Discover the extensions the registry offers (Zot search, CVE, ...).
+    ///
+    /// The result is kept for the lifetime of this instance. Registries
+    /// without extension discovery (plain Distribution) report no
+    /// capabilities rather than an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let capabilities = rex.capabilities()?;
+    ///     if capabilities.search {
+    ///         println!("Zot search is available");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The registry is unreachable
+    /// - Authentication is required but not provided
+    pub fn capabilities(&mut self) -> Result<RegistryCapabilities> {
+        if let Some(capabilities) = &self.capabilities {
+            return Ok(capabilities.clone());
+        }
+
+        let capabilities = zot::discover(self.registry.client())?;
+        self.capabilities = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
     /// Set credentials for authenticated requests.
     ///
     /// # Arguments
@@ -226,6 +280,8 @@ impl Rex {
     /// ```
     pub fn login(&mut self, credentials: Credentials) {
         self.registry.set_credentials(credentials);
+        // Extensions may only be visible to authenticated users
+        self.capabilities = None;
     }
 
     /// Clear credentials and switch to anonymous access.
@@ -244,6 +300,7 @@ impl Rex {
     /// ```
     pub fn logout(&mut self) {
         self.registry.clear_credentials();
+        self.capabilities = None;
     }
 
     /// List all repositories in the registry.
@@ -269,24 +326,1068 @@ impl Rex {
     ///         println!("{}", repo);
     ///     }
     ///
-    ///     Ok(())
-    /// }
-    /// ```
-    pub fn list_repositories(&mut self) -> Result<Vec<String>> {
-        let repos = self.registry.list_repositories()?;
-        self.cached_repositories = Some(repos.clone());
-        Ok(repos)
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_repositories(&mut self) -> Result<Vec<String>> {
+        let repos = self.registry.list_repositories()?;
+        self.cached_repositories = Some(repos.clone());
+        Ok(repos)
+    }
+
+    /// List all tags for a specific repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name (e.g., "alpine")
+    ///
+    /// # Returns
+    ///
+    /// A vector of tag names for the repository.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let tags = rex.list_tags("alpine")?;
+    ///     for tag in tags {
+    ///         println!("{}", tag);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_tags(&mut self, repository: &str) -> Result<Vec<String>> {
+        let tags = self.registry.list_tags(repository)?;
+        self.cached_tags
+            .insert(repository.to_string(), tags.clone());
+        Ok(tags)
+    }
+
+    /// Get the manifest or index for a specific image.
+    ///
+    /// This method automatically detects whether the image is a single-platform
+    /// manifest or a multi-platform index and returns the appropriate type.
+    ///
+    /// For single-platform images, you can extract the manifest using `.as_manifest()`
+    /// or `.into_manifest()`. For multi-platform images, use `.as_index()` or
+    /// `.into_index()` to access the platform list.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "alpine:latest" or "alpine@sha256:...")
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing:
+    /// - `ManifestOrIndex` enum (either a single-platform manifest or multi-platform index)
+    /// - The manifest digest as returned by the registry
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::{Rex, ManifestOrIndex};
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (manifest_or_index, digest) = rex.get_manifest("alpine:latest")?;
+    ///     println!("Digest: {}", digest);
+    ///     match manifest_or_index {
+    ///         ManifestOrIndex::Manifest(manifest) => {
+    ///             println!("Single-platform: {} layers", manifest.layers().len());
+    ///         }
+    ///         ManifestOrIndex::Index(index) => {
+    ///             println!("Multi-platform: {} platforms", index.manifests().len());
+    ///         }
+    ///     }
+    ///
+    ///     // Or use helper methods:
+    ///     let (manifest_or_index, _) = rex.get_manifest("alpine:latest")?;
+    ///     if let Some(manifest) = manifest_or_index.as_manifest() {
+    ///         println!("Layers: {}", manifest.layers().len());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn get_manifest(&mut self, image: &str) -> Result<(ManifestOrIndex, String)> {
+        let reference = image.parse::<Reference>()?;
+        self.registry.get_manifest(&reference)
+    }
+
+    /// Resolve an image reference to its manifest digest.
+    ///
+    /// Tags are resolved with a HEAD request, so the manifest is not
+    /// downloaded and Docker Hub does not count the request as a pull. Digest
+    /// references are returned as-is.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The image reference (e.g., "alpine:latest" or "alpine@sha256:...")
+    ///
+    /// # Returns
+    ///
+    /// The manifest digest (e.g., "sha256:abc123...").
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let digest = rex.resolve_digest("alpine:3.19")?;
+    ///     println!("alpine:3.19 -> {}", digest);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The image does not exist
+    /// - Authentication is required but not provided
+    pub fn resolve_digest(&mut self, reference: &str) -> Result<String> {
+        let reference = reference.parse::<Reference>()?;
+        self.registry.resolve_digest(&reference)
+    }
+
+    /// List the artifacts attached to an image.
+    ///
+    /// Signatures, SBOMs and attestations are pushed as manifests whose
+    /// `subject` is the image. This uses the registry's referrers API, or the
+    /// `sha256-<hex>` tag schema on registries without it.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "myapp:v1")
+    /// * `artifact_type` - Only return referrers of this artifact type
+    ///
+    /// # Returns
+    ///
+    /// The referrer descriptors (digest, artifact type, size, annotations).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     for referrer in rex.list_referrers("myapp:v1", None)? {
+    ///         println!("{} {:?}", referrer.digest(), referrer.artifact_type());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The image does not exist
+    /// - Authentication is required but not provided
+    pub fn list_referrers(
+        &mut self,
+        image: &str,
+        artifact_type: Option<&str>,
+    ) -> Result<Vec<Descriptor>> {
+        let reference = image.parse::<Reference>()?;
+        self.registry.list_referrers(&reference, artifact_type)
+    }
+
+    /// Find the cosign signatures of an image.
+    ///
+    /// Both the `sha256-<hex>.sig` tag and the referrers API are searched.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+    ///
+    /// # Returns
+    ///
+    /// The image's manifest digest and the signatures found, unverified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The image does not exist
+    /// - A signature manifest or payload cannot be fetched
+    pub fn find_signatures(&mut self, image: &str) -> Result<(String, Vec<Signature>)> {
+        let reference = image.parse::<Reference>()?;
+        signature::find_signatures(&mut self.registry, &reference)
+    }
+
+    /// Verify the cosign signatures of an image against a public key.
+    ///
+    /// Every signature found is checked: it must match the key and sign the
+    /// image's manifest digest. No transparency log or certificate lookups
+    /// are made.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+    /// * `key` - The public key (e.g., parsed from `cosign.pub`)
+    ///
+    /// # Returns
+    ///
+    /// The image's manifest digest and one check per signature found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    /// use librex::signature::PublicKey;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///     let key = PublicKey::from_pem(&std::fs::read_to_string("cosign.pub")?)?;
+    ///
+    ///     let (digest, checks) = rex.verify_signatures("myapp:v1", &key)?;
+    ///     let verified = checks.iter().any(|check| check.verified);
+    ///     println!("{}: {}", digest, if verified { "verified" } else { "not verified" });
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signatures cannot be fetched, see
+    /// [`Rex::find_signatures`]. A signature that fails verification is
+    /// reported in its check, not as an error.
+    pub fn verify_signatures(
+        &mut self,
+        image: &str,
+        key: &PublicKey,
+    ) -> Result<(String, Vec<SignatureCheck>)> {
+        let (digest, signatures) = self.find_signatures(image)?;
+        let checks = signatures
+            .iter()
+            .map(|signature| signature.verify(key, &digest))
+            .collect();
+        Ok((digest, checks))
+    }
+
+    /// Find the SBOMs attached to an image.
+    ///
+    /// Referrers (SPDX, CycloneDX and in-toto attestations) and cosign's
+    /// `.att` and `.sbom` tags are searched. DSSE envelopes and in-toto
+    /// statements are unwrapped; attestations that are not SBOMs are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+    ///
+    /// # Returns
+    ///
+    /// The image's manifest digest and the SBOMs found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (_, sboms) = rex.find_sboms("myapp:v1")?;
+    ///     for sbom in &sboms {
+    ///         for package in sbom.document.packages() {
+    ///             if package.matches("log4j", Some("2.14")) {
+    ///                 println!("{} {:?}", package.name, package.version);
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The image does not exist
+    /// - An SBOM manifest or layer cannot be fetched
+    pub fn find_sboms(&mut self, image: &str) -> Result<(String, Vec<Sbom>)> {
+        let reference = image.parse::<Reference>()?;
+        sbom::find_sboms(&mut self.registry, &reference)
+    }
+
+    /// List the files of an image, or of one of its layers.
+    ///
+    /// Layers are streamed and decompressed (gzip or zstd) one at a time.
+    /// Without `layer`, the layers are merged in order, applying whiteouts,
+    /// to give the image's final filesystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference, used for the repository name
+    /// * `manifest` - The image manifest (a single platform, see [`Rex::get_manifest`])
+    /// * `layer` - Index of a single layer to list (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The entries sorted by path. A single layer's listing keeps its
+    /// whiteouts; the merged filesystem has none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (manifest, _) = rex.get_manifest("alpine:3.19")?;
+    ///     if let Some(manifest) = manifest.as_manifest() {
+    ///         for file in rex.list_files("alpine:3.19", manifest, None)? {
+    ///             println!("{} {}", file.path, file.size);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - `layer` is out of range
+    /// - A layer cannot be fetched, or is not a (compressed) tar archive
+    pub fn list_files(
+        &mut self,
+        image: &str,
+        manifest: &ImageManifest,
+        layer: Option<usize>,
+    ) -> Result<Vec<FileEntry>> {
+        let reference = image.parse::<Reference>()?;
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        layer::list_files(&self.registry, repository, manifest, layer)
+    }
+
+    /// Write the content of a file of an image.
+    ///
+    /// The layers are searched from the top down, stopping at the first one
+    /// holding the file, and symbolic links are followed inside the image.
+    /// Nothing is pulled besides the layers read.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference, used for the repository name
+    /// * `manifest` - The image manifest (a single platform, see [`Rex::get_manifest`])
+    /// * `path` - Path of the file in the image (e.g., "/etc/os-release")
+    /// * `writer` - Destination for the file content
+    ///
+    /// # Returns
+    ///
+    /// The entry the content was read from, after following links.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (manifest, _) = rex.get_manifest("alpine:3.19")?;
+    ///     if let Some(manifest) = manifest.as_manifest() {
+    ///         let mut content = Vec::new();
+    ///         rex.read_file("alpine:3.19", manifest, "/etc/os-release", &mut content)?;
+    ///         println!("{}", String::from_utf8_lossy(&content));
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The file does not exist in the image, or was deleted by a layer
+    /// - The path is a directory or a special file
+    /// - A layer cannot be fetched, or is not a (compressed) tar archive
+    pub fn read_file<W: std::io::Write + ?Sized>(
+        &mut self,
+        image: &str,
+        manifest: &ImageManifest,
+        path: &str,
+        writer: &mut W,
+    ) -> Result<FileEntry> {
+        let reference = image.parse::<Reference>()?;
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        layer::read_file(&self.registry, repository, manifest, path, writer)
+    }
+
+    /// Extract a file or directory of an image into a local directory.
+    ///
+    /// The layers are applied in order, whiteouts included, for the entries
+    /// at or below `path`. Entries keep their full image path below
+    /// `destination`.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference, used for the repository name
+    /// * `manifest` - The image manifest (a single platform, see [`Rex::get_manifest`])
+    /// * `path` - Path to extract (e.g., "/etc/nginx")
+    /// * `destination` - Local directory, created if needed
+    ///
+    /// # Returns
+    ///
+    /// What was extracted, and the entries that were skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (manifest, _) = rex.get_manifest("nginx:latest")?;
+    ///     if let Some(manifest) = manifest.as_manifest() {
+    ///         let report = rex.extract_files(
+    ///             "nginx:latest",
+    ///             manifest,
+    ///             "/etc/nginx",
+    ///             std::path::Path::new("out"),
+    ///         )?;
+    ///         println!("{} files extracted", report.files);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - Nothing exists at `path` in the image
+    /// - The destination cannot be created
+    /// - A layer cannot be fetched, or is not a (compressed) tar archive
+    pub fn extract_files(
+        &mut self,
+        image: &str,
+        manifest: &ImageManifest,
+        path: &str,
+        destination: &Path,
+    ) -> Result<ExtractReport> {
+        let reference = image.parse::<Reference>()?;
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        layer::extract(&self.registry, repository, manifest, path, destination)
+    }
+
+    /// Compare the merged filesystems of two images.
+    ///
+    /// Every layer of both images is streamed and regular files are compared
+    /// by content; layers the images share are read once. Both images must
+    /// be in this registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_image` - Reference of the old image, used for the repository name
+    /// * `old` - Manifest of the old image (a single platform)
+    /// * `new_image` - Reference of the new image, used for the repository name
+    /// * `new` - Manifest of the new image (a single platform)
+    ///
+    /// # Returns
+    ///
+    /// The added, removed and modified entries, sorted by path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let (old, _) = rex.get_manifest("myapp:release-1.4")?;
+    ///     let (new, _) = rex.get_manifest("myapp:release-1.5")?;
+    ///     if let (Some(old), Some(new)) = (old.as_manifest(), new.as_manifest()) {
+    ///         for change in rex.diff_files("myapp:release-1.4", old, "myapp:release-1.5", new)? {
+    ///             println!("{} /{}", change.change.symbol(), change.path);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A reference format is invalid
+    /// - A layer cannot be fetched, or is not a (compressed) tar archive
+    pub fn diff_files(
+        &mut self,
+        old_image: &str,
+        old: &ImageManifest,
+        new_image: &str,
+        new: &ImageManifest,
+    ) -> Result<Vec<FileChange>> {
+        let compat = self.registry.dockerhub_compat();
+        let old_reference = old_image.parse::<Reference>()?;
+        let new_reference = new_image.parse::<Reference>()?;
+        diff::diff_files(
+            &self.registry,
+            old_reference.repository_for_registry(compat),
+            old,
+            new_reference.repository_for_registry(compat),
+            new,
+        )
+    }
+
+    /// List available platforms for a multi-platform image.
+    ///
+    /// This method fetches the manifest/index and returns the available platforms.
+    /// If the image is a single-platform manifest, an empty vector is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "alpine:latest")
+    ///
+    /// # Returns
+    ///
+    /// A vector of tuples containing (os, architecture, variant) for each platform.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let platforms = rex.list_platforms("alpine:latest")?;
+    ///     for (os, arch, variant) in platforms {
+    ///         match variant {
+    ///             Some(v) => println!("{}/{}/{}", os, arch, v),
+    ///             None => println!("{}/{}", os, arch),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn list_platforms(&mut self, image: &str) -> Result<Vec<(String, String, Option<String>)>> {
+        let (manifest_or_index, _digest) = self.get_manifest(image)?;
+        let platforms = manifest_or_index.platforms();
+
+        Ok(platforms
+            .into_iter()
+            .map(|(platform, _)| {
+                (
+                    platform.os().to_string(),
+                    platform.architecture().to_string(),
+                    platform.variant().as_ref().map(|s| s.to_string()),
+                )
+            })
+            .collect())
+    }
+
+    /// Get a blob (layer or config) by digest.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest (e.g., "sha256:abc123...")
+    ///
+    /// # Returns
+    ///
+    /// The raw blob content as bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let digest = "sha256:abc123...".parse()?;
+    ///     let blob = rex.get_blob("alpine", &digest)?;
+    ///     println!("Blob size: {} bytes", blob.len());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    /// Retrieves a blob for a specific reference, respecting Docker Hub compatibility settings.
+    ///
+    /// This is a convenience method that automatically uses the correct repository name
+    /// based on the registry's dockerhub_compat setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `reference_str` - The image reference string
+    /// * `digest` - The content digest
+    ///
+    /// # Returns
+    ///
+    /// The raw blob content as bytes.
+    pub fn get_blob_for_reference(
+        &mut self,
+        reference_str: &str,
+        digest: &Digest,
+    ) -> Result<Vec<u8>> {
+        let reference = reference_str.parse::<Reference>()?;
+        self.registry.get_blob_for_reference(&reference, digest)
+    }
+
+    pub fn get_blob(&mut self, repository: &str, digest: &Digest) -> Result<Vec<u8>> {
+        self.registry.get_blob(repository, digest)
+    }
+
+    /// Stream a blob into a writer, verifying its digest on the way.
+    ///
+    /// Nothing is buffered in memory or cached, which makes this suitable for
+    /// layers of any size.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest
+    /// * `writer` - Destination for the blob content
+    /// * `progress` - Callback receiving `(bytes_written, total_size)`
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let digest = "sha256:abc123...".parse()?;
+    ///     let mut stdout = std::io::stdout();
+    ///     rex.fetch_blob_to("alpine", &digest, &mut stdout, |_, _| {})?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn fetch_blob_to<W, F>(
+        &self,
+        repository: &str,
+        digest: &Digest,
+        writer: &mut W,
+        progress: F,
+    ) -> Result<u64>
+    where
+        W: std::io::Write + ?Sized,
+        F: FnMut(u64, Option<u64>),
+    {
+        self.registry
+            .fetch_blob_to(repository, digest, writer, progress)
+    }
+
+    /// Download a blob into the blob store and return its path.
+    ///
+    /// Requires a blob store, configured with [`RexBuilder::with_cache`] or
+    /// [`RexBuilder::with_blob_store`]. Blobs already in the store are not
+    /// downloaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `digest` - The content digest
+    /// * `progress` - Callback receiving `(bytes_written, total_size)`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let rex = Rex::builder()
+    ///         .registry_url("http://localhost:5000")
+    ///         .with_cache("/tmp/rex-cache")
+    ///         .build()?;
+    ///
+    ///     let digest = "sha256:abc123...".parse()?;
+    ///     let path = rex.download_blob("alpine", &digest, |done, total| {
+    ///         eprintln!("{} / {:?} bytes", done, total);
+    ///     })?;
+    ///     println!("Layer stored at {}", path.display());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn download_blob<F>(
+        &self,
+        repository: &str,
+        digest: &Digest,
+        progress: F,
+    ) -> Result<PathBuf>
+    where
+        F: FnMut(u64, Option<u64>),
+    {
+        self.registry.download_blob(repository, digest, progress)
+    }
+
+    /// Search for repositories by name using fuzzy matching.
+    ///
+    /// On registries offering Zot's search extension the query is answered
+    /// server-side, in Zot's ranking order. Otherwise this uses an fzf-like
+    /// fuzzy matching algorithm and automatically fetches the repository
+    /// catalog if not already cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query (e.g., "alp" to match "alpine")
+    ///
+    /// # Returns
+    ///
+    /// A vector of search results sorted by relevance.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let results = rex.search_repositories("alp")?;
+    ///     for result in results {
+    ///         println!("{} (score: {})", result.value, result.score);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_repositories(&mut self, query: &str) -> Result<Vec<SearchResult>> {
+        if let Some(found) = self.zot_search(|client| zot::global_search(client, query, None))? {
+            return Ok(ranked(found.repos.into_iter().map(|repo| repo.name)));
+        }
+
+        // Fetch repositories if not cached
+        if self.cached_repositories.is_none() {
+            self.list_repositories()?;
+        }
+
+        let repos = self.cached_repositories.as_ref().unwrap();
+        Ok(search_repositories(query, repos))
+    }
+
+    /// Search for tags within a specific repository using fuzzy matching.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The repository name
+    /// * `query` - The search query (e.g., "lat" to match "latest")
+    ///
+    /// # Returns
+    ///
+    /// A vector of search results sorted by relevance.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let results = rex.search_tags("alpine", "lat")?;
+    ///     for result in results {
+    ///         println!("{}", result.value);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_tags(&mut self, repository: &str, query: &str) -> Result<Vec<SearchResult>> {
+        // Fetch tags if not cached
+        if !self.cached_tags.contains_key(repository) {
+            self.list_tags(repository)?;
+        }
+
+        let tags = self.cached_tags.get(repository).unwrap();
+        Ok(search_tags(query, tags))
+    }
+
+    /// Search for images (repository:tag combinations) using fuzzy matching.
+    ///
+    /// This searches across both repository names and tags. If the query contains
+    /// a colon (e.g., "alp:lat"), it searches repositories and tags separately.
+    ///
+    /// On registries offering Zot's search extension the images of the
+    /// matching repositories are listed server-side. Otherwise every tag
+    /// list is fetched and matched client-side.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query (e.g., "alp" or "alp:lat")
+    ///
+    /// # Returns
+    ///
+    /// A vector of search results with full image references (repository:tag).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     // Search for repositories matching "alp"
+    ///     let results = rex.search_images("alp")?;
+    ///
+    ///     // Search for repositories matching "alp" with tags matching "lat"
+    ///     let results = rex.search_images("alp:lat")?;
+    ///
+    ///     for result in results {
+    ///         println!("{}", result.value);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn search_images(&mut self, query: &str) -> Result<Vec<SearchResult>> {
+        let images = self.zot_search(|client| {
+            let found = zot::global_search(client, query, None)?;
+            if query.contains(':') {
+                return Ok(found.images);
+            }
+            repository_images(client, &found.repos, None)
+        })?;
+        if let Some(images) = images {
+            return Ok(ranked(images.iter().map(ImageSummary::reference)));
+        }
+
+        // Fetch repositories if not cached
+        if self.cached_repositories.is_none() {
+            self.list_repositories()?;
+        }
+
+        // Clone repos list to avoid borrow conflicts
+        let repos = self.cached_repositories.as_ref().unwrap().clone();
+
+        // Fetch tags for all repositories if not cached
+        for repo in &repos {
+            if !self.cached_tags.contains_key(repo)
+                && let Ok(tags) = self.list_tags(repo)
+            {
+                self.cached_tags.insert(repo.clone(), tags);
+            }
+        }
+
+        Ok(search_images(query, &repos, &self.cached_tags))
+    }
+
+    /// Search repositories and images with Zot's search extension.
+    ///
+    /// Unlike [`Rex::search_repositories`] and [`Rex::search_images`], the
+    /// results carry what Zot indexes server-side: sizes, last update times
+    /// and vendors. A plain query returns the matching repositories with all
+    /// of their images; a `repository:tag` query returns matching images.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The search query (e.g., "alp" or "alpine:3")
+    /// * `limit` - Maximum number of repositories and of images to return
+    ///
+    /// # Returns
+    ///
+    /// `None` if the registry does not offer the search extension, in which
+    /// case the fuzzy search methods should be used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     if let Some(found) = rex.search_summaries("alp", Some(20))? {
+    ///         for repo in found.repos {
+    ///             println!("{} {:?}", repo.name, repo.size);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry offers the extension but the query
+    /// fails.
+    pub fn search_summaries(
+        &mut self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Option<GlobalSearchResult>> {
+        self.zot_search(|client| {
+            let mut found = zot::global_search(client, query, limit)?;
+            if !query.contains(':') {
+                // GlobalSearch only returns repositories for a plain query
+                found.images = repository_images(client, &found.repos, limit)?;
+            }
+            Ok(found)
+        })
+    }
+
+    /// List the vulnerabilities Zot's scanner found in an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - The image reference (e.g., "alpine:3.20" or "alpine@sha256:...")
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    /// use librex::zot::Severity;
+    ///
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///
+    ///     let report = rex.list_cves("alpine:3.20")?;
+    ///     for cve in report.at_least(Severity::High).cves {
+    ///         println!("{} {}", cve.id, cve.severity);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The registry does not offer CVE scanning
+    /// - The image is unknown to the scanner
+    pub fn list_cves(&mut self, image: &str) -> Result<CveReport> {
+        let reference = image.parse::<Reference>()?;
+        self.require_cve_scanning()?;
+
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        let image = match (reference.digest(), reference.tag()) {
+            (Some(digest), _) => format!("{}@{}", repository, digest),
+            (None, Some(tag)) => format!("{}:{}", repository, tag),
+            (None, None) => format!("{}:latest", repository),
+        };
+        zot::cve_list_for_image(self.registry.client(), &image)
+    }
+
+    /// List the images affected by a vulnerability.
+    ///
+    /// # Arguments
+    ///
+    /// * `cve_id` - The CVE identifier (e.g., "CVE-2024-1234")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the registry does not offer CVE scanning or the
+    /// query fails.
+    pub fn images_affected_by(&mut self, cve_id: &str) -> Result<Vec<ImageSummary>> {
+        self.require_cve_scanning()?;
+        zot::image_list_for_cve(self.registry.client(), cve_id)
+    }
+
+    /// Fails with a validation error unless the registry scans for CVEs.
+    fn require_cve_scanning(&mut self) -> Result<()> {
+        if self.capabilities()?.cve {
+            Ok(())
+        } else {
+            Err(RexError::validation(
+                "Registry does not offer CVE scanning (requires Zot's search extension)",
+            ))
+        }
+    }
+
+    /// Returns true if the registry offers Zot's search extension.
+    ///
+    /// A failed discovery counts as no: searches then use the catalog.
+    fn zot_search_available(&mut self) -> bool {
+        self.capabilities().is_ok_and(|c| c.search)
     }
 
-    /// List all tags for a specific repository.
+    /// Runs a query with Zot's search extension.
+    ///
+    /// Returns `None` if the registry does not offer the extension: discovery
+    /// does not report it, or the endpoint answers 404. The latter is
+    /// remembered, so fallbacks do not ask again. Other errors are returned.
+    fn zot_search<T, F>(&mut self, search: F) -> Result<Option<T>>
+    where
+        F: FnOnce(&Client) -> Result<T>,
+    {
+        if !self.zot_search_available() {
+            return Ok(None);
+        }
+
+        match search(self.registry.client()) {
+            Ok(found) => Ok(Some(found)),
+            Err(RexError::NotFound { .. }) => {
+                if let Some(capabilities) = self.capabilities.as_mut() {
+                    capabilities.search = false;
+                }
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the registry URL.
+    pub fn registry_url(&self) -> &str {
+        &self.registry_url
+    }
+
+    /// Tag an existing image without pulling its layers.
+    ///
+    /// Fetches the raw manifest of `source` and pushes it unchanged under
+    /// `new_tag` in the same repository, so both tags resolve to the same
+    /// digest. Use this to promote an image, e.g. `myapp:sha-abc` to
+    /// `myapp:prod`.
     ///
     /// # Arguments
     ///
-    /// * `repository` - The repository name (e.g., "alpine")
+    /// * `source` - The image reference (e.g., "myapp:sha-abc" or "myapp@sha256:...")
+    /// * `new_tag` - The tag to create or move (e.g., "prod")
     ///
     /// # Returns
     ///
-    /// A vector of tag names for the repository.
+    /// The manifest digest the new tag points to.
     ///
     /// # Examples
     ///
@@ -297,188 +1398,281 @@ impl Rex {
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     let tags = rex.list_tags("alpine")?;
-    ///     for tag in tags {
-    ///         println!("{}", tag);
-    ///     }
+    ///     let digest = rex.tag("myapp:sha-abc", "prod")?;
+    ///     println!("myapp:prod -> {}", digest);
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn list_tags(&mut self, repository: &str) -> Result<Vec<String>> {
-        let tags = self.registry.list_tags(repository)?;
-        self.cached_tags
-            .insert(repository.to_string(), tags.clone());
-        Ok(tags)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format or the new tag is invalid
+    /// - The source image does not exist
+    /// - Authentication is required but not provided, or push access is denied
+    /// - The registry rejects the manifest
+    pub fn tag(&mut self, source: &str, new_tag: &str) -> Result<String> {
+        let source = source.parse::<Reference>()?;
+        self.registry.tag(&source, new_tag)
     }
 
-    /// Get the manifest or index for a specific image.
+    /// Copy an image to another repository or registry.
     ///
-    /// This method automatically detects whether the image is a single-platform
-    /// manifest or a multi-platform index and returns the appropriate type.
-    ///
-    /// For single-platform images, you can extract the manifest using `.as_manifest()`
-    /// or `.into_manifest()`. For multi-platform images, use `.as_index()` or
-    /// `.into_index()` to access the platform list.
+    /// The whole image is copied: for a multi-platform image every platform
+    /// manifest with its config and layers, then the index itself, unless
+    /// `options` selects a single platform. Blobs the destination already has
+    /// are skipped, blobs are mounted when both sides are the same registry,
+    /// and everything else is streamed.
     ///
     /// # Arguments
     ///
-    /// * `image` - The image reference (e.g., "alpine:latest" or "alpine@sha256:...")
+    /// * `source` - The image reference on this registry (e.g., "alpine:3.19")
+    /// * `destination` - The registry to copy to (may point at the same registry)
+    /// * `destination_ref` - The image reference on the destination (e.g., "mirror/alpine:3.19")
+    /// * `options` - Platform filter and chunk size
+    /// * `on_event` - Called as each blob and manifest is processed
     ///
     /// # Returns
     ///
-    /// A tuple containing:
-    /// - `ManifestOrIndex` enum (either a single-platform manifest or multi-platform index)
-    /// - The manifest digest as returned by the registry
+    /// A [`CopyReport`] with the pushed digest and transfer counts.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use librex::{Rex, ManifestOrIndex};
+    /// use librex::Rex;
+    /// use librex::copy::CopyOptions;
     ///
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let mut rex = Rex::connect("http://localhost:5000")?;
-    ///
-    ///     let (manifest_or_index, digest) = rex.get_manifest("alpine:latest")?;
-    ///     println!("Digest: {}", digest);
-    ///     match manifest_or_index {
-    ///         ManifestOrIndex::Manifest(manifest) => {
-    ///             println!("Single-platform: {} layers", manifest.layers().len());
-    ///         }
-    ///         ManifestOrIndex::Index(index) => {
-    ///             println!("Multi-platform: {} platforms", index.manifests().len());
-    ///         }
-    ///     }
+    ///     let mut source = Rex::connect("https://registry-1.docker.io")?;
+    ///     let mut mirror = Rex::connect("http://localhost:5000")?;
     ///
-    ///     // Or use helper methods:
-    ///     let (manifest_or_index, _) = rex.get_manifest("alpine:latest")?;
-    ///     if let Some(manifest) = manifest_or_index.as_manifest() {
-    ///         println!("Layers: {}", manifest.layers().len());
-    ///     }
+    ///     let options = CopyOptions::new().with_platform("linux", "amd64", None);
+    ///     let report = source.copy_image("alpine:3.19", &mut mirror, "alpine:3.19", &options, |_| {})?;
+    ///     println!("Copied {} blobs", report.blobs_copied);
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn get_manifest(&mut self, image: &str) -> Result<(ManifestOrIndex, String)> {
-        let reference = image.parse::<Reference>()?;
-        self.registry.get_manifest(&reference)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - A reference format is invalid
+    /// - The source image does not exist, or the platform filter matches nothing
+    /// - Authentication is required but not provided, or push access is denied
+    /// - A blob fails digest validation, or the destination rejects content
+    pub fn copy_image<F>(
+        &mut self,
+        source: &str,
+        destination: &mut Rex,
+        destination_ref: &str,
+        options: &CopyOptions,
+        on_event: F,
+    ) -> Result<CopyReport>
+    where
+        F: FnMut(&CopyEvent),
+    {
+        let source = source.parse::<Reference>()?;
+        let target = destination_ref.parse::<Reference>()?;
+
+        let source_reference = source.digest().or(source.tag()).unwrap_or("latest");
+        let target_reference = target.digest().or(target.tag()).unwrap_or("latest");
+        let target_repository = target
+            .repository_for_registry(destination.registry.dockerhub_compat())
+            .to_string();
+
+        let report = copy::copy_image(
+            &CopyEndpoint::new(
+                self.registry.client(),
+                source.repository_for_registry(self.registry.dockerhub_compat()),
+                source_reference,
+            ),
+            &CopyEndpoint::new(
+                destination.registry.client(),
+                &target_repository,
+                target_reference,
+            ),
+            options,
+            on_event,
+        )?;
+
+        if let Some(tag) = target.tag() {
+            destination.registry.invalidate_tag(&target_repository, tag);
+        }
+
+        Ok(report)
     }
 
-    /// List available platforms for a multi-platform image.
+    /// Save an image to an OCI image layout or a `docker load` archive.
     ///
-    /// This method fetches the manifest/index and returns the available platforms.
-    /// If the image is a single-platform manifest, an empty vector is returned.
+    /// Blobs are streamed from the registry and verified against their
+    /// digests as they are written; no container runtime is involved. When
+    /// the options carry no image name, the reference's repository and tag
+    /// are recorded (e.g., "alpine:3.19").
     ///
     /// # Arguments
     ///
-    /// * `image` - The image reference (e.g., "alpine:latest")
+    /// * `image` - Image reference (e.g., "alpine:3.19")
+    /// * `options` - Format, platform filter and image name
+    /// * `output` - Layout directory, or tarball path (see [`oci_layout::save_image`])
+    /// * `on_event` - Called as each blob and manifest is written
     ///
     /// # Returns
     ///
-    /// A vector of tuples containing (os, architecture, variant) for each platform.
+    /// A [`SaveReport`] with the saved digest and transfer counts.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use librex::Rex;
+    /// use librex::oci_layout::{ArchiveFormat, SaveOptions};
+    /// use std::path::Path;
     ///
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     let platforms = rex.list_platforms("alpine:latest")?;
-    ///     for (os, arch, variant) in platforms {
-    ///         match variant {
-    ///             Some(v) => println!("{}/{}/{}", os, arch, v),
-    ///             None => println!("{}/{}", os, arch),
-    ///         }
-    ///     }
+    ///     let options = SaveOptions::new(ArchiveFormat::DockerArchive)
+    ///         .with_platform("linux", "amd64", None);
+    ///     let report = rex.save_image("alpine:3.19", &options, Path::new("alpine.tar"), |_| {})?;
+    ///     println!("Saved {} ({} bytes)", report.digest, report.bytes_written);
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn list_platforms(&mut self, image: &str) -> Result<Vec<(String, String, Option<String>)>> {
-        let (manifest_or_index, _digest) = self.get_manifest(image)?;
-        let platforms = manifest_or_index.platforms();
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - The image does not exist, or the platform filter matches nothing
+    /// - A docker archive is asked for a multi-platform image without a platform
+    /// - A blob fails digest validation, or the output cannot be written
+    pub fn save_image<F>(
+        &mut self,
+        image: &str,
+        options: &SaveOptions,
+        output: &Path,
+        on_event: F,
+    ) -> Result<SaveReport>
+    where
+        F: FnMut(&SaveEvent),
+    {
+        let reference = image.parse::<Reference>()?;
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        let target = reference.digest().or(reference.tag()).unwrap_or("latest");
 
-        Ok(platforms
-            .into_iter()
-            .map(|(platform, _)| {
-                (
-                    platform.os().to_string(),
-                    platform.architecture().to_string(),
-                    platform.variant().as_ref().map(|s| s.to_string()),
-                )
-            })
-            .collect())
+        let mut options = options.clone();
+        if options.name.is_none()
+            && let Some(tag) = reference.tag()
+        {
+            options.name = Some(format!(
+                "{}:{}",
+                reference.repository_for_registry(false),
+                tag
+            ));
+        }
+
+        oci_layout::save_image(
+            self.registry.client(),
+            repository,
+            target,
+            &options,
+            output,
+            on_event,
+        )
     }
 
-    /// Get a blob (layer or config) by digest.
+    /// Push an image from an OCI image layout or a Docker archive.
+    ///
+    /// This is the inverse of [`Rex::save_image`]: manifests and blobs are
+    /// read from `source` and pushed, skipping blobs the registry already
+    /// has. Layouts keep their digests.
     ///
     /// # Arguments
     ///
-    /// * `repository` - The repository name
-    /// * `digest` - The content digest (e.g., "sha256:abc123...")
+    /// * `source` - Layout directory, or tarball (OCI layout or `docker save` output)
+    /// * `image` - Image reference to push to (e.g., "alpine:3.19")
+    /// * `options` - Image selection, for sources holding several images
+    /// * `on_event` - Called as each blob and manifest is processed
     ///
     /// # Returns
     ///
-    /// The raw blob content as bytes.
+    /// A [`LoadReport`] with the pushed digest and transfer counts.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use librex::Rex;
+    /// use librex::oci_layout::LoadOptions;
+    /// use std::path::Path;
     ///
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     let digest = "sha256:abc123...".parse()?;
-    ///     let blob = rex.get_blob("alpine", &digest)?;
-    ///     println!("Blob size: {} bytes", blob.len());
+    ///     let report = rex.load_image(Path::new("alpine.tar"), "alpine:3.19", &LoadOptions::new(), |_| {})?;
+    ///     println!("Pushed {}", report.digest);
     ///
     ///     Ok(())
     /// }
     /// ```
-    /// Retrieves a blob for a specific reference, respecting Docker Hub compatibility settings.
-    ///
-    /// This is a convenience method that automatically uses the correct repository name
-    /// based on the registry's dockerhub_compat setting.
     ///
-    /// # Arguments
-    ///
-    /// * `reference_str` - The image reference string
-    /// * `digest` - The content digest
-    ///
-    /// # Returns
+    /// # Errors
     ///
-    /// The raw blob content as bytes.
-    pub fn get_blob_for_reference(
+    /// Returns an error if:
+    /// - The reference format is invalid
+    /// - `source` is not a layout or Docker archive, or holds several images and none was named
+    /// - A manifest or blob is missing from the source, or fails digest validation
+    /// - Authentication is required but not provided, or push access is denied
+    pub fn load_image<F>(
         &mut self,
-        reference_str: &str,
-        digest: &Digest,
-    ) -> Result<Vec<u8>> {
-        let reference = reference_str.parse::<Reference>()?;
-        self.registry.get_blob_for_reference(&reference, digest)
-    }
+        source: &Path,
+        image: &str,
+        options: &LoadOptions,
+        on_event: F,
+    ) -> Result<LoadReport>
+    where
+        F: FnMut(&LoadEvent),
+    {
+        let reference = image.parse::<Reference>()?;
+        let repository = reference.repository_for_registry(self.registry.dockerhub_compat());
+        let target = reference.digest().or(reference.tag()).unwrap_or("latest");
 
-    pub fn get_blob(&mut self, repository: &str, digest: &Digest) -> Result<Vec<u8>> {
-        self.registry.get_blob(repository, digest)
+        let report = oci_layout::load_image(
+            self.registry.client(),
+            repository,
+            target,
+            source,
+            options,
+            on_event,
+        )?;
+
+        if let Some(tag) = reference.tag() {
+            self.registry.invalidate_tag(repository, tag);
+        }
+
+        Ok(report)
     }
 
-    /// Search for repositories by name using fuzzy matching.
+    /// Compute the deduplicated storage usage of the registry.
     ///
-    /// This uses an fzf-like fuzzy matching algorithm. It automatically fetches
-    /// the repository catalog if not already cached.
+    /// Every tag of every repository is read, and each blob (manifests
+    /// included) is counted once however many tags reference it. The report
+    /// tells apart bytes only one tag or repository uses, which deleting it
+    /// would free, from bytes shared with others.
+    ///
+    /// Tags and manifests are read from the registry rather than the cache.
     ///
     /// # Arguments
     ///
-    /// * `query` - The search query (e.g., "alp" to match "alpine")
+    /// * `on_event` - Called as repositories are scanned, and for each tag that cannot be read
     ///
     /// # Returns
     ///
-    /// A vector of search results sorted by relevance.
+    /// A [`UsageReport`] with repositories sorted by unique bytes, largest first.
     ///
     /// # Examples
     ///
@@ -487,36 +1681,41 @@ impl Rex {
     ///
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///     let rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     let results = rex.search_repositories("alp")?;
-    ///     for result in results {
-    ///         println!("{} (score: {})", result.value, result.score);
-    ///     }
+    ///     let report = rex.disk_usage(|_| {})?;
+    ///     println!("{} bytes stored, {} saved by sharing", report.total_bytes, report.saved_bytes());
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn search_repositories(&mut self, query: &str) -> Result<Vec<SearchResult>> {
-        // Fetch repositories if not cached
-        if self.cached_repositories.is_none() {
-            self.list_repositories()?;
-        }
-
-        let repos = self.cached_repositories.as_ref().unwrap();
-        Ok(search_repositories(query, repos))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The catalog cannot be listed
+    /// - Authentication is required but not provided
+    pub fn disk_usage<F>(&self, on_event: F) -> Result<UsageReport>
+    where
+        F: FnMut(&UsageEvent),
+    {
+        usage::analyze(self.registry.client(), on_event)
     }
 
-    /// Search for tags within a specific repository using fuzzy matching.
+    /// Find untagged manifests and broken links in the registry.
+    ///
+    /// Every tag is walked through index children, referrers and referrer
+    /// subjects. Reported are manifests no tag reaches, indexes whose
+    /// children are missing, and referrers whose subject is gone. Nothing is
+    /// deleted; see [`Rex::gc_delete`].
     ///
     /// # Arguments
     ///
-    /// * `repository` - The repository name
-    /// * `query` - The search query (e.g., "lat" to match "latest")
+    /// * `on_event` - Called as repositories are scanned, and for each manifest that cannot be read
     ///
     /// # Returns
     ///
-    /// A vector of search results sorted by relevance.
+    /// A [`GcReport`] with the findings in deletion order.
     ///
     /// # Examples
     ///
@@ -525,38 +1724,43 @@ impl Rex {
     ///
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let mut rex = Rex::connect("http://localhost:5000")?;
+    ///     let rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     let results = rex.search_tags("alpine", "lat")?;
-    ///     for result in results {
-    ///         println!("{}", result.value);
+    ///     let report = rex.gc_report(|_| {})?;
+    ///     for finding in &report.findings {
+    ///         println!("{}@{}: {}", finding.repository, finding.digest, finding.reason);
     ///     }
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn search_tags(&mut self, repository: &str, query: &str) -> Result<Vec<SearchResult>> {
-        // Fetch tags if not cached
-        if !self.cached_tags.contains_key(repository) {
-            self.list_tags(repository)?;
-        }
-
-        let tags = self.cached_tags.get(repository).unwrap();
-        Ok(search_tags(query, tags))
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The catalog cannot be listed
+    /// - Authentication is required but not provided
+    pub fn gc_report<F>(&self, on_event: F) -> Result<GcReport>
+    where
+        F: FnMut(&GcEvent),
+    {
+        gc::analyze(self.registry.client(), on_event)
     }
 
-    /// Search for images (repository:tag combinations) using fuzzy matching.
+    /// Delete the findings of a [`Rex::gc_report`] by digest.
     ///
-    /// This searches across both repository names and tags. If the query contains
-    /// a colon (e.g., "alp:lat"), it searches repositories and tags separately.
+    /// Referrers go first, then indexes, then image manifests. A failed
+    /// deletion does not stop the others. Findings a regular tag reaches,
+    /// such as a tagged index with missing children, are not deleted.
     ///
     /// # Arguments
     ///
-    /// * `query` - The search query (e.g., "alp" or "alp:lat")
+    /// * `report` - Report whose findings are deleted
+    /// * `on_event` - Called for each deletion
     ///
     /// # Returns
     ///
-    /// A vector of search results with full image references (repository:tag).
+    /// A [`GcDeletion`] listing deleted and failed manifests.
     ///
     /// # Examples
     ///
@@ -567,43 +1771,18 @@ impl Rex {
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let mut rex = Rex::connect("http://localhost:5000")?;
     ///
-    ///     // Search for repositories matching "alp"
-    ///     let results = rex.search_images("alp")?;
-    ///
-    ///     // Search for repositories matching "alp" with tags matching "lat"
-    ///     let results = rex.search_images("alp:lat")?;
-    ///
-    ///     for result in results {
-    ///         println!("{}", result.value);
-    ///     }
+    ///     let report = rex.gc_report(|_| {})?;
+    ///     let deletion = rex.gc_delete(&report, |_| {});
+    ///     println!("Deleted {} manifests", deletion.deleted.len());
     ///
     ///     Ok(())
     /// }
     /// ```
-    pub fn search_images(&mut self, query: &str) -> Result<Vec<SearchResult>> {
-        // Fetch repositories if not cached
-        if self.cached_repositories.is_none() {
-            self.list_repositories()?;
-        }
-
-        // Clone repos list to avoid borrow conflicts
-        let repos = self.cached_repositories.as_ref().unwrap().clone();
-
-        // Fetch tags for all repositories if not cached
-        for repo in &repos {
-            if !self.cached_tags.contains_key(repo)
-                && let Ok(tags) = self.list_tags(repo)
-            {
-                self.cached_tags.insert(repo.clone(), tags);
-            }
-        }
-
-        Ok(search_images(query, &repos, &self.cached_tags))
-    }
-
-    /// Get the registry URL.
-    pub fn registry_url(&self) -> &str {
-        &self.registry_url
+    pub fn gc_delete<F>(&mut self, report: &GcReport, on_event: F) -> GcDeletion
+    where
+        F: FnMut(&GcEvent),
+    {
+        gc::delete(&mut self.registry, report, on_event)
     }
 
     /// Delete a specific image tag.
@@ -732,6 +1911,8 @@ pub struct RexBuilder {
     memory_capacity: Option<usize>,
     credentials: Option<Credentials>,
     dockerhub_compat: Option<bool>,
+    client_config: Option<ClientConfig>,
+    blob_store_dir: Option<PathBuf>,
 }
 
 impl RexBuilder {
@@ -744,6 +1925,8 @@ impl RexBuilder {
             memory_capacity: None,
             credentials: None,
             dockerhub_compat: None,
+            client_config: None,
+            blob_store_dir: None,
         }
     }
 
@@ -754,11 +1937,22 @@ impl RexBuilder {
     }
 
     /// Enable caching with the specified directory.
+    ///
+    /// Unless [`RexBuilder::with_blob_store`] is used, blobs are stored in
+    /// the `blobstore` subdirectory.
     pub fn with_cache(mut self, cache_dir: impl Into<PathBuf>) -> Self {
         self.cache_dir = Some(cache_dir.into());
         self
     }
 
+    /// Store downloaded blobs in the specified directory.
+    ///
+    /// Blobs are kept as content-addressed files (`<dir>/sha256/<hex>`).
+    pub fn with_blob_store(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.blob_store_dir = Some(dir.into());
+        self
+    }
+
     /// Set cache TTL (time-to-live) configuration.
     pub fn with_cache_ttl(mut self, ttl: CacheTtl) -> Self {
         self.cache_ttl = Some(ttl);
@@ -787,13 +1981,44 @@ impl RexBuilder {
         self
     }
 
+    /// Set the HTTP client configuration (timeouts, TLS, etc.).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use librex::Rex;
+    /// use librex::client::ClientConfig;
+    ///
+    /// # fn example() -> librex::Result<()> {
+    /// let rex = Rex::builder()
+    ///     .registry_url("https://zot.internal")
+    ///     .with_client_config(ClientConfig::new().with_ca_cert("/etc/ssl/internal-ca.pem"))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_client_config(mut self, config: ClientConfig) -> Self {
+        self.client_config = Some(config);
+        self
+    }
+
     /// Build the `Rex` instance.
     pub fn build(self) -> Result<Rex> {
         let registry_url = self
             .registry_url
             .ok_or_else(|| crate::error::RexError::validation("Registry URL is required"))?;
 
-        let client = Client::new(&registry_url, self.credentials.clone())?;
+        let client = Client::with_config(
+            &registry_url,
+            self.client_config.unwrap_or_default(),
+            self.credentials.clone(),
+        )?;
+
+        let blob_store_dir = self.blob_store_dir.or_else(|| {
+            self.cache_dir
+                .as_ref()
+                .map(|dir| dir.join(crate::blob::BLOB_STORE_DIR))
+        });
 
         // Create cache if specified
         let cache = if let Some(cache_dir) = self.cache_dir {
@@ -805,13 +2030,17 @@ impl RexBuilder {
         };
 
         let dockerhub_compat = self.dockerhub_compat.unwrap_or(false);
-        let registry = Registry::new(client, cache, self.credentials, dockerhub_compat);
+        let mut registry = Registry::new(client, cache, self.credentials, dockerhub_compat);
+        if let Some(dir) = blob_store_dir {
+            registry = registry.with_blob_store(BlobStore::new(dir));
+        }
 
         Ok(Rex {
             registry,
             registry_url,
             cached_repositories: None,
             cached_tags: HashMap::new(),
+            capabilities: None,
         })
     }
 }
@@ -821,3 +2050,35 @@ impl Default for RexBuilder {
         Self::new()
     }
 }
+
+/// Lists the images of repositories found by `GlobalSearch`, which only
+/// returns repositories for a plain query.
+///
+/// With a limit, repositories are listed until that many images are found.
+fn repository_images(
+    client: &Client,
+    repos: &[zot::RepoSummary],
+    limit: Option<usize>,
+) -> Result<Vec<ImageSummary>> {
+    let mut images = Vec::new();
+    for repo in repos {
+        let remaining = match limit {
+            Some(limit) if images.len() >= limit => break,
+            Some(limit) => Some(limit - images.len()),
+            None => None,
+        };
+        images.extend(zot::image_list(client, &repo.name, remaining)?);
+    }
+    Ok(images)
+}
+
+/// Turns server-ranked values into search results, best first.
+fn ranked(values: impl Iterator<Item = String>) -> Vec<SearchResult> {
+    let values: Vec<String> = values.collect();
+    let count = values.len() as u32;
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| SearchResult::new(value, count - i as u32))
+        .collect()
+}
@@ -0,0 +1,402 @@
+//! SBOM discovery and parsing.
+//!
+//! Software bills of materials reach a registry in several wrappers:
+//!
+//! - referrers of the image with an SPDX or CycloneDX artifact type, or an
+//!   in-toto attestation (`oras attach`, `cosign attest` in OCI 1.1 mode)
+//! - cosign's `sha256-<hex>.att` tag, whose layers are DSSE envelopes around
+//!   in-toto statements (`cosign attest`)
+//! - cosign's `sha256-<hex>.sbom` tag, whose layers are the plain documents
+//!   (`cosign attach sbom`)
+//!
+//! Every layer is unwrapped (DSSE envelope, then in-toto statement) down to
+//! an SPDX or CycloneDX JSON document, from which the packages are read.
+//! Attestations with other predicates (e.g. SLSA provenance) are skipped.
+
+use crate::client::referrers_tag;
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::oci::{ArtifactKind, Descriptor, ManifestOrIndex};
+use crate::reference::Reference;
+use crate::registry::Registry;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// Format of an SBOM document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SbomFormat {
+    /// SPDX JSON
+    #[serde(rename = "spdx")]
+    Spdx,
+    /// CycloneDX JSON
+    #[serde(rename = "cyclonedx")]
+    CycloneDx,
+}
+
+impl fmt::Display for SbomFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SbomFormat::Spdx => "SPDX",
+            SbomFormat::CycloneDx => "CycloneDX",
+        })
+    }
+}
+
+/// Where an SBOM was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SbomSource {
+    /// The referrers API
+    Referrer,
+    /// Cosign's `sha256-<hex>.att` tag
+    AttestationTag,
+    /// Cosign's `sha256-<hex>.sbom` tag
+    SbomTag,
+}
+
+impl fmt::Display for SbomSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SbomSource::Referrer => "referrer",
+            SbomSource::AttestationTag => "attestation tag",
+            SbomSource::SbomTag => "sbom tag",
+        })
+    }
+}
+
+/// A package listed in an SBOM.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct Package {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: Option<String>,
+    /// License (SPDX expression or name)
+    pub license: Option<String>,
+    /// Package URL (e.g., "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1")
+    pub purl: Option<String>,
+}
+
+impl Package {
+    /// Returns true if the package matches a name and, optionally, a version.
+    ///
+    /// The name matches as a case-insensitive substring; the version matches
+    /// as a prefix, so "2.14" matches "2.14.1".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use librex::sbom::Package;
+    ///
+    /// let package = Package {
+    ///     name: "log4j-core".to_string(),
+    ///     version: Some("2.14.1".to_string()),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(package.matches("log4j", Some("2.14")));
+    /// assert!(!package.matches("log4j", Some("2.17")));
+    /// assert!(!package.matches("openssl", None));
+    /// ```
+    pub fn matches(&self, name: &str, version: Option<&str>) -> bool {
+        let name_matches = self
+            .name
+            .to_ascii_lowercase()
+            .contains(&name.to_ascii_lowercase());
+        let version_matches = version.is_none_or(|wanted| {
+            self.version
+                .as_deref()
+                .is_some_and(|v| v.starts_with(wanted))
+        });
+        name_matches && version_matches
+    }
+}
+
+/// An SBOM document, unwrapped from its envelope.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SbomDocument {
+    /// Document format
+    pub format: SbomFormat,
+    /// Predicate type, when the document came from an in-toto attestation
+    pub predicate_type: Option<String>,
+    /// The document as stored
+    pub document: Value,
+}
+
+impl SbomDocument {
+    /// Unwraps DSSE envelopes and in-toto statements down to an SBOM.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the JSON is not an SBOM (e.g. a provenance attestation).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the bytes are not JSON or an envelope
+    /// payload cannot be decoded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Option<Self>> {
+        let value: Value = serde_json::from_slice(bytes)
+            .map_err(|e| RexError::validation_with_source("SBOM layer is not JSON", e))?;
+        Self::from_value(value, None)
+    }
+
+    fn from_value(value: Value, predicate_type: Option<String>) -> Result<Option<Self>> {
+        // DSSE envelope: base64 payload, usually an in-toto statement
+        if let (Some(payload), Some(_)) = (
+            value.get("payload").and_then(Value::as_str),
+            value.get("payloadType"),
+        ) {
+            let decoded = STANDARD.decode(payload).map_err(|e| {
+                RexError::validation_with_source("DSSE payload is not valid base64", e)
+            })?;
+            return Self::from_bytes(&decoded);
+        }
+
+        // In-toto statement: the SBOM is the predicate
+        if value
+            .get("_type")
+            .and_then(Value::as_str)
+            .is_some_and(|t| t.starts_with("https://in-toto.io/Statement"))
+        {
+            let predicate_type = value
+                .get("predicateType")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let predicate = match value.get("predicate") {
+                // Cosign wraps predicates it does not know as {"Data": "<json>"}
+                Some(Value::Object(map)) if map.get("Data").is_some_and(Value::is_string) => {
+                    match serde_json::from_str(map["Data"].as_str().unwrap_or_default()) {
+                        Ok(inner) => inner,
+                        Err(_) => return Ok(None),
+                    }
+                }
+                Some(predicate) => predicate.clone(),
+                None => return Ok(None),
+            };
+            return Self::from_value(predicate, predicate_type);
+        }
+
+        let format = if value.get("spdxVersion").is_some() {
+            SbomFormat::Spdx
+        } else if value.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+            SbomFormat::CycloneDx
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(SbomDocument {
+            format,
+            predicate_type,
+            document: value,
+        }))
+    }
+
+    /// Lists the packages of the document.
+    pub fn packages(&self) -> Vec<Package> {
+        match self.format {
+            SbomFormat::Spdx => spdx_packages(&self.document),
+            SbomFormat::CycloneDx => {
+                let mut packages = Vec::new();
+                cyclonedx_components(&self.document, &mut packages);
+                packages
+            }
+        }
+    }
+}
+
+/// Reads a string field, treating SPDX's placeholders as absent.
+fn text(value: &Value, field: &str) -> Option<String> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty() && *s != "NOASSERTION" && *s != "NONE")
+        .map(str::to_string)
+}
+
+fn spdx_packages(document: &Value) -> Vec<Package> {
+    let Some(packages) = document.get("packages").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    packages
+        .iter()
+        .map(|package| Package {
+            name: text(package, "name").unwrap_or_default(),
+            version: text(package, "versionInfo"),
+            license: text(package, "licenseConcluded").or_else(|| text(package, "licenseDeclared")),
+            purl: package
+                .get("externalRefs")
+                .and_then(Value::as_array)
+                .and_then(|refs| {
+                    refs.iter()
+                        .find(|r| r.get("referenceType").and_then(Value::as_str) == Some("purl"))
+                })
+                .and_then(|r| text(r, "referenceLocator")),
+        })
+        .collect()
+}
+
+/// Collects components, including nested ones, in document order.
+fn cyclonedx_components(parent: &Value, packages: &mut Vec<Package>) {
+    let Some(components) = parent.get("components").and_then(Value::as_array) else {
+        return;
+    };
+
+    for component in components {
+        let licenses: Vec<String> = component
+            .get("licenses")
+            .and_then(Value::as_array)
+            .map(|licenses| {
+                licenses
+                    .iter()
+                    .filter_map(|l| {
+                        text(l, "expression").or_else(|| {
+                            l.get("license")
+                                .and_then(|l| text(l, "id").or_else(|| text(l, "name")))
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        packages.push(Package {
+            name: text(component, "name").unwrap_or_default(),
+            version: text(component, "version"),
+            license: (!licenses.is_empty()).then(|| licenses.join(", ")),
+            purl: text(component, "purl"),
+        });
+        cyclonedx_components(component, packages);
+    }
+}
+
+/// An SBOM found for an image.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sbom {
+    /// Where the SBOM was found
+    pub source: SbomSource,
+    /// Digest of the manifest holding the SBOM
+    pub manifest_digest: String,
+    /// Digest of the layer holding the SBOM
+    pub layer_digest: String,
+    /// The unwrapped document
+    #[serde(flatten)]
+    pub document: SbomDocument,
+}
+
+/// Finds the SBOMs attached to an image.
+///
+/// Looks at the image's referrers and at cosign's `.att` and `.sbom` tags.
+/// Missing tags are not an error, and layers that are not SBOMs are skipped.
+/// The referrers are not needed when the tags already hold SBOMs, so
+/// failing to list them is only an error otherwise.
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding the image
+/// * `reference` - The image reference (tag or digest)
+///
+/// # Returns
+///
+/// The image's manifest digest and the SBOMs found.
+///
+/// # Errors
+///
+/// Returns an error if the image does not exist, or if an SBOM manifest or
+/// layer cannot be fetched.
+pub fn find_sboms(registry: &mut Registry, reference: &Reference) -> Result<(String, Vec<Sbom>)> {
+    let image_digest = registry.resolve_digest(reference)?;
+    let repository = reference
+        .repository_for_registry(registry.dockerhub_compat())
+        .to_string();
+
+    // Cosign's tags are rewritten as attestations are added, so they are
+    // always fetched from the registry
+    let mut tagged = Vec::new();
+    let base_tag = referrers_tag(&Digest::from_str(&image_digest)?);
+    for (suffix, source) in [
+        ("att", SbomSource::AttestationTag),
+        ("sbom", SbomSource::SbomTag),
+    ] {
+        let tag = format!("{}.{}", base_tag, suffix);
+        match registry.client().fetch_manifest(&repository, &tag) {
+            Ok((bytes, digest)) => {
+                tagged.extend(sbom_layers(registry, &repository, source, &bytes, &digest)?);
+            }
+            Err(RexError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Registries without the referrers API may answer with a server error
+    // rather than 404
+    let referrers = match registry.list_referrers(reference, None) {
+        Ok(referrers) => referrers,
+        Err(_) if !tagged.is_empty() => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let mut sboms = Vec::new();
+    for descriptor in referrers.iter().filter(|d| is_sbom_referrer(d)) {
+        let digest = descriptor.digest().to_string();
+        let (bytes, _) = registry.client().fetch_manifest(&repository, &digest)?;
+        sboms.extend(sbom_layers(
+            registry,
+            &repository,
+            SbomSource::Referrer,
+            &bytes,
+            &digest,
+        )?);
+    }
+    sboms.extend(tagged);
+
+    Ok((image_digest, sboms))
+}
+
+/// Returns true for referrers that may hold an SBOM.
+fn is_sbom_referrer(descriptor: &Descriptor) -> bool {
+    let kind = descriptor
+        .artifact_type()
+        .as_ref()
+        .and_then(|t| ArtifactKind::from_media_type(t.as_ref()));
+    matches!(
+        kind,
+        Some(
+            ArtifactKind::SpdxSbom | ArtifactKind::CycloneDxSbom | ArtifactKind::InTotoAttestation
+        )
+    )
+}
+
+/// Reads the SBOMs among the layers of a manifest.
+fn sbom_layers(
+    registry: &mut Registry,
+    repository: &str,
+    source: SbomSource,
+    manifest_bytes: &[u8],
+    manifest_digest: &str,
+) -> Result<Vec<Sbom>> {
+    let ManifestOrIndex::Manifest(manifest) = ManifestOrIndex::from_bytes(manifest_bytes)? else {
+        return Ok(Vec::new());
+    };
+
+    let mut sboms = Vec::new();
+    for layer in manifest.layers() {
+        let digest = Digest::from_str(layer.digest().as_ref())?;
+        let bytes = registry.get_blob(repository, &digest)?;
+        // Non-JSON layers (e.g. binary attachments) are not SBOMs
+        if let Ok(Some(document)) = SbomDocument::from_bytes(&bytes) {
+            sboms.push(Sbom {
+                source,
+                manifest_digest: manifest_digest.to_string(),
+                layer_digest: digest.to_string(),
+                document,
+            });
+        }
+    }
+    Ok(sboms)
+}
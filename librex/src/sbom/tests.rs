@@ -0,0 +1,175 @@
+use super::*;
+
+const SPDX_JSON: &str = r#"{
+    "spdxVersion": "SPDX-2.3",
+    "name": "app",
+    "packages": [
+        {"SPDXID": "SPDXRef-log4j", "name": "log4j-core", "versionInfo": "2.14.1",
+         "licenseConcluded": "NOASSERTION", "licenseDeclared": "Apache-2.0",
+         "externalRefs": [
+            {"referenceCategory": "SECURITY", "referenceType": "cpe23Type", "referenceLocator": "cpe:2.3:a:apache:log4j:2.14.1"},
+            {"referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1"}
+         ]},
+        {"SPDXID": "SPDXRef-musl", "name": "musl", "versionInfo": "1.2.4", "licenseConcluded": "MIT"}
+    ]
+}"#;
+
+const CYCLONEDX_JSON: &str = r#"{
+    "bomFormat": "CycloneDX",
+    "specVersion": "1.5",
+    "components": [
+        {"type": "library", "name": "openssl", "version": "3.1.4",
+         "licenses": [{"license": {"id": "Apache-2.0"}}], "purl": "pkg:apk/alpine/openssl@3.1.4",
+         "components": [{"type": "library", "name": "libcrypto3", "version": "3.1.4"}]},
+        {"type": "library", "name": "zlib", "version": "1.3",
+         "licenses": [{"expression": "Zlib OR MIT"}, {"license": {"name": "custom"}}]}
+    ]
+}"#;
+
+fn in_toto(predicate_type: &str, predicate: &str) -> String {
+    format!(
+        r#"{{"_type":"https://in-toto.io/Statement/v0.1","predicateType":"{}","subject":[],"predicate":{}}}"#,
+        predicate_type, predicate
+    )
+}
+
+fn dsse(payload: &str) -> String {
+    format!(
+        r#"{{"payloadType":"application/vnd.in-toto+json","payload":"{}","signatures":[]}}"#,
+        STANDARD.encode(payload)
+    )
+}
+
+#[test]
+fn test_spdx_packages() {
+    let document = SbomDocument::from_bytes(SPDX_JSON.as_bytes())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(document.format, SbomFormat::Spdx);
+    assert_eq!(document.predicate_type, None);
+    let packages = document.packages();
+    assert_eq!(packages.len(), 2);
+    assert_eq!(
+        packages[0],
+        Package {
+            name: "log4j-core".to_string(),
+            version: Some("2.14.1".to_string()),
+            license: Some("Apache-2.0".to_string()),
+            purl: Some("pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1".to_string()),
+        }
+    );
+    assert_eq!(packages[1].license.as_deref(), Some("MIT"));
+    assert_eq!(packages[1].purl, None);
+}
+
+#[test]
+fn test_cyclonedx_packages_include_nested_components() {
+    let document = SbomDocument::from_bytes(CYCLONEDX_JSON.as_bytes())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(document.format, SbomFormat::CycloneDx);
+    let names: Vec<String> = document.packages().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["openssl", "libcrypto3", "zlib"]);
+
+    let packages = document.packages();
+    assert_eq!(packages[0].license.as_deref(), Some("Apache-2.0"));
+    assert_eq!(
+        packages[0].purl.as_deref(),
+        Some("pkg:apk/alpine/openssl@3.1.4")
+    );
+    assert_eq!(packages[1].license, None);
+    assert_eq!(packages[2].license.as_deref(), Some("Zlib OR MIT, custom"));
+}
+
+#[test]
+fn test_in_toto_statement() {
+    let statement = in_toto("https://spdx.dev/Document", SPDX_JSON);
+
+    let document = SbomDocument::from_bytes(statement.as_bytes())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(document.format, SbomFormat::Spdx);
+    assert_eq!(
+        document.predicate_type.as_deref(),
+        Some("https://spdx.dev/Document")
+    );
+    assert_eq!(document.packages().len(), 2);
+}
+
+#[test]
+fn test_dsse_envelope_with_wrapped_predicate() {
+    // cosign's generic predicate: {"Data": "<json string>"}
+    let wrapped = serde_json::json!({ "Data": CYCLONEDX_JSON }).to_string();
+    let envelope = dsse(&in_toto("https://cyclonedx.org/bom", &wrapped));
+
+    let document = SbomDocument::from_bytes(envelope.as_bytes())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(document.format, SbomFormat::CycloneDx);
+    assert_eq!(
+        document.predicate_type.as_deref(),
+        Some("https://cyclonedx.org/bom")
+    );
+    assert_eq!(document.packages().len(), 3);
+}
+
+#[test]
+fn test_non_sbom_documents() {
+    let provenance = dsse(&in_toto(
+        "https://slsa.dev/provenance/v1",
+        r#"{"buildDefinition":{}}"#,
+    ));
+    assert_eq!(
+        SbomDocument::from_bytes(provenance.as_bytes()).unwrap(),
+        None
+    );
+    assert_eq!(SbomDocument::from_bytes(b"{}").unwrap(), None);
+
+    assert!(matches!(
+        SbomDocument::from_bytes(b"\x00binary"),
+        Err(RexError::Validation { .. })
+    ));
+    assert!(SbomDocument::from_bytes(br#"{"payloadType":"x","payload":"!!"}"#).is_err());
+}
+
+#[test]
+fn test_package_matches() {
+    let package = Package {
+        name: "log4j-core".to_string(),
+        version: Some("2.14.1".to_string()),
+        ..Default::default()
+    };
+
+    assert!(package.matches("LOG4J", None));
+    assert!(package.matches("log4j-core", Some("2.14.1")));
+    assert!(!package.matches("log4j", Some("2.15")));
+
+    let unversioned = Package {
+        name: "log4j-api".to_string(),
+        ..Default::default()
+    };
+    assert!(!unversioned.matches("log4j", Some("2")));
+}
+
+#[test]
+fn test_sbom_serializes_document_and_source() {
+    let sbom = Sbom {
+        source: SbomSource::AttestationTag,
+        manifest_digest: "sha256:aaaa".to_string(),
+        layer_digest: "sha256:bbbb".to_string(),
+        document: SbomDocument::from_bytes(SPDX_JSON.as_bytes())
+            .unwrap()
+            .unwrap(),
+    };
+
+    let json = serde_json::to_value(&sbom).unwrap();
+    assert_eq!(json["source"], "attestation-tag");
+    assert_eq!(json["format"], "spdx");
+    assert_eq!(json["document"]["spdxVersion"], "SPDX-2.3");
+    assert_eq!(sbom.source.to_string(), "attestation tag");
+    assert_eq!(SbomFormat::CycloneDx.to_string(), "CycloneDX");
+}
@@ -0,0 +1,520 @@
+//! Cosign signature discovery and offline, keyed verification.
+//!
+//! Cosign stores signatures in two places:
+//!
+//! - a manifest tagged `sha256-<hex>.sig` in the image's repository (the
+//!   original convention, still the default)
+//! - a referrer of the image manifest with artifact type
+//!   `application/vnd.dev.cosign.artifact.sig.v1+json` (OCI 1.1 mode)
+//!
+//! Either way every layer of the signature manifest is one signature: the
+//! layer blob is a "simple signing" JSON payload naming the signed manifest
+//! digest, and the `dev.cosignproject.cosign/signature` annotation holds the
+//! base64 signature over that payload.
+//!
+//! Only keyed verification is implemented: signatures are checked against a
+//! public key (ECDSA P-256/P-384 or Ed25519), without Rekor or Fulcio.
+
+use crate::client::referrers_tag;
+use crate::digest::Digest;
+use crate::error::{Result, RexError};
+use crate::oci::{ArtifactKind, Descriptor, ManifestOrIndex};
+use crate::reference::Reference;
+use crate::registry::Registry;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::signature::{self as ring_signature, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+/// Annotation holding the base64 signature of a layer.
+pub const SIGNATURE_ANNOTATION: &str = "dev.cosignproject.cosign/signature";
+
+/// Media type of a simple signing payload layer.
+pub const SIMPLE_SIGNING_MEDIA_TYPE: &str = "application/vnd.dev.cosign.simplesigning.v1+json";
+
+/// Artifact type of signatures stored as referrers.
+pub const SIGNATURE_ARTIFACT_TYPE: &str = "application/vnd.dev.cosign.artifact.sig.v1+json";
+
+/// Media type prefix of sigstore bundles, which are not supported.
+const SIGSTORE_BUNDLE_PREFIX: &str = "application/vnd.dev.sigstore.bundle";
+
+/// Returns the tag cosign stores the signatures of a manifest under.
+///
+/// This is the referrers tag schema (`<algorithm>-<hex>`) with a `.sig`
+/// suffix.
+///
+/// # Examples
+///
+/// ```
+/// use librex::signature::signature_tag;
+///
+/// let digest = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+/// assert_eq!(
+///     signature_tag(digest).unwrap(),
+///     "sha256-c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b.sig"
+/// );
+/// assert!(signature_tag("abc123").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns a validation error if `digest` is not a valid digest.
+pub fn signature_tag(digest: &str) -> Result<String> {
+    let digest = Digest::from_str(digest)?;
+    Ok(format!("{}.sig", referrers_tag(&digest)))
+}
+
+/// Algorithm of a public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// ECDSA on P-256 with SHA-256 (cosign's default)
+    EcdsaP256,
+    /// ECDSA on P-384 with SHA-384
+    EcdsaP384,
+    /// Ed25519
+    Ed25519,
+}
+
+impl fmt::Display for KeyAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyAlgorithm::EcdsaP256 => "ECDSA P-256",
+            KeyAlgorithm::EcdsaP384 => "ECDSA P-384",
+            KeyAlgorithm::Ed25519 => "Ed25519",
+        })
+    }
+}
+
+/// A public key to verify signatures with, as written by `cosign generate-key-pair`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    algorithm: KeyAlgorithm,
+    /// Raw key: an uncompressed EC point, or the 32 Ed25519 key bytes
+    key: Vec<u8>,
+}
+
+/// DER object identifiers of the supported key types.
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const OID_P256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const OID_P384: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+const OID_ED25519: &[u8] = &[0x2b, 0x65, 0x70];
+
+impl PublicKey {
+    /// Parses a PEM `PUBLIC KEY` (SubjectPublicKeyInfo).
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the PEM is malformed or the key type is
+    /// not ECDSA P-256/P-384 or Ed25519.
+    pub fn from_pem(pem: &str) -> Result<Self> {
+        const BEGIN: &str = "-----BEGIN PUBLIC KEY-----";
+        const END: &str = "-----END PUBLIC KEY-----";
+
+        let body = pem
+            .split_once(BEGIN)
+            .and_then(|(_, rest)| rest.split_once(END))
+            .map(|(body, _)| body)
+            .ok_or_else(|| RexError::validation("Public key is not a PEM 'PUBLIC KEY'"))?;
+        let base64: String = body.split_whitespace().collect();
+        let der = STANDARD.decode(base64).map_err(|e| {
+            RexError::validation_with_source("Public key PEM is not valid base64", e)
+        })?;
+
+        Self::from_spki_der(&der)
+    }
+
+    /// Parses a DER SubjectPublicKeyInfo.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the structure is malformed or the key
+    /// type is not supported.
+    pub fn from_spki_der(der: &[u8]) -> Result<Self> {
+        let invalid = || RexError::validation("Public key is not a valid SubjectPublicKeyInfo");
+
+        let (spki, _) = der_element(der, 0x30).ok_or_else(invalid)?;
+        let (algorithm, rest) = der_element(spki, 0x30).ok_or_else(invalid)?;
+        let (bit_string, _) = der_element(rest, 0x03).ok_or_else(invalid)?;
+        let (oid, parameters) = der_element(algorithm, 0x06).ok_or_else(invalid)?;
+
+        let algorithm = if oid == OID_ED25519 {
+            KeyAlgorithm::Ed25519
+        } else if oid == OID_EC_PUBLIC_KEY {
+            let (curve, _) = der_element(parameters, 0x06).ok_or_else(invalid)?;
+            if curve == OID_P256 {
+                KeyAlgorithm::EcdsaP256
+            } else if curve == OID_P384 {
+                KeyAlgorithm::EcdsaP384
+            } else {
+                return Err(RexError::validation(
+                    "Unsupported elliptic curve: only P-256 and P-384 are supported",
+                ));
+            }
+        } else {
+            return Err(RexError::validation(
+                "Unsupported public key type: only ECDSA and Ed25519 keys are supported",
+            ));
+        };
+
+        // The first byte of a BIT STRING counts unused bits; keys have none
+        match bit_string.split_first() {
+            Some((0, key)) if !key.is_empty() => Ok(PublicKey {
+                algorithm,
+                key: key.to_vec(),
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns the key's algorithm.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        self.algorithm
+    }
+
+    /// Verifies a signature over a message.
+    ///
+    /// ECDSA signatures are ASN.1 DER encoded, as cosign writes them.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the signature does not match.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        let algorithm: &dyn ring_signature::VerificationAlgorithm = match self.algorithm {
+            KeyAlgorithm::EcdsaP256 => &ring_signature::ECDSA_P256_SHA256_ASN1,
+            KeyAlgorithm::EcdsaP384 => &ring_signature::ECDSA_P384_SHA384_ASN1,
+            KeyAlgorithm::Ed25519 => &ring_signature::ED25519,
+        };
+
+        UnparsedPublicKey::new(algorithm, &self.key)
+            .verify(message, signature)
+            .map_err(|_| RexError::validation("Signature does not match the public key"))
+    }
+}
+
+impl FromStr for PublicKey {
+    type Err = RexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_pem(s)
+    }
+}
+
+/// Reads one DER element with the expected tag.
+///
+/// Returns the element's content and the bytes after it.
+fn der_element(input: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual, rest) = input.split_first()?;
+    if actual != tag {
+        return None;
+    }
+
+    let (&first, rest) = rest.split_first()?;
+    let (length, rest) = if first < 0x80 {
+        (first as usize, rest)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let length = rest[..count]
+            .iter()
+            .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        (length, &rest[count..])
+    };
+
+    (rest.len() >= length).then(|| rest.split_at(length))
+}
+
+/// The signed statement of a cosign signature.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimpleSigning {
+    /// Fields the signature vouches for
+    pub critical: Critical,
+    /// Extra claims added with `cosign sign -a key=value`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Critical section of a simple signing payload.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Critical {
+    /// Repository the image was signed in
+    pub identity: Identity,
+    /// The signed image
+    pub image: SignedImage,
+    /// Payload type ("cosign container image signature")
+    #[serde(rename = "type")]
+    pub payload_type: String,
+}
+
+/// Identity of a signed image.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identity {
+    /// Repository reference (e.g., "registry.example.com/app")
+    #[serde(rename = "docker-reference")]
+    pub docker_reference: String,
+}
+
+/// The image a payload signs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedImage {
+    /// Digest of the signed manifest
+    #[serde(rename = "docker-manifest-digest")]
+    pub docker_manifest_digest: String,
+}
+
+impl SimpleSigning {
+    /// Parses a simple signing payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the payload is not simple signing JSON.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            RexError::validation_with_source("Signature payload is not a simple signing payload", e)
+        })
+    }
+}
+
+/// Where a signature was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureSource {
+    /// The `sha256-<hex>.sig` tag
+    Tag,
+    /// The referrers API
+    Referrer,
+}
+
+impl fmt::Display for SignatureSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignatureSource::Tag => "tag",
+            SignatureSource::Referrer => "referrer",
+        })
+    }
+}
+
+/// One signature found for an image, not yet verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// Where the signature manifest was found
+    pub source: SignatureSource,
+    /// Digest of the signature manifest
+    pub manifest_digest: String,
+    /// Digest of the payload layer
+    pub layer_digest: String,
+    /// Media type of the payload layer
+    pub media_type: String,
+    /// The payload, as signed
+    pub payload: Vec<u8>,
+    /// Base64 signature from the layer annotation
+    pub signature: Option<String>,
+}
+
+/// Result of verifying one signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignatureCheck {
+    /// Where the signature manifest was found
+    pub source: SignatureSource,
+    /// Digest of the signature manifest
+    pub manifest_digest: String,
+    /// Digest of the payload layer
+    pub layer_digest: String,
+    /// Repository named in the payload
+    pub docker_reference: Option<String>,
+    /// Manifest digest named in the payload
+    pub signed_digest: Option<String>,
+    /// True if the signature matches the key and signs the image's digest
+    pub verified: bool,
+    /// Why verification failed
+    pub error: Option<String>,
+}
+
+impl Signature {
+    /// Verifies the signature against a key and the expected image digest.
+    ///
+    /// Never fails; the outcome, and the reason for a failure, are reported
+    /// in the returned [`SignatureCheck`].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The public key the image should be signed with
+    /// * `image_digest` - Digest of the image manifest (e.g., "sha256:...")
+    pub fn verify(&self, key: &PublicKey, image_digest: &str) -> SignatureCheck {
+        let payload = SimpleSigning::from_bytes(&self.payload).ok();
+        let mut check = SignatureCheck {
+            source: self.source,
+            manifest_digest: self.manifest_digest.clone(),
+            layer_digest: self.layer_digest.clone(),
+            docker_reference: payload
+                .as_ref()
+                .map(|p| p.critical.identity.docker_reference.clone()),
+            signed_digest: payload
+                .as_ref()
+                .map(|p| p.critical.image.docker_manifest_digest.clone()),
+            verified: false,
+            error: None,
+        };
+
+        match self.check(key, image_digest, payload.as_ref()) {
+            Ok(()) => check.verified = true,
+            Err(e) => check.error = Some(e),
+        }
+        check
+    }
+
+    fn check(
+        &self,
+        key: &PublicKey,
+        image_digest: &str,
+        payload: Option<&SimpleSigning>,
+    ) -> std::result::Result<(), String> {
+        if self.media_type.starts_with(SIGSTORE_BUNDLE_PREFIX) {
+            return Err("Sigstore bundles are not supported, only cosign simple signing".into());
+        }
+
+        let encoded = self
+            .signature
+            .as_deref()
+            .ok_or_else(|| format!("Layer has no '{}' annotation", SIGNATURE_ANNOTATION))?;
+        let signature = STANDARD
+            .decode(encoded.trim())
+            .map_err(|_| "Signature annotation is not valid base64".to_string())?;
+
+        key.verify(&self.payload, &signature)
+            .map_err(|e| e.to_string())?;
+
+        let payload = payload.ok_or("Signed payload is not a simple signing payload")?;
+        let signed = &payload.critical.image.docker_manifest_digest;
+        if signed != image_digest {
+            return Err(format!(
+                "Signature is for {}, not for the image ({})",
+                signed, image_digest
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Finds the cosign signatures of an image.
+///
+/// Looks at the `sha256-<hex>.sig` tag and at the image's referrers; a
+/// missing signature tag is not an error, and neither is a failed referrers
+/// lookup once the tag produced signatures.
+///
+/// # Arguments
+///
+/// * `registry` - The registry holding the image
+/// * `reference` - The image reference (tag or digest)
+///
+/// # Returns
+///
+/// The image's manifest digest and the signatures found.
+///
+/// # Errors
+///
+/// Returns an error if the image does not exist, if a signature manifest or
+/// payload cannot be fetched, or if the referrers cannot be listed and the
+/// tag held no signature.
+pub fn find_signatures(
+    registry: &mut Registry,
+    reference: &Reference,
+) -> Result<(String, Vec<Signature>)> {
+    let image_digest = registry.resolve_digest(reference)?;
+    let repository = reference
+        .repository_for_registry(registry.dockerhub_compat())
+        .to_string();
+
+    let mut signatures = Vec::new();
+
+    // Tag convention; the tag moves as signatures are added, so it is always
+    // fetched from the registry
+    let tag = signature_tag(&image_digest)?;
+    match registry.client().fetch_manifest(&repository, &tag) {
+        Ok((bytes, digest)) => {
+            signatures.extend(signature_layers(
+                registry,
+                &repository,
+                SignatureSource::Tag,
+                &bytes,
+                &digest,
+            )?);
+        }
+        Err(RexError::NotFound { .. }) => {}
+        Err(e) => return Err(e),
+    }
+
+    // Referrers (cosign's OCI 1.1 mode). Registries without the referrers
+    // API may answer with a server error rather than 404
+    let referrers = match registry.list_referrers(reference, None) {
+        Ok(referrers) => referrers,
+        Err(_) if !signatures.is_empty() => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    for descriptor in referrers.iter().filter(|d| is_signature_referrer(d)) {
+        let digest = descriptor.digest().to_string();
+        let (bytes, _) = registry.client().fetch_manifest(&repository, &digest)?;
+        signatures.extend(signature_layers(
+            registry,
+            &repository,
+            SignatureSource::Referrer,
+            &bytes,
+            &digest,
+        )?);
+    }
+
+    Ok((image_digest, signatures))
+}
+
+/// Returns true for referrers that hold cosign or sigstore signatures.
+fn is_signature_referrer(descriptor: &Descriptor) -> bool {
+    descriptor
+        .artifact_type()
+        .as_ref()
+        .and_then(|t| ArtifactKind::from_media_type(t.as_ref()))
+        == Some(ArtifactKind::CosignSignature)
+}
+
+/// Reads the signatures (one per layer) of a signature manifest.
+fn signature_layers(
+    registry: &mut Registry,
+    repository: &str,
+    source: SignatureSource,
+    manifest_bytes: &[u8],
+    manifest_digest: &str,
+) -> Result<Vec<Signature>> {
+    let manifest = match ManifestOrIndex::from_bytes(manifest_bytes)? {
+        ManifestOrIndex::Manifest(manifest) => manifest,
+        ManifestOrIndex::Index(_) => {
+            return Err(RexError::validation(format!(
+                "Signature manifest {} is an index",
+                manifest_digest
+            )));
+        }
+    };
+
+    let mut signatures = Vec::new();
+    for layer in manifest.layers() {
+        let digest = Digest::from_str(layer.digest().as_ref())?;
+        let payload = registry.get_blob(repository, &digest)?;
+        signatures.push(Signature {
+            source,
+            manifest_digest: manifest_digest.to_string(),
+            layer_digest: digest.to_string(),
+            media_type: layer.media_type().to_string(),
+            payload,
+            signature: layer
+                .annotations()
+                .as_ref()
+                .and_then(|a| a.get(SIGNATURE_ANNOTATION))
+                .cloned(),
+        });
+    }
+    Ok(signatures)
+}
@@ -0,0 +1,208 @@
+use super::*;
+use ring::rand::SystemRandom;
+use ring::signature::{
+    ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, Ed25519KeyPair, KeyPair as RingKeyPair,
+};
+
+const IMAGE_DIGEST: &str =
+    "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+/// SubjectPublicKeyInfo prefixes for raw public keys
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+const ED25519_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+/// An RSA SubjectPublicKeyInfo header (rsaEncryption), truncated
+const RSA_SPKI: &[u8] = &[
+    0x30, 0x12, 0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01, 0x05,
+    0x00, 0x03, 0x01, 0x00,
+];
+
+fn pem(prefix: &[u8], key: &[u8]) -> String {
+    let der = [prefix, key].concat();
+    format!(
+        "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+        STANDARD.encode(der)
+    )
+}
+
+fn p256_key_pair() -> EcdsaKeyPair {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+    EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap()
+}
+
+fn ed25519_key_pair() -> Ed25519KeyPair {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+}
+
+fn payload(digest: &str) -> Vec<u8> {
+    format!(
+        r#"{{"critical":{{"identity":{{"docker-reference":"localhost:5000/app"}},"image":{{"docker-manifest-digest":"{}"}},"type":"cosign container image signature"}},"optional":null}}"#,
+        digest
+    )
+    .into_bytes()
+}
+
+fn signature(payload: Vec<u8>, signature: Option<String>) -> Signature {
+    Signature {
+        source: SignatureSource::Tag,
+        manifest_digest: "sha256:aaaa".to_string(),
+        layer_digest: "sha256:bbbb".to_string(),
+        media_type: SIMPLE_SIGNING_MEDIA_TYPE.to_string(),
+        payload,
+        signature,
+    }
+}
+
+#[test]
+fn test_signature_tag() {
+    assert_eq!(
+        signature_tag(IMAGE_DIGEST).unwrap(),
+        "sha256-c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b.sig"
+    );
+    assert!(signature_tag("sha256:").is_err());
+}
+
+#[test]
+fn test_public_key_from_pem() {
+    let p256 = p256_key_pair();
+    let key = PublicKey::from_pem(&pem(P256_SPKI_PREFIX, p256.public_key().as_ref())).unwrap();
+    assert_eq!(key.algorithm(), KeyAlgorithm::EcdsaP256);
+
+    let ed25519 = ed25519_key_pair();
+    let key: PublicKey = pem(ED25519_SPKI_PREFIX, ed25519.public_key().as_ref())
+        .parse()
+        .unwrap();
+    assert_eq!(key.algorithm(), KeyAlgorithm::Ed25519);
+}
+
+#[test]
+fn test_public_key_rejects_invalid() {
+    assert!(matches!(
+        PublicKey::from_pem("not a key"),
+        Err(RexError::Validation { .. })
+    ));
+    assert!(
+        PublicKey::from_pem("-----BEGIN PUBLIC KEY-----\n!!!\n-----END PUBLIC KEY-----").is_err()
+    );
+
+    let error = PublicKey::from_spki_der(RSA_SPKI).unwrap_err();
+    assert!(error.to_string().contains("Unsupported public key type"));
+
+    // Truncated structure
+    assert!(PublicKey::from_spki_der(&P256_SPKI_PREFIX[..10]).is_err());
+}
+
+#[test]
+fn test_verify_ecdsa_signature() {
+    let key_pair = p256_key_pair();
+    let key = PublicKey::from_pem(&pem(P256_SPKI_PREFIX, key_pair.public_key().as_ref())).unwrap();
+    let payload = payload(IMAGE_DIGEST);
+    let signed = key_pair.sign(&SystemRandom::new(), &payload).unwrap();
+
+    let check =
+        signature(payload, Some(STANDARD.encode(signed.as_ref()))).verify(&key, IMAGE_DIGEST);
+
+    assert!(check.verified, "{:?}", check.error);
+    assert_eq!(check.signed_digest.as_deref(), Some(IMAGE_DIGEST));
+    assert_eq!(
+        check.docker_reference.as_deref(),
+        Some("localhost:5000/app")
+    );
+    assert_eq!(check.error, None);
+}
+
+#[test]
+fn test_verify_ed25519_signature() {
+    let key_pair = ed25519_key_pair();
+    let key =
+        PublicKey::from_pem(&pem(ED25519_SPKI_PREFIX, key_pair.public_key().as_ref())).unwrap();
+    let payload = payload(IMAGE_DIGEST);
+    let signed = key_pair.sign(&payload);
+
+    let check =
+        signature(payload, Some(STANDARD.encode(signed.as_ref()))).verify(&key, IMAGE_DIGEST);
+
+    assert!(check.verified, "{:?}", check.error);
+}
+
+#[test]
+fn test_verify_wrong_key() {
+    let signer = p256_key_pair();
+    let other = p256_key_pair();
+    let key = PublicKey::from_pem(&pem(P256_SPKI_PREFIX, other.public_key().as_ref())).unwrap();
+    let payload = payload(IMAGE_DIGEST);
+    let signed = signer.sign(&SystemRandom::new(), &payload).unwrap();
+
+    let check =
+        signature(payload, Some(STANDARD.encode(signed.as_ref()))).verify(&key, IMAGE_DIGEST);
+
+    assert!(!check.verified);
+    assert!(
+        check
+            .error
+            .unwrap()
+            .contains("does not match the public key")
+    );
+}
+
+#[test]
+fn test_verify_digest_mismatch() {
+    let key_pair = ed25519_key_pair();
+    let key =
+        PublicKey::from_pem(&pem(ED25519_SPKI_PREFIX, key_pair.public_key().as_ref())).unwrap();
+    // A valid signature, but for another image
+    let other = "sha256:1111111111111111111111111111111111111111111111111111111111111111";
+    let payload = payload(other);
+    let signed = key_pair.sign(&payload);
+
+    let check =
+        signature(payload, Some(STANDARD.encode(signed.as_ref()))).verify(&key, IMAGE_DIGEST);
+
+    assert!(!check.verified);
+    assert_eq!(check.signed_digest.as_deref(), Some(other));
+    assert!(check.error.unwrap().contains("not for the image"));
+}
+
+#[test]
+fn test_verify_missing_annotation_and_bundle() {
+    let key_pair = ed25519_key_pair();
+    let key =
+        PublicKey::from_pem(&pem(ED25519_SPKI_PREFIX, key_pair.public_key().as_ref())).unwrap();
+
+    let check = signature(payload(IMAGE_DIGEST), None).verify(&key, IMAGE_DIGEST);
+    assert!(!check.verified);
+    assert!(check.error.unwrap().contains(SIGNATURE_ANNOTATION));
+
+    let mut bundle = signature(b"{}".to_vec(), None);
+    bundle.media_type = "application/vnd.dev.sigstore.bundle.v0.3+json".to_string();
+    let check = bundle.verify(&key, IMAGE_DIGEST);
+    assert!(!check.verified);
+    assert!(
+        check
+            .error
+            .unwrap()
+            .contains("Sigstore bundles are not supported")
+    );
+}
+
+#[test]
+fn test_simple_signing_from_bytes() {
+    let payload = SimpleSigning::from_bytes(&payload(IMAGE_DIGEST)).unwrap();
+    assert_eq!(payload.critical.image.docker_manifest_digest, IMAGE_DIGEST);
+    assert_eq!(
+        payload.critical.payload_type,
+        "cosign container image signature"
+    );
+    assert!(payload.optional.is_none());
+
+    assert!(matches!(
+        SimpleSigning::from_bytes(b"[]"),
+        Err(RexError::Validation { .. })
+    ));
+}
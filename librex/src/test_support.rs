@@ -0,0 +1,201 @@
+//! Helpers shared by the unit tests: digests, manifest and index JSON,
+//! mocked registry endpoints and tar layers.
+
+use crate::digest::Digest;
+use mockito::{Matcher, Mock, Server};
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::str::FromStr;
+
+/// Media type of a gzip layer.
+pub(crate) const LAYER: &str = "application/vnd.oci.image.layer.v1.tar+gzip";
+
+/// Returns the sha256 digest string of `content`.
+pub(crate) fn sha256_of(content: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(content))
+}
+
+/// Returns the sha256 digest of `content`.
+pub(crate) fn digest_of(content: &[u8]) -> Digest {
+    Digest::from_str(&sha256_of(content)).unwrap()
+}
+
+/// Builds an image manifest referencing `config` and `layers`.
+pub(crate) fn manifest_json(config: &[u8], layers: &[(&[u8], &str)]) -> Vec<u8> {
+    let layers: Vec<serde_json::Value> = layers
+        .iter()
+        .map(|(data, media_type)| {
+            serde_json::json!({
+                "mediaType": media_type,
+                "digest": sha256_of(data),
+                "size": data.len(),
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": "application/vnd.oci.image.config.v1+json",
+            "digest": sha256_of(config),
+            "size": config.len(),
+        },
+        "layers": layers,
+    }))
+    .unwrap()
+}
+
+/// Builds an image index with one entry per (manifest, os, arch).
+pub(crate) fn index_json(manifests: &[(&[u8], &str, &str)]) -> Vec<u8> {
+    let manifests: Vec<serde_json::Value> = manifests
+        .iter()
+        .map(|(data, os, arch)| {
+            serde_json::json!({
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": sha256_of(data),
+                "size": data.len(),
+                "platform": {"os": os, "architecture": arch},
+            })
+        })
+        .collect();
+
+    serde_json::to_vec(&serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.index.v1+json",
+        "manifests": manifests,
+    }))
+    .unwrap()
+}
+
+/// Serves a manifest from `repository` under `reference`.
+pub(crate) fn serve_manifest(
+    server: &mut Server,
+    repository: &str,
+    reference: &str,
+    body: &[u8],
+) -> Mock {
+    let media_type = crate::oci::manifest_media_type(body).unwrap();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", &media_type)
+        .with_header("Docker-Content-Digest", &sha256_of(body))
+        .with_body(body)
+        .create()
+}
+
+/// Serves a blob from `repository`.
+pub(crate) fn serve_blob(server: &mut Server, repository: &str, data: &[u8]) -> Mock {
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/blobs/{}", repository, sha256_of(data)).as_str(),
+        )
+        .with_status(200)
+        .with_body(data)
+        .create()
+}
+
+/// Answers a blob HEAD request on `repository` with `status`.
+pub(crate) fn head_blob(server: &mut Server, repository: &str, data: &[u8], status: usize) -> Mock {
+    server
+        .mock(
+            "HEAD",
+            format!("/v2/{}/blobs/{}", repository, sha256_of(data)).as_str(),
+        )
+        .with_status(status)
+        .with_header("Content-Length", &data.len().to_string())
+        .create()
+}
+
+/// Accepts a monolithic upload of `data` to `repository`.
+pub(crate) fn accept_upload(server: &mut Server, repository: &str, data: &[u8]) -> (Mock, Mock) {
+    let session = format!("/v2/{}/blobs/uploads/{}", repository, sha256_of(data));
+    let post = server
+        .mock(
+            "POST",
+            format!("/v2/{}/blobs/uploads/", repository).as_str(),
+        )
+        .match_query(Matcher::Missing)
+        .with_status(202)
+        .with_header("Location", &session)
+        .create();
+    let put = server
+        .mock("PUT", session.as_str())
+        .match_query(Matcher::UrlEncoded("digest".into(), sha256_of(data)))
+        .match_body(data.to_vec())
+        .with_status(201)
+        .with_header("Docker-Content-Digest", &sha256_of(data))
+        .create();
+    (post, put)
+}
+
+/// Accepts a manifest push of `body` to `repository` under `reference`.
+pub(crate) fn accept_manifest(
+    server: &mut Server,
+    repository: &str,
+    reference: &str,
+    body: &[u8],
+) -> Mock {
+    server
+        .mock(
+            "PUT",
+            format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+        )
+        .match_body(body.to_vec())
+        .with_status(201)
+        .with_header("Docker-Content-Digest", &sha256_of(body))
+        .create()
+}
+
+/// Entries to put in a test tar: (path, content or link target, type)
+pub(crate) enum TarEntry<'a> {
+    File(&'a str, &'a str),
+    Dir(&'a str),
+    Symlink(&'a str, &'a str),
+    Hardlink(&'a str, &'a str),
+}
+
+/// Builds an uncompressed tar layer.
+pub(crate) fn tar(entries: &[TarEntry]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for entry in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_uid(1000);
+        header.set_gid(1000);
+        match entry {
+            TarEntry::File(path, content) => {
+                header.set_entry_type(tar::EntryType::Regular);
+                header.set_mode(0o640);
+                header.set_size(content.len() as u64);
+                builder
+                    .append_data(&mut header, path, content.as_bytes())
+                    .unwrap();
+            }
+            TarEntry::Dir(path) => {
+                header.set_entry_type(tar::EntryType::Directory);
+                header.set_mode(0o755);
+                header.set_size(0);
+                builder
+                    .append_data(&mut header, path, std::io::empty())
+                    .unwrap();
+            }
+            TarEntry::Symlink(path, target) => {
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_mode(0o777);
+                header.set_size(0);
+                builder.append_link(&mut header, path, target).unwrap();
+            }
+            TarEntry::Hardlink(path, target) => {
+                header.set_entry_type(tar::EntryType::Link);
+                header.set_mode(0o640);
+                header.set_size(0);
+                builder.append_link(&mut header, path, target).unwrap();
+            }
+        }
+    }
+    builder.into_inner().unwrap()
+}
@@ -0,0 +1,400 @@
+//! Deduplicated storage usage.
+//!
+//! Registries store each blob once, however many tags and repositories
+//! reference it, so adding up image sizes overstates what is stored. This
+//! module walks every tag of a registry, records which tags reference each
+//! blob (manifests, configs and layers), and reports for every tag,
+//! repository and for the registry:
+//!
+//! - total bytes: distinct blobs referenced
+//! - unique bytes: blobs nothing else references, freed if it is deleted
+//! - shared bytes: the rest, kept alive by other tags or repositories
+
+use crate::client::Client;
+use crate::copy::is_foreign;
+use crate::error::{Result, RexError};
+use crate::oci::ManifestOrIndex;
+use serde::Serialize;
+use sha2::{Digest as Sha2Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[cfg(test)]
+mod tests;
+
+/// Blobs stored for an image, its manifests included: digest → size.
+type BlobSet = BTreeMap<String, u64>;
+
+/// Progress of a usage scan.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsageEvent {
+    /// The catalog was listed; holds the number of repositories to scan
+    Catalog(usize),
+    /// A repository was scanned
+    Repository {
+        /// Repository name
+        name: String,
+        /// Number of tags read
+        tags: usize,
+    },
+    /// A repository or tag could not be read and was left out
+    Skipped {
+        /// Repository or image reference
+        reference: String,
+        /// Why it was skipped
+        error: String,
+    },
+}
+
+/// Storage used by one tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagUsage {
+    /// Tag name
+    pub tag: String,
+    /// Digest the tag points to
+    pub digest: String,
+    /// Bytes of the blobs the tag references
+    pub total_bytes: u64,
+    /// Bytes only this tag references
+    pub unique_bytes: u64,
+    /// Bytes also referenced by other tags or repositories
+    pub shared_bytes: u64,
+}
+
+/// Storage used by one repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepositoryUsage {
+    /// Repository name
+    pub name: String,
+    /// Bytes of the distinct blobs the repository's tags reference
+    pub total_bytes: u64,
+    /// Bytes no other repository references
+    pub unique_bytes: u64,
+    /// Bytes also referenced by other repositories
+    pub shared_bytes: u64,
+    /// Sum of the tag sizes, counting shared blobs once per tag
+    pub apparent_bytes: u64,
+    /// Usage of each tag, by tag name
+    pub tags: Vec<TagUsage>,
+}
+
+/// Storage used by a registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UsageReport {
+    /// Bytes of all distinct blobs referenced by a tag
+    pub total_bytes: u64,
+    /// Sum of the tag sizes, counting shared blobs once per tag
+    pub apparent_bytes: u64,
+    /// Bytes of the blobs referenced by more than one repository
+    pub shared_bytes: u64,
+    /// Number of distinct blobs
+    pub blobs: usize,
+    /// Usage of each repository, largest unique usage first
+    pub repositories: Vec<RepositoryUsage>,
+    /// Repositories and tags that could not be read ("reference: error")
+    pub skipped: Vec<String>,
+}
+
+impl UsageReport {
+    /// Bytes saved by deduplication (`apparent_bytes - total_bytes`).
+    pub fn saved_bytes(&self) -> u64 {
+        self.apparent_bytes.saturating_sub(self.total_bytes)
+    }
+}
+
+/// Which tags reference which blobs.
+///
+/// Filled tag by tag with [`UsageGraph::add_tag`], then turned into a
+/// [`UsageReport`].
+///
+/// # Examples
+///
+/// ```
+/// use librex::usage::UsageGraph;
+/// use std::collections::BTreeMap;
+///
+/// let base = ("sha256:aa".to_string(), 100);
+/// let mut graph = UsageGraph::new();
+/// graph.add_tag("app", "1.0", "sha256:m1", BTreeMap::from([base.clone(), ("sha256:c1".to_string(), 1)]));
+/// graph.add_tag("app", "1.1", "sha256:m2", BTreeMap::from([base, ("sha256:c2".to_string(), 2)]));
+///
+/// let report = graph.report();
+/// assert_eq!(report.total_bytes, 103);
+/// assert_eq!(report.apparent_bytes, 203);
+/// assert_eq!(report.repositories[0].tags[0].unique_bytes, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UsageGraph {
+    /// Blob digest → size and referencing (repository, tag) pairs
+    blobs: BTreeMap<String, (u64, BTreeSet<(String, String)>)>,
+    /// (repository, tag) → digest and blobs
+    tags: BTreeMap<(String, String), (String, BlobSet)>,
+    skipped: Vec<String>,
+}
+
+impl UsageGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a tag pointing at `digest` and the blobs it references
+    /// (digest → size), manifests included.
+    pub fn add_tag(
+        &mut self,
+        repository: &str,
+        tag: &str,
+        digest: &str,
+        blobs: BTreeMap<String, u64>,
+    ) {
+        let key = (repository.to_string(), tag.to_string());
+        for (blob, size) in &blobs {
+            self.blobs
+                .entry(blob.clone())
+                .or_insert_with(|| (*size, BTreeSet::new()))
+                .1
+                .insert(key.clone());
+        }
+        self.tags.insert(key, (digest.to_string(), blobs));
+    }
+
+    /// Records a repository or tag left out of the graph.
+    pub fn add_skipped(&mut self, reference: &str, error: &str) {
+        self.skipped.push(format!("{}: {}", reference, error));
+    }
+
+    /// Computes the usage of every tag and repository.
+    pub fn report(&self) -> UsageReport {
+        let mut repositories: BTreeMap<&str, RepositoryUsage> = BTreeMap::new();
+        let mut repository_blobs: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+
+        for ((repository, tag), (digest, blobs)) in &self.tags {
+            let unique_bytes = blobs
+                .iter()
+                .filter(|(blob, _)| self.referrers(blob).len() == 1)
+                .map(|(_, size)| size)
+                .sum();
+            let total_bytes = blobs.values().sum();
+
+            let usage = repositories
+                .entry(repository)
+                .or_insert_with(|| RepositoryUsage {
+                    name: repository.clone(),
+                    total_bytes: 0,
+                    unique_bytes: 0,
+                    shared_bytes: 0,
+                    apparent_bytes: 0,
+                    tags: Vec::new(),
+                });
+            usage.apparent_bytes += total_bytes;
+            usage.tags.push(TagUsage {
+                tag: tag.clone(),
+                digest: digest.clone(),
+                total_bytes,
+                unique_bytes,
+                shared_bytes: total_bytes - unique_bytes,
+            });
+            repository_blobs
+                .entry(repository)
+                .or_default()
+                .extend(blobs.keys().map(String::as_str));
+        }
+
+        for (repository, usage) in repositories.iter_mut() {
+            for blob in &repository_blobs[repository] {
+                let (size, referrers) = &self.blobs[*blob];
+                usage.total_bytes += size;
+                if referrers.iter().all(|(other, _)| other == repository) {
+                    usage.unique_bytes += size;
+                }
+            }
+            usage.shared_bytes = usage.total_bytes - usage.unique_bytes;
+        }
+
+        let mut repositories: Vec<RepositoryUsage> = repositories.into_values().collect();
+        repositories.sort_by(|a, b| {
+            b.unique_bytes
+                .cmp(&a.unique_bytes)
+                .then_with(|| b.total_bytes.cmp(&a.total_bytes))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let shared_bytes = self
+            .blobs
+            .values()
+            .filter(|(_, referrers)| {
+                let mut names = referrers.iter().map(|(repository, _)| repository);
+                names
+                    .next()
+                    .is_some_and(|first| names.any(|other| other != first))
+            })
+            .map(|(size, _)| size)
+            .sum();
+
+        UsageReport {
+            total_bytes: self.blobs.values().map(|(size, _)| size).sum(),
+            apparent_bytes: repositories.iter().map(|r| r.apparent_bytes).sum(),
+            shared_bytes,
+            blobs: self.blobs.len(),
+            repositories,
+            skipped: self.skipped.clone(),
+        }
+    }
+
+    fn referrers(&self, blob: &str) -> &BTreeSet<(String, String)> {
+        &self.blobs[blob].1
+    }
+}
+
+/// Walks tags and manifests, remembering what each manifest references.
+struct Scanner<'a, F> {
+    client: &'a Client,
+    /// Manifest digest → blobs below it (content-addressed, so valid in
+    /// every repository)
+    manifests: HashMap<String, BlobSet>,
+    on_event: F,
+}
+
+impl<F: FnMut(&UsageEvent)> Scanner<'_, F> {
+    /// Adds every tag of `repository` to the graph.
+    fn scan_repository(&mut self, graph: &mut UsageGraph, repository: &str) {
+        let tags = match self.client.fetch_tags(repository) {
+            Ok(tags) => tags,
+            Err(e) => {
+                self.skip(graph, repository, &e);
+                return;
+            }
+        };
+
+        let mut scanned = 0;
+        for tag in &tags {
+            match self.image_blobs(repository, tag) {
+                Ok((digest, blobs)) => {
+                    graph.add_tag(repository, tag, &digest, blobs);
+                    scanned += 1;
+                }
+                Err(e) => self.skip(graph, &format!("{}:{}", repository, tag), &e),
+            }
+        }
+
+        (self.on_event)(&UsageEvent::Repository {
+            name: repository.to_string(),
+            tags: scanned,
+        });
+    }
+
+    /// Fetches a manifest or index and collects the blobs below it.
+    fn image_blobs(&mut self, repository: &str, reference: &str) -> Result<(String, BlobSet)> {
+        let (bytes, _) = self.client.fetch_manifest(repository, reference)?;
+        let digest = format!("sha256:{:x}", Sha256::digest(&bytes));
+        if let Some(blobs) = self.manifests.get(&digest) {
+            return Ok((digest, blobs.clone()));
+        }
+
+        let mut blobs = BlobSet::from([(digest.clone(), bytes.len() as u64)]);
+        match ManifestOrIndex::from_bytes(&bytes)? {
+            ManifestOrIndex::Manifest(manifest) => {
+                for descriptor in std::iter::once(manifest.config()).chain(manifest.layers()) {
+                    // Foreign layers live outside the registry
+                    if !is_foreign(descriptor) {
+                        blobs.insert(descriptor.digest().to_string(), descriptor.size());
+                    }
+                }
+            }
+            ManifestOrIndex::Index(index) => {
+                for child in index.manifests() {
+                    let child_digest = child.digest().to_string();
+                    let child_blobs = match self.manifests.get(&child_digest) {
+                        Some(child_blobs) => child_blobs.clone(),
+                        None => self.image_blobs(repository, &child_digest)?.1,
+                    };
+                    blobs.extend(child_blobs);
+                }
+            }
+        }
+
+        self.manifests.insert(digest.clone(), blobs.clone());
+        Ok((digest, blobs))
+    }
+
+    fn skip(&mut self, graph: &mut UsageGraph, reference: &str, error: &RexError) {
+        graph.add_skipped(reference, &error.to_string());
+        (self.on_event)(&UsageEvent::Skipped {
+            reference: reference.to_string(),
+            error: error.to_string(),
+        });
+    }
+}
+
+/// Scans repositories into a usage graph.
+///
+/// Tags and repositories that cannot be read are recorded as skipped
+/// rather than failing the scan, so one broken image does not hide the
+/// usage of the rest.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `repositories` - Repositories to scan
+/// * `on_event` - Called after each repository, and for each skipped tag
+///
+/// # Returns
+///
+/// The graph of tags and the blobs they reference.
+pub fn scan<F>(client: &Client, repositories: &[String], on_event: F) -> UsageGraph
+where
+    F: FnMut(&UsageEvent),
+{
+    let mut scanner = Scanner {
+        client,
+        manifests: HashMap::new(),
+        on_event,
+    };
+    let mut graph = UsageGraph::new();
+    for repository in repositories {
+        scanner.scan_repository(&mut graph, repository);
+    }
+    graph
+}
+
+/// Computes the deduplicated storage usage of a whole registry.
+///
+/// Tags and manifests are read from the registry, not from the cache: the
+/// report reflects what is stored now.
+///
+/// # Arguments
+///
+/// * `client` - Client connected to the registry
+/// * `on_event` - Called as the scan progresses
+///
+/// # Returns
+///
+/// A [`UsageReport`] with repositories sorted by unique bytes, largest first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::usage::analyze;
+///
+/// # fn example() -> librex::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+///
+/// let report = analyze(&client, |_| {})?;
+/// for repository in report.repositories.iter().take(5) {
+///     println!("{}: {} bytes only it uses", repository.name, repository.unique_bytes);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the catalog cannot be listed.
+pub fn analyze<F>(client: &Client, mut on_event: F) -> Result<UsageReport>
+where
+    F: FnMut(&UsageEvent),
+{
+    let repositories = client.fetch_catalog()?;
+    on_event(&UsageEvent::Catalog(repositories.len()));
+    Ok(scan(client, &repositories, on_event).report())
+}
@@ -0,0 +1,168 @@
+use super::*;
+use crate::test_support::{LAYER, index_json, manifest_json, serve_manifest, sha256_of};
+use mockito::Server;
+use serde_json::json;
+
+fn blobs(entries: &[(&str, u64)]) -> BlobSet {
+    entries
+        .iter()
+        .map(|(digest, size)| (digest.to_string(), *size))
+        .collect()
+}
+
+fn serve_tags(server: &mut Server, repository: &str, tags: &[&str]) {
+    server
+        .mock("GET", format!("/v2/{}/tags/list", repository).as_str())
+        .with_status(200)
+        .with_body(json!({"name": repository, "tags": tags}).to_string())
+        .create();
+}
+
+#[test]
+fn test_report_counts_shared_blobs_once() {
+    let mut graph = UsageGraph::new();
+    // Two tags of app share the base layer with web
+    graph.add_tag(
+        "app",
+        "1.0",
+        "sha256:m1",
+        blobs(&[("sha256:m1", 1), ("sha256:base", 100), ("sha256:a1", 20)]),
+    );
+    graph.add_tag(
+        "app",
+        "1.1",
+        "sha256:m2",
+        blobs(&[("sha256:m2", 1), ("sha256:base", 100), ("sha256:a2", 30)]),
+    );
+    graph.add_tag(
+        "web",
+        "latest",
+        "sha256:m3",
+        blobs(&[("sha256:m3", 1), ("sha256:base", 100), ("sha256:w", 500)]),
+    );
+    graph.add_skipped("broken:1.0", "manifest unknown");
+
+    let report = graph.report();
+
+    assert_eq!(report.total_bytes, 653);
+    assert_eq!(report.apparent_bytes, 121 + 131 + 601);
+    assert_eq!(report.saved_bytes(), 200);
+    assert_eq!(report.shared_bytes, 100);
+    assert_eq!(report.blobs, 7);
+    assert_eq!(report.skipped, vec!["broken:1.0: manifest unknown"]);
+
+    // Sorted by unique bytes
+    let web = &report.repositories[0];
+    assert_eq!(web.name, "web");
+    assert_eq!(
+        (web.total_bytes, web.unique_bytes, web.shared_bytes),
+        (601, 501, 100)
+    );
+
+    let app = &report.repositories[1];
+    assert_eq!(app.name, "app");
+    assert_eq!(
+        (
+            app.total_bytes,
+            app.unique_bytes,
+            app.shared_bytes,
+            app.apparent_bytes
+        ),
+        (152, 52, 100, 252)
+    );
+    assert_eq!(
+        app.tags[0],
+        TagUsage {
+            tag: "1.0".to_string(),
+            digest: "sha256:m1".to_string(),
+            total_bytes: 121,
+            unique_bytes: 21,
+            shared_bytes: 100,
+        }
+    );
+}
+
+#[test]
+fn test_report_tag_sharing_within_a_repository() {
+    let mut graph = UsageGraph::new();
+    // Two tags on the same manifest: nothing is unique to either tag, but
+    // all of it is unique to the repository
+    let image = blobs(&[("sha256:m", 2), ("sha256:l", 40)]);
+    graph.add_tag("app", "latest", "sha256:m", image.clone());
+    graph.add_tag("app", "1.0", "sha256:m", image);
+
+    let report = graph.report();
+    let app = &report.repositories[0];
+
+    assert_eq!(app.total_bytes, 42);
+    assert_eq!(app.unique_bytes, 42);
+    assert_eq!(app.apparent_bytes, 84);
+    assert!(app.tags.iter().all(|tag| tag.unique_bytes == 0));
+    assert_eq!(report.shared_bytes, 0);
+}
+
+#[test]
+fn test_analyze_walks_indexes_and_skips_broken_tags() {
+    let base = [b'b'; 100];
+    let amd64 = manifest_json(&[b'c'; 10], &[(&base, LAYER), (&[b'1'; 50], LAYER)]);
+    let arm64 = manifest_json(&[b'd'; 10], &[(&base, LAYER), (&[b'2'; 60], LAYER)]);
+    let index = index_json(&[(&amd64, "linux", "amd64"), (&arm64, "linux", "arm64")]);
+    // The tool image reuses the amd64 base layer
+    let tool = manifest_json(&[b'e'; 10], &[(&base, LAYER), (&[b'3'; 5], LAYER)]);
+
+    let mut server = Server::new();
+    server
+        .mock("GET", "/v2/_catalog")
+        .with_status(200)
+        .with_body(json!({"repositories": ["app", "tool"]}).to_string())
+        .create();
+    serve_tags(&mut server, "app", &["1.0", "gone"]);
+    serve_tags(&mut server, "tool", &["latest"]);
+    serve_manifest(&mut server, "app", "1.0", &index);
+    serve_manifest(&mut server, "app", &sha256_of(&amd64), &amd64);
+    serve_manifest(&mut server, "app", &sha256_of(&arm64), &arm64);
+    serve_manifest(&mut server, "tool", "latest", &tool);
+    server
+        .mock("GET", "/v2/app/manifests/gone")
+        .with_status(404)
+        .create();
+    let client = Client::new(&server.url(), None).unwrap();
+    let mut events = Vec::new();
+
+    let report = analyze(&client, |event| events.push(event.clone())).unwrap();
+
+    let manifests = (index.len() + amd64.len() + arm64.len()) as u64;
+    let app = report
+        .repositories
+        .iter()
+        .find(|r| r.name == "app")
+        .unwrap();
+    assert_eq!(app.tags.len(), 1);
+    assert_eq!(app.total_bytes, manifests + 10 + 10 + 100 + 50 + 60);
+    assert_eq!(app.unique_bytes, app.total_bytes - 100);
+    assert_eq!(app.tags[0].digest, sha256_of(&index));
+
+    let tool_usage = report
+        .repositories
+        .iter()
+        .find(|r| r.name == "tool")
+        .unwrap();
+    assert_eq!(tool_usage.unique_bytes, tool.len() as u64 + 10 + 5);
+    assert_eq!(report.shared_bytes, 100);
+    assert_eq!(
+        report.total_bytes,
+        app.total_bytes + tool_usage.total_bytes - 100
+    );
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].starts_with("app:gone: "));
+
+    assert_eq!(events[0], UsageEvent::Catalog(2));
+    assert!(matches!(&events[1], UsageEvent::Skipped { reference, .. } if reference == "app:gone"));
+    assert_eq!(
+        events[2],
+        UsageEvent::Repository {
+            name: "app".to_string(),
+            tags: 1,
+        }
+    );
+}
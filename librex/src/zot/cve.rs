@@ -0,0 +1,269 @@
+//! Vulnerability reports from Zot's CVE scanning.
+//!
+//! Zot scans images with Trivy and serves the results through its GraphQL
+//! search extension: `CVEListForImage` lists the CVEs of one image and
+//! `ImageListForCVE` lists the images affected by one CVE. A registry
+//! without a configured scanner answers both queries with a GraphQL error.
+
+use super::search::{IMAGE_FIELDS, ImageSummary, execute, list, string_literal};
+use crate::client::Client;
+use crate::error::{Result, RexError};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Fields requested for every CVE.
+const CVE_FIELDS: &str =
+    "Id Title Description Severity PackageList { Name InstalledVersion FixedVersion }";
+
+/// Severity of a CVE, ordered from least to most severe.
+///
+/// # Examples
+///
+/// ```
+/// use librex::zot::Severity;
+///
+/// let severity: Severity = "high".parse().unwrap();
+/// assert!(severity > Severity::Medium);
+/// assert_eq!(severity.to_string(), "HIGH");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Severity {
+    /// Not rated by the scanner
+    #[default]
+    Unknown,
+    /// Low
+    Low,
+    /// Medium
+    Medium,
+    /// High
+    High,
+    /// Critical
+    Critical,
+}
+
+impl Severity {
+    /// All severities, from most to least severe.
+    pub const ALL: [Severity; 5] = [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Unknown,
+    ];
+
+    /// Returns the upper-case name used by Zot (e.g., "HIGH").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Unknown => "UNKNOWN",
+            Severity::Low => "LOW",
+            Severity::Medium => "MEDIUM",
+            Severity::High => "HIGH",
+            Severity::Critical => "CRITICAL",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Severity {
+    type Err = RexError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "UNKNOWN" | "NONE" | "" => Ok(Severity::Unknown),
+            "LOW" => Ok(Severity::Low),
+            "MEDIUM" => Ok(Severity::Medium),
+            "HIGH" => Ok(Severity::High),
+            "CRITICAL" => Ok(Severity::Critical),
+            _ => Err(RexError::validation(format!(
+                "Invalid severity '{}': expected one of critical, high, medium, low, unknown",
+                s
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Severity {
+    /// Zot reports unrated CVEs with an empty or unexpected severity; those
+    /// are kept as `Unknown` rather than failing the whole report.
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Option::<String>::deserialize(deserializer)?;
+        Ok(value
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(Severity::Unknown))
+    }
+}
+
+/// A package affected by a CVE.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct VulnerablePackage {
+    /// Package name
+    #[serde(default)]
+    pub name: String,
+    /// Version installed in the image
+    #[serde(default)]
+    pub installed_version: Option<String>,
+    /// First version with a fix, if any
+    #[serde(default)]
+    pub fixed_version: Option<String>,
+}
+
+/// A vulnerability found in an image.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Cve {
+    /// CVE identifier (e.g., "CVE-2024-1234")
+    pub id: String,
+    /// Short title
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Description
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Severity
+    #[serde(default)]
+    pub severity: Severity,
+    /// Affected packages
+    #[serde(default, rename = "PackageList", deserialize_with = "list")]
+    pub packages: Vec<VulnerablePackage>,
+}
+
+/// CVEs found in one image.
+///
+/// # Examples
+///
+/// ```
+/// use librex::zot::{Cve, CveReport, Severity};
+///
+/// let report = CveReport {
+///     tag: "3.20".to_string(),
+///     cves: vec![
+///         Cve { id: "CVE-1".to_string(), severity: Severity::High, ..Default::default() },
+///         Cve { id: "CVE-2".to_string(), severity: Severity::Low, ..Default::default() },
+///     ],
+/// };
+///
+/// assert_eq!(report.max_severity(), Some(Severity::High));
+/// assert_eq!(report.at_least(Severity::Medium).cves.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CveReport {
+    /// Tag of the scanned image
+    #[serde(default)]
+    pub tag: String,
+    /// CVEs found, as ordered by Zot
+    #[serde(default, rename = "CVEList", deserialize_with = "list")]
+    pub cves: Vec<Cve>,
+}
+
+impl CveReport {
+    /// Returns the highest severity in the report, `None` if it is empty.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.cves.iter().map(|cve| cve.severity).max()
+    }
+
+    /// Counts the CVEs of each severity, most severe first.
+    pub fn counts(&self) -> Vec<(Severity, usize)> {
+        Severity::ALL
+            .iter()
+            .map(|&severity| {
+                let count = self
+                    .cves
+                    .iter()
+                    .filter(|cve| cve.severity == severity)
+                    .count();
+                (severity, count)
+            })
+            .collect()
+    }
+
+    /// Returns a report with only the CVEs at or above `severity`.
+    pub fn at_least(&self, severity: Severity) -> CveReport {
+        CveReport {
+            tag: self.tag.clone(),
+            cves: self
+                .cves
+                .iter()
+                .filter(|cve| cve.severity >= severity)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Lists the CVEs found in an image.
+///
+/// # Arguments
+///
+/// * `client` - Client for a registry offering the search extension
+/// * `image` - The image, as `repository:tag` or `repository@digest`
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::zot;
+///
+/// # fn example() -> librex::error::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+/// let report = zot::cve_list_for_image(&client, "alpine:3.20")?;
+/// for cve in &report.cves {
+///     println!("{} {}", cve.id, cve.severity);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The registry does not offer the search extension (`NotFound`)
+/// - The registry has no CVE scanner configured, or the image is unknown
+/// - The registry is unreachable
+/// - Authentication is required but not provided
+pub fn cve_list_for_image(client: &Client, image: &str) -> Result<CveReport> {
+    let graphql = format!(
+        "{{ CVEListForImage(image: {}) {{ Tag CVEList {{ {} }} }} }}",
+        string_literal(image),
+        CVE_FIELDS
+    );
+    execute(client, &graphql, "CVEListForImage")
+}
+
+/// Lists the images affected by a CVE.
+///
+/// # Arguments
+///
+/// * `client` - Client for a registry offering the search extension
+/// * `id` - The CVE identifier (e.g., "CVE-2024-1234")
+///
+/// # Errors
+///
+/// Returns an error if the query fails, see [`cve_list_for_image`].
+pub fn image_list_for_cve(client: &Client, id: &str) -> Result<Vec<ImageSummary>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PaginatedImages {
+        #[serde(default, deserialize_with = "list")]
+        results: Vec<ImageSummary>,
+    }
+
+    let graphql = format!(
+        "{{ ImageListForCVE(id: {}) {{ Results {{ {} }} }} }}",
+        string_literal(id),
+        IMAGE_FIELDS
+    );
+    let images: PaginatedImages = execute(client, &graphql, "ImageListForCVE")?;
+    Ok(images.results)
+}
@@ -0,0 +1,132 @@
+use super::*;
+use mockito::Matcher;
+
+const CVE_LIST_RESPONSE: &str = r#"{
+    "data": {
+        "CVEListForImage": {
+            "Tag": "3.20",
+            "CVEList": [
+                {
+                    "Id": "CVE-2024-0001",
+                    "Title": "openssl: buffer overflow",
+                    "Severity": "CRITICAL",
+                    "PackageList": [
+                        {"Name": "openssl", "InstalledVersion": "3.1.0", "FixedVersion": "3.1.5"}
+                    ]
+                },
+                {"Id": "CVE-2024-0002", "Severity": "LOW", "PackageList": null},
+                {"Id": "CVE-2024-0003", "Severity": "", "PackageList": []},
+                {"Id": "CVE-2024-0004", "Severity": "HIGH"}
+            ]
+        }
+    }
+}"#;
+
+fn search_mock(server: &mut mockito::ServerGuard, pattern: &str, body: &str) -> mockito::Mock {
+    server
+        .mock("GET", SEARCH_ENDPOINT)
+        .match_query(Matcher::Regex(pattern.to_string()))
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn test_severity_order_and_parse() {
+    assert!(Severity::Critical > Severity::High);
+    assert!(Severity::Low > Severity::Unknown);
+    assert_eq!("Critical".parse::<Severity>().unwrap(), Severity::Critical);
+    assert_eq!("none".parse::<Severity>().unwrap(), Severity::Unknown);
+    assert!(matches!(
+        "severe".parse::<Severity>(),
+        Err(RexError::Validation { .. })
+    ));
+}
+
+#[test]
+fn test_cve_list_for_image() {
+    let mut server = mockito::Server::new();
+    let mock = search_mock(
+        &mut server,
+        r#"CVEListForImage%28image%3A\+%22alpine%3A3.20%22%29"#,
+        CVE_LIST_RESPONSE,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let report = cve_list_for_image(&client, "alpine:3.20").unwrap();
+
+    mock.assert();
+    assert_eq!(report.tag, "3.20");
+    assert_eq!(report.cves.len(), 4);
+
+    let first = &report.cves[0];
+    assert_eq!(first.id, "CVE-2024-0001");
+    assert_eq!(first.severity, Severity::Critical);
+    assert_eq!(first.packages[0].fixed_version.as_deref(), Some("3.1.5"));
+
+    assert!(report.cves[1].packages.is_empty());
+    assert_eq!(report.cves[2].severity, Severity::Unknown);
+}
+
+#[test]
+fn test_cve_report_counts_and_filter() {
+    let report: CveReport = serde_json::from_value(
+        serde_json::from_str::<serde_json::Value>(CVE_LIST_RESPONSE).unwrap()["data"]
+            ["CVEListForImage"]
+            .clone(),
+    )
+    .unwrap();
+
+    assert_eq!(report.max_severity(), Some(Severity::Critical));
+    assert_eq!(
+        report.counts(),
+        vec![
+            (Severity::Critical, 1),
+            (Severity::High, 1),
+            (Severity::Medium, 0),
+            (Severity::Low, 1),
+            (Severity::Unknown, 1),
+        ]
+    );
+
+    let high = report.at_least(Severity::High);
+    let ids: Vec<&str> = high.cves.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["CVE-2024-0001", "CVE-2024-0004"]);
+    assert_eq!(CveReport::default().max_severity(), None);
+}
+
+#[test]
+fn test_cve_list_without_scanner() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "CVEListForImage",
+        r#"{"errors":[{"message":"cve search is disabled"}],"data":null}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let error = cve_list_for_image(&client, "alpine:3.20").unwrap_err();
+
+    assert!(matches!(error, RexError::Validation { .. }));
+    assert!(error.to_string().contains("cve search is disabled"));
+}
+
+#[test]
+fn test_image_list_for_cve() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "ImageListForCVE",
+        r#"{"data":{"ImageListForCVE":{"Results":[
+            {"RepoName":"alpine","Tag":"3.19","Digest":"sha256:aaaa"},
+            {"RepoName":"nginx","Tag":"1.25","Digest":"sha256:bbbb"}
+        ]}}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let images = image_list_for_cve(&client, "CVE-2024-0001").unwrap();
+
+    let references: Vec<String> = images.iter().map(ImageSummary::reference).collect();
+    assert_eq!(references, vec!["alpine:3.19", "nginx:1.25"]);
+}
@@ -0,0 +1,204 @@
+//! Zot registry extensions.
+//!
+//! Registries list their extensions at `GET /v2/_oci/ext/discover` (OCI
+//! Distribution extensions). Zot advertises its `_zot` extension there, with
+//! one endpoint per feature that is enabled in its configuration: search
+//! (GraphQL, which also answers CVE queries), user preferences and
+//! management.
+//!
+//! Plain Distribution registries answer the discover endpoint with `404`;
+//! they are reported without capabilities rather than as an error, so callers
+//! can fall back to the standard API.
+
+use crate::client::Client;
+use crate::error::{Result, RexError};
+use serde::{Deserialize, Serialize};
+
+mod cve;
+mod search;
+
+pub use cve::{
+    Cve, CveReport, Severity, VulnerablePackage, cve_list_for_image, image_list_for_cve,
+};
+pub use search::{
+    GlobalSearchResult, ImageSummary, RepoInfo, RepoSummary, expanded_repo_info, global_search,
+    image_list,
+};
+
+/// Path of the OCI extension discovery endpoint.
+pub const DISCOVER_PATH: &str = "/v2/_oci/ext/discover";
+
+/// Name of the extension Zot advertises.
+pub const ZOT_EXTENSION: &str = "_zot";
+
+/// Zot's GraphQL search endpoint.
+pub const SEARCH_ENDPOINT: &str = "/v2/_zot/ext/search";
+
+/// Zot's user preferences endpoint (stars and bookmarks).
+pub const USER_PREFS_ENDPOINT: &str = "/v2/_zot/ext/userprefs";
+
+/// Zot's management endpoint.
+pub const MGMT_ENDPOINT: &str = "/v2/_zot/ext/mgmt";
+
+/// An extension advertised by the discover endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Extension {
+    /// Extension name (e.g., "_zot")
+    pub name: String,
+    /// Link to the extension's documentation
+    #[serde(default)]
+    pub url: String,
+    /// Short description
+    #[serde(default)]
+    pub description: String,
+    /// Endpoints served by the extension
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+}
+
+/// Body of the discover endpoint.
+#[derive(Debug, Deserialize)]
+struct DiscoverResponse {
+    #[serde(rename = "distSpecVersion")]
+    dist_spec_version: Option<String>,
+    #[serde(default)]
+    extensions: Option<Vec<Extension>>,
+}
+
+/// Features a registry offers beyond the OCI Distribution API.
+///
+/// The default value describes a plain Distribution registry.
+///
+/// # Examples
+///
+/// ```
+/// use librex::zot::RegistryCapabilities;
+///
+/// let bytes = br#"{
+///     "distSpecVersion": "1.1.0",
+///     "extensions": [{
+///         "name": "_zot",
+///         "endpoints": ["/v2/_zot/ext/search", "/v2/_zot/ext/userprefs"]
+///     }]
+/// }"#;
+///
+/// let capabilities = RegistryCapabilities::from_discovery(bytes).unwrap();
+/// assert!(capabilities.is_zot());
+/// assert!(capabilities.search);
+/// assert!(!capabilities.mgmt);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RegistryCapabilities {
+    /// Distribution spec version reported by the registry
+    pub dist_spec_version: Option<String>,
+    /// Extensions as advertised
+    pub extensions: Vec<Extension>,
+    /// GraphQL search (`/v2/_zot/ext/search`)
+    pub search: bool,
+    /// CVE scan results, queried through the search extension
+    pub cve: bool,
+    /// User preferences: stars and bookmarks (`/v2/_zot/ext/userprefs`)
+    pub user_prefs: bool,
+    /// Management API (`/v2/_zot/ext/mgmt`)
+    pub mgmt: bool,
+}
+
+impl RegistryCapabilities {
+    /// Parses the body of the discover endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the body is not a discover response.
+    pub fn from_discovery(bytes: &[u8]) -> Result<Self> {
+        let response: DiscoverResponse = serde_json::from_slice(bytes).map_err(|e| {
+            RexError::validation_with_source("Failed to parse extension discovery response", e)
+        })?;
+
+        let extensions = response.extensions.unwrap_or_default();
+        let serves = |endpoint: &str| {
+            extensions
+                .iter()
+                .any(|ext| ext.endpoints.iter().any(|e| e == endpoint))
+        };
+        let search = serves(SEARCH_ENDPOINT);
+
+        Ok(RegistryCapabilities {
+            dist_spec_version: response.dist_spec_version,
+            search,
+            // Zot has no separate CVE endpoint; whether a scanner is
+            // configured only shows when querying
+            cve: search,
+            user_prefs: serves(USER_PREFS_ENDPOINT),
+            mgmt: serves(MGMT_ENDPOINT),
+            extensions,
+        })
+    }
+
+    /// Returns true if the registry advertises Zot's extension.
+    pub fn is_zot(&self) -> bool {
+        self.extensions.iter().any(|ext| ext.name == ZOT_EXTENSION)
+    }
+
+    /// Names of the available features, for display.
+    ///
+    /// Returns an empty vector for a plain Distribution registry.
+    pub fn feature_names(&self) -> Vec<&'static str> {
+        [
+            (self.search, "search"),
+            (self.cve, "cve"),
+            (self.user_prefs, "userprefs"),
+            (self.mgmt, "mgmt"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+/// Discovers the extensions a registry offers.
+///
+/// # Arguments
+///
+/// * `client` - Client for the registry
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::zot;
+///
+/// # fn example() -> librex::error::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+/// let capabilities = zot::discover(&client)?;
+/// if capabilities.search {
+///     println!("GraphQL search is available");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Returns
+///
+/// The registry's capabilities; the default (none) when the registry does
+/// not implement extension discovery.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The registry is unreachable
+/// - Authentication is required but not provided
+/// - The discover response is invalid
+pub fn discover(client: &Client) -> Result<RegistryCapabilities> {
+    match client.fetch_extension(DISCOVER_PATH, &[]) {
+        Ok(bytes) => RegistryCapabilities::from_discovery(&bytes),
+        Err(RexError::NotFound { .. }) => Ok(RegistryCapabilities::default()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod cve_tests;
+#[cfg(test)]
+mod search_tests;
+#[cfg(test)]
+mod tests;
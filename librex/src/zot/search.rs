@@ -0,0 +1,297 @@
+//! Zot's GraphQL search extension.
+//!
+//! Zot indexes repositories and images server-side and answers GraphQL
+//! queries at `/v2/_zot/ext/search`. Queries are sent with `GET ?query=`,
+//! which every Zot version with the extension accepts.
+//!
+//! Zot reports sizes as strings (GraphQL has no 64-bit integer); they are
+//! parsed into `u64` here.
+
+use super::SEARCH_ENDPOINT;
+use crate::client::Client;
+use crate::error::{Result, RexError};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Fields requested for every image summary.
+pub(super) const IMAGE_FIELDS: &str = "RepoName Tag Digest Size LastUpdated Vendor Description";
+
+/// Fields requested for every repository summary.
+const REPO_FIELDS: &str = "Name LastUpdated Size Vendors NewestImage { Tag Description }";
+
+/// A repository as indexed by Zot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RepoSummary {
+    /// Repository name
+    pub name: String,
+    /// Time of the most recent push (RFC 3339)
+    #[serde(default)]
+    pub last_updated: Option<String>,
+    /// Total size of the repository's images in bytes
+    #[serde(default, deserialize_with = "size")]
+    pub size: Option<u64>,
+    /// Vendors declared by the images (`org.opencontainers.image.vendor`)
+    #[serde(default, deserialize_with = "strings")]
+    pub vendors: Vec<String>,
+    /// The most recently pushed image
+    #[serde(default)]
+    pub newest_image: Option<ImageSummary>,
+}
+
+/// An image (tag) as indexed by Zot.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ImageSummary {
+    /// Repository name
+    #[serde(default)]
+    pub repo_name: String,
+    /// Tag
+    #[serde(default)]
+    pub tag: String,
+    /// Manifest digest
+    #[serde(default)]
+    pub digest: Option<String>,
+    /// Image size in bytes
+    #[serde(default, deserialize_with = "size")]
+    pub size: Option<u64>,
+    /// Time the image was created or pushed (RFC 3339)
+    #[serde(default)]
+    pub last_updated: Option<String>,
+    /// Vendor (`org.opencontainers.image.vendor`)
+    #[serde(default)]
+    pub vendor: Option<String>,
+    /// Description (`org.opencontainers.image.description`)
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ImageSummary {
+    /// Returns the image reference, `repository:tag`.
+    pub fn reference(&self) -> String {
+        format!("{}:{}", self.repo_name, self.tag)
+    }
+}
+
+/// Result of a `GlobalSearch` query.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct GlobalSearchResult {
+    /// Matching repositories, in Zot's ranking order
+    #[serde(default, deserialize_with = "list")]
+    pub repos: Vec<RepoSummary>,
+    /// Matching images, for `repository:tag` queries
+    #[serde(default, deserialize_with = "list")]
+    pub images: Vec<ImageSummary>,
+}
+
+/// Result of an `ExpandedRepoInfo` query.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RepoInfo {
+    /// Repository summary
+    #[serde(default)]
+    pub summary: RepoSummary,
+    /// Images of the repository
+    #[serde(default, deserialize_with = "list")]
+    pub images: Vec<ImageSummary>,
+}
+
+/// Searches repositories and images.
+///
+/// Zot matches a plain query against repository names; a `repository:tag`
+/// query matches images of the repository by tag.
+///
+/// # Arguments
+///
+/// * `client` - Client for a registry offering the search extension
+/// * `query` - The search query (e.g., "alp" or "alpine:3")
+/// * `limit` - Maximum number of repositories and of images Zot returns
+///
+/// # Examples
+///
+/// ```no_run
+/// use librex::client::Client;
+/// use librex::zot;
+///
+/// # fn example() -> librex::error::Result<()> {
+/// let client = Client::new("http://localhost:5000", None)?;
+/// let result = zot::global_search(&client, "alp", Some(20))?;
+/// for repo in result.repos {
+///     println!("{} {:?}", repo.name, repo.last_updated);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The registry does not offer the search extension (`NotFound`)
+/// - The registry is unreachable
+/// - Authentication is required but not provided
+/// - Zot reports an error for the query
+pub fn global_search(
+    client: &Client,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<GlobalSearchResult> {
+    let graphql = format!(
+        "{{ GlobalSearch(query: {}{}) {{ Repos {{ {} }} Images {{ {} }} }} }}",
+        string_literal(query),
+        requested_page(limit),
+        REPO_FIELDS,
+        IMAGE_FIELDS
+    );
+    execute(client, &graphql, "GlobalSearch")
+}
+
+/// Lists the images (tags) of a repository.
+///
+/// # Arguments
+///
+/// * `client` - Client for a registry offering the search extension
+/// * `repository` - The repository name
+/// * `limit` - Maximum number of images Zot returns
+///
+/// # Errors
+///
+/// Returns an error if the query fails, see [`global_search`].
+pub fn image_list(
+    client: &Client,
+    repository: &str,
+    limit: Option<usize>,
+) -> Result<Vec<ImageSummary>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct PaginatedImages {
+        #[serde(default, deserialize_with = "list")]
+        results: Vec<ImageSummary>,
+    }
+
+    let graphql = format!(
+        "{{ ImageList(repo: {}{}) {{ Results {{ {} }} }} }}",
+        string_literal(repository),
+        requested_page(limit),
+        IMAGE_FIELDS
+    );
+    let images: PaginatedImages = execute(client, &graphql, "ImageList")?;
+    Ok(images.results)
+}
+
+/// Fetches a repository summary together with its images.
+///
+/// # Arguments
+///
+/// * `client` - Client for a registry offering the search extension
+/// * `repository` - The repository name
+///
+/// # Errors
+///
+/// Returns an error if the query fails, see [`global_search`].
+pub fn expanded_repo_info(client: &Client, repository: &str) -> Result<RepoInfo> {
+    let graphql = format!(
+        "{{ ExpandedRepoInfo(repo: {}) {{ Summary {{ {} }} Images {{ {} }} }} }}",
+        string_literal(repository),
+        REPO_FIELDS,
+        IMAGE_FIELDS
+    );
+    execute(client, &graphql, "ExpandedRepoInfo")
+}
+
+/// Runs a GraphQL query and deserializes `data.<field>`.
+pub(crate) fn execute<T>(client: &Client, graphql: &str, field: &str) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    struct GraphqlError {
+        message: String,
+    }
+
+    #[derive(Deserialize)]
+    struct GraphqlResponse {
+        #[serde(default)]
+        data: Option<serde_json::Value>,
+        #[serde(default)]
+        errors: Option<Vec<GraphqlError>>,
+    }
+
+    let bytes = client.fetch_extension(SEARCH_ENDPOINT, &[("query", graphql)])?;
+    let response: GraphqlResponse = serde_json::from_slice(&bytes)
+        .map_err(|e| RexError::validation_with_source("Failed to parse search response", e))?;
+
+    if let Some(errors) = response.errors.filter(|e| !e.is_empty()) {
+        let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+        return Err(RexError::validation(format!(
+            "{} query failed: {}",
+            field,
+            messages.join("; ")
+        )));
+    }
+
+    let value = response
+        .data
+        .and_then(|mut data| data.get_mut(field).map(serde_json::Value::take))
+        .filter(|value| !value.is_null())
+        .ok_or_else(|| RexError::validation(format!("Search response has no {} data", field)))?;
+
+    serde_json::from_value(value).map_err(|e| {
+        RexError::validation_with_source(format!("Failed to parse {} result", field), e)
+    })
+}
+
+/// Returns the `requestedPage` argument of a paginated query, empty without
+/// a limit.
+fn requested_page(limit: Option<usize>) -> String {
+    limit
+        .map(|limit| format!(", requestedPage: {{ limit: {}, offset: 0 }}", limit))
+        .unwrap_or_default()
+}
+
+/// Quotes a value as a GraphQL string literal.
+///
+/// JSON string escaping is valid GraphQL string escaping.
+pub(super) fn string_literal(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+/// Deserializes a size sent as a string or a number.
+fn size<'de, D>(deserializer: D) -> std::result::Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Size {
+        Number(u64),
+        Text(String),
+    }
+
+    Ok(match Option::<Size>::deserialize(deserializer)? {
+        Some(Size::Number(n)) => Some(n),
+        Some(Size::Text(s)) => s.parse().ok(),
+        None => None,
+    })
+}
+
+/// Deserializes a list that Zot may send as `null`.
+pub(super) fn list<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Deserializes a list of strings that may be `null` or contain `null`s.
+fn strings<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<Vec<Option<String>>>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .collect())
+}
@@ -0,0 +1,180 @@
+use super::*;
+use mockito::Matcher;
+
+const GLOBAL_SEARCH_RESPONSE: &str = r#"{
+    "data": {
+        "GlobalSearch": {
+            "Repos": [
+                {
+                    "Name": "alpine",
+                    "LastUpdated": "2024-05-01T10:00:00Z",
+                    "Size": "3623807",
+                    "Vendors": ["Alpine Linux", null],
+                    "NewestImage": {"Tag": "3.20", "Description": "Alpine base image"}
+                },
+                {
+                    "Name": "library/alpine-curl",
+                    "LastUpdated": null,
+                    "Size": "",
+                    "Vendors": null,
+                    "NewestImage": null
+                }
+            ],
+            "Images": null
+        }
+    }
+}"#;
+
+fn search_mock(server: &mut mockito::ServerGuard, pattern: &str, body: &str) -> mockito::Mock {
+    server
+        .mock("GET", SEARCH_ENDPOINT)
+        .match_query(Matcher::Regex(pattern.to_string()))
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn test_global_search_repos() {
+    let mut server = mockito::Server::new();
+    let mock = search_mock(&mut server, "GlobalSearch", GLOBAL_SEARCH_RESPONSE);
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = global_search(&client, "alp", None).unwrap();
+
+    mock.assert();
+    assert_eq!(result.repos.len(), 2);
+    assert!(result.images.is_empty());
+
+    let alpine = &result.repos[0];
+    assert_eq!(alpine.name, "alpine");
+    assert_eq!(alpine.size, Some(3623807));
+    assert_eq!(alpine.vendors, vec!["Alpine Linux"]);
+    assert_eq!(alpine.newest_image.as_ref().unwrap().tag, "3.20");
+
+    let curl = &result.repos[1];
+    assert_eq!(curl.size, None);
+    assert!(curl.vendors.is_empty());
+}
+
+#[test]
+fn test_global_search_images() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "GlobalSearch",
+        r#"{"data":{"GlobalSearch":{"Repos":[],"Images":[
+            {"RepoName":"alpine","Tag":"3.20","Digest":"sha256:aaaa","Size":"3623807",
+             "LastUpdated":"2024-05-01T10:00:00Z","Vendor":"Alpine Linux"}
+        ]}}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = global_search(&client, "alpine:3", None).unwrap();
+
+    assert_eq!(result.images.len(), 1);
+    assert_eq!(result.images[0].reference(), "alpine:3.20");
+    assert_eq!(result.images[0].vendor.as_deref(), Some("Alpine Linux"));
+}
+
+#[test]
+fn test_global_search_escapes_query() {
+    let mut server = mockito::Server::new();
+    // The query must arrive as a quoted GraphQL string with the quote escaped
+    let mock = search_mock(
+        &mut server,
+        r#"GlobalSearch%28query%3A\+%22a%5C%22b%22%29"#,
+        r#"{"data":{"GlobalSearch":{"Repos":[],"Images":[]}}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    global_search(&client, "a\"b", None).unwrap();
+
+    mock.assert();
+}
+
+#[test]
+fn test_global_search_requests_page() {
+    let mut server = mockito::Server::new();
+    let mock = search_mock(
+        &mut server,
+        r#"requestedPage%3A\+%7B\+limit%3A\+5%2C\+offset%3A\+0\+%7D"#,
+        r#"{"data":{"GlobalSearch":{"Repos":[],"Images":[]}}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    global_search(&client, "alp", Some(5)).unwrap();
+
+    mock.assert();
+}
+
+#[test]
+fn test_image_list() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "ImageList",
+        r#"{"data":{"ImageList":{"Results":[
+            {"RepoName":"alpine","Tag":"latest","Size":"100"},
+            {"RepoName":"alpine","Tag":"3.20","Size":"100"}
+        ]}}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let images = image_list(&client, "alpine", None).unwrap();
+
+    let tags: Vec<&str> = images.iter().map(|i| i.tag.as_str()).collect();
+    assert_eq!(tags, vec!["latest", "3.20"]);
+}
+
+#[test]
+fn test_expanded_repo_info() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "ExpandedRepoInfo",
+        r#"{"data":{"ExpandedRepoInfo":{
+            "Summary":{"Name":"alpine","Size":"200","Vendors":["Alpine Linux"]},
+            "Images":[{"RepoName":"alpine","Tag":"latest","Size":"100"}]
+        }}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let info = expanded_repo_info(&client, "alpine").unwrap();
+
+    assert_eq!(info.summary.name, "alpine");
+    assert_eq!(info.summary.size, Some(200));
+    assert_eq!(info.images.len(), 1);
+}
+
+#[test]
+fn test_graphql_errors() {
+    let mut server = mockito::Server::new();
+    let _mock = search_mock(
+        &mut server,
+        "ExpandedRepoInfo",
+        r#"{"errors":[{"message":"repo not found","path":["ExpandedRepoInfo"]}],"data":{"ExpandedRepoInfo":null}}"#,
+    );
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let error = expanded_repo_info(&client, "missing").unwrap_err();
+
+    assert!(matches!(error, RexError::Validation { .. }));
+    assert!(error.to_string().contains("repo not found"));
+}
+
+#[test]
+fn test_search_not_offered() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("GET", SEARCH_ENDPOINT)
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = global_search(&client, "alp", None);
+
+    assert!(matches!(result, Err(RexError::NotFound { .. })));
+}
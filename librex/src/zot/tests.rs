@@ -0,0 +1,108 @@
+use super::*;
+
+const ZOT_DISCOVERY: &str = r#"{
+    "distSpecVersion": "1.1.0",
+    "extensions": [
+        {
+            "name": "_zot",
+            "url": "https://github.com/project-zot/zot/blob/main/pkg/extensions/_zot.md",
+            "description": "zot registry extensions",
+            "endpoints": [
+                "/v2/_zot/ext/search",
+                "/v2/_zot/ext/userprefs",
+                "/v2/_zot/ext/mgmt"
+            ]
+        }
+    ]
+}"#;
+
+#[test]
+fn test_from_discovery_zot() {
+    let capabilities = RegistryCapabilities::from_discovery(ZOT_DISCOVERY.as_bytes()).unwrap();
+
+    assert!(capabilities.is_zot());
+    assert_eq!(capabilities.dist_spec_version.as_deref(), Some("1.1.0"));
+    assert!(capabilities.search);
+    assert!(capabilities.cve);
+    assert!(capabilities.user_prefs);
+    assert!(capabilities.mgmt);
+    assert_eq!(
+        capabilities.feature_names(),
+        vec!["search", "cve", "userprefs", "mgmt"]
+    );
+}
+
+#[test]
+fn test_from_discovery_without_search() {
+    let bytes = br#"{
+        "distSpecVersion": "1.1.0",
+        "extensions": [{"name": "_zot", "endpoints": ["/v2/_zot/ext/mgmt"]}]
+    }"#;
+
+    let capabilities = RegistryCapabilities::from_discovery(bytes).unwrap();
+
+    assert!(capabilities.is_zot());
+    assert!(!capabilities.search);
+    assert!(!capabilities.cve);
+    assert_eq!(capabilities.feature_names(), vec!["mgmt"]);
+}
+
+#[test]
+fn test_from_discovery_null_extensions() {
+    let bytes = br#"{"distSpecVersion": "1.1.0", "extensions": null}"#;
+
+    let capabilities = RegistryCapabilities::from_discovery(bytes).unwrap();
+
+    assert!(!capabilities.is_zot());
+    assert!(capabilities.feature_names().is_empty());
+}
+
+#[test]
+fn test_from_discovery_invalid() {
+    let result = RegistryCapabilities::from_discovery(b"<html></html>");
+
+    assert!(matches!(result, Err(RexError::Validation { .. })));
+}
+
+#[test]
+fn test_discover_zot() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", DISCOVER_PATH)
+        .with_status(200)
+        .with_header("Content-Type", "application/json")
+        .with_body(ZOT_DISCOVERY)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let capabilities = discover(&client).unwrap();
+
+    mock.assert();
+    assert!(capabilities.search);
+}
+
+#[test]
+fn test_discover_plain_distribution() {
+    let mut server = mockito::Server::new();
+    let _mock = server
+        .mock("GET", DISCOVER_PATH)
+        .with_status(404)
+        .with_body(r#"{"errors":[{"code":"NAME_UNKNOWN"}]}"#)
+        .create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let capabilities = discover(&client).unwrap();
+
+    assert_eq!(capabilities, RegistryCapabilities::default());
+}
+
+#[test]
+fn test_discover_unauthorized() {
+    let mut server = mockito::Server::new();
+    let _mock = server.mock("GET", DISCOVER_PATH).with_status(401).create();
+
+    let client = Client::new(&server.url(), None).unwrap();
+    let result = discover(&client);
+
+    assert!(matches!(result, Err(RexError::Authentication { .. })));
+}
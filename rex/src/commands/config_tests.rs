@@ -21,6 +21,7 @@ fn test_config_format_pretty_with_registries() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
 
     let output = config.format_pretty();
@@ -0,0 +1,94 @@
+use super::files::FileInfo;
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format;
+use std::io::Write;
+
+/// Handle the image cat command
+///
+/// The file content goes to stdout as is; messages go to stderr.
+pub fn handle_image_cat(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    path: &str,
+    platform: Option<&str>,
+) {
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Reading {} from {}", path, reference),
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let file = match cat_image_file(&registry_url, reference, platform, path, &mut out) {
+        Ok(file) => file,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = out.flush() {
+        format::error(ctx, &format!("Failed to write output: {}", e));
+        std::process::exit(1);
+    }
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Read /{} from layer {}", file.path, file.layer + 1),
+    );
+}
+
+/// Write a file of an image without pulling it.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "alpine:3.19" or "alpine@sha256:...")
+/// * `platform` - Platform for multi-platform images (e.g., "linux/amd64")
+/// * `path` - Path of the file in the image (e.g., "/etc/os-release")
+/// * `writer` - Destination for the file content
+///
+/// # Returns
+///
+/// Returns the entry the content came from, after following links
+pub(crate) fn cat_image_file<W: std::io::Write + ?Sized>(
+    registry_url: &str,
+    reference: &str,
+    platform: Option<&str>,
+    path: &str,
+    writer: &mut W,
+) -> Result<FileInfo, String> {
+    let parsed = librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let fetched = rex
+        .get_manifest(reference)
+        .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
+    let (manifest, _) = select_platform_manifest(&mut rex, &parsed, fetched, platform)?;
+
+    match rex.read_file(reference, &manifest, path, writer) {
+        Ok(entry) => Ok(FileInfo::from(entry)),
+        Err(e @ librex::error::RexError::IsDirectory { .. }) => Err(format!(
+            "{}\nUse `rex image extract` to copy a directory.",
+            e
+        )),
+        Err(e) => Err(format!("Failed to read {}: {}", path, e)),
+    }
+}
+
+#[cfg(test)]
+#[path = "cat_tests.rs"]
+mod tests;
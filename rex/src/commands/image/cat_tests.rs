@@ -0,0 +1,57 @@
+use super::*;
+use crate::test_support::alpine_server;
+
+// Note: These tests read from the alpine-like image of `alpine_server`.
+
+#[test]
+fn test_cat_image_file_follows_symlink() {
+    let (server, _base) = alpine_server("cat-release");
+    let mut out = Vec::new();
+
+    let file = cat_image_file(
+        &server.url(),
+        "cat-release:v1",
+        None,
+        "/etc/os-release",
+        &mut out,
+    )
+    .unwrap();
+
+    assert_eq!(out, b"ID=alpine\nVERSION_ID=3.19.1\n");
+    assert_eq!(file.path, "usr/lib/os-release");
+    assert_eq!(file.layer, 1);
+}
+
+#[test]
+fn test_cat_image_file_stops_at_top_layer() {
+    let (server, base) = alpine_server("cat-top");
+    let mut out = Vec::new();
+
+    let file = cat_image_file(
+        &server.url(),
+        "cat-top:v1",
+        None,
+        "app/config.yaml",
+        &mut out,
+    )
+    .unwrap();
+
+    assert_eq!(out, b"port: 8080\n");
+    assert_eq!(file.layer, 2);
+    assert!(!base.matched());
+}
+
+#[test]
+fn test_cat_image_file_errors() {
+    let (server, _base) = alpine_server("cat-errors");
+    let mut out = Vec::new();
+
+    let err =
+        cat_image_file(&server.url(), "cat-errors:v1", None, "/etc/motd", &mut out).unwrap_err();
+    assert!(err.contains("File not found: /etc/motd"));
+
+    let err = cat_image_file(&server.url(), "cat-errors:v1", None, "/etc", &mut out).unwrap_err();
+    assert!(err.contains("/etc is a directory"));
+    assert!(err.contains("rex image extract"));
+    assert!(out.is_empty());
+}
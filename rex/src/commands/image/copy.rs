@@ -0,0 +1,222 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format;
+use librex::copy::CopyEvent;
+
+/// Handle the image copy command (copy an image between repositories or registries)
+pub fn handle_image_copy(
+    ctx: &crate::context::AppContext,
+    source: &str,
+    destination: &str,
+    platform: Option<&str>,
+    all_platforms: bool,
+) {
+    let resolved = get_registry_url().and_then(|default_url| {
+        let registries = config::Config::load(&config::get_config_path())
+            .map(|cfg| cfg.registries.list)
+            .unwrap_or_default();
+        let source = resolve_image_registry(source, &default_url, &registries)?;
+        let destination = resolve_image_registry(destination, &default_url, &registries)?;
+        Ok((source, destination))
+    });
+    let ((source_url, source_ref), (destination_url, destination_ref)) = match resolved {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!(
+            "Copying {} from {} to {} on {}...",
+            source_ref, source_url, destination_ref, destination_url
+        ),
+    );
+
+    let on_event = |event: &CopyEvent| {
+        let message = match event {
+            CopyEvent::BlobExists(digest) => format!("Blob {} already exists", digest),
+            CopyEvent::BlobMounted(digest) => format!("Mounted blob {}", digest),
+            CopyEvent::BlobCopied { digest, size } => format!(
+                "Copied blob {} ({})",
+                digest,
+                librex::format::format_size(*size)
+            ),
+            CopyEvent::ManifestExists(digest) => format!("Manifest {} already exists", digest),
+            CopyEvent::ManifestPushed { reference, digest } => {
+                format!("Pushed manifest {} as {}", digest, reference)
+            }
+        };
+        format::print(ctx, VerbosityLevel::Verbose, &message);
+    };
+
+    match copy_image(
+        &source_url,
+        &source_ref,
+        &destination_url,
+        &destination_ref,
+        platform,
+        all_platforms,
+        on_event,
+    ) {
+        Ok(report) => {
+            format::success(
+                ctx,
+                &format!(
+                    "Copied {} to {} ({}): {} blobs copied ({}), {} mounted, {} already present",
+                    source,
+                    destination,
+                    report.digest,
+                    report.blobs_copied,
+                    librex::format::format_size(report.bytes_copied),
+                    report.blobs_mounted,
+                    report.blobs_skipped
+                ),
+            );
+        }
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Split the registry host off an image reference.
+///
+/// A reference whose first path component looks like a host (contains a
+/// `.` or `:`, or is `localhost`) names its registry explicitly, and that
+/// host must belong to one of the configured registries. Any other
+/// reference lives on the default registry.
+///
+/// # Arguments
+///
+/// * `reference` - Image reference (e.g., "alpine:3.19" or "ghcr.io/org/app:v1")
+/// * `default_url` - URL of the default registry
+/// * `registries` - Configured registries
+///
+/// # Returns
+///
+/// Returns the registry URL and the reference without the host
+pub(crate) fn resolve_image_registry(
+    reference: &str,
+    default_url: &str,
+    registries: &[config::RegistryEntry],
+) -> Result<(String, String), String> {
+    let Some((host, rest)) = reference.split_once('/') else {
+        return Ok((default_url.to_string(), reference.to_string()));
+    };
+
+    if !(host.contains('.') || host.contains(':') || host == "localhost") {
+        return Ok((default_url.to_string(), reference.to_string()));
+    }
+
+    let url_host = |url: &str| {
+        url.split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .trim_end_matches('/')
+            .to_string()
+    };
+
+    registries
+        .iter()
+        .find(|r| url_host(&r.url) == host)
+        .map(|r| (r.url.clone(), rest.to_string()))
+        .ok_or_else(|| {
+            format!(
+                "Registry '{}' is not configured. Add it with 'rex registry init <name> <url>'",
+                host
+            )
+        })
+}
+
+/// Copy an image between repositories or registries.
+///
+/// Multi-platform images need an explicit choice: a single `platform`, or
+/// `all_platforms` to copy the whole index.
+///
+/// # Arguments
+///
+/// * `source_url` - URL of the source registry
+/// * `source` - Source image reference, without registry host
+/// * `destination_url` - URL of the destination registry (may equal `source_url`)
+/// * `destination` - Destination image reference, without registry host
+/// * `platform` - Platform to copy (e.g., "linux/amd64")
+/// * `all_platforms` - Copy every platform of a multi-platform image
+/// * `on_event` - Called as each blob and manifest is processed
+///
+/// # Returns
+///
+/// Returns the copy report
+pub(crate) fn copy_image<F>(
+    source_url: &str,
+    source: &str,
+    destination_url: &str,
+    destination: &str,
+    platform: Option<&str>,
+    all_platforms: bool,
+    on_event: F,
+) -> Result<librex::copy::CopyReport, String>
+where
+    F: FnMut(&librex::copy::CopyEvent),
+{
+    librex::reference::Reference::from_str(source)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+    librex::reference::Reference::from_str(destination)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut options = librex::copy::CopyOptions::new();
+    if let Some(platform_str) = platform {
+        let (os, arch, variant) = parse_platform(platform_str)?;
+        options = options.with_platform(&os, &arch, variant.as_deref());
+    }
+
+    let mut source_rex = connect_rex(source_url)?;
+    let mut destination_rex = connect_rex(destination_url)?;
+
+    copy_between(
+        &mut source_rex,
+        source,
+        &mut destination_rex,
+        destination,
+        &options,
+        all_platforms,
+        on_event,
+    )
+}
+
+/// Copy an image between two connected registries, see [`copy_image`].
+fn copy_between<F>(
+    source_rex: &mut librex::Rex,
+    source: &str,
+    destination_rex: &mut librex::Rex,
+    destination: &str,
+    options: &librex::copy::CopyOptions,
+    all_platforms: bool,
+    on_event: F,
+) -> Result<librex::copy::CopyReport, String>
+where
+    F: FnMut(&librex::copy::CopyEvent),
+{
+    if options.platform.is_none() && !all_platforms {
+        let (manifest, _) = source_rex
+            .get_manifest(source)
+            .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
+        if manifest.is_index() {
+            return Err(
+                "Source image is multi-platform. Use --platform to copy one platform or --all-platforms to copy all of them"
+                    .to_string(),
+            );
+        }
+    }
+
+    source_rex
+        .copy_image(source, destination_rex, destination, options, on_event)
+        .map_err(|e| format!("Failed to copy image: {}", e))
+}
+
+#[cfg(test)]
+#[path = "copy_tests.rs"]
+mod tests;
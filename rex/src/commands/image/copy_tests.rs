@@ -0,0 +1,242 @@
+use super::*;
+
+// Note: These tests use mockito to test copy_image end-to-end with mock HTTP responses.
+
+const CONFIG_JSON: &str = r#"{"architecture":"amd64","os":"linux"}"#;
+
+const CONFIG_DIGEST: &str =
+    "sha256:9d99a75171aea000c711b34c0e5e3f28d3d537dd99d110eafbfbc2bd8e52c2bf";
+
+fn manifest_json() -> String {
+    format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","config":{{"mediaType":"application/vnd.oci.image.config.v1+json","size":{},"digest":"{}"}},"layers":[]}}"#,
+        CONFIG_JSON.len(),
+        CONFIG_DIGEST
+    )
+}
+
+const INDEX_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.index.v1+json",
+    "manifests": [
+        {
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "size": 100,
+            "digest": "sha256:1111111111111111111111111111111111111111111111111111111111111111",
+            "platform": {"os": "linux", "architecture": "amd64"}
+        }
+    ]
+}"#;
+
+/// Connects to a mock registry with a cache of its own: mockito reuses
+/// ports, so the shared per-registry cache may hold manifests another test
+/// served under the same URL.
+fn connect(url: &str, cache: &tempfile::TempDir) -> librex::Rex {
+    librex::Rex::builder()
+        .registry_url(url)
+        .with_cache(cache.path())
+        .build()
+        .unwrap()
+}
+
+/// Copies with `copy_between` through isolated connections.
+fn copy_isolated<F>(
+    registry_url: &str,
+    source: &str,
+    destination: &str,
+    platform: Option<&str>,
+    on_event: F,
+) -> Result<librex::copy::CopyReport, String>
+where
+    F: FnMut(&librex::copy::CopyEvent),
+{
+    let cache = tempfile::TempDir::new().unwrap();
+    let mut options = librex::copy::CopyOptions::new();
+    if let Some(platform) = platform {
+        let (os, arch, variant) = parse_platform(platform).unwrap();
+        options = options.with_platform(&os, &arch, variant.as_deref());
+    }
+    copy_between(
+        &mut connect(registry_url, &cache),
+        source,
+        &mut connect(registry_url, &cache),
+        destination,
+        &options,
+        false,
+        on_event,
+    )
+}
+
+fn registry(name: &str, url: &str) -> config::RegistryEntry {
+    config::RegistryEntry {
+        name: name.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_resolve_image_registry_without_host_uses_default() {
+    let registries = vec![registry("ghcr", "https://ghcr.io")];
+
+    let (url, reference) =
+        resolve_image_registry("org/app:v1", "http://localhost:5000", &registries).unwrap();
+
+    assert_eq!(url, "http://localhost:5000");
+    assert_eq!(reference, "org/app:v1");
+}
+
+#[test]
+fn test_resolve_image_registry_matches_configured_host() {
+    let registries = vec![
+        registry("local", "http://localhost:5000/"),
+        registry("ghcr", "https://ghcr.io"),
+    ];
+
+    let (url, reference) =
+        resolve_image_registry("ghcr.io/org/app:v1", "http://localhost:5000", &registries).unwrap();
+    assert_eq!(url, "https://ghcr.io");
+    assert_eq!(reference, "org/app:v1");
+
+    let (url, reference) =
+        resolve_image_registry("localhost:5000/app:v1", "https://ghcr.io", &registries).unwrap();
+    assert_eq!(url, "http://localhost:5000/");
+    assert_eq!(reference, "app:v1");
+}
+
+#[test]
+fn test_resolve_image_registry_unknown_host() {
+    let result = resolve_image_registry("quay.io/org/app:v1", "http://localhost:5000", &[]);
+
+    let error = result.unwrap_err();
+    assert!(error.contains("quay.io"));
+    assert!(error.contains("rex registry init"));
+}
+
+#[test]
+fn test_copy_image_within_registry() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+    let manifest = manifest_json();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _get_mock = server
+        .mock("GET", "/v2/myapp/manifests/v1")
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .with_body(&manifest)
+        .create();
+    let _head_mock = server
+        .mock(
+            "HEAD",
+            format!("/v2/mirror/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(404)
+        .create();
+    let mount_mock = server
+        .mock("POST", "/v2/mirror/blobs/uploads/")
+        .match_query(mockito::Matcher::UrlEncoded("from".into(), "myapp".into()))
+        .with_status(201)
+        .create();
+    let put_mock = server
+        .mock("PUT", "/v2/mirror/manifests/v1")
+        .match_body(manifest.as_str())
+        .with_status(201)
+        .create();
+
+    let mut events = Vec::new();
+    let report = copy_isolated(&registry_url, "myapp:v1", "mirror:v1", None, |event| {
+        events.push(event.clone())
+    })
+    .unwrap();
+
+    mount_mock.assert();
+    put_mock.assert();
+    assert_eq!(report.blobs_mounted, 1);
+    assert_eq!(report.manifests, 1);
+    assert_eq!(
+        events[0],
+        librex::copy::CopyEvent::BlobMounted(CONFIG_DIGEST.to_string())
+    );
+}
+
+#[test]
+fn test_copy_image_multi_platform_requires_choice() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _get_mock = server
+        .mock("GET", "/v2/multiarch/manifests/v1")
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(INDEX_JSON)
+        .create();
+    let put_mock = server
+        .mock("PUT", mockito::Matcher::Regex("^/v2/mirror/".to_string()))
+        .expect(0)
+        .create();
+
+    let result = copy_isolated(&registry_url, "multiarch:v1", "mirror:v1", None, |_| {});
+
+    put_mock.assert();
+    let error = result.unwrap_err();
+    assert!(error.contains("--platform"));
+    assert!(error.contains("--all-platforms"));
+}
+
+#[test]
+fn test_copy_image_unknown_platform() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _get_mock = server
+        .mock("GET", "/v2/multiarch/manifests/v1")
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(INDEX_JSON)
+        .create();
+
+    let result = copy_isolated(
+        &registry_url,
+        "multiarch:v1",
+        "mirror:v1",
+        Some("linux/arm64"),
+        |_| {},
+    );
+
+    let error = result.unwrap_err();
+    assert!(error.starts_with("Failed to copy image"));
+    assert!(error.contains("linux/amd64"));
+}
+
+#[test]
+fn test_copy_image_invalid_platform() {
+    let result = copy_image(
+        "http://localhost:5000",
+        "alpine:latest",
+        "http://localhost:5000",
+        "mirror:latest",
+        Some("linux"),
+        false,
+        |_| {},
+    );
+
+    assert!(result.unwrap_err().contains("Invalid platform format"));
+}
+
+#[test]
+fn test_copy_image_invalid_reference() {
+    let result = copy_image(
+        "http://localhost:5000",
+        "INVALID:::",
+        "http://localhost:5000",
+        "mirror:latest",
+        None,
+        true,
+        |_| {},
+    );
+
+    assert!(result.unwrap_err().contains("Invalid image reference"));
+}
@@ -0,0 +1,355 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+use librex::zot::Severity;
+
+/// Parse a severity option, exiting with an error message if it is invalid
+fn parse_severity(ctx: &crate::context::AppContext, value: Option<&str>) -> Option<Severity> {
+    value.map(|value| match Severity::from_str(value) {
+        Ok(severity) => severity,
+        Err(e) => {
+            format::error(ctx, &e.to_string());
+            std::process::exit(1);
+        }
+    })
+}
+
+/// Handle the image cves command (vulnerability report from the registry's scanner)
+///
+/// With `cve_id`, lists the images affected by that vulnerability instead.
+/// Exits with status 1 when `fail_on` is given and a vulnerability at or
+/// above that severity was found, so the command can gate CI pipelines.
+pub fn handle_image_cves(
+    ctx: &crate::context::AppContext,
+    reference: Option<&str>,
+    format: OutputFormat,
+    severity: Option<&str>,
+    fail_on: Option<&str>,
+    cve_id: Option<&str>,
+) {
+    let min_severity = parse_severity(ctx, severity);
+    let fail_on = parse_severity(ctx, fail_on);
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(cve_id) = cve_id {
+        format::print(
+            ctx,
+            VerbosityLevel::Verbose,
+            &format!("Listing images affected by {}", cve_id),
+        );
+
+        let images = match list_images_affected_by(&registry_url, cve_id) {
+            Ok(images) => images,
+            Err(e) => {
+                format::error(ctx, &e);
+                std::process::exit(1);
+            }
+        };
+
+        match format {
+            OutputFormat::Pretty => {
+                if images.is_empty() {
+                    println!("No images affected by {}.", cve_id);
+                    return;
+                }
+                println!("{:50} {:71} LAST UPDATED", "IMAGE", "DIGEST");
+                for image in &images {
+                    println!("{}", image.format_pretty());
+                }
+            }
+            OutputFormat::Json => match serde_json::to_string_pretty(&images) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    eprintln!("Error formatting JSON: {}", e);
+                    std::process::exit(1);
+                }
+            },
+        }
+        return;
+    }
+
+    // clap requires a reference when no CVE is given
+    let Some(reference) = reference else {
+        format::error(ctx, "An image reference or --cve is required");
+        std::process::exit(1);
+    };
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Listing vulnerabilities for image: {}", reference),
+    );
+
+    let report = match list_cves(&registry_url, reference, min_severity) {
+        Ok(report) => report,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => print!("{}", report.format_pretty()),
+        OutputFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+
+    if let Some(threshold) = fail_on
+        && report.has_findings_at_least(threshold)
+    {
+        format::error(
+            ctx,
+            &format!(
+                "Found vulnerabilities at or above {} severity in {}",
+                threshold, reference
+            ),
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Number of vulnerabilities per severity
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CveSummary {
+    pub total: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub unknown: usize,
+    /// Highest severity found (e.g., "HIGH")
+    pub max_severity: Option<librex::zot::Severity>,
+}
+
+impl From<&librex::zot::CveReport> for CveSummary {
+    fn from(report: &librex::zot::CveReport) -> Self {
+        use librex::zot::Severity;
+
+        let count = |severity: Severity| {
+            report
+                .cves
+                .iter()
+                .filter(|cve| cve.severity == severity)
+                .count()
+        };
+        Self {
+            total: report.cves.len(),
+            critical: count(Severity::Critical),
+            high: count(Severity::High),
+            medium: count(Severity::Medium),
+            low: count(Severity::Low),
+            unknown: count(Severity::Unknown),
+            max_severity: report.max_severity(),
+        }
+    }
+}
+
+impl CveSummary {
+    /// One-line summary, e.g. "4 (critical: 1, high: 1, medium: 0, low: 1, unknown: 1)"
+    pub(super) fn format_line(&self) -> String {
+        format!(
+            "{} (critical: {}, high: {}, medium: {}, low: {}, unknown: {})",
+            self.total, self.critical, self.high, self.medium, self.low, self.unknown
+        )
+    }
+}
+
+/// Package affected by a vulnerability
+#[derive(Debug, Clone, Serialize)]
+pub struct CvePackage {
+    pub name: String,
+    pub installed_version: Option<String>,
+    pub fixed_version: Option<String>,
+}
+
+/// Vulnerability found in an image
+#[derive(Debug, Clone, Serialize)]
+pub struct CveInfo {
+    /// CVE identifier (e.g., "CVE-2024-1234")
+    pub id: String,
+    /// Severity (e.g., "HIGH")
+    pub severity: librex::zot::Severity,
+    pub title: Option<String>,
+    pub packages: Vec<CvePackage>,
+}
+
+impl From<&librex::zot::Cve> for CveInfo {
+    fn from(cve: &librex::zot::Cve) -> Self {
+        Self {
+            id: cve.id.clone(),
+            severity: cve.severity,
+            title: cve.title.clone(),
+            packages: cve
+                .packages
+                .iter()
+                .map(|p| CvePackage {
+                    name: p.name.clone(),
+                    installed_version: p.installed_version.clone(),
+                    fixed_version: p.fixed_version.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Vulnerability report for an image
+#[derive(Debug, Serialize)]
+pub struct ImageCves {
+    /// Image reference as given
+    pub reference: String,
+    /// Counts over all vulnerabilities found, regardless of filtering
+    pub summary: CveSummary,
+    /// Vulnerabilities at or above the requested severity
+    pub cves: Vec<CveInfo>,
+}
+
+impl ImageCves {
+    /// Returns true if any vulnerability is at or above `severity`.
+    pub fn has_findings_at_least(&self, severity: librex::zot::Severity) -> bool {
+        self.summary.max_severity.is_some_and(|max| max >= severity)
+    }
+}
+
+impl Formattable for ImageCves {
+    fn format_pretty(&self) -> String {
+        let mut output = format!(
+            "Vulnerabilities in {}: {}\n",
+            self.reference,
+            self.summary.format_line()
+        );
+        if self.cves.is_empty() {
+            return output;
+        }
+
+        output.push_str(&format!(
+            "\n{:20} {:9} {:24} {:18} {:18} TITLE\n",
+            "ID", "SEVERITY", "PACKAGE", "INSTALLED", "FIXED"
+        ));
+        for cve in &self.cves {
+            let title = cve.title.as_deref().unwrap_or("-");
+            if cve.packages.is_empty() {
+                output.push_str(&format!(
+                    "{:20} {:9} {:24} {:18} {:18} {}\n",
+                    cve.id, cve.severity, "-", "-", "-", title
+                ));
+            }
+            for package in &cve.packages {
+                output.push_str(&format!(
+                    "{:20} {:9} {:24} {:18} {:18} {}\n",
+                    cve.id,
+                    cve.severity,
+                    package.name,
+                    package.installed_version.as_deref().unwrap_or("-"),
+                    package.fixed_version.as_deref().unwrap_or("-"),
+                    title
+                ));
+            }
+        }
+        output
+    }
+}
+
+/// Image affected by a vulnerability
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedImage {
+    /// Image reference (name:tag)
+    pub reference: String,
+    /// Manifest digest
+    pub digest: Option<String>,
+    /// Last update time reported by the registry
+    pub last_updated: Option<String>,
+}
+
+impl From<&librex::zot::ImageSummary> for AffectedImage {
+    fn from(image: &librex::zot::ImageSummary) -> Self {
+        Self {
+            reference: image.reference(),
+            digest: image.digest.clone(),
+            last_updated: image.last_updated.clone(),
+        }
+    }
+}
+
+impl Formattable for AffectedImage {
+    fn format_pretty(&self) -> String {
+        format!(
+            "{:50} {:71} {}",
+            self.reference,
+            self.digest.as_deref().unwrap_or("-"),
+            self.last_updated.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// Report the vulnerabilities the registry's scanner found in an image.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+/// * `min_severity` - Only list vulnerabilities at or above this severity
+///
+/// # Returns
+///
+/// Returns the report; its summary counts every vulnerability found
+pub(crate) fn list_cves(
+    registry_url: &str,
+    reference: &str,
+    min_severity: Option<librex::zot::Severity>,
+) -> Result<ImageCves, String> {
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let report = rex
+        .list_cves(reference)
+        .map_err(|e| format!("Failed to list vulnerabilities: {}", e))?;
+    let listed = report.at_least(min_severity.unwrap_or_default());
+
+    Ok(ImageCves {
+        reference: reference.to_string(),
+        summary: CveSummary::from(&report),
+        cves: listed.cves.iter().map(CveInfo::from).collect(),
+    })
+}
+
+/// List the images affected by a vulnerability.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `cve_id` - CVE identifier (e.g., "CVE-2024-1234")
+///
+/// # Returns
+///
+/// Returns the affected images
+pub(crate) fn list_images_affected_by(
+    registry_url: &str,
+    cve_id: &str,
+) -> Result<Vec<AffectedImage>, String> {
+    let mut rex = connect_rex(registry_url)?;
+
+    let images = rex
+        .images_affected_by(cve_id)
+        .map_err(|e| format!("Failed to list affected images: {}", e))?;
+
+    Ok(images.iter().map(AffectedImage::from).collect())
+}
+
+#[cfg(test)]
+#[path = "cves_tests.rs"]
+mod tests;
@@ -0,0 +1,209 @@
+use super::*;
+use librex::zot::Severity;
+
+// Note: These tests use mockito to stub Zot's extension discovery and GraphQL
+// search endpoint, which serves the CVE queries.
+
+const ZOT_DISCOVERY: &str = r#"{"distSpecVersion":"1.1.0","extensions":[{"name":"_zot","endpoints":["/v2/_zot/ext/search"]}]}"#;
+
+const CVE_LIST_JSON: &str = r#"{"data":{"CVEListForImage":{"Tag":"v1","CVEList":[
+    {"Id":"CVE-2024-0001","Title":"openssl: buffer overflow","Severity":"CRITICAL",
+     "PackageList":[{"Name":"openssl","InstalledVersion":"3.1.0","FixedVersion":"3.1.5"}]},
+    {"Id":"CVE-2024-0002","Title":"zlib: out-of-bounds read","Severity":"MEDIUM","PackageList":[]},
+    {"Id":"CVE-2024-0003","Severity":"LOW","PackageList":null}
+]}}}"#;
+
+const CONFIG_JSON: &str =
+    r#"{"architecture":"amd64","os":"linux","rootfs":{"type":"layers","diff_ids":[]}}"#;
+
+const CONFIG_DIGEST: &str =
+    "sha256:c5b1d63604f273462ef36fadac3182d43ae6a6138731cf594b314835cf1c034f";
+
+const MANIFEST_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+    "config": {
+        "mediaType": "application/vnd.oci.image.config.v1+json",
+        "size": 78,
+        "digest": "sha256:c5b1d63604f273462ef36fadac3182d43ae6a6138731cf594b314835cf1c034f"
+    },
+    "layers": []
+}"#;
+
+const MANIFEST_DIGEST: &str =
+    "sha256:af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+fn zot_server() -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(200)
+        .with_body(ZOT_DISCOVERY)
+        .create();
+    server
+}
+
+fn cve_mock(server: &mut mockito::ServerGuard, pattern: &str, body: &str) -> mockito::Mock {
+    server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Regex(pattern.to_string()))
+        .with_status(200)
+        .with_body(body)
+        .create()
+}
+
+#[test]
+fn test_list_cves() {
+    let mut server = zot_server();
+    let registry_url = server.url();
+    let mock = cve_mock(&mut server, "CVEListForImage.*cves-app%3Av1", CVE_LIST_JSON);
+
+    let report = list_cves(&registry_url, "cves-app:v1", None).unwrap();
+
+    mock.assert();
+    assert_eq!(report.summary.total, 3);
+    assert_eq!(report.summary.critical, 1);
+    assert_eq!(report.summary.medium, 1);
+    assert_eq!(report.summary.low, 1);
+    assert_eq!(report.summary.max_severity, Some(Severity::Critical));
+    assert_eq!(report.cves.len(), 3);
+    assert_eq!(report.cves[0].packages[0].name, "openssl");
+
+    let pretty = report.format_pretty();
+    assert!(pretty.contains("Vulnerabilities in cves-app:v1: 3 (critical: 1, high: 0"));
+    assert!(pretty.contains("CVE-2024-0001"));
+    assert!(pretty.contains("3.1.5"));
+}
+
+#[test]
+fn test_list_cves_severity_filter_keeps_summary() {
+    let mut server = zot_server();
+    let registry_url = server.url();
+    let _mock = cve_mock(&mut server, "CVEListForImage", CVE_LIST_JSON);
+
+    let report = list_cves(&registry_url, "cves-filter:v1", Some(Severity::Medium)).unwrap();
+
+    let ids: Vec<&str> = report.cves.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["CVE-2024-0001", "CVE-2024-0002"]);
+    assert_eq!(report.summary.total, 3);
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["cves"][0]["severity"], "CRITICAL");
+    assert_eq!(json["summary"]["max_severity"], "CRITICAL");
+}
+
+#[test]
+fn test_image_cves_fail_on_threshold() {
+    let mut server = zot_server();
+    let registry_url = server.url();
+    let _mock = cve_mock(&mut server, "CVEListForImage", CVE_LIST_JSON);
+
+    let report = list_cves(&registry_url, "cves-gate:v1", None).unwrap();
+
+    assert!(report.has_findings_at_least(Severity::Critical));
+    assert!(report.has_findings_at_least(Severity::Low));
+
+    let clean = ImageCves {
+        reference: "clean:v1".to_string(),
+        summary: CveSummary::default(),
+        cves: vec![],
+    };
+    assert!(!clean.has_findings_at_least(Severity::Unknown));
+}
+
+#[test]
+fn test_list_cves_requires_scanner() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(404)
+        .create();
+
+    let err = list_cves(&registry_url, "cves-plain:v1", None).unwrap_err();
+
+    assert!(err.contains("Failed to list vulnerabilities"));
+    assert!(err.contains("does not offer CVE scanning"));
+}
+
+#[test]
+fn test_list_images_affected_by() {
+    let mut server = zot_server();
+    let registry_url = server.url();
+    let _mock = cve_mock(
+        &mut server,
+        "ImageListForCVE.*CVE-2024-0001",
+        r#"{"data":{"ImageListForCVE":{"Results":[
+            {"RepoName":"cves-affected","Tag":"v1","Digest":"sha256:aaaa","LastUpdated":"2024-05-01T10:00:00Z"}
+        ]}}}"#,
+    );
+
+    let images = list_images_affected_by(&registry_url, "CVE-2024-0001").unwrap();
+
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].reference, "cves-affected:v1");
+    assert_eq!(images[0].digest.as_deref(), Some("sha256:aaaa"));
+}
+
+#[test]
+fn test_get_image_inspect_shows_vulnerabilities() {
+    let mut server = zot_server();
+    let registry_url = server.url();
+    let _manifest_mock = server
+        .mock("GET", "/v2/cves-inspect/manifests/v1")
+        .with_status(200)
+        .with_header("docker-content-digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/cves-inspect/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(CONFIG_JSON)
+        .create();
+    let _cve_mock = cve_mock(
+        &mut server,
+        "CVEListForImage.*cves-inspect%40sha256",
+        CVE_LIST_JSON,
+    );
+
+    let inspect = get_image_inspect(&registry_url, "cves-inspect:v1", None, false, false).unwrap();
+
+    let vulnerabilities = inspect.vulnerabilities.as_ref().unwrap();
+    assert_eq!(vulnerabilities.total, 3);
+    let pretty = inspect.format_pretty();
+    assert!(pretty.contains("Vulnerabilities:"));
+    assert!(pretty.contains("Total: 3 (critical: 1"));
+}
+
+#[test]
+fn test_get_image_inspect_without_scanner() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/cves-noscan/manifests/v1")
+        .with_status(200)
+        .with_header("docker-content-digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/cves-noscan/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(CONFIG_JSON)
+        .create();
+
+    let inspect = get_image_inspect(&registry_url, "cves-noscan:v1", None, false, false).unwrap();
+
+    assert!(inspect.vulnerabilities.is_none());
+    assert!(!inspect.format_pretty().contains("Vulnerabilities"));
+    let json = serde_json::to_value(&inspect).unwrap();
+    assert!(json.get("vulnerabilities").is_none());
+}
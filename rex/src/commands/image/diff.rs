@@ -0,0 +1,321 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image diff command
+///
+/// Without `other`, two platforms of `reference` are compared.
+pub fn handle_image_diff(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    other: Option<&str>,
+    platform: Option<&str>,
+    other_platform: Option<&str>,
+    files: bool,
+    format: OutputFormat,
+) {
+    let other = other.unwrap_or(reference);
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Comparing {} with {}", reference, other),
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let diff = match diff_images(
+        &registry_url,
+        reference,
+        other,
+        platform,
+        other_platform.or(platform),
+        files,
+    ) {
+        Ok(diff) => diff,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => print!("{}", diff.format_colored(format::should_color(ctx))),
+        OutputFormat::Json => match serde_json::to_string_pretty(&diff) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// One side of an image comparison
+#[derive(Debug, Serialize)]
+pub struct DiffSide {
+    /// Image reference
+    pub reference: String,
+    /// Digest the reference resolved to (the index, for multi-platform images)
+    pub digest: String,
+    /// Platform compared, from the image configuration
+    pub platform: String,
+}
+
+/// Differences between two images
+#[derive(Debug, Serialize)]
+pub struct ImageDiff {
+    /// Image compared from
+    pub old: DiffSide,
+    /// Image compared to
+    pub new: DiffSide,
+    /// Configuration fields that differ
+    pub config: Vec<librex::diff::ConfigChange>,
+    /// Layers by digest
+    pub layers: librex::diff::LayerDiff,
+    /// Files that differ; only when the filesystems were compared
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<librex::diff::FileChange>>,
+}
+
+/// Format a size difference with its sign (e.g., "+1.50 KiB")
+fn format_size_delta(delta: i64) -> String {
+    let size = librex::format::format_size(delta.unsigned_abs());
+    match delta {
+        0 => size,
+        d if d > 0 => format!("+{}", size),
+        _ => format!("-{}", size),
+    }
+}
+
+/// Color a diff line by its kind of change
+fn paint_change(line: String, change: librex::diff::Change, color: bool) -> String {
+    use owo_colors::OwoColorize;
+    if !color {
+        return line;
+    }
+    match change {
+        librex::diff::Change::Added => line.green().to_string(),
+        librex::diff::Change::Removed => line.red().to_string(),
+        librex::diff::Change::Modified => line.yellow().to_string(),
+    }
+}
+
+impl ImageDiff {
+    /// Whether the images differ at all the levels compared
+    pub fn is_identical(&self) -> bool {
+        self.config.is_empty()
+            && self.layers.is_empty()
+            && self.files.as_ref().is_none_or(|files| files.is_empty())
+    }
+
+    /// Format for the terminal, coloring added, removed and modified lines
+    pub fn format_colored(&self, color: bool) -> String {
+        use librex::diff::Change;
+
+        let mut output = String::new();
+
+        for (label, side) in [("Old", &self.old), ("New", &self.new)] {
+            output.push_str(&format!(
+                "{}: {} ({})\n     {}\n",
+                label, side.reference, side.platform, side.digest
+            ));
+        }
+
+        if self.config.is_empty() {
+            output.push_str("\nConfig: no changes\n");
+        } else {
+            output.push_str(&format!("\nConfig ({} changes):\n", self.config.len()));
+            for change in &self.config {
+                let old = change.old.as_deref().unwrap_or_default();
+                let new = change.new.as_deref().unwrap_or_default();
+                let line = match (&change.key, change.change) {
+                    (Some(key), Change::Modified) => {
+                        format!("{} {}: {} -> {}", change.field, key, old, new)
+                    }
+                    (Some(key), Change::Added) => format!("{} {}={}", change.field, key, new),
+                    (Some(key), Change::Removed) => format!("{} {}={}", change.field, key, old),
+                    (None, Change::Modified) => format!("{}: {} -> {}", change.field, old, new),
+                    (None, Change::Added) => format!("{}: {}", change.field, new),
+                    (None, Change::Removed) => format!("{}: {}", change.field, old),
+                };
+                output.push_str(&paint_change(
+                    format!("  {} {}\n", change.change.symbol(), line),
+                    change.change,
+                    color,
+                ));
+            }
+        }
+
+        output.push_str(&format!(
+            "\nLayers: {} shared, {} added, {} removed\n",
+            self.layers.shared.len(),
+            self.layers.added.len(),
+            self.layers.removed.len()
+        ));
+        for (change, layers) in [
+            (Change::Removed, &self.layers.removed),
+            (Change::Added, &self.layers.added),
+        ] {
+            for layer in layers {
+                output.push_str(&paint_change(
+                    format!(
+                        "  {} {}  {}\n",
+                        change.symbol(),
+                        layer.digest,
+                        librex::format::format_size(layer.size)
+                    ),
+                    change,
+                    color,
+                ));
+            }
+        }
+
+        match &self.files {
+            None => output.push_str("\nFiles: not compared (use --files)\n"),
+            Some(files) if files.is_empty() => output.push_str("\nFiles: no changes\n"),
+            Some(files) => {
+                let count = |change: Change| files.iter().filter(|f| f.change == change).count();
+                let delta: i64 = files.iter().map(|f| f.size_delta()).sum();
+                output.push_str(&format!(
+                    "\nFiles: {} added, {} removed, {} modified ({})\n",
+                    count(Change::Added),
+                    count(Change::Removed),
+                    count(Change::Modified),
+                    format_size_delta(delta)
+                ));
+                for file in files {
+                    let mut line = format!("  {} /{}", file.change.symbol(), file.path);
+                    if file.kind == librex::layer::EntryKind::Directory {
+                        line.push('/');
+                    }
+                    let size = |size: Option<u64>| {
+                        size.map(librex::format::format_size)
+                            .unwrap_or_else(|| "-".to_string())
+                    };
+                    match (file.change, file.old_size, file.new_size) {
+                        (Change::Modified, None, None) => {}
+                        (Change::Modified, old, new) => line.push_str(&format!(
+                            "  ({} -> {}, {})",
+                            size(old),
+                            size(new),
+                            format_size_delta(file.size_delta())
+                        )),
+                        (_, Some(old), None) => line.push_str(&format!("  ({})", size(Some(old)))),
+                        (_, None, Some(new)) => line.push_str(&format!("  ({})", size(Some(new)))),
+                        _ => {}
+                    }
+                    line.push('\n');
+                    output.push_str(&paint_change(line, file.change, color));
+                }
+            }
+        }
+
+        if self.is_identical() {
+            output.push_str("\nImages are identical.\n");
+        }
+        output
+    }
+}
+
+impl Formattable for ImageDiff {
+    fn format_pretty(&self) -> String {
+        self.format_colored(false)
+    }
+}
+
+/// Resolve one side of an image comparison: its manifest and configuration.
+fn read_diff_side(
+    rex: &mut librex::Rex,
+    reference: &str,
+    platform: Option<&str>,
+) -> Result<
+    (
+        DiffSide,
+        librex::oci::ImageManifest,
+        librex::oci::ImageConfiguration,
+    ),
+    String,
+> {
+    let parsed = librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let fetched = rex
+        .get_manifest(reference)
+        .map_err(|e| format!("Failed to fetch manifest of {}: {}", reference, e))?;
+    let digest = fetched.1.clone();
+    let (manifest, _) = select_platform_manifest(rex, &parsed, fetched, platform)?;
+
+    if !librex::oci::Artifact::from_manifest(&manifest).is_image() {
+        return Err(format!("{} is not a container image", reference));
+    }
+    let config_digest = librex::digest::Digest::from_str(manifest.config().digest().as_ref())
+        .map_err(|e| format!("Invalid config digest: {}", e))?;
+    let config_bytes = rex
+        .get_blob_for_reference(reference, &config_digest)
+        .map_err(|e| format!("Failed to fetch config blob: {}", e))?;
+    let config: librex::oci::ImageConfiguration = serde_json::from_slice(&config_bytes)
+        .map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let side = DiffSide {
+        reference: reference.to_string(),
+        digest,
+        platform: librex::diff::platform_string(&config),
+    };
+    Ok((side, manifest, config))
+}
+
+/// Compare two images, or two platforms of one image.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `old` - Reference of the image to compare from (e.g., "myapp:release-1.4")
+/// * `new` - Reference of the image to compare to (e.g., "myapp:release-1.5")
+/// * `old_platform` - Platform of the old image, for multi-platform images
+/// * `new_platform` - Platform of the new image, for multi-platform images
+/// * `files` - Also compare the merged filesystems (reads every layer)
+///
+/// # Returns
+///
+/// Returns the configuration, layer and (optionally) file differences
+pub(crate) fn diff_images(
+    registry_url: &str,
+    old: &str,
+    new: &str,
+    old_platform: Option<&str>,
+    new_platform: Option<&str>,
+    files: bool,
+) -> Result<ImageDiff, String> {
+    let mut rex = connect_rex(registry_url)?;
+
+    let (old_side, old_manifest, old_config) = read_diff_side(&mut rex, old, old_platform)?;
+    let (new_side, new_manifest, new_config) = read_diff_side(&mut rex, new, new_platform)?;
+
+    let files = if files {
+        Some(
+            rex.diff_files(old, &old_manifest, new, &new_manifest)
+                .map_err(|e| format!("Failed to compare files: {}", e))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ImageDiff {
+        old: old_side,
+        new: new_side,
+        config: librex::diff::diff_configs(&old_config, &new_config),
+        layers: librex::diff::diff_layers(old_manifest.layers(), new_manifest.layers()),
+        files,
+    })
+}
+
+#[cfg(test)]
+#[path = "diff_tests.rs"]
+mod tests;
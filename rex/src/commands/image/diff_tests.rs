@@ -0,0 +1,266 @@
+use super::*;
+use crate::test_support::{config, layer, mock_image, sha256};
+use librex::diff::Change;
+
+// Note: These tests serve two releases of an image from mockito, sharing a
+// base layer, with configs and gzip layers built in memory.
+
+/// Serve release-1.4 and release-1.5, sharing their base layer
+fn releases_server(repository: &str) -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+
+    let base = layer(&[("etc/os-release", "ID=alpine\n"), ("etc/motd", "Hi\n")]);
+    mock_image(
+        &mut server,
+        repository,
+        "release-1.4",
+        &config("amd64", "root", "1.4"),
+        &[base.clone(), layer(&[("app/server", "v1-binary")])],
+    );
+    mock_image(
+        &mut server,
+        repository,
+        "release-1.5",
+        &config("amd64", "app", "1.5"),
+        &[
+            base,
+            layer(&[
+                ("etc/.wh.motd", ""),
+                ("app/server", "v2-binary-larger"),
+                ("app/static/index.html", "<html></html>"),
+            ]),
+        ],
+    );
+    server
+}
+
+#[test]
+fn test_diff_images_config_and_layers() {
+    let server = releases_server("diff-releases");
+
+    let diff = diff_images(
+        &server.url(),
+        "diff-releases:release-1.4",
+        "diff-releases:release-1.5",
+        None,
+        None,
+        false,
+    )
+    .unwrap();
+
+    let fields: Vec<(&str, Option<&str>)> = diff
+        .config
+        .iter()
+        .map(|c| (c.field.as_str(), c.key.as_deref()))
+        .collect();
+    assert_eq!(fields, vec![("user", None), ("env", Some("VERSION"))]);
+    assert_eq!(diff.layers.shared.len(), 1);
+    assert_eq!(diff.layers.added.len(), 1);
+    assert_eq!(diff.layers.removed.len(), 1);
+    assert!(diff.files.is_none());
+    assert_eq!(diff.old.platform, "linux/amd64");
+
+    let pretty = diff.format_pretty();
+    assert!(pretty.contains("Old: diff-releases:release-1.4 (linux/amd64)"));
+    assert!(pretty.contains("Config (2 changes):"));
+    assert!(pretty.contains("  ~ user: root -> app"));
+    assert!(pretty.contains("  ~ env VERSION: 1.4 -> 1.5"));
+    assert!(pretty.contains("Layers: 1 shared, 1 added, 1 removed"));
+    assert!(pretty.contains("Files: not compared (use --files)"));
+    assert!(!pretty.contains("identical"));
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["config"][0]["change"], "modified");
+    assert!(json.get("files").is_none());
+}
+
+#[test]
+fn test_diff_images_files() {
+    let server = releases_server("diff-files");
+
+    let diff = diff_images(
+        &server.url(),
+        "diff-files:release-1.4",
+        "diff-files:release-1.5",
+        None,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let files = diff.files.as_ref().unwrap();
+    let changes: Vec<(&str, Change)> = files.iter().map(|f| (f.path.as_str(), f.change)).collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("app/server", Change::Modified),
+            ("app/static/index.html", Change::Added),
+            ("etc/motd", Change::Removed),
+        ]
+    );
+
+    let pretty = diff.format_pretty();
+    assert!(pretty.contains("Files: 1 added, 1 removed, 1 modified (+17 B)"));
+    assert!(pretty.contains("  ~ /app/server  (9 B -> 16 B, +7 B)"));
+    assert!(pretty.contains("  + /app/static/index.html  (13 B)"));
+    assert!(pretty.contains("  - /etc/motd  (3 B)"));
+
+    // Colors only when asked for
+    assert!(!pretty.contains('\u{1b}'));
+    assert!(diff.format_colored(true).contains('\u{1b}'));
+}
+
+#[test]
+fn test_diff_images_identical() {
+    let server = releases_server("diff-same");
+
+    let diff = diff_images(
+        &server.url(),
+        "diff-same:release-1.4",
+        "diff-same:release-1.4",
+        None,
+        None,
+        true,
+    )
+    .unwrap();
+
+    assert!(diff.is_identical());
+    let pretty = diff.format_pretty();
+    assert!(pretty.contains("Config: no changes"));
+    assert!(pretty.contains("Files: no changes"));
+    assert!(pretty.contains("Images are identical."));
+}
+
+/// Serve an index tagged `v1` with one image per (architecture, variant,
+/// layer), each served by digest
+fn platforms_server(
+    repository: &str,
+    platforms: &[(&str, Option<&str>, Vec<u8>)],
+) -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+
+    let mut descriptors = Vec::new();
+    for (architecture, variant, app) in platforms {
+        let config = config(architecture, "app", "1.5");
+        // Render the manifest to learn its digest, then serve it by digest
+        let manifest = mock_image(
+            &mut server,
+            repository,
+            "pending",
+            &config,
+            std::slice::from_ref(app),
+        );
+        let digest = sha256(manifest.as_bytes());
+        mock_image(
+            &mut server,
+            repository,
+            &digest,
+            &config,
+            std::slice::from_ref(app),
+        );
+        let variant = variant
+            .map(|v| format!(r#","variant":"{}""#, v))
+            .unwrap_or_default();
+        descriptors.push(format!(
+            r#"{{"mediaType":"application/vnd.oci.image.manifest.v1+json","size":{},"digest":"{}",
+                "platform":{{"os":"linux","architecture":"{}"{}}}}}"#,
+            manifest.len(),
+            digest,
+            architecture,
+            variant
+        ));
+    }
+    let index = format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[{}]}}"#,
+        descriptors.join(",")
+    );
+    server
+        .mock("GET", format!("/v2/{}/manifests/v1", repository).as_str())
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_header("Docker-Content-Digest", &sha256(index.as_bytes()))
+        .with_body(&index)
+        .create();
+    server
+}
+
+#[test]
+fn test_diff_images_two_platforms() {
+    let app = layer(&[("app/server", "binary")]);
+    let server = platforms_server(
+        "diff-platforms",
+        &[("amd64", None, app.clone()), ("arm64", None, app)],
+    );
+
+    let diff = diff_images(
+        &server.url(),
+        "diff-platforms:v1",
+        "diff-platforms:v1",
+        Some("linux/amd64"),
+        Some("linux/arm64"),
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(diff.config.len(), 1);
+    assert_eq!(diff.config[0].field, "platform");
+    assert_eq!(diff.config[0].new.as_deref(), Some("linux/arm64"));
+    assert!(diff.layers.is_empty());
+    assert_eq!(diff.new.platform, "linux/arm64");
+
+    let err = diff_images(
+        &server.url(),
+        "diff-platforms:v1",
+        "diff-platforms:v1",
+        None,
+        None,
+        false,
+    )
+    .unwrap_err();
+    assert!(err.contains("--platform"));
+}
+
+#[test]
+fn test_diff_images_platform_variants() {
+    let server = platforms_server(
+        "diff-variants",
+        &[
+            ("arm", Some("v6"), layer(&[("app/server", "v6-binary")])),
+            (
+                "arm",
+                Some("v7"),
+                layer(&[("app/server", "v7-binary-larger")]),
+            ),
+        ],
+    );
+
+    let diff = diff_images(
+        &server.url(),
+        "diff-variants:v1",
+        "diff-variants:v1",
+        Some("linux/arm/v6"),
+        Some("linux/arm/v7"),
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(diff.layers.added.len(), 1);
+    assert_eq!(diff.layers.removed.len(), 1);
+    let files = diff.files.as_ref().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].path, "app/server");
+    assert_eq!(files[0].change, Change::Modified);
+
+    let err = diff_images(
+        &server.url(),
+        "diff-variants:v1",
+        "diff-variants:v1",
+        Some("linux/arm/v8"),
+        None,
+        false,
+    )
+    .unwrap_err();
+    assert!(err.contains("Available platforms: linux/arm/v6, linux/arm/v7"));
+}
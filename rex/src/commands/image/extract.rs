@@ -0,0 +1,138 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image extract command
+pub fn handle_image_extract(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    path: &str,
+    output: &std::path::Path,
+    platform: Option<&str>,
+    format: OutputFormat,
+) {
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!(
+            "Extracting {} from {} to {}",
+            path,
+            reference,
+            output.display()
+        ),
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let extracted = match extract_image_path(&registry_url, reference, platform, path, output) {
+        Ok(extracted) => extracted,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => print!("{}", extracted.format_pretty()),
+        OutputFormat::Json => match serde_json::to_string_pretty(&extracted) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// Result of extracting a path of an image
+#[derive(Debug, Serialize)]
+pub struct ImageExtract {
+    /// Image reference
+    pub reference: String,
+    /// Path extracted from the image
+    pub path: String,
+    /// Local directory the path was extracted to
+    pub destination: String,
+    /// What was written
+    #[serde(flatten)]
+    pub report: librex::layer::ExtractReport,
+}
+
+impl Formattable for ImageExtract {
+    fn format_pretty(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "Extracted {} from {} to {}\n",
+            self.path, self.reference, self.destination
+        ));
+        output.push_str(&format!(
+            "  {} files ({}), {} directories, {} links\n",
+            self.report.files,
+            librex::format::format_size(self.report.bytes),
+            self.report.directories,
+            self.report.links
+        ));
+
+        if !self.report.skipped.is_empty() {
+            output.push_str(&format!("\nSkipped ({}):\n", self.report.skipped.len()));
+            for skipped in &self.report.skipped {
+                output.push_str(&format!("  {}\n", skipped));
+            }
+        }
+        output
+    }
+}
+
+/// Extract a file or directory of an image into a local directory.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "nginx:latest" or "nginx@sha256:...")
+/// * `platform` - Platform for multi-platform images (e.g., "linux/amd64")
+/// * `path` - Path to extract (e.g., "/etc/nginx")
+/// * `destination` - Local directory; entries keep their image path below it
+///
+/// # Returns
+///
+/// Returns what was extracted, and the entries that were skipped
+pub(crate) fn extract_image_path(
+    registry_url: &str,
+    reference: &str,
+    platform: Option<&str>,
+    path: &str,
+    destination: &std::path::Path,
+) -> Result<ImageExtract, String> {
+    let parsed = librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let fetched = rex
+        .get_manifest(reference)
+        .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
+    let (manifest, _) = select_platform_manifest(&mut rex, &parsed, fetched, platform)?;
+
+    let report = rex
+        .extract_files(reference, &manifest, path, destination)
+        .map_err(|e| format!("Failed to extract {}: {}", path, e))?;
+
+    Ok(ImageExtract {
+        reference: reference.to_string(),
+        path: format!("/{}", path.trim_matches('/')),
+        destination: destination.display().to_string(),
+        report,
+    })
+}
+
+#[cfg(test)]
+#[path = "extract_tests.rs"]
+mod tests;
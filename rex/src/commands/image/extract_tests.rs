@@ -0,0 +1,73 @@
+use super::*;
+use crate::test_support::alpine_server;
+
+// Note: These tests extract from the alpine-like image of `alpine_server`.
+
+#[test]
+fn test_extract_image_path_directory() {
+    let (server, _base) = alpine_server("extract-etc");
+    let dir = tempfile::tempdir().unwrap();
+
+    let extracted =
+        extract_image_path(&server.url(), "extract-etc:v1", None, "etc/", dir.path()).unwrap();
+
+    // The whiteout removed motd; the symlink is kept as a link
+    assert!(!dir.path().join("etc/motd").exists());
+    assert!(
+        std::fs::symlink_metadata(dir.path().join("etc/os-release"))
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    assert!(!dir.path().join("app").exists());
+    assert_eq!(extracted.path, "/etc");
+    assert_eq!(extracted.report.files, 0);
+    assert_eq!(extracted.report.links, 1);
+    assert_eq!(extracted.report.directories, 1);
+
+    let pretty = extracted.format_pretty();
+    assert!(pretty.starts_with("Extracted /etc from extract-etc:v1 to "));
+    assert!(pretty.contains("0 files (0 B), 1 directories, 1 links"));
+
+    let json = serde_json::to_value(&extracted).unwrap();
+    assert_eq!(json["links"], 1);
+    assert_eq!(json["skipped"], serde_json::json!([]));
+}
+
+#[test]
+fn test_extract_image_path_everything() {
+    let (server, _base) = alpine_server("extract-all");
+    let dir = tempfile::tempdir().unwrap();
+
+    let extracted =
+        extract_image_path(&server.url(), "extract-all:v1", None, "/", dir.path()).unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("etc/os-release")).unwrap(),
+        "ID=alpine\nVERSION_ID=3.19.1\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("app/config.yaml")).unwrap(),
+        "port: 8080\n"
+    );
+    assert_eq!(extracted.report.files, 2);
+    assert_eq!(extracted.report.bytes, 39);
+}
+
+#[test]
+fn test_extract_image_path_missing() {
+    let (server, _base) = alpine_server("extract-missing");
+    let dir = tempfile::tempdir().unwrap();
+
+    let err = extract_image_path(
+        &server.url(),
+        "extract-missing:v1",
+        None,
+        "/etc/motd",
+        dir.path(),
+    )
+    .unwrap_err();
+
+    assert!(err.contains("Failed to extract /etc/motd"));
+    assert!(err.contains("not found"));
+}
@@ -0,0 +1,280 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image files command
+pub fn handle_image_files(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    platform: Option<&str>,
+    layer: Option<usize>,
+    path: Option<&str>,
+    format: OutputFormat,
+) {
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &match layer {
+            Some(layer) => format!("Listing files of {} (layer {})", reference, layer),
+            None => format!("Listing files of {}", reference),
+        },
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let files = match list_image_files(&registry_url, reference, platform, layer, path) {
+        Ok(files) => files,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => print!("{}", files.format_pretty()),
+        OutputFormat::Json => match serde_json::to_string_pretty(&files) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// A file in an image or layer listing
+#[derive(Debug, Clone, Serialize)]
+pub struct FileInfo {
+    /// Path without leading "/" (e.g., "etc/os-release")
+    pub path: String,
+    /// Entry kind (file, directory, symlink, hardlink, other, whiteout, opaque-whiteout)
+    pub kind: librex::layer::EntryKind,
+    /// Size in bytes
+    pub size: u64,
+    /// Permission bits in octal (e.g., "0755")
+    pub mode: String,
+    /// Owner user ID
+    pub uid: u64,
+    /// Owner group ID
+    pub gid: u64,
+    /// Target of symbolic and hard links
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<String>,
+    /// Layer the entry comes from (1-based, as in `rex image inspect`)
+    pub layer: usize,
+}
+
+impl From<librex::layer::FileEntry> for FileInfo {
+    fn from(entry: librex::layer::FileEntry) -> Self {
+        FileInfo {
+            path: entry.path,
+            kind: entry.kind,
+            size: entry.size,
+            mode: format!("{:04o}", entry.mode),
+            uid: entry.uid,
+            gid: entry.gid,
+            link_target: entry.link_target,
+            layer: entry.layer + 1,
+        }
+    }
+}
+
+impl FileInfo {
+    /// Permissions in `ls -l` notation (e.g., "drwxr-xr-x")
+    fn symbolic_mode(&self) -> String {
+        let kind = match self.kind {
+            librex::layer::EntryKind::Directory => 'd',
+            librex::layer::EntryKind::Symlink => 'l',
+            librex::layer::EntryKind::Other => 'c',
+            _ => '-',
+        };
+        let bits = u32::from_str_radix(&self.mode, 8).unwrap_or(0);
+        let mut mode = String::from(kind);
+        for shift in [6, 3, 0] {
+            let triplet = (bits >> shift) & 0o7;
+            mode.push(if triplet & 0o4 != 0 { 'r' } else { '-' });
+            mode.push(if triplet & 0o2 != 0 { 'w' } else { '-' });
+            mode.push(if triplet & 0o1 != 0 { 'x' } else { '-' });
+        }
+        mode
+    }
+}
+
+/// Files of an image (merged filesystem) or of one layer
+#[derive(Debug, Serialize)]
+pub struct ImageFiles {
+    /// Image reference
+    pub reference: String,
+    /// Layer listed (1-based); none for the merged filesystem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<usize>,
+    /// Path prefix the listing is limited to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Number of regular files
+    pub file_count: usize,
+    /// Total size of the regular files in bytes
+    pub total_size: u64,
+    /// Entries, sorted by path
+    pub files: Vec<FileInfo>,
+}
+
+/// Node of the directory tree printed by `rex image files`
+#[derive(Default)]
+struct FileTreeNode<'a> {
+    file: Option<&'a FileInfo>,
+    children: std::collections::BTreeMap<&'a str, FileTreeNode<'a>>,
+}
+
+impl<'a> FileTreeNode<'a> {
+    fn insert(&mut self, file: &'a FileInfo) {
+        let mut node = self;
+        for part in file.path.split('/') {
+            node = node.children.entry(part).or_default();
+        }
+        node.file = Some(file);
+    }
+
+    fn render(&self, prefix: &str, output: &mut String) {
+        let count = self.children.len();
+        for (i, (name, child)) in self.children.iter().enumerate() {
+            let last = i + 1 == count;
+            let branch = if last { "└── " } else { "├── " };
+            output.push_str(&format!("{}{}{}", prefix, branch, name));
+
+            if let Some(file) = child.file {
+                use librex::layer::EntryKind;
+                match file.kind {
+                    EntryKind::Whiteout => output.push_str("  [deleted]"),
+                    EntryKind::OpaqueWhiteout => output.push_str("/  [opaque]"),
+                    _ => {
+                        if file.kind == EntryKind::Directory {
+                            output.push('/');
+                        }
+                        if let Some(target) = &file.link_target {
+                            output.push_str(&format!(" -> {}", target));
+                        }
+                        output.push_str(&format!(
+                            "  ({} {}:{}",
+                            file.symbolic_mode(),
+                            file.uid,
+                            file.gid
+                        ));
+                        if file.kind == EntryKind::File {
+                            output
+                                .push_str(&format!(", {}", librex::format::format_size(file.size)));
+                        }
+                        output.push(')');
+                    }
+                }
+            }
+            output.push('\n');
+
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            child.render(&child_prefix, output);
+        }
+    }
+}
+
+impl Formattable for ImageFiles {
+    fn format_pretty(&self) -> String {
+        let mut output = String::new();
+
+        let scope = match self.layer {
+            Some(layer) => format!("layer {}", layer),
+            None => "merged filesystem".to_string(),
+        };
+        output.push_str(&format!("{} ({})\n", self.reference, scope));
+
+        if self.files.is_empty() {
+            output.push_str("No files found.\n");
+            return output;
+        }
+
+        let mut root = FileTreeNode::default();
+        for file in &self.files {
+            root.insert(file);
+        }
+        let base = self
+            .path
+            .as_deref()
+            .map(|p| format!("/{}", p.trim_matches('/')))
+            .unwrap_or_else(|| "/".to_string());
+        output.push_str(&format!("{}\n", base));
+        root.render("", &mut output);
+
+        output.push_str(&format!(
+            "\n{} files, {}\n",
+            self.file_count,
+            librex::format::format_size(self.total_size)
+        ));
+        output
+    }
+}
+
+/// List the files of an image, or of one of its layers.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "alpine:3.19" or "alpine@sha256:...")
+/// * `platform` - Platform for multi-platform images (e.g., "linux/amd64")
+/// * `layer` - Layer to list (1-based, as numbered by `rex image inspect`)
+/// * `path` - Only list entries at or below this path (e.g., "/etc")
+///
+/// # Returns
+///
+/// Returns the entries sorted by path; without `layer`, the merged filesystem
+pub(crate) fn list_image_files(
+    registry_url: &str,
+    reference: &str,
+    platform: Option<&str>,
+    layer: Option<usize>,
+    path: Option<&str>,
+) -> Result<ImageFiles, String> {
+    let parsed = librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+    if layer == Some(0) {
+        return Err("Layers are numbered from 1".to_string());
+    }
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let fetched = rex
+        .get_manifest(reference)
+        .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
+    let (manifest, _) = select_platform_manifest(&mut rex, &parsed, fetched, platform)?;
+
+    let entries = rex
+        .list_files(reference, &manifest, layer.map(|n| n - 1))
+        .map_err(|e| format!("Failed to list files: {}", e))?;
+
+    let files: Vec<FileInfo> = entries
+        .into_iter()
+        .filter(|entry| path.is_none_or(|prefix| entry.is_under(prefix)))
+        .map(FileInfo::from)
+        .collect();
+    let regular = files
+        .iter()
+        .filter(|f| f.kind == librex::layer::EntryKind::File);
+
+    Ok(ImageFiles {
+        reference: reference.to_string(),
+        layer,
+        path: path.map(str::to_string),
+        file_count: regular.clone().count(),
+        total_size: regular.map(|f| f.size).sum(),
+        files,
+    })
+}
+
+#[cfg(test)]
+#[path = "files_tests.rs"]
+mod tests;
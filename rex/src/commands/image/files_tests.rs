@@ -0,0 +1,137 @@
+use super::*;
+use crate::test_support::{layer, mock_image, sha256};
+use librex::layer::EntryKind;
+
+// Note: These tests build gzip-compressed tar layers in memory and serve them
+// from mockito together with the image manifest.
+
+/// Serve a two-layer image: a base, then a layer deleting and adding files
+fn image_server(repository: &str) -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+
+    let layers = vec![
+        layer(&[
+            ("etc/", ""),
+            ("etc/os-release", "ID=alpine\n"),
+            ("etc/motd", "Welcome!\n"),
+            ("var/cache/", ""),
+            ("var/cache/apk.idx", "index"),
+        ]),
+        layer(&[
+            ("etc/.wh.motd", ""),
+            ("var/cache/.wh..wh..opq", ""),
+            ("app/", ""),
+            ("app/server", "binary-content"),
+        ]),
+    ];
+    mock_image(&mut server, repository, "v1", "{}", &layers);
+    server
+}
+
+fn paths(files: &ImageFiles) -> Vec<&str> {
+    files.files.iter().map(|f| f.path.as_str()).collect()
+}
+
+#[test]
+fn test_list_image_files_merged() {
+    let server = image_server("files-merged");
+
+    let files = list_image_files(&server.url(), "files-merged:v1", None, None, None).unwrap();
+
+    assert_eq!(
+        paths(&files),
+        vec!["app", "app/server", "etc", "etc/os-release", "var/cache"]
+    );
+    assert_eq!(files.file_count, 2);
+    assert_eq!(files.total_size, 24);
+    let server_file = &files.files[1];
+    assert_eq!(server_file.layer, 2);
+    assert_eq!(server_file.mode, "0644");
+
+    let pretty = files.format_pretty();
+    assert!(pretty.contains("files-merged:v1 (merged filesystem)"));
+    assert!(pretty.contains("├── app/  (drwxr-xr-x 0:0)"));
+    assert!(pretty.contains("│   └── server  (-rw-r--r-- 0:0, 14 B)"));
+    assert!(!pretty.contains("motd"));
+    assert!(pretty.contains("2 files, 24 B"));
+}
+
+#[test]
+fn test_list_image_files_single_layer_shows_whiteouts() {
+    let server = image_server("files-layer");
+
+    let files = list_image_files(&server.url(), "files-layer:v1", None, Some(2), None).unwrap();
+
+    assert_eq!(files.layer, Some(2));
+    let motd = files.files.iter().find(|f| f.path == "etc/motd").unwrap();
+    assert_eq!(motd.kind, EntryKind::Whiteout);
+
+    let pretty = files.format_pretty();
+    assert!(pretty.contains("(layer 2)"));
+    assert!(pretty.contains("motd  [deleted]"));
+    assert!(pretty.contains("cache/  [opaque]"));
+
+    let json = serde_json::to_value(&files).unwrap();
+    assert_eq!(json["layer"], 2);
+    assert!(
+        json["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|f| f["kind"] == "opaque-whiteout")
+    );
+}
+
+#[test]
+fn test_list_image_files_path_prefix() {
+    let server = image_server("files-prefix");
+
+    let files =
+        list_image_files(&server.url(), "files-prefix:v1", None, None, Some("/etc/")).unwrap();
+
+    assert_eq!(paths(&files), vec!["etc", "etc/os-release"]);
+    assert!(
+        files
+            .format_pretty()
+            .starts_with("files-prefix:v1 (merged filesystem)\n/etc\n")
+    );
+
+    let json = serde_json::to_value(&files).unwrap();
+    assert_eq!(json["path"], "/etc/");
+    assert!(json.get("layer").is_none());
+}
+
+#[test]
+fn test_list_image_files_layer_out_of_range() {
+    let server = image_server("files-range");
+
+    let err = list_image_files(&server.url(), "files-range:v1", None, Some(3), None).unwrap_err();
+    assert!(err.contains("Layer 3 does not exist (the image has 2 layers)"));
+
+    let err = list_image_files(&server.url(), "files-range:v1", None, Some(0), None).unwrap_err();
+    assert!(err.contains("numbered from 1"));
+}
+
+#[test]
+fn test_list_image_files_multi_platform_requires_platform() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    let index = r#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[
+        {"mediaType":"application/vnd.oci.image.manifest.v1+json","size":10,
+         "digest":"sha256:1111111111111111111111111111111111111111111111111111111111111111",
+         "platform":{"os":"linux","architecture":"amd64"}}
+    ]}"#;
+    server
+        .mock("GET", "/v2/files-index/manifests/v1")
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_header("Docker-Content-Digest", &sha256(index.as_bytes()))
+        .with_body(index)
+        .create();
+
+    let err = list_image_files(&server.url(), "files-index:v1", None, None, None).unwrap_err();
+
+    assert!(err.contains("--platform"));
+    assert!(err.contains("linux/amd64"));
+}
@@ -37,13 +37,13 @@ pub fn handle_image_inspect(
         };
 
     // Handle raw output flags (these take precedence over format flags)
-    if raw_manifest && inspect.raw_manifest.is_some() {
-        println!("{}", inspect.raw_manifest.as_ref().unwrap());
+    if raw_manifest && let Some(manifest) = &inspect.raw_manifest {
+        println!("{}", manifest);
         return;
     }
 
-    if raw_config && inspect.raw_config.is_some() {
-        println!("{}", inspect.raw_config.as_ref().unwrap());
+    if raw_config && let Some(config) = &inspect.raw_config {
+        println!("{}", config);
         return;
     }
 
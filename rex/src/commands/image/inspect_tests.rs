@@ -794,3 +794,192 @@ fn test_get_image_inspect_both_raw_flags() {
     assert!(inspect.raw_manifest.is_some());
     assert!(inspect.raw_config.is_some());
 }
+
+const HELM_CONFIG_JSON: &str = r#"{"name":"mychart","version":"0.1.0","apiVersion":"v2"}"#;
+
+const HELM_CONFIG_DIGEST: &str =
+    "sha256:ca3dcfca780843a579dc24b9d2dd3cd1ca51f45d0bc636dac64dbb1953f4ff19";
+
+const HELM_MANIFEST_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+    "config": {
+        "mediaType": "application/vnd.cncf.helm.config.v1+json",
+        "size": 54,
+        "digest": "sha256:ca3dcfca780843a579dc24b9d2dd3cd1ca51f45d0bc636dac64dbb1953f4ff19"
+    },
+    "layers": [
+        {
+            "mediaType": "application/vnd.cncf.helm.chart.content.v1.tar+gzip",
+            "size": 3712,
+            "digest": "sha256:3333333333333333333333333333333333333333333333333333333333333333"
+        }
+    ],
+    "annotations": {
+        "org.opencontainers.image.created": "2024-06-01T12:00:00Z"
+    }
+}"#;
+
+const HELM_MANIFEST_DIGEST: &str =
+    "sha256:cfd0323260dc2df206d79944562c71b46516e0d8f8eb89434dd9ea46a0e7628f";
+
+const SBOM_MANIFEST_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+    "artifactType": "application/spdx+json",
+    "config": {
+        "mediaType": "application/vnd.oci.empty.v1+json",
+        "size": 2,
+        "digest": "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+    },
+    "layers": [
+        {
+            "mediaType": "application/spdx+json",
+            "size": 4096,
+            "digest": "sha256:4444444444444444444444444444444444444444444444444444444444444444"
+        }
+    ],
+    "subject": {
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "size": 1024,
+        "digest": "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b"
+    }
+}"#;
+
+const SBOM_MANIFEST_DIGEST: &str =
+    "sha256:44a814c3ddb62eac1c560362e4a9ec79c20489e3c212646c9b568913c93b6741";
+
+// Artifacts have no image configuration: the config blob must not be fetched
+#[test]
+fn test_get_image_inspect_helm_chart() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/charts/mychart/manifests/0.1.0")
+        .with_status(200)
+        .with_header("content-type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("docker-content-digest", HELM_MANIFEST_DIGEST)
+        .with_body(HELM_MANIFEST_JSON)
+        .create();
+    let config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/charts/mychart/blobs/{}", HELM_CONFIG_DIGEST).as_str(),
+        )
+        .expect(0)
+        .create();
+
+    let inspect =
+        get_image_inspect(&registry_url, "charts/mychart:0.1.0", None, false, false).unwrap();
+
+    config_mock.assert();
+    assert_eq!(inspect.manifest_type, "OCI Artifact (Helm chart)");
+    assert_eq!(inspect.created.as_deref(), Some("2024-06-01T12:00:00Z"));
+    assert_eq!(inspect.size, 3712);
+
+    let artifact = inspect.artifact.as_ref().unwrap();
+    assert_eq!(artifact.kind, "Helm chart");
+    assert_eq!(
+        artifact.artifact_type,
+        "application/vnd.cncf.helm.config.v1+json"
+    );
+
+    let pretty = inspect.format_pretty();
+    assert!(pretty.contains("Artifact:"));
+    assert!(pretty.contains("Kind: Helm chart"));
+    assert!(!pretty.contains("Configuration:"));
+    assert!(!pretty.contains("RootFS:"));
+}
+
+#[test]
+fn test_get_image_inspect_artifact_raw_config() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/charts/rawchart/manifests/0.1.0")
+        .with_status(200)
+        .with_header("docker-content-digest", HELM_MANIFEST_DIGEST)
+        .with_body(HELM_MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/charts/rawchart/blobs/{}", HELM_CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(HELM_CONFIG_JSON)
+        .create();
+
+    let inspect =
+        get_image_inspect(&registry_url, "charts/rawchart:0.1.0", None, false, true).unwrap();
+
+    let raw_config = inspect.raw_config.unwrap();
+    assert!(raw_config.contains("\"name\": \"mychart\""));
+}
+
+#[test]
+fn test_get_image_inspect_sbom_subject() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock(
+            "GET",
+            format!("/v2/artifact-sbom/manifests/{}", SBOM_MANIFEST_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_header("docker-content-digest", SBOM_MANIFEST_DIGEST)
+        .with_body(SBOM_MANIFEST_JSON)
+        .create();
+
+    let inspect = get_image_inspect(
+        &registry_url,
+        &format!("artifact-sbom@{}", SBOM_MANIFEST_DIGEST),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let artifact = inspect.artifact.as_ref().unwrap();
+    assert_eq!(artifact.kind, "SPDX SBOM");
+    assert_eq!(artifact.artifact_type, "application/spdx+json");
+    assert_eq!(
+        artifact.subject.as_deref(),
+        Some("sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b")
+    );
+    assert!(inspect.created.is_none());
+
+    let json = serde_json::to_value(&inspect).unwrap();
+    assert_eq!(json["artifact"]["kind"], "SPDX SBOM");
+}
+
+#[test]
+fn test_get_image_details_artifact() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/charts/showchart/manifests/0.1.0")
+        .with_status(200)
+        .with_header("docker-content-digest", HELM_MANIFEST_DIGEST)
+        .with_body(HELM_MANIFEST_JSON)
+        .create();
+
+    let details = get_image_details(&registry_url, "charts/showchart:0.1.0").unwrap();
+
+    assert_eq!(details.manifest_type, "OCI Artifact (Helm chart)");
+    assert!(details.platforms.is_empty());
+    assert_eq!(details.layers, 1);
+    assert_eq!(details.created.as_deref(), Some("2024-06-01T12:00:00Z"));
+
+    let pretty = details.format_pretty();
+    assert!(pretty.contains("Artifact Type: application/vnd.cncf.helm.config.v1+json"));
+    assert!(!pretty.contains("Platform:"));
+}
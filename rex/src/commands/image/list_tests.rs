@@ -1,4 +1,3 @@
-use super::*;
 use crate::context::{AppContext, VerbosityLevel};
 use crate::format::{ColorChoice, OutputFormat};
 
@@ -41,8 +40,7 @@ fn test_handle_image_list_quiet_flag() {
 fn test_handle_image_list_filter_validation() {
     // Test filter string handling
     let filter = Some("alpine");
-    assert!(filter.is_some());
-    assert_eq!(filter.unwrap(), "alpine");
+    assert_eq!(filter, Some("alpine"));
 
     let no_filter: Option<&str> = None;
     assert!(no_filter.is_none());
@@ -52,8 +50,7 @@ fn test_handle_image_list_filter_validation() {
 fn test_handle_image_list_limit_validation() {
     // Test limit parameter handling
     let limit = Some(10);
-    assert!(limit.is_some());
-    assert_eq!(limit.unwrap(), 10);
+    assert_eq!(limit, Some(10));
 
     let no_limit: Option<usize> = None;
     assert!(no_limit.is_none());
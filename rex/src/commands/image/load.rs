@@ -0,0 +1,119 @@
+use super::copy::resolve_image_registry;
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format;
+use librex::oci_layout::LoadEvent;
+use std::path::Path;
+
+/// Handle the image load command (push an image from an OCI layout or docker archive)
+pub fn handle_image_load(
+    ctx: &crate::context::AppContext,
+    source: &Path,
+    reference: &str,
+    name: Option<&str>,
+) {
+    let resolved = get_registry_url().and_then(|default_url| {
+        let registries = config::Config::load(&config::get_config_path())
+            .map(|cfg| cfg.registries.list)
+            .unwrap_or_default();
+        resolve_image_registry(reference, &default_url, &registries)
+    });
+    let (registry_url, image_ref) = match resolved {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!(
+            "Loading {} into {} on {}...",
+            source.display(),
+            image_ref,
+            registry_url
+        ),
+    );
+
+    let on_event = |event: &LoadEvent| {
+        let message = match event {
+            LoadEvent::BlobExists(digest) => format!("Blob {} already exists", digest),
+            LoadEvent::BlobUploaded { digest, size } => format!(
+                "Uploaded blob {} ({})",
+                digest,
+                librex::format::format_size(*size)
+            ),
+            LoadEvent::ManifestExists(digest) => format!("Manifest {} already exists", digest),
+            LoadEvent::ManifestPushed { reference, digest } => {
+                format!("Pushed manifest {} as {}", digest, reference)
+            }
+        };
+        format::print(ctx, VerbosityLevel::Verbose, &message);
+    };
+
+    match load_image(&registry_url, source, &image_ref, name, on_event) {
+        Ok(report) => {
+            format::success(
+                ctx,
+                &format!(
+                    "Loaded {} into {} ({}): {} blobs uploaded ({}), {} already present",
+                    source.display(),
+                    reference,
+                    report.digest,
+                    report.blobs_uploaded,
+                    librex::format::format_size(report.bytes_uploaded),
+                    report.blobs_skipped
+                ),
+            );
+        }
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Push an image from an OCI image layout or a Docker archive.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `source` - Layout directory, or tarball
+/// * `reference` - Image reference to push to, without registry host
+/// * `name` - Image to load when the source holds several (e.g., "alpine:3.19")
+/// * `on_event` - Called as each blob and manifest is processed
+///
+/// # Returns
+///
+/// Returns the load report
+pub(crate) fn load_image<F>(
+    registry_url: &str,
+    source: &std::path::Path,
+    reference: &str,
+    name: Option<&str>,
+    on_event: F,
+) -> Result<librex::oci_layout::LoadReport, String>
+where
+    F: FnMut(&librex::oci_layout::LoadEvent),
+{
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+    if !source.exists() {
+        return Err(format!("{} does not exist", source.display()));
+    }
+
+    let mut options = librex::oci_layout::LoadOptions::new();
+    if let Some(name) = name {
+        options = options.with_name(name);
+    }
+
+    let mut rex = connect_rex(registry_url)?;
+    rex.load_image(source, reference, &options, on_event)
+        .map_err(|e| format!("Failed to load image: {}", e))
+}
+
+#[cfg(test)]
+#[path = "load_tests.rs"]
+mod tests;
@@ -0,0 +1,124 @@
+use super::*;
+use crate::commands::image::save::save_image;
+use crate::test_support::{config, layer, mock_image, sha256};
+use librex::oci_layout::LoadEvent;
+
+// Note: These tests save an image served by mockito (see `test_support`),
+// then load the archive into a second mock registry that accepts pushes.
+
+/// Accept uploads of `blobs` and a manifest push under `reference`
+fn accepting_server(
+    repository: &str,
+    reference: &str,
+    blobs: &[&[u8]],
+    manifest: &[u8],
+) -> (mockito::ServerGuard, Vec<mockito::Mock>) {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    let mut mocks = Vec::new();
+    for blob in blobs {
+        let digest = sha256(blob);
+        server
+            .mock(
+                "HEAD",
+                format!("/v2/{}/blobs/{}", repository, digest).as_str(),
+            )
+            .with_status(404)
+            .create();
+        let session = format!("/v2/{}/blobs/uploads/{}", repository, &digest[7..]);
+        server
+            .mock(
+                "POST",
+                format!("/v2/{}/blobs/uploads/", repository).as_str(),
+            )
+            .with_status(202)
+            .with_header("Location", &session)
+            .create();
+        mocks.push(
+            server
+                .mock("PUT", session.as_str())
+                .match_query(mockito::Matcher::UrlEncoded("digest".into(), digest))
+                .match_body(blob.to_vec())
+                .with_status(201)
+                .create(),
+        );
+    }
+    mocks.push(
+        server
+            .mock(
+                "PUT",
+                format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+            )
+            .match_body(manifest.to_vec())
+            .with_status(201)
+            .with_header("Docker-Content-Digest", &sha256(manifest))
+            .create(),
+    );
+    (server, mocks)
+}
+
+#[test]
+fn test_load_image_round_trip() {
+    let mut source = mockito::Server::new();
+    source.mock("GET", "/v2/").with_status(200).create();
+    let app = layer(&[("app/server", "binary")]);
+    let config = config("amd64", "app", "1.5");
+    let manifest = mock_image(
+        &mut source,
+        "load-app",
+        "1.5",
+        &config,
+        std::slice::from_ref(&app),
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("app.tar");
+    save_image(&source.url(), "load-app:1.5", &archive, "oci", None, |_| {}).unwrap();
+
+    // The mirror already has the config
+    let (mut mirror, mocks) = accepting_server("mirror/app", "1.5", &[&app], manifest.as_bytes());
+    mirror
+        .mock(
+            "HEAD",
+            format!("/v2/mirror/app/blobs/{}", sha256(config.as_bytes())).as_str(),
+        )
+        .with_status(200)
+        .create();
+    let mut events = Vec::new();
+
+    let report = load_image(&mirror.url(), &archive, "mirror/app:1.5", None, |event| {
+        events.push(event.clone())
+    })
+    .unwrap();
+
+    assert_eq!(report.digest, sha256(manifest.as_bytes()));
+    assert_eq!(report.blobs_uploaded, 1);
+    assert_eq!(report.blobs_skipped, 1);
+    mocks.iter().for_each(|mock| mock.assert());
+    assert_eq!(
+        events.last(),
+        Some(&LoadEvent::ManifestPushed {
+            reference: "1.5".to_string(),
+            digest: sha256(manifest.as_bytes()),
+        })
+    );
+}
+
+#[test]
+fn test_load_image_errors() {
+    let server = mockito::Server::new();
+    let dir = tempfile::tempdir().unwrap();
+
+    let err = load_image(
+        &server.url(),
+        &dir.path().join("missing.tar"),
+        "app:1.5",
+        None,
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(err.contains("missing.tar does not exist"));
+
+    let err = load_image(&server.url(), dir.path(), "app:1.5", None, |_| {}).unwrap_err();
+    assert!(err.starts_with("Failed to load image: "));
+    assert!(err.contains("is not an OCI image layout or Docker archive"));
+}
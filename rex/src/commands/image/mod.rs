@@ -1,23 +1,49 @@
 use crate::config;
 use crate::context::VerbosityLevel;
 use crate::format::{self, Formattable};
-use librex::auth::CredentialStore;
 use serde::Serialize;
 use std::str::FromStr;
 
 // Handler modules (one per subcommand)
+pub mod cat;
+pub mod copy;
+pub mod cves;
 pub mod details;
+pub mod diff;
+pub mod extract;
+pub mod files;
 pub mod inspect;
 pub mod list;
+pub mod load;
+pub mod referrers;
 pub mod remove;
+pub mod save;
+pub mod sbom;
+pub mod tag;
 pub mod tags;
+pub mod verify;
 
 // Re-export public handlers
+pub use cat::handle_image_cat;
+pub use copy::handle_image_copy;
+pub use cves::handle_image_cves;
 pub use details::handle_image_details;
+pub use diff::handle_image_diff;
+pub use extract::handle_image_extract;
+pub use files::handle_image_files;
 pub use inspect::handle_image_inspect;
 pub use list::handle_image_list;
+pub use load::handle_image_load;
+pub use referrers::handle_image_referrers;
 pub use remove::handle_image_remove;
+pub use save::handle_image_save;
+pub use sbom::handle_image_sbom;
+pub use tag::handle_image_tag;
 pub use tags::handle_image_tags;
+pub use verify::handle_image_verify;
+
+use cves::CveSummary;
+use referrers::ReferrerInfo;
 
 // Re-export TagInfo and RepositoryItem from shared image module
 pub use crate::image::{RepositoryItem, TagInfo};
@@ -57,6 +83,9 @@ pub struct ImageDetails {
     pub layers: usize,
     /// Created timestamp
     pub created: Option<String>,
+    /// Artifact details, for manifests that are not container images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ArtifactInfo>,
 }
 
 impl ImageDetails {
@@ -78,8 +107,15 @@ impl ImageDetails {
             platforms,
             layers,
             created,
+            artifact: None,
         }
     }
+
+    /// Attach artifact details
+    pub fn with_artifact(mut self, artifact: ArtifactInfo) -> Self {
+        self.artifact = Some(artifact);
+        self
+    }
 }
 
 impl Formattable for ImageDetails {
@@ -121,6 +157,12 @@ impl Formattable for ImageDetails {
 
         output.push_str(&format!("Digest: {}\n", self.digest));
         output.push_str(&format!("Type: {}\n", self.manifest_type));
+        if let Some(artifact) = &self.artifact {
+            output.push_str(&format!("Artifact Type: {}\n", artifact.artifact_type));
+            if let Some(subject) = &artifact.subject {
+                output.push_str(&format!("Subject: {}\n", subject));
+            }
+        }
         output.push_str(&format!("Size: {}\n", format_bytes(self.size)));
 
         if !self.platforms.is_empty() {
@@ -156,6 +198,40 @@ pub struct HistoryEntry {
     pub empty_layer: bool,
 }
 
+/// Annotation holding the creation time of an image or artifact
+const ANNOTATION_CREATED: &str = "org.opencontainers.image.created";
+
+/// Details of a manifest that stores an artifact instead of a container image
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactInfo {
+    /// Artifact kind (e.g., "Helm chart", "SPDX SBOM")
+    pub kind: String,
+    /// Artifact type, or the config media type when the manifest has none
+    pub artifact_type: String,
+    /// Config media type
+    pub config_media_type: String,
+    /// Digest of the manifest this artifact refers to
+    pub subject: Option<String>,
+    /// Manifest annotations
+    pub annotations: std::collections::BTreeMap<String, String>,
+}
+
+impl From<&librex::oci::Artifact> for ArtifactInfo {
+    fn from(artifact: &librex::oci::Artifact) -> Self {
+        Self {
+            kind: artifact.kind.to_string(),
+            artifact_type: artifact.artifact_type.clone(),
+            config_media_type: artifact.config_media_type.clone(),
+            subject: artifact.subject.as_ref().map(|s| s.digest().to_string()),
+            annotations: artifact
+                .annotations
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
 /// Complete inspection data for an image
 #[derive(Debug, Serialize)]
 pub struct ImageInspect {
@@ -199,6 +275,14 @@ pub struct ImageInspect {
     pub history: Vec<HistoryEntry>,
     /// RootFS diff IDs
     pub rootfs_diff_ids: Vec<String>,
+    /// Artifact details, for manifests that are not container images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ArtifactInfo>,
+    /// Vulnerability counts, on registries that scan for CVEs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<CveSummary>,
+    /// Artifacts attached to the image
+    pub referrers: Vec<ReferrerInfo>,
     /// Raw manifest JSON (only populated when requested)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_manifest: Option<String>,
@@ -239,66 +323,89 @@ impl Formattable for ImageInspect {
         output.push_str(&format!("Config Digest: {}\n", self.config_digest));
         output.push('\n');
 
-        // Configuration
-        output.push_str("Configuration:\n");
-        output.push_str(&format!("  Architecture: {}\n", self.architecture));
-        output.push_str(&format!("  OS: {}\n", self.os));
-        if let Some(created) = &self.created {
-            output.push_str(&format!("  Created: {}\n", created));
-        }
-        output.push('\n');
-
-        // Config details
-        output.push_str("  Config:\n");
-        if let Some(user) = &self.user {
-            output.push_str(&format!("    User: {}\n", user));
+        // Artifact details replace the image configuration
+        if let Some(artifact) = &self.artifact {
+            output.push_str("Artifact:\n");
+            output.push_str(&format!("  Kind: {}\n", artifact.kind));
+            output.push_str(&format!("  Artifact Type: {}\n", artifact.artifact_type));
+            output.push_str(&format!(
+                "  Config Media Type: {}\n",
+                artifact.config_media_type
+            ));
+            if let Some(subject) = &artifact.subject {
+                output.push_str(&format!("  Subject: {}\n", subject));
+            }
+            if let Some(created) = &self.created {
+                output.push_str(&format!("  Created: {}\n", created));
+            }
+            if !artifact.annotations.is_empty() {
+                output.push_str("\n  Annotations:\n");
+                for (key, value) in &artifact.annotations {
+                    output.push_str(&format!("    {}: {}\n", key, value));
+                }
+            }
         } else {
-            output.push_str("    User: (empty)\n");
-        }
+            // Configuration
+            output.push_str("Configuration:\n");
+            output.push_str(&format!("  Architecture: {}\n", self.architecture));
+            output.push_str(&format!("  OS: {}\n", self.os));
+            if let Some(created) = &self.created {
+                output.push_str(&format!("  Created: {}\n", created));
+            }
+            output.push('\n');
 
-        if !self.env.is_empty() {
-            output.push_str("    Env:\n");
-            for env in &self.env {
-                output.push_str(&format!("      - {}\n", env));
+            // Config details
+            output.push_str("  Config:\n");
+            if let Some(user) = &self.user {
+                output.push_str(&format!("    User: {}\n", user));
+            } else {
+                output.push_str("    User: (empty)\n");
             }
-        }
 
-        if let Some(entrypoint) = &self.entrypoint {
-            output.push_str("    Entrypoint:\n");
-            for entry in entrypoint {
-                output.push_str(&format!("      - {}\n", entry));
+            if !self.env.is_empty() {
+                output.push_str("    Env:\n");
+                for env in &self.env {
+                    output.push_str(&format!("      - {}\n", env));
+                }
             }
-        }
 
-        if let Some(cmd) = &self.cmd {
-            output.push_str("    Cmd:\n");
-            for c in cmd {
-                output.push_str(&format!("      - {}\n", c));
+            if let Some(entrypoint) = &self.entrypoint {
+                output.push_str("    Entrypoint:\n");
+                for entry in entrypoint {
+                    output.push_str(&format!("      - {}\n", entry));
+                }
             }
-        }
 
-        if let Some(wd) = &self.working_dir {
-            output.push_str(&format!("    WorkingDir: {}\n", wd));
-        }
+            if let Some(cmd) = &self.cmd {
+                output.push_str("    Cmd:\n");
+                for c in cmd {
+                    output.push_str(&format!("      - {}\n", c));
+                }
+            }
 
-        if !self.exposed_ports.is_empty() {
-            output.push_str("    ExposedPorts:\n");
-            for port in &self.exposed_ports {
-                output.push_str(&format!("      - {}\n", port));
+            if let Some(wd) = &self.working_dir {
+                output.push_str(&format!("    WorkingDir: {}\n", wd));
+            }
+
+            if !self.exposed_ports.is_empty() {
+                output.push_str("    ExposedPorts:\n");
+                for port in &self.exposed_ports {
+                    output.push_str(&format!("      - {}\n", port));
+                }
             }
-        }
 
-        if !self.volumes.is_empty() {
-            output.push_str("    Volumes:\n");
-            for vol in &self.volumes {
-                output.push_str(&format!("      - {}\n", vol));
+            if !self.volumes.is_empty() {
+                output.push_str("    Volumes:\n");
+                for vol in &self.volumes {
+                    output.push_str(&format!("      - {}\n", vol));
+                }
             }
-        }
 
-        if !self.labels.is_empty() {
-            output.push_str("\n  Labels:\n");
-            for (key, value) in &self.labels {
-                output.push_str(&format!("    {}: {}\n", key, value));
+            if !self.labels.is_empty() {
+                output.push_str("\n  Labels:\n");
+                for (key, value) in &self.labels {
+                    output.push_str(&format!("    {}: {}\n", key, value));
+                }
             }
         }
 
@@ -314,30 +421,53 @@ impl Formattable for ImageInspect {
             output.push_str(&format!("     Media Type: {}\n", layer.media_type));
         }
 
-        // History
-        if !self.history.is_empty() {
-            output.push_str(&format!("\nHistory ({} entries):\n", self.history.len()));
-            for (i, entry) in self.history.iter().enumerate() {
-                output.push_str(&format!("  {}. ", i + 1));
-                if let Some(created) = &entry.created {
-                    output.push_str(&format!("Created: {}", created));
-                }
-                if entry.empty_layer {
-                    output.push_str(" (empty layer)");
-                }
-                output.push('\n');
-                if let Some(created_by) = &entry.created_by {
-                    output.push_str(&format!("     {}\n", created_by));
+        if self.artifact.is_none() {
+            // History
+            if !self.history.is_empty() {
+                output.push_str(&format!("\nHistory ({} entries):\n", self.history.len()));
+                for (i, entry) in self.history.iter().enumerate() {
+                    output.push_str(&format!("  {}. ", i + 1));
+                    if let Some(created) = &entry.created {
+                        output.push_str(&format!("Created: {}", created));
+                    }
+                    if entry.empty_layer {
+                        output.push_str(" (empty layer)");
+                    }
+                    output.push('\n');
+                    if let Some(created_by) = &entry.created_by {
+                        output.push_str(&format!("     {}\n", created_by));
+                    }
                 }
             }
+
+            // RootFS
+            output.push_str("\nRootFS:\n");
+            output.push_str("  Type: layers\n");
+            output.push_str("  DiffIDs:\n");
+            for diff_id in &self.rootfs_diff_ids {
+                output.push_str(&format!("    - {}\n", diff_id));
+            }
+        }
+
+        // Vulnerabilities
+        if let Some(vulnerabilities) = &self.vulnerabilities {
+            output.push_str("\nVulnerabilities:\n");
+            output.push_str(&format!("  Total: {}\n", vulnerabilities.format_line()));
         }
 
-        // RootFS
-        output.push_str("\nRootFS:\n");
-        output.push_str("  Type: layers\n");
-        output.push_str("  DiffIDs:\n");
-        for diff_id in &self.rootfs_diff_ids {
-            output.push_str(&format!("    - {}\n", diff_id));
+        // Referrers
+        if !self.referrers.is_empty() {
+            output.push_str(&format!("\nReferrers ({}):\n", self.referrers.len()));
+            for referrer in &self.referrers {
+                output.push_str(&format!(
+                    "  - {}\n",
+                    referrer.artifact_type.as_deref().unwrap_or("unknown type")
+                ));
+                output.push_str(&format!("    Digest: {}\n", referrer.digest));
+                if let Some(created) = &referrer.created {
+                    output.push_str(&format!("    Created: {}\n", created));
+                }
+            }
         }
 
         output
@@ -373,16 +503,7 @@ pub(crate) fn list_images(
     let cache_dir = get_registry_cache_dir(registry_url)?;
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(registry_url);
 
     // Use RepositoryMetadataFetcher for parallel metadata fetching
     let concurrency = ctx.config.concurrency;
@@ -399,6 +520,7 @@ pub(crate) fn list_images(
     let mut builder = librex::Rex::builder()
         .registry_url(registry_url)
         .with_cache(cache_dir.clone())
+        .with_client_config(config::load_client_config(registry_url))
         .with_dockerhub_compat(dockerhub_compat);
 
     if let Some(ref creds) = credentials {
@@ -492,23 +614,15 @@ pub(crate) fn list_tags(
     let cache_dir = get_registry_cache_dir(registry_url)?;
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(registry_url);
 
     // Handle filter/search if specified
     if let Some(pattern) = filter {
         // Use fuzzy search for filtering
         let mut builder = librex::Rex::builder()
             .registry_url(registry_url)
-            .with_cache(&cache_dir);
+            .with_cache(&cache_dir)
+            .with_client_config(config::load_client_config(registry_url));
 
         if let Some(ref creds) = credentials {
             builder = builder.with_credentials(creds.clone());
@@ -570,21 +684,13 @@ pub(crate) fn get_image_details(
     let cache_dir = get_registry_cache_dir(registry_url)?;
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(registry_url);
 
     // Build Rex instance with cache and credentials
     let mut builder = librex::Rex::builder()
         .registry_url(registry_url)
-        .with_cache(cache_dir.clone());
+        .with_cache(cache_dir.clone())
+        .with_client_config(config::load_client_config(registry_url));
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -603,15 +709,24 @@ pub(crate) fn get_image_details(
         .get_manifest(reference_str)
         .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
 
+    // Artifact details, for manifests that are not container images
+    let mut artifact_info = None;
+
     // Extract details based on manifest type
     let (manifest_type, size, platforms, layers, created) = match manifest_or_index {
         librex::oci::ManifestOrIndex::Manifest(manifest) => {
             // Single-platform image
             let total_size: u64 = manifest.layers().iter().map(|layer| layer.size()).sum();
             let layer_count = manifest.layers().len();
-
-            // Get platform and created date from config blob
-            let (platform, created_timestamp) = {
+            let artifact = librex::oci::Artifact::from_manifest(&manifest);
+
+            // Get platform and created date from config blob; artifacts have
+            // no image configuration and carry the created date as annotation
+            let (platform, created_timestamp) = if !artifact.is_image() {
+                let created = artifact.annotations.get(ANNOTATION_CREATED).cloned();
+                artifact_info = Some(ArtifactInfo::from(&artifact));
+                (vec![], created)
+            } else {
                 // Parse config digest
                 let config_digest_str = manifest.config().digest().to_string();
                 let config_digest = librex::digest::Digest::from_str(&config_digest_str)
@@ -633,8 +748,13 @@ pub(crate) fn get_image_details(
                 (platform, created)
             };
 
+            let manifest_type = match &artifact_info {
+                Some(info) => format!("OCI Artifact ({})", info.kind),
+                None => "OCI Image Manifest".to_string(),
+            };
+
             (
-                "OCI Image Manifest".to_string(),
+                manifest_type,
                 total_size,
                 platform,
                 layer_count,
@@ -670,7 +790,7 @@ pub(crate) fn get_image_details(
     };
 
     // Use the manifest digest returned from the registry
-    Ok(ImageDetails::new(
+    let details = ImageDetails::new(
         reference_str.to_string(),
         manifest_digest,
         manifest_type,
@@ -678,7 +798,85 @@ pub(crate) fn get_image_details(
         platforms,
         layers,
         created,
-    ))
+    );
+
+    Ok(match artifact_info {
+        Some(info) => details.with_artifact(info),
+        None => details,
+    })
+}
+
+/// Resolve a manifest or index to a single-platform manifest.
+///
+/// # Arguments
+///
+/// * `rex` - Connected registry client
+/// * `reference` - The image reference the manifest was fetched for
+/// * `fetched` - The fetched manifest or index, with its digest
+/// * `platform` - Platform to pick from an index (e.g., "linux/amd64")
+///
+/// # Returns
+///
+/// Returns the manifest itself, or the manifest of the requested platform,
+/// along with the digest of the returned manifest
+fn select_platform_manifest(
+    rex: &mut librex::Rex,
+    reference: &librex::reference::Reference,
+    fetched: (librex::oci::ManifestOrIndex, String),
+    platform: Option<&str>,
+) -> Result<(librex::oci::ImageManifest, String), String> {
+    let (manifest_or_index, digest) = fetched;
+    match manifest_or_index {
+        librex::oci::ManifestOrIndex::Manifest(m) => Ok((m, digest)),
+        librex::oci::ManifestOrIndex::Index(index) => {
+            // Multi-platform image - need platform specification
+            if let Some(platform_str) = platform {
+                // A platform without a variant matches any variant
+                let requested = parse_platform(platform_str)?;
+                let descriptor = librex::copy::select_platform(index.manifests(), &requested)
+                    .map_err(|_| {
+                        format!(
+                            "Platform '{}' not found in image. Available platforms: {}",
+                            platform_str,
+                            available_platforms(&index)
+                        )
+                    })?;
+
+                // Fetch the platform-specific manifest using its digest
+                let platform_digest = descriptor.digest().to_string();
+                let platform_ref = format!("{}@{}", reference.repository(), platform_digest);
+
+                // Fetch the platform-specific manifest
+                let (platform_manifest_or_index, _) = rex
+                    .get_manifest(&platform_ref)
+                    .map_err(|e| format!("Failed to fetch platform-specific manifest: {}", e))?;
+
+                // Extract the manifest (should be a single-platform manifest now)
+                match platform_manifest_or_index {
+                    librex::oci::ManifestOrIndex::Manifest(m) => Ok((m, platform_digest)),
+                    librex::oci::ManifestOrIndex::Index(_) => {
+                        Err("Unexpected: platform-specific reference returned an index".to_string())
+                    }
+                }
+            } else {
+                // No platform specified - list available platforms and error
+                Err(format!(
+                    "Multi-platform image detected. Please specify a platform using --platform flag.\nAvailable platforms: {}",
+                    available_platforms(&index)
+                ))
+            }
+        }
+    }
+}
+
+/// List the platforms of an index as `os/arch[/variant]`
+fn available_platforms(index: &librex::oci::ImageIndex) -> String {
+    index
+        .manifests()
+        .iter()
+        .filter_map(|desc| desc.platform().as_ref().map(librex::copy::format_platform))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Get complete inspection details for a specific image reference
@@ -705,21 +903,13 @@ pub(crate) fn get_image_inspect(
     let cache_dir = get_registry_cache_dir(registry_url)?;
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(registry_url);
 
     // Build Rex instance with cache and credentials
     let mut builder = librex::Rex::builder()
         .registry_url(registry_url)
-        .with_cache(cache_dir.clone());
+        .with_cache(cache_dir.clone())
+        .with_client_config(config::load_client_config(registry_url));
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -734,96 +924,39 @@ pub(crate) fn get_image_inspect(
         .map_err(|e| format!("Invalid image reference: {}", e))?;
 
     // Get the manifest
-    let (manifest_or_index, manifest_digest) = rex
+    let fetched = rex
         .get_manifest(reference_str)
         .map_err(|e| format!("Failed to fetch manifest: {}", e))?;
+    let manifest_digest = fetched.1.clone();
 
-    // Handle platform filtering for multi-platform images
-    let manifest = match manifest_or_index {
-        librex::oci::ManifestOrIndex::Manifest(m) => m,
-        librex::oci::ManifestOrIndex::Index(index) => {
-            // Multi-platform image - need platform specification
-            if let Some(platform_str) = platform {
-                // Parse the platform string
-                let (os, arch, _variant) = parse_platform(platform_str)?;
-
-                // Find the matching platform descriptor
-                let descriptor = index
-                    .manifests()
-                    .iter()
-                    .find(|desc| {
-                        desc.platform().as_ref().is_some_and(|p| {
-                            p.os().to_string() == os && p.architecture().to_string() == arch
-                        })
-                    })
-                    .ok_or_else(|| {
-                        // List available platforms for better error message
-                        let available: Vec<String> = index
-                            .manifests()
-                            .iter()
-                            .filter_map(|desc| {
-                                desc.platform()
-                                    .as_ref()
-                                    .map(|p| format!("{}/{}", p.os(), p.architecture()))
-                            })
-                            .collect();
-
-                        format!(
-                            "Platform '{}' not found in image. Available platforms: {}",
-                            platform_str,
-                            available.join(", ")
-                        )
-                    })?;
+    // Referrers and scan results belong to the selected platform's manifest
+    let (manifest, image_digest) =
+        select_platform_manifest(&mut rex, &reference, fetched, platform)?;
 
-                // Fetch the platform-specific manifest using its digest
-                let platform_digest = descriptor.digest().to_string();
-                let platform_ref = format!("{}@{}", reference.repository(), platform_digest);
-
-                // Fetch the platform-specific manifest
-                let (platform_manifest_or_index, _) = rex
-                    .get_manifest(&platform_ref)
-                    .map_err(|e| format!("Failed to fetch platform-specific manifest: {}", e))?;
-
-                // Extract the manifest (should be a single-platform manifest now)
-                match platform_manifest_or_index {
-                    librex::oci::ManifestOrIndex::Manifest(m) => m,
-                    librex::oci::ManifestOrIndex::Index(_) => {
-                        return Err(
-                            "Unexpected: platform-specific reference returned an index".to_string()
-                        );
-                    }
-                }
-            } else {
-                // No platform specified - list available platforms and error
-                let available: Vec<String> = index
-                    .manifests()
-                    .iter()
-                    .filter_map(|desc| {
-                        desc.platform()
-                            .as_ref()
-                            .map(|p| format!("{}/{}", p.os(), p.architecture()))
-                    })
-                    .collect();
-
-                return Err(format!(
-                    "Multi-platform image detected. Please specify a platform using --platform flag.\nAvailable platforms: {}",
-                    available.join(", ")
-                ));
-            }
-        }
-    };
+    // Helm charts, signatures, SBOMs, ... are stored in image manifests but
+    // have no image configuration
+    let artifact = librex::oci::Artifact::from_manifest(&manifest);
 
     // Get config blob
     let config_digest_str = manifest.config().digest().to_string();
     let config_digest = librex::digest::Digest::from_str(&config_digest_str)
         .map_err(|e| format!("Invalid config digest: {}", e))?;
 
-    let config_bytes = rex
-        .get_blob_for_reference(reference_str, &config_digest)
-        .map_err(|e| format!("Failed to fetch config blob: {}", e))?;
+    let config_bytes = if artifact.is_image() || raw_config {
+        Some(
+            rex.get_blob_for_reference(reference_str, &config_digest)
+                .map_err(|e| format!("Failed to fetch config blob: {}", e))?,
+        )
+    } else {
+        None
+    };
 
-    let config: librex::oci::ImageConfiguration = serde_json::from_slice(&config_bytes)
-        .map_err(|e| format!("Failed to parse config: {}", e))?;
+    let config: Option<librex::oci::ImageConfiguration> = match &config_bytes {
+        Some(bytes) if artifact.is_image() => Some(
+            serde_json::from_slice(bytes).map_err(|e| format!("Failed to parse config: {}", e))?,
+        ),
+        _ => None,
+    };
 
     // Extract layer information
     let layers: Vec<LayerInfo> = manifest
@@ -841,8 +974,8 @@ pub(crate) fn get_image_inspect(
 
     // Extract history
     let history: Vec<HistoryEntry> = config
-        .history()
         .as_ref()
+        .and_then(|c| c.history().as_ref())
         .map(|h| {
             h.iter()
                 .map(|entry| HistoryEntry {
@@ -854,73 +987,82 @@ pub(crate) fn get_image_inspect(
         })
         .unwrap_or_default();
 
+    let container_config = config.as_ref().and_then(|c| c.config().as_ref());
+
     // Extract environment variables
-    let env = config
-        .config()
-        .as_ref()
+    let env = container_config
         .and_then(|c| c.env().as_ref())
         .map(|e| e.to_vec())
         .unwrap_or_default();
 
     // Extract entrypoint
-    let entrypoint = config
-        .config()
-        .as_ref()
+    let entrypoint = container_config
         .and_then(|c| c.entrypoint().as_ref())
         .map(|e| e.to_vec());
 
     // Extract cmd
-    let cmd = config
-        .config()
-        .as_ref()
+    let cmd = container_config
         .and_then(|c| c.cmd().as_ref())
         .map(|c| c.to_vec());
 
     // Extract working directory
-    let working_dir = config
-        .config()
-        .as_ref()
+    let working_dir = container_config
         .and_then(|c| c.working_dir().as_ref())
         .map(|s| s.to_string());
 
     // Extract user
-    let user = config
-        .config()
-        .as_ref()
+    let user = container_config
         .and_then(|c| c.user().as_ref())
         .map(|s| s.to_string());
 
     // Extract labels
-    let labels = config
-        .config()
-        .as_ref()
+    let labels = container_config
         .and_then(|c| c.labels().as_ref())
         .cloned()
         .unwrap_or_default();
 
     // Extract exposed ports
-    let exposed_ports = config
-        .config()
-        .as_ref()
+    let exposed_ports = container_config
         .and_then(|c| c.exposed_ports().as_ref())
         .map(|ports| ports.to_vec())
         .unwrap_or_default();
 
     // Extract volumes
-    let volumes = config
-        .config()
-        .as_ref()
+    let volumes = container_config
         .and_then(|c| c.volumes().as_ref())
         .map(|vols| vols.to_vec())
         .unwrap_or_default();
 
     // Extract RootFS diff IDs
     let rootfs_diff_ids = config
-        .rootfs()
-        .diff_ids()
-        .iter()
-        .map(|d| d.to_string())
-        .collect();
+        .as_ref()
+        .map(|c| {
+            c.rootfs()
+                .diff_ids()
+                .iter()
+                .map(|d| d.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Attached artifacts are informational; registries without referrer
+    // support or access to them must not break inspection
+    let referrers = rex
+        .list_referrers(
+            &format!("{}@{}", reference.repository(), image_digest),
+            None,
+        )
+        .map(|descriptors| descriptors.iter().map(ReferrerInfo::from).collect())
+        .unwrap_or_default();
+
+    // Likewise for scan results: only registries with a CVE scanner have them
+    let vulnerabilities = if artifact.is_image() {
+        rex.list_cves(&format!("{}@{}", reference.repository(), image_digest))
+            .ok()
+            .map(|report| CveSummary::from(&report))
+    } else {
+        None
+    };
 
     // Optionally serialize raw manifest JSON
     let raw_manifest_json = if raw_manifest {
@@ -932,14 +1074,32 @@ pub(crate) fn get_image_inspect(
         None
     };
 
-    // Optionally serialize raw config JSON
-    let raw_config_json = if raw_config {
-        Some(
-            serde_json::to_string_pretty(&config)
+    // Optionally serialize raw config JSON (artifact configs are shown as stored)
+    let raw_config_json = match (&config, &config_bytes) {
+        (Some(config), _) if raw_config => Some(
+            serde_json::to_string_pretty(config)
                 .map_err(|e| format!("Failed to serialize config: {}", e))?,
-        )
-    } else {
-        None
+        ),
+        (None, Some(bytes)) if raw_config => Some(
+            serde_json::from_slice::<serde_json::Value>(bytes)
+                .ok()
+                .and_then(|value| serde_json::to_string_pretty(&value).ok())
+                .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned()),
+        ),
+        _ => None,
+    };
+
+    let (manifest_type, created, artifact) = match &config {
+        Some(config) => (
+            "OCI Image Manifest".to_string(),
+            config.created().as_ref().map(|c| c.to_string()),
+            None,
+        ),
+        None => (
+            format!("OCI Artifact ({})", artifact.kind),
+            artifact.annotations.get(ANNOTATION_CREATED).cloned(),
+            Some(ArtifactInfo::from(&artifact)),
+        ),
     };
 
     // Use the manifest digest returned from the registry
@@ -947,12 +1107,18 @@ pub(crate) fn get_image_inspect(
         reference: reference_str.to_string(),
         registry: registry_url.to_string(),
         manifest_digest,
-        manifest_type: "OCI Image Manifest".to_string(),
+        manifest_type,
         config_digest: config_digest_str,
         size: total_size,
-        architecture: config.architecture().to_string(),
-        os: config.os().to_string(),
-        created: config.created().as_ref().map(|c| c.to_string()),
+        architecture: config
+            .as_ref()
+            .map(|c| c.architecture().to_string())
+            .unwrap_or_default(),
+        os: config
+            .as_ref()
+            .map(|c| c.os().to_string())
+            .unwrap_or_default(),
+        created,
         env,
         entrypoint,
         cmd,
@@ -964,11 +1130,33 @@ pub(crate) fn get_image_inspect(
         layers,
         history,
         rootfs_diff_ids,
+        artifact,
+        vulnerabilities,
+        referrers,
         raw_manifest: raw_manifest_json,
         raw_config: raw_config_json,
     })
 }
 
+/// Connect to a registry with its cache, client settings and stored credentials
+fn connect_rex(registry_url: &str) -> Result<librex::Rex, String> {
+    let cache_dir = get_registry_cache_dir(registry_url)?;
+    let credentials = config::load_credentials(registry_url);
+
+    let mut builder = librex::Rex::builder()
+        .registry_url(registry_url)
+        .with_cache(cache_dir)
+        .with_client_config(config::load_client_config(registry_url));
+
+    if let Some(ref creds) = credentials {
+        builder = builder.with_credentials(creds.clone());
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to connect to registry: {}", e))
+}
+
 /// Get the registry URL from config or use default
 pub(crate) fn get_registry_url() -> Result<String, String> {
     let config_path = config::get_config_path();
@@ -1017,6 +1205,7 @@ pub(crate) fn get_registry_entry() -> Result<config::RegistryEntry, String> {
                 name: "default".to_string(),
                 url: "http://localhost:5000".to_string(),
                 dockerhub_compat: false,
+                tls: Default::default(),
             });
         }
     };
@@ -1044,6 +1233,7 @@ pub(crate) fn get_registry_entry() -> Result<config::RegistryEntry, String> {
         name: "default".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     })
 }
 
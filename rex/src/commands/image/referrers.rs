@@ -0,0 +1,129 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image referrers command (list signatures, SBOMs and attestations)
+pub fn handle_image_referrers(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    format: OutputFormat,
+    artifact_type: Option<&str>,
+) {
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Listing referrers for image: {}", reference),
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let referrers = match list_referrers(&registry_url, reference, artifact_type) {
+        Ok(referrers) => referrers,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => {
+            if referrers.is_empty() {
+                println!("No referrers found for '{}'.", reference);
+                return;
+            }
+            println!(
+                "{:50} {:71} {:>10} CREATED",
+                "ARTIFACT TYPE", "DIGEST", "SIZE"
+            );
+            for referrer in &referrers {
+                println!("{}", referrer.format_pretty());
+            }
+        }
+        OutputFormat::Json => match serde_json::to_string_pretty(&referrers) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// Artifact attached to an image (signature, SBOM, attestation, ...)
+#[derive(Debug, Clone, Serialize)]
+pub struct ReferrerInfo {
+    /// Referrer manifest digest
+    pub digest: String,
+    /// Artifact type (e.g., "application/spdx+json")
+    pub artifact_type: Option<String>,
+    /// Referrer manifest size in bytes
+    pub size: u64,
+    /// Creation time from the `org.opencontainers.image.created` annotation
+    pub created: Option<String>,
+}
+
+impl From<&librex::oci::Descriptor> for ReferrerInfo {
+    fn from(descriptor: &librex::oci::Descriptor) -> Self {
+        Self {
+            digest: descriptor.digest().to_string(),
+            artifact_type: descriptor.artifact_type().as_ref().map(|t| t.to_string()),
+            size: descriptor.size(),
+            created: descriptor
+                .annotations()
+                .as_ref()
+                .and_then(|a| a.get(ANNOTATION_CREATED))
+                .cloned(),
+        }
+    }
+}
+
+impl Formattable for ReferrerInfo {
+    fn format_pretty(&self) -> String {
+        format!(
+            "{:50} {:71} {:>10} {}",
+            self.artifact_type.as_deref().unwrap_or("-"),
+            self.digest,
+            librex::format::format_size(self.size),
+            self.created.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// List the artifacts attached to an image.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+/// * `artifact_type` - Only list referrers of this artifact type
+///
+/// # Returns
+///
+/// Returns the referrers of the image's manifest
+pub(crate) fn list_referrers(
+    registry_url: &str,
+    reference: &str,
+    artifact_type: Option<&str>,
+) -> Result<Vec<ReferrerInfo>, String> {
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let referrers = rex
+        .list_referrers(reference, artifact_type)
+        .map_err(|e| format!("Failed to list referrers: {}", e))?;
+
+    Ok(referrers.iter().map(ReferrerInfo::from).collect())
+}
+
+#[cfg(test)]
+#[path = "referrers_tests.rs"]
+mod tests;
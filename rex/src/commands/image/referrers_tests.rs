@@ -0,0 +1,274 @@
+use super::*;
+
+// Note: These tests use mockito to test list_referrers and the referrers section
+// of get_image_inspect end-to-end with mock HTTP responses.
+
+const SUBJECT: &str = "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+const CONFIG_JSON: &str =
+    r#"{"architecture":"amd64","os":"linux","rootfs":{"type":"layers","diff_ids":[]}}"#;
+
+const CONFIG_DIGEST: &str =
+    "sha256:c5b1d63604f273462ef36fadac3182d43ae6a6138731cf594b314835cf1c034f";
+
+const MANIFEST_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+    "config": {
+        "mediaType": "application/vnd.oci.image.config.v1+json",
+        "size": 78,
+        "digest": "sha256:c5b1d63604f273462ef36fadac3182d43ae6a6138731cf594b314835cf1c034f"
+    },
+    "layers": []
+}"#;
+
+const MANIFEST_DIGEST: &str =
+    "sha256:af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+const REFERRERS_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.index.v1+json",
+    "manifests": [
+        {
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "digest": "sha256:1111111111111111111111111111111111111111111111111111111111111111",
+            "size": 788,
+            "artifactType": "application/spdx+json",
+            "annotations": {
+                "org.opencontainers.image.created": "2024-05-01T10:00:00Z"
+            }
+        },
+        {
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "digest": "sha256:2222222222222222222222222222222222222222222222222222222222222222",
+            "size": 512,
+            "artifactType": "application/vnd.dev.sigstore.bundle.v0.3+json"
+        }
+    ]
+}"#;
+
+#[test]
+fn test_list_referrers() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _head_mock = server
+        .mock("HEAD", "/v2/referrers-app/manifests/v1")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", SUBJECT)
+        .create();
+    let referrers_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-app/referrers/{}", SUBJECT).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(REFERRERS_JSON)
+        .create();
+
+    let referrers = list_referrers(&registry_url, "referrers-app:v1", None).unwrap();
+
+    referrers_mock.assert();
+    assert_eq!(referrers.len(), 2);
+    assert_eq!(
+        referrers[0].artifact_type.as_deref(),
+        Some("application/spdx+json")
+    );
+    assert_eq!(referrers[0].size, 788);
+    assert_eq!(
+        referrers[0].created.as_deref(),
+        Some("2024-05-01T10:00:00Z")
+    );
+    assert_eq!(referrers[1].created, None);
+}
+
+#[test]
+fn test_list_referrers_with_artifact_type() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _referrers_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-app/referrers/{}", SUBJECT).as_str(),
+        )
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(REFERRERS_JSON)
+        .create();
+
+    let referrers = list_referrers(
+        &registry_url,
+        &format!("referrers-app@{}", SUBJECT),
+        Some("application/vnd.dev.sigstore.bundle.v0.3+json"),
+    )
+    .unwrap();
+
+    assert_eq!(referrers.len(), 1);
+    assert_eq!(
+        referrers[0].digest,
+        "sha256:2222222222222222222222222222222222222222222222222222222222222222"
+    );
+}
+
+#[test]
+fn test_list_referrers_image_not_found() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _head_mock = server
+        .mock("HEAD", "/v2/referrers-app/manifests/missing")
+        .with_status(404)
+        .create();
+
+    let result = list_referrers(&registry_url, "referrers-app:missing", None);
+
+    assert!(result.unwrap_err().contains("Failed to list referrers"));
+}
+
+#[test]
+fn test_list_referrers_invalid_reference() {
+    let result = list_referrers("http://localhost:5000", "", None);
+
+    assert!(result.unwrap_err().contains("Invalid image reference"));
+}
+
+#[test]
+fn test_get_image_inspect_includes_referrers() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/referrers-inspect/manifests/v1")
+        .with_status(200)
+        .with_header("content-type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("docker-content-digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-inspect/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(CONFIG_JSON)
+        .create();
+    let _referrers_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-inspect/referrers/{}", MANIFEST_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(REFERRERS_JSON)
+        .create();
+
+    let inspect =
+        get_image_inspect(&registry_url, "referrers-inspect:v1", None, false, false).unwrap();
+
+    assert_eq!(inspect.referrers.len(), 2);
+    let pretty = inspect.format_pretty();
+    assert!(pretty.contains("Referrers (2):"));
+    assert!(pretty.contains("application/spdx+json"));
+}
+
+#[test]
+fn test_get_image_inspect_platform_referrers() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    // Referrers are attached to the platform's manifest, not to the index
+    let index_json = format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[
+            {{"mediaType":"application/vnd.oci.image.manifest.v1+json","size":{},"digest":"{}",
+              "platform":{{"os":"linux","architecture":"amd64"}}}}]}}"#,
+        MANIFEST_JSON.len(),
+        MANIFEST_DIGEST
+    );
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _index_mock = server
+        .mock("GET", "/v2/referrers-platform/manifests/v1")
+        .with_status(200)
+        .with_header("content-type", "application/vnd.oci.image.index.v1+json")
+        .with_header("docker-content-digest", SUBJECT)
+        .with_body(&index_json)
+        .create();
+    let _manifest_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-platform/manifests/{}", MANIFEST_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("docker-content-digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-platform/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(CONFIG_JSON)
+        .create();
+    let referrers_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-platform/referrers/{}", MANIFEST_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(REFERRERS_JSON)
+        .create();
+
+    let inspect = get_image_inspect(
+        &registry_url,
+        "referrers-platform:v1",
+        Some("linux/amd64"),
+        false,
+        false,
+    )
+    .unwrap();
+
+    referrers_mock.assert();
+    assert_eq!(inspect.manifest_digest, SUBJECT);
+    assert_eq!(inspect.referrers.len(), 2);
+}
+
+#[test]
+fn test_get_image_inspect_ignores_referrers_failure() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _manifest_mock = server
+        .mock("GET", "/v2/referrers-denied/manifests/v1")
+        .with_status(200)
+        .with_header("docker-content-digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let _config_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-denied/blobs/{}", CONFIG_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_body(CONFIG_JSON)
+        .create();
+    let _referrers_mock = server
+        .mock(
+            "GET",
+            format!("/v2/referrers-denied/referrers/{}", MANIFEST_DIGEST).as_str(),
+        )
+        .with_status(403)
+        .create();
+
+    let inspect =
+        get_image_inspect(&registry_url, "referrers-denied:v1", None, false, false).unwrap();
+
+    assert!(inspect.referrers.is_empty());
+    assert!(!inspect.format_pretty().contains("Referrers"));
+}
@@ -41,20 +41,12 @@ pub fn handle_image_remove(
         }
     };
 
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(&registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(&registry_url);
 
     let mut builder = librex::Rex::builder()
         .registry_url(&registry_url)
-        .with_cache(&cache_dir);
+        .with_cache(&cache_dir)
+        .with_client_config(config::load_client_config(&registry_url));
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -437,9 +429,11 @@ fn fetch_tag_metadata_for_deletion(
         );
 
         // Build Rex client for multi-platform timestamp resolution
+        let client_config = config::load_client_config(registry_url);
         let mut builder = librex::Rex::builder()
             .registry_url(registry_url)
-            .with_cache(cache_dir);
+            .with_cache(cache_dir)
+            .with_client_config(client_config.clone());
 
         if let Some(ref creds) = credentials {
             builder = builder.with_credentials(creds.clone());
@@ -461,6 +455,7 @@ fn fetch_tag_metadata_for_deletion(
                 registry_url,
                 cache_dir,
                 credentials.as_ref(),
+                &client_config,
             ) {
                 tag_info.created_timestamp = Some(timestamp);
                 tag_info.created = librex::format::format_timestamp(&timestamp);
@@ -482,6 +477,7 @@ fn resolve_multiplatform_timestamp(
     registry_url: &str,
     cache_dir: &std::path::Path,
     credentials: Option<&librex::auth::Credentials>,
+    client_config: &librex::client::ClientConfig,
 ) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
     // Fetch the manifest to check if it's multi-platform
     let (manifest_or_index, _digest) = rex
@@ -527,6 +523,7 @@ fn resolve_multiplatform_timestamp(
                     desc,
                     Some(&cache_dir),
                     credentials.clone(),
+                    client_config,
                 )
             })
             .collect()
@@ -543,11 +540,14 @@ fn fetch_platform_timestamp(
     platform_desc: &librex::oci::Descriptor,
     cache_dir: Option<&std::path::Path>,
     credentials: Option<librex::auth::Credentials>,
+    client_config: &librex::client::ClientConfig,
 ) -> Option<chrono::DateTime<chrono::Utc>> {
     use std::str::FromStr;
 
     // Build per-thread Rex client
-    let mut builder = librex::Rex::builder().registry_url(registry_url);
+    let mut builder = librex::Rex::builder()
+        .registry_url(registry_url)
+        .with_client_config(client_config.clone());
 
     if let Some(dir) = cache_dir {
         builder = builder.with_cache(dir);
@@ -0,0 +1,126 @@
+use super::copy::resolve_image_registry;
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format;
+use librex::oci_layout::SaveEvent;
+use std::path::Path;
+
+/// Handle the image save command (write an image to an OCI layout or docker archive)
+pub fn handle_image_save(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    output: &Path,
+    archive_format: &str,
+    platform: Option<&str>,
+) {
+    let resolved = get_registry_url().and_then(|default_url| {
+        let registries = config::Config::load(&config::get_config_path())
+            .map(|cfg| cfg.registries.list)
+            .unwrap_or_default();
+        resolve_image_registry(reference, &default_url, &registries)
+    });
+    let (registry_url, image_ref) = match resolved {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!(
+            "Saving {} from {} to {}...",
+            image_ref,
+            registry_url,
+            output.display()
+        ),
+    );
+
+    let on_event = |event: &SaveEvent| {
+        let message = match event {
+            SaveEvent::BlobExists(digest) => format!("Blob {} already saved", digest),
+            SaveEvent::BlobWritten { digest, size } => format!(
+                "Saved blob {} ({})",
+                digest,
+                librex::format::format_size(*size)
+            ),
+            SaveEvent::ManifestWritten(digest) => format!("Saved manifest {}", digest),
+        };
+        format::print(ctx, VerbosityLevel::Verbose, &message);
+    };
+
+    match save_image(
+        &registry_url,
+        &image_ref,
+        output,
+        archive_format,
+        platform,
+        on_event,
+    ) {
+        Ok(report) => {
+            format::success(
+                ctx,
+                &format!(
+                    "Saved {} ({}) to {}: {} blobs written ({}), {} already present",
+                    reference,
+                    report.digest,
+                    output.display(),
+                    report.blobs_written,
+                    librex::format::format_size(report.bytes_written),
+                    report.blobs_skipped
+                ),
+            );
+        }
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Save an image to an OCI image layout or a `docker load` archive.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference, without registry host
+/// * `output` - Layout directory, or tarball path (`*.tar`)
+/// * `archive_format` - "oci" or "docker-archive"
+/// * `platform` - Platform to save (e.g., "linux/amd64"); every platform when omitted
+/// * `on_event` - Called as each blob and manifest is written
+///
+/// # Returns
+///
+/// Returns the save report
+pub(crate) fn save_image<F>(
+    registry_url: &str,
+    reference: &str,
+    output: &std::path::Path,
+    archive_format: &str,
+    platform: Option<&str>,
+    on_event: F,
+) -> Result<librex::oci_layout::SaveReport, String>
+where
+    F: FnMut(&librex::oci_layout::SaveEvent),
+{
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+    let archive_format =
+        librex::oci_layout::ArchiveFormat::from_str(archive_format).map_err(|e| e.to_string())?;
+
+    let mut options = librex::oci_layout::SaveOptions::new(archive_format);
+    if let Some(platform_str) = platform {
+        let (os, arch, variant) = parse_platform(platform_str)?;
+        options = options.with_platform(&os, &arch, variant.as_deref());
+    }
+
+    let mut rex = connect_rex(registry_url)?;
+    rex.save_image(reference, &options, output, on_event)
+        .map_err(|e| format!("Failed to save image: {}", e))
+}
+
+#[cfg(test)]
+#[path = "save_tests.rs"]
+mod tests;
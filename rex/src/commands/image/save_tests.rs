@@ -0,0 +1,128 @@
+use super::*;
+use crate::test_support::{config, layer, mock_image, sha256};
+use librex::oci_layout::SaveEvent;
+use std::io::Read;
+
+// Note: These tests save images served by mockito (see `test_support`) and
+// read back the layouts and tarballs written.
+
+/// Read every file of a tarball into (path, content) pairs
+fn tar_entries(path: &std::path::Path) -> Vec<(String, Vec<u8>)> {
+    let mut archive = tar::Archive::new(std::fs::File::open(path).unwrap());
+    archive
+        .entries()
+        .unwrap()
+        .map(|entry| {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).unwrap();
+            (path, content)
+        })
+        .collect()
+}
+
+#[test]
+fn test_save_image_to_layout_directory() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    let manifest = mock_image(
+        &mut server,
+        "save-dir",
+        "1.5",
+        &config("amd64", "app", "1.5"),
+        &[layer(&[("app/server", "binary")])],
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("layout");
+    let mut events = Vec::new();
+
+    let report = save_image(
+        &server.url(),
+        "save-dir:1.5",
+        &output,
+        "oci",
+        None,
+        |event| events.push(event.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(report.digest, sha256(manifest.as_bytes()));
+    assert_eq!(report.blobs_written, 2);
+    assert!(matches!(events.last(), Some(SaveEvent::ManifestWritten(_))));
+    assert!(output.join("oci-layout").is_file());
+    let digest = sha256(manifest.as_bytes());
+    assert!(output.join("blobs/sha256").join(&digest[7..]).is_file());
+
+    let index: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(output.join("index.json")).unwrap()).unwrap();
+    let annotations = &index["manifests"][0]["annotations"];
+    assert_eq!(annotations["org.opencontainers.image.ref.name"], "1.5");
+    assert_eq!(annotations["io.containerd.image.name"], "save-dir:1.5");
+}
+
+#[test]
+fn test_save_image_docker_archive() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    let app = layer(&[("app/server", "binary")]);
+    let config = config("amd64", "app", "1.5");
+    mock_image(
+        &mut server,
+        "save-docker",
+        "1.5",
+        &config,
+        std::slice::from_ref(&app),
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("app.tar");
+
+    save_image(
+        &server.url(),
+        "save-docker:1.5",
+        &output,
+        "docker-archive",
+        None,
+        |_| {},
+    )
+    .unwrap();
+
+    let entries = tar_entries(&output);
+    let (_, manifest) = entries
+        .iter()
+        .find(|(path, _)| path == "manifest.json")
+        .unwrap();
+    let manifest: serde_json::Value = serde_json::from_slice(manifest).unwrap();
+    assert_eq!(manifest[0]["RepoTags"][0], "save-docker:1.5");
+    assert_eq!(
+        manifest[0]["Config"],
+        format!("blobs/sha256/{}", &sha256(config.as_bytes())[7..])
+    );
+    assert!(
+        entries
+            .iter()
+            .any(|(path, content)| path.ends_with(&sha256(&app)[7..]) && *content == app)
+    );
+}
+
+#[test]
+fn test_save_image_errors() {
+    let server = mockito::Server::new();
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("app.tar");
+
+    let err = save_image(&server.url(), "app:1.5", &output, "zip", None, |_| {}).unwrap_err();
+    assert!(err.contains("Unknown archive format: zip"));
+
+    let err = save_image(
+        &server.url(),
+        "app:1.5",
+        &output,
+        "oci",
+        Some("linux"),
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(err.contains("Invalid platform format"));
+    assert!(!output.exists());
+}
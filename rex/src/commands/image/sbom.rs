@@ -0,0 +1,359 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image sbom command
+///
+/// With `all_tags`, every tag of the reference's repository is searched for
+/// `package`. With `raw`, the SBOM documents are printed as stored.
+pub fn handle_image_sbom(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    package: Option<&str>,
+    all_tags: bool,
+    raw: bool,
+    format: OutputFormat,
+) {
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    if all_tags {
+        let repository = match librex::reference::Reference::from_str(reference) {
+            Ok(parsed) => parsed.repository().to_string(),
+            Err(e) => {
+                format::error(ctx, &format!("Invalid image reference: {}", e));
+                std::process::exit(1);
+            }
+        };
+        let package = package.unwrap_or_default();
+        format::print(
+            ctx,
+            VerbosityLevel::Verbose,
+            &format!("Searching SBOMs of {} for {}", repository, package),
+        );
+
+        match search_sbom_packages(&registry_url, &repository, package) {
+            Ok(search) => print_formatted(&search, format),
+            Err(e) => {
+                format::error(ctx, &e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Finding SBOMs of {}", reference),
+    );
+
+    let sbom = match image_sbom(&registry_url, reference, package) {
+        Ok(sbom) => sbom,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    if raw {
+        // One document as is, several as an array
+        let documents: Vec<&serde_json::Value> = sbom.sboms.iter().map(|s| &s.document).collect();
+        let json = match documents.as_slice() {
+            [] => {
+                format::error(ctx, &format!("No SBOMs found for {}", reference));
+                std::process::exit(1);
+            }
+            [document] => serde_json::to_string_pretty(document),
+            _ => serde_json::to_string_pretty(&documents),
+        };
+        match json {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    print_formatted(&sbom, format);
+}
+
+fn print_formatted<T: Formattable + Serialize>(value: &T, format: OutputFormat) {
+    match format {
+        OutputFormat::Pretty => print!("{}", value.format_pretty()),
+        OutputFormat::Json => match serde_json::to_string_pretty(value) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// An SBOM attached to an image
+#[derive(Debug, Serialize)]
+pub struct SbomInfo {
+    /// Document format
+    pub format: librex::sbom::SbomFormat,
+    /// Where the SBOM was found
+    pub source: librex::sbom::SbomSource,
+    /// Digest of the manifest holding the SBOM
+    pub manifest_digest: String,
+    /// Predicate type, for in-toto attestations
+    pub predicate_type: Option<String>,
+    /// Number of packages listed
+    pub package_count: usize,
+    /// The document as stored (printed by `--raw`)
+    #[serde(skip)]
+    pub document: serde_json::Value,
+}
+
+/// Packages of an image, read from its SBOMs
+#[derive(Debug, Serialize)]
+pub struct ImageSbom {
+    /// Image reference
+    pub reference: String,
+    /// Manifest digest
+    pub digest: String,
+    /// SBOMs found
+    pub sboms: Vec<SbomInfo>,
+    /// Packages, deduplicated across SBOMs
+    pub packages: Vec<librex::sbom::Package>,
+}
+
+/// Format package rows, optionally prefixed with an image column
+fn format_package_rows(image: Option<&str>, packages: &[librex::sbom::Package]) -> String {
+    let mut output = String::new();
+    for package in packages {
+        if let Some(image) = image {
+            output.push_str(&format!("{:30} ", image));
+        }
+        output.push_str(&format!(
+            "{:30} {:16} {:20} {}\n",
+            package.name,
+            package.version.as_deref().unwrap_or("-"),
+            package.license.as_deref().unwrap_or("-"),
+            package.purl.as_deref().unwrap_or("-")
+        ));
+    }
+    output
+}
+
+impl Formattable for ImageSbom {
+    fn format_pretty(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("Image: {}\n", self.reference));
+        output.push_str(&format!("Digest: {}\n", self.digest));
+
+        if self.sboms.is_empty() {
+            output.push_str("\nNo SBOMs found.\n");
+            return output;
+        }
+
+        output.push_str(&format!("\nSBOMs ({}):\n", self.sboms.len()));
+        for sbom in &self.sboms {
+            output.push_str(&format!(
+                "  {} {} ({}, {} packages)\n",
+                sbom.format, sbom.manifest_digest, sbom.source, sbom.package_count
+            ));
+            if let Some(predicate_type) = &sbom.predicate_type {
+                output.push_str(&format!("    Predicate: {}\n", predicate_type));
+            }
+        }
+
+        output.push_str(&format!("\nPackages ({}):\n", self.packages.len()));
+        if !self.packages.is_empty() {
+            output.push_str(&format!(
+                "{:30} {:16} {:20} PURL\n",
+                "NAME", "VERSION", "LICENSE"
+            ));
+            output.push_str(&format_package_rows(None, &self.packages));
+        }
+        output
+    }
+}
+
+/// Images of a repository containing a package
+#[derive(Debug, Serialize)]
+pub struct SbomSearch {
+    /// Repository searched
+    pub repository: String,
+    /// Package filter as given (NAME or NAME@VERSION)
+    pub package: String,
+    /// Number of tags searched
+    pub scanned: usize,
+    /// Images with matching packages (only the matching packages are listed)
+    pub matches: Vec<ImageSbom>,
+    /// Tags that could not be read, with the reason
+    pub failed: Vec<String>,
+}
+
+impl Formattable for SbomSearch {
+    fn format_pretty(&self) -> String {
+        let mut output = String::new();
+
+        if !self.matches.is_empty() {
+            output.push_str(&format!(
+                "{:30} {:30} {:16} {:20} PURL\n",
+                "IMAGE", "NAME", "VERSION", "LICENSE"
+            ));
+            for image in &self.matches {
+                output.push_str(&format_package_rows(
+                    Some(&image.reference),
+                    &image.packages,
+                ));
+            }
+            output.push('\n');
+        }
+
+        output.push_str(&format!(
+            "{} of {} images in {} contain {}\n",
+            self.matches.len(),
+            self.scanned,
+            self.repository,
+            self.package
+        ));
+        for failure in &self.failed {
+            output.push_str(&format!("Skipped {}\n", failure));
+        }
+        output
+    }
+}
+
+/// Split a package filter into name and optional version.
+///
+/// "log4j-core@2.14" becomes ("log4j-core", Some("2.14")); a leading "@"
+/// (npm scopes, e.g. "@babel/core") is part of the name.
+pub(crate) fn parse_package_filter(filter: &str) -> (&str, Option<&str>) {
+    match filter.rsplit_once('@') {
+        Some((name, version)) if !name.is_empty() && !version.is_empty() => (name, Some(version)),
+        _ => (filter, None),
+    }
+}
+
+/// Read the SBOMs attached to an image.
+fn read_image_sbom(rex: &mut librex::Rex, reference: &str) -> Result<ImageSbom, String> {
+    let (digest, sboms) = rex
+        .find_sboms(reference)
+        .map_err(|e| format!("Failed to find SBOMs: {}", e))?;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut packages = Vec::new();
+    let mut infos = Vec::new();
+    for sbom in sboms {
+        let listed = sbom.document.packages();
+        infos.push(SbomInfo {
+            format: sbom.document.format,
+            source: sbom.source,
+            manifest_digest: sbom.manifest_digest,
+            predicate_type: sbom.document.predicate_type,
+            package_count: listed.len(),
+            document: sbom.document.document,
+        });
+        packages.extend(listed.into_iter().filter(|p| seen.insert(p.clone())));
+    }
+
+    Ok(ImageSbom {
+        reference: reference.to_string(),
+        digest,
+        sboms: infos,
+        packages,
+    })
+}
+
+/// List the packages of an image from its SBOMs.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+/// * `package` - Only keep packages matching NAME or NAME@VERSION
+///
+/// # Returns
+///
+/// Returns the SBOMs found and their packages
+pub(crate) fn image_sbom(
+    registry_url: &str,
+    reference: &str,
+    package: Option<&str>,
+) -> Result<ImageSbom, String> {
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+    let mut sbom = read_image_sbom(&mut rex, reference)?;
+
+    if let Some(filter) = package {
+        let (name, version) = parse_package_filter(filter);
+        sbom.packages.retain(|p| p.matches(name, version));
+    }
+    Ok(sbom)
+}
+
+/// Find the images of a repository whose SBOMs list a package.
+///
+/// Every image tag is read; images without SBOMs or without a match are left
+/// out. Tag-schema tags (`sha256-<hex>`, cosign's `.sig`/`.att`/`.sbom`)
+/// hold referrers, not images, and are skipped.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `repository` - Repository to search (e.g., "myapp")
+/// * `package` - Package as NAME or NAME@VERSION (e.g., "log4j-core@2.14")
+///
+/// # Returns
+///
+/// Returns the matching images, with only the matching packages
+pub(crate) fn search_sbom_packages(
+    registry_url: &str,
+    repository: &str,
+    package: &str,
+) -> Result<SbomSearch, String> {
+    let mut rex = connect_rex(registry_url)?;
+    let (name, version) = parse_package_filter(package);
+
+    let tags: Vec<String> = rex
+        .list_tags(repository)
+        .map_err(|e| format!("Failed to list tags: {}", e))?
+        .into_iter()
+        .filter(|tag| librex::client::referrers_tag_subject(tag).is_none())
+        .collect();
+
+    let mut matches = Vec::new();
+    let mut failed = Vec::new();
+    for tag in &tags {
+        let reference = format!("{}:{}", repository, tag);
+        match read_image_sbom(&mut rex, &reference) {
+            Ok(mut sbom) => {
+                sbom.packages.retain(|p| p.matches(name, version));
+                if !sbom.packages.is_empty() {
+                    matches.push(sbom);
+                }
+            }
+            Err(e) => failed.push(format!("{}: {}", reference, e)),
+        }
+    }
+
+    Ok(SbomSearch {
+        repository: repository.to_string(),
+        package: package.to_string(),
+        scanned: tags.len(),
+        matches,
+        failed,
+    })
+}
+
+#[cfg(test)]
+#[path = "sbom_tests.rs"]
+mod tests;
@@ -0,0 +1,336 @@
+use super::*;
+use crate::test_support::sha256;
+use base64::Engine;
+use librex::sbom::{SbomFormat, SbomSource};
+
+// Note: These tests serve SBOM manifests and blobs from mockito: an SPDX
+// document attached as a referrer and a CycloneDX attestation (DSSE envelope)
+// under cosign's `.att` tag.
+
+const IMAGE_DIGEST: &str =
+    "sha256:af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+const IMAGE_HEX: &str = "af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+const OTHER_DIGEST: &str =
+    "sha256:c5b1d63604f273462ef36fadac3182d43ae6a6138731cf594b314835cf1c034f";
+
+fn spdx(log4j_version: &str) -> String {
+    format!(
+        r#"{{"spdxVersion":"SPDX-2.3","name":"app","packages":[
+            {{"name":"log4j-core","versionInfo":"{0}","licenseConcluded":"Apache-2.0",
+              "externalRefs":[{{"referenceType":"purl","referenceLocator":"pkg:maven/org.apache.logging.log4j/log4j-core@{0}"}}]}},
+            {{"name":"musl","versionInfo":"1.2.4","licenseConcluded":"MIT"}}
+        ]}}"#,
+        log4j_version
+    )
+}
+
+const CYCLONEDX_JSON: &str = r#"{"bomFormat":"CycloneDX","specVersion":"1.5","components":[
+    {"name":"musl","version":"1.2.4","licenses":[{"license":{"id":"MIT"}}]},
+    {"name":"openssl","version":"3.1.4","purl":"pkg:apk/alpine/openssl@3.1.4"}
+]}"#;
+
+fn attestation(document: &str) -> String {
+    let statement = format!(
+        r#"{{"_type":"https://in-toto.io/Statement/v0.1","predicateType":"https://cyclonedx.org/bom","subject":[],"predicate":{}}}"#,
+        document
+    );
+    format!(
+        r#"{{"payloadType":"application/vnd.in-toto+json","payload":"{}","signatures":[]}}"#,
+        base64::engine::general_purpose::STANDARD.encode(statement)
+    )
+}
+
+/// A manifest with a single layer holding `blob`
+fn layer_manifest(media_type: &str, blob: &str) -> String {
+    format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json",
+            "config":{{"mediaType":"application/vnd.oci.empty.v1+json","size":2,
+                "digest":"sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"}},
+            "layers":[{{"mediaType":"{}","size":{},"digest":"{}"}}]}}"#,
+        media_type,
+        blob.len(),
+        sha256(blob.as_bytes())
+    )
+}
+
+fn sbom_server() -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    server
+}
+
+/// Mock a tag resolving to `digest`
+fn mock_tag(server: &mut mockito::ServerGuard, repository: &str, tag: &str, digest: &str) {
+    server
+        .mock(
+            "HEAD",
+            format!("/v2/{}/manifests/{}", repository, tag).as_str(),
+        )
+        .with_status(200)
+        .with_header("Docker-Content-Digest", digest)
+        .create();
+}
+
+/// Mock a manifest (by tag or digest) and its single layer blob
+fn mock_layer_manifest(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    reference: &str,
+    manifest: &str,
+    blob: &str,
+) {
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("Docker-Content-Digest", &sha256(manifest.as_bytes()))
+        .with_body(manifest)
+        .create();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/blobs/{}", repository, sha256(blob.as_bytes())).as_str(),
+        )
+        .with_status(200)
+        .with_body(blob)
+        .create();
+}
+
+/// Mock the referrers of `digest`, listing an SPDX SBOM manifest if given
+fn mock_referrers(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    digest: &str,
+    sbom: Option<&str>,
+) {
+    let manifests = sbom
+        .map(|manifest| {
+            format!(
+                r#"{{"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"{}","size":{},"artifactType":"application/spdx+json"}}"#,
+                sha256(manifest.as_bytes()),
+                manifest.len()
+            )
+        })
+        .unwrap_or_default();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/referrers/{}", repository, digest).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(format!(
+            r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[{}]}}"#,
+            manifests
+        ))
+        .create();
+}
+
+/// Mock the absence of cosign's `.att` and/or `.sbom` tags
+fn mock_missing_tags(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    digest: &str,
+    suffixes: &[&str],
+) {
+    let hex = digest.trim_start_matches("sha256:");
+    for suffix in suffixes {
+        server
+            .mock(
+                "GET",
+                format!("/v2/{}/manifests/sha256-{}.{}", repository, hex, suffix).as_str(),
+            )
+            .with_status(404)
+            .create();
+    }
+}
+
+/// Image with an SPDX referrer and a CycloneDX attestation
+fn mock_attached_sboms(server: &mut mockito::ServerGuard, repository: &str) {
+    mock_tag(server, repository, "v1", IMAGE_DIGEST);
+
+    let document = spdx("2.14.1");
+    let spdx_manifest = layer_manifest("application/spdx+json", &document);
+    mock_referrers(server, repository, IMAGE_DIGEST, Some(&spdx_manifest));
+    mock_layer_manifest(
+        server,
+        repository,
+        &sha256(spdx_manifest.as_bytes()),
+        &spdx_manifest,
+        &document,
+    );
+
+    let envelope = attestation(CYCLONEDX_JSON);
+    mock_layer_manifest(
+        server,
+        repository,
+        &format!("sha256-{}.att", IMAGE_HEX),
+        &layer_manifest("application/vnd.dsse.envelope.v1+json", &envelope),
+        &envelope,
+    );
+    mock_missing_tags(server, repository, IMAGE_DIGEST, &["sbom"]);
+}
+
+#[test]
+fn test_image_sbom_referrer_and_attestation() {
+    let repository = "sbom-attached";
+    let mut server = sbom_server();
+    mock_attached_sboms(&mut server, repository);
+
+    let sbom = image_sbom(&server.url(), "sbom-attached:v1", None).unwrap();
+
+    assert_eq!(sbom.digest, IMAGE_DIGEST);
+    assert_eq!(sbom.sboms.len(), 2);
+    assert_eq!(sbom.sboms[0].format, SbomFormat::Spdx);
+    assert_eq!(sbom.sboms[0].source, SbomSource::Referrer);
+    assert_eq!(sbom.sboms[0].package_count, 2);
+    assert_eq!(sbom.sboms[1].format, SbomFormat::CycloneDx);
+    assert_eq!(sbom.sboms[1].source, SbomSource::AttestationTag);
+    assert_eq!(
+        sbom.sboms[1].predicate_type.as_deref(),
+        Some("https://cyclonedx.org/bom")
+    );
+
+    // musl 1.2.4 is listed by both SBOMs, with the same license
+    let names: Vec<&str> = sbom.packages.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["log4j-core", "musl", "openssl"]);
+
+    let pretty = sbom.format_pretty();
+    assert!(pretty.contains("SBOMs (2):"));
+    assert!(pretty.contains("SPDX"));
+    assert!(pretty.contains("(attestation tag, 2 packages)"));
+    assert!(pretty.contains("Predicate: https://cyclonedx.org/bom"));
+    assert!(pretty.contains("Packages (3):"));
+    assert!(pretty.contains("pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1"));
+
+    // Raw documents stay out of the structured JSON
+    let json = serde_json::to_value(&sbom).unwrap();
+    assert_eq!(json["sboms"][0]["source"], "referrer");
+    assert!(json["sboms"][0].get("document").is_none());
+    assert_eq!(sbom.sboms[0].document["spdxVersion"], "SPDX-2.3");
+}
+
+#[test]
+fn test_image_sbom_attestation_without_referrers_api() {
+    let repository = "sbom-no-referrers";
+    let mut server = sbom_server();
+    mock_tag(&mut server, repository, "v1", IMAGE_DIGEST);
+    // A registry without the referrers API that answers 500, not 404
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/referrers/{}", repository, IMAGE_DIGEST).as_str(),
+        )
+        .with_status(500)
+        .create();
+    let envelope = attestation(CYCLONEDX_JSON);
+    mock_layer_manifest(
+        &mut server,
+        repository,
+        &format!("sha256-{}.att", IMAGE_HEX),
+        &layer_manifest("application/vnd.dsse.envelope.v1+json", &envelope),
+        &envelope,
+    );
+    mock_missing_tags(&mut server, repository, IMAGE_DIGEST, &["sbom"]);
+
+    let sbom = image_sbom(&server.url(), "sbom-no-referrers:v1", None).unwrap();
+
+    assert_eq!(sbom.sboms.len(), 1);
+    assert_eq!(sbom.sboms[0].source, SbomSource::AttestationTag);
+}
+
+#[test]
+fn test_image_sbom_package_filter() {
+    let repository = "sbom-filtered";
+    let mut server = sbom_server();
+    mock_attached_sboms(&mut server, repository);
+
+    let sbom = image_sbom(&server.url(), "sbom-filtered:v1", Some("LOG4J@2.14")).unwrap();
+
+    assert_eq!(sbom.packages.len(), 1);
+    assert_eq!(sbom.packages[0].version.as_deref(), Some("2.14.1"));
+    assert_eq!(sbom.packages[0].license.as_deref(), Some("Apache-2.0"));
+}
+
+#[test]
+fn test_image_sbom_none_found() {
+    let repository = "sbom-none";
+    let mut server = sbom_server();
+    mock_tag(&mut server, repository, "v1", IMAGE_DIGEST);
+    mock_referrers(&mut server, repository, IMAGE_DIGEST, None);
+    mock_missing_tags(&mut server, repository, IMAGE_DIGEST, &["att", "sbom"]);
+
+    let sbom = image_sbom(&server.url(), "sbom-none:v1", None).unwrap();
+
+    assert!(sbom.sboms.is_empty());
+    assert!(sbom.packages.is_empty());
+    assert!(sbom.format_pretty().contains("No SBOMs found."));
+}
+
+#[test]
+fn test_search_sbom_packages_across_tags() {
+    let repository = "sbom-search";
+    let mut server = sbom_server();
+    server
+        .mock("GET", "/v2/sbom-search/tags/list")
+        .with_status(200)
+        .with_body(format!(
+            r#"{{"name":"sbom-search","tags":["v1","v2","sha256-{}.sbom","sha256-{}.sig"]}}"#,
+            IMAGE_DIGEST.trim_start_matches("sha256:"),
+            OTHER_DIGEST.trim_start_matches("sha256:"),
+        ))
+        .create();
+
+    // v1 ships log4j 2.14.1 (attached with `cosign attach sbom`), v2 ships 2.17.1
+    for (tag, digest, version) in [
+        ("v1", IMAGE_DIGEST, "2.14.1"),
+        ("v2", OTHER_DIGEST, "2.17.1"),
+    ] {
+        let hex = digest.trim_start_matches("sha256:");
+        let document = spdx(version);
+        mock_tag(&mut server, repository, tag, digest);
+        mock_referrers(&mut server, repository, digest, None);
+        mock_missing_tags(&mut server, repository, digest, &["att"]);
+        mock_layer_manifest(
+            &mut server,
+            repository,
+            &format!("sha256-{}.sbom", hex),
+            &layer_manifest("text/spdx+json", &document),
+            &document,
+        );
+    }
+
+    let search = search_sbom_packages(&server.url(), repository, "log4j-core@2.14").unwrap();
+
+    assert_eq!(search.scanned, 2);
+    assert!(search.failed.is_empty());
+    assert_eq!(search.matches.len(), 1);
+    assert_eq!(search.matches[0].reference, "sbom-search:v1");
+    assert_eq!(search.matches[0].sboms[0].source, SbomSource::SbomTag);
+    assert_eq!(search.matches[0].packages.len(), 1);
+
+    let pretty = search.format_pretty();
+    assert!(pretty.contains("sbom-search:v1"));
+    assert!(!pretty.contains("sbom-search:v2"));
+    assert!(pretty.contains("1 of 2 images in sbom-search contain log4j-core@2.14"));
+}
+
+#[test]
+fn test_parse_package_filter() {
+    assert_eq!(parse_package_filter("log4j"), ("log4j", None));
+    assert_eq!(
+        parse_package_filter("log4j-core@2.14"),
+        ("log4j-core", Some("2.14"))
+    );
+    assert_eq!(parse_package_filter("@babel/core"), ("@babel/core", None));
+    assert_eq!(
+        parse_package_filter("@babel/core@7"),
+        ("@babel/core", Some("7"))
+    );
+    assert_eq!(parse_package_filter("openssl@"), ("openssl@", None));
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format;
+
+/// Handle the image tag command (add a tag to an existing image)
+pub fn handle_image_tag(ctx: &crate::context::AppContext, source: &str, new_tag: &str) {
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!("Tagging {} as {}...", source, new_tag),
+    );
+
+    match tag_image(&registry_url, source, new_tag) {
+        Ok(digest) => {
+            format::success(
+                ctx,
+                &format!("Tagged {} as '{}' ({})", source, new_tag, digest),
+            );
+        }
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Tag an existing image in the registry.
+///
+/// The source manifest is pushed unchanged under `new_tag` in the same
+/// repository, so no layers are transferred.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `source` - Source image reference (e.g., "myapp:sha-abc" or "myapp@sha256:...")
+/// * `new_tag` - Tag to create or move (e.g., "prod")
+///
+/// # Returns
+///
+/// Returns the manifest digest the new tag points to
+pub(crate) fn tag_image(registry_url: &str, source: &str, new_tag: &str) -> Result<String, String> {
+    let mut rex = connect_rex(registry_url)?;
+
+    librex::reference::Reference::from_str(source)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    rex.tag(source, new_tag)
+        .map_err(|e| format!("Failed to tag image: {}", e))
+}
+
+#[cfg(test)]
+#[path = "tag_tests.rs"]
+mod tests;
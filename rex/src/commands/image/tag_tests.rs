@@ -0,0 +1,83 @@
+use super::*;
+
+// Note: These tests use mockito to test tag_image end-to-end with mock HTTP responses.
+
+const MANIFEST_JSON: &str = r#"{
+    "schemaVersion": 2,
+    "mediaType": "application/vnd.oci.image.manifest.v1+json",
+    "config": {
+        "mediaType": "application/vnd.oci.image.config.v1+json",
+        "size": 1024,
+        "digest": "sha256:76eff6f8609d534c7586db61b67b4d1920ae32e08765dbf0033ca165be010c5a"
+    },
+    "layers": []
+}"#;
+
+const MANIFEST_DIGEST: &str =
+    "sha256:c5b1261d6d3e43071626931fc004f70149baeba2c8ec672bd4f27761f8e1ad6b";
+
+#[test]
+fn test_tag_image_pushes_manifest_under_new_tag() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let get_mock = server
+        .mock("GET", "/v2/myapp/manifests/sha-abc")
+        .with_status(200)
+        .with_header("Docker-Content-Digest", MANIFEST_DIGEST)
+        .with_body(MANIFEST_JSON)
+        .create();
+    let put_mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .match_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .match_body(MANIFEST_JSON)
+        .with_status(201)
+        .with_header("Docker-Content-Digest", MANIFEST_DIGEST)
+        .create();
+
+    let result = tag_image(&registry_url, "myapp:sha-abc", "prod");
+
+    get_mock.assert();
+    put_mock.assert();
+    assert_eq!(result.unwrap(), MANIFEST_DIGEST);
+}
+
+#[test]
+fn test_tag_image_source_not_found() {
+    let mut server = mockito::Server::new();
+    let registry_url = server.url();
+
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _get_mock = server
+        .mock("GET", "/v2/myapp/manifests/missing")
+        .with_status(404)
+        .create();
+    let put_mock = server
+        .mock("PUT", "/v2/myapp/manifests/prod")
+        .expect(0)
+        .create();
+
+    let result = tag_image(&registry_url, "myapp:missing", "prod");
+
+    put_mock.assert();
+    assert!(result.unwrap_err().contains("Failed to tag image"));
+}
+
+#[test]
+fn test_tag_image_invalid_reference() {
+    let server = mockito::Server::new();
+
+    let result = tag_image(&server.url(), "", "prod");
+
+    assert!(result.unwrap_err().contains("Invalid image reference"));
+}
+
+#[test]
+fn test_tag_image_invalid_tag() {
+    let server = mockito::Server::new();
+
+    let result = tag_image(&server.url(), "myapp:v1", "not a tag");
+
+    assert!(result.unwrap_err().contains("Invalid tag"));
+}
@@ -1,4 +1,3 @@
-use super::*;
 use crate::context::{AppContext, VerbosityLevel};
 use crate::format::{ColorChoice, OutputFormat};
 
@@ -0,0 +1,155 @@
+use super::*;
+use crate::context::VerbosityLevel;
+use crate::format::{self, OutputFormat};
+
+/// Handle the image verify command (keyed cosign signature verification)
+///
+/// Exits with status 1 unless at least one signature verifies.
+pub fn handle_image_verify(
+    ctx: &crate::context::AppContext,
+    reference: &str,
+    key: &std::path::Path,
+    format: OutputFormat,
+) {
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        &format!(
+            "Verifying signatures of {} with {}",
+            reference,
+            key.display()
+        ),
+    );
+
+    // Get registry URL from config
+    let registry_url = match get_registry_url() {
+        Ok(url) => url,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = match verify_image(&registry_url, reference, key) {
+        Ok(report) => report,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    match format {
+        OutputFormat::Pretty => print!("{}", report.format_pretty()),
+        OutputFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Error formatting JSON: {}", e);
+                std::process::exit(1);
+            }
+        },
+    }
+
+    if !report.verified {
+        format::error(
+            ctx,
+            &format!("No valid signature for {} with the given key", reference),
+        );
+        std::process::exit(1);
+    }
+}
+
+/// Signature verification report for an image
+#[derive(Debug, Serialize)]
+pub struct VerifyReport {
+    /// Image reference as given
+    pub reference: String,
+    /// Manifest digest the signatures must sign
+    pub digest: String,
+    /// Algorithm of the public key (e.g., "ECDSA P-256")
+    pub key_algorithm: String,
+    /// True if at least one signature verified
+    pub verified: bool,
+    /// One entry per signature found
+    pub signatures: Vec<librex::signature::SignatureCheck>,
+}
+
+impl Formattable for VerifyReport {
+    fn format_pretty(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("Image: {}\n", self.reference));
+        output.push_str(&format!("Digest: {}\n", self.digest));
+        output.push_str(&format!("Key: {}\n", self.key_algorithm));
+
+        if self.signatures.is_empty() {
+            output.push_str("\nNo signatures found.\n");
+        } else {
+            output.push_str(&format!("\nSignatures ({}):\n", self.signatures.len()));
+            for check in &self.signatures {
+                let status = if check.verified { "✓" } else { "✗" };
+                output.push_str(&format!(
+                    "  {} {} ({})\n",
+                    status, check.manifest_digest, check.source
+                ));
+                if let Some(reference) = &check.docker_reference {
+                    output.push_str(&format!("    Identity: {}\n", reference));
+                }
+                if let Some(signed) = &check.signed_digest {
+                    output.push_str(&format!("    Signed Digest: {}\n", signed));
+                }
+                if let Some(error) = &check.error {
+                    output.push_str(&format!("    Error: {}\n", error));
+                }
+            }
+        }
+
+        output.push_str(&format!(
+            "\nVerified: {}\n",
+            if self.verified { "yes" } else { "no" }
+        ));
+        output
+    }
+}
+
+/// Verify the cosign signatures of an image against a public key file.
+///
+/// # Arguments
+///
+/// * `registry_url` - URL of the registry
+/// * `reference` - Image reference (e.g., "myapp:v1" or "myapp@sha256:...")
+/// * `key_path` - Path to a PEM public key (e.g., "cosign.pub")
+///
+/// # Returns
+///
+/// Returns the report, with one entry per signature found
+pub(crate) fn verify_image(
+    registry_url: &str,
+    reference: &str,
+    key_path: &std::path::Path,
+) -> Result<VerifyReport, String> {
+    librex::reference::Reference::from_str(reference)
+        .map_err(|e| format!("Invalid image reference: {}", e))?;
+
+    let pem = std::fs::read_to_string(key_path)
+        .map_err(|e| format!("Failed to read key '{}': {}", key_path.display(), e))?;
+    let key = librex::signature::PublicKey::from_pem(&pem)
+        .map_err(|e| format!("Invalid key '{}': {}", key_path.display(), e))?;
+
+    let mut rex = connect_rex(registry_url)?;
+
+    let (digest, signatures) = rex
+        .verify_signatures(reference, &key)
+        .map_err(|e| format!("Failed to verify signatures: {}", e))?;
+
+    Ok(VerifyReport {
+        reference: reference.to_string(),
+        digest,
+        key_algorithm: key.algorithm().to_string(),
+        verified: signatures.iter().any(|check| check.verified),
+        signatures,
+    })
+}
+
+#[cfg(test)]
+#[path = "verify_tests.rs"]
+mod tests;
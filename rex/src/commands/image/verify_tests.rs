@@ -0,0 +1,293 @@
+use super::*;
+use crate::test_support::sha256;
+use librex::signature::SignatureSource;
+use ring::rand::SystemRandom;
+use ring::signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair};
+
+// Note: These tests sign payloads with freshly generated keys and serve the
+// signature manifests from mockito, covering discovery and verification.
+
+const IMAGE_DIGEST: &str =
+    "sha256:af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+const IMAGE_HEX: &str = "af81a33baea81dcac4011c06b80d3c779f510feadff8819a774d7d9c1f7e0e0c";
+
+/// SubjectPublicKeyInfo prefix of an uncompressed P-256 public key
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+
+fn key_pair() -> EcdsaKeyPair {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+    EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap()
+}
+
+/// Write the public key as a cosign.pub file
+fn write_public_key(dir: &tempfile::TempDir, key_pair: &EcdsaKeyPair) -> std::path::PathBuf {
+    use base64::Engine;
+
+    let der = [P256_SPKI_PREFIX, key_pair.public_key().as_ref()].concat();
+    let pem = format!(
+        "-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n",
+        base64::engine::general_purpose::STANDARD.encode(der)
+    );
+    let path = dir.path().join("cosign.pub");
+    std::fs::write(&path, pem).unwrap();
+    path
+}
+
+/// Sign a simple signing payload for IMAGE_DIGEST; returns (payload, manifest)
+fn signature_manifest(key_pair: &EcdsaKeyPair, repository: &str) -> (String, String) {
+    use base64::Engine;
+
+    let payload = format!(
+        r#"{{"critical":{{"identity":{{"docker-reference":"localhost/{}"}},"image":{{"docker-manifest-digest":"{}"}},"type":"cosign container image signature"}},"optional":null}}"#,
+        repository, IMAGE_DIGEST
+    );
+    let signature = key_pair
+        .sign(&SystemRandom::new(), payload.as_bytes())
+        .unwrap();
+    let manifest = format!(
+        r#"{{
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "config": {{
+                "mediaType": "application/vnd.oci.image.config.v1+json",
+                "size": 2,
+                "digest": "sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+            }},
+            "layers": [{{
+                "mediaType": "application/vnd.dev.cosign.simplesigning.v1+json",
+                "size": {},
+                "digest": "{}",
+                "annotations": {{"dev.cosignproject.cosign/signature": "{}"}}
+            }}]
+        }}"#,
+        payload.len(),
+        sha256(payload.as_bytes()),
+        base64::engine::general_purpose::STANDARD.encode(signature.as_ref())
+    );
+    (payload, manifest)
+}
+
+/// Mock the image, its (empty) referrers and the registry root
+fn image_server(repository: &str) -> mockito::ServerGuard {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+    server
+        .mock("HEAD", format!("/v2/{}/manifests/v1", repository).as_str())
+        .with_status(200)
+        .with_header("Docker-Content-Digest", IMAGE_DIGEST)
+        .create();
+    server
+}
+
+fn mock_no_referrers(server: &mut mockito::ServerGuard, repository: &str) {
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/referrers/{}", repository, IMAGE_DIGEST).as_str(),
+        )
+        .with_status(404)
+        .create();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/sha256-{}", repository, IMAGE_HEX).as_str(),
+        )
+        .with_status(404)
+        .create();
+}
+
+fn mock_signature(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    reference: &str,
+    payload: &str,
+    manifest: &str,
+) {
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("Docker-Content-Digest", &sha256(manifest.as_bytes()))
+        .with_body(manifest)
+        .create();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/blobs/{}", repository, sha256(payload.as_bytes())).as_str(),
+        )
+        .with_status(200)
+        .with_body(payload)
+        .create();
+}
+
+#[test]
+fn test_verify_image_tag_signature() {
+    let repository = "verify-tagged";
+    let mut server = image_server(repository);
+    mock_no_referrers(&mut server, repository);
+    let signer = key_pair();
+    let (payload, manifest) = signature_manifest(&signer, repository);
+    let signature_tag = format!("sha256-{}.sig", IMAGE_HEX);
+    mock_signature(&mut server, repository, &signature_tag, &payload, &manifest);
+
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = write_public_key(&dir, &signer);
+
+    let report = verify_image(&server.url(), "verify-tagged:v1", &key_path).unwrap();
+
+    assert!(report.verified);
+    assert_eq!(report.digest, IMAGE_DIGEST);
+    assert_eq!(report.key_algorithm, "ECDSA P-256");
+    assert_eq!(report.signatures.len(), 1);
+    let check = &report.signatures[0];
+    assert_eq!(check.source, SignatureSource::Tag);
+    assert_eq!(check.signed_digest.as_deref(), Some(IMAGE_DIGEST));
+
+    let pretty = report.format_pretty();
+    assert!(pretty.contains("Signatures (1):"));
+    assert!(pretty.contains("✓"));
+    assert!(pretty.contains("Verified: yes"));
+}
+
+#[test]
+fn test_verify_image_tag_signature_without_referrers_api() {
+    let repository = "verify-no-referrers";
+    let mut server = image_server(repository);
+    // A registry without the referrers API that answers 500, not 404
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/referrers/{}", repository, IMAGE_DIGEST).as_str(),
+        )
+        .with_status(500)
+        .create();
+    let signer = key_pair();
+    let (payload, manifest) = signature_manifest(&signer, repository);
+    let signature_tag = format!("sha256-{}.sig", IMAGE_HEX);
+    mock_signature(&mut server, repository, &signature_tag, &payload, &manifest);
+
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = write_public_key(&dir, &signer);
+
+    let report = verify_image(&server.url(), "verify-no-referrers:v1", &key_path).unwrap();
+
+    assert!(report.verified);
+    assert_eq!(report.signatures.len(), 1);
+    assert_eq!(report.signatures[0].source, SignatureSource::Tag);
+}
+
+#[test]
+fn test_verify_image_referrer_signature_wrong_key() {
+    let repository = "verify-referrer";
+    let mut server = image_server(repository);
+    let signer = key_pair();
+    let (payload, manifest) = signature_manifest(&signer, repository);
+    let manifest_digest = sha256(manifest.as_bytes());
+
+    // No signature tag, one referrer
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/sha256-{}.sig", repository, IMAGE_HEX).as_str(),
+        )
+        .with_status(404)
+        .create();
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/referrers/{}", repository, IMAGE_DIGEST).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.index.v1+json")
+        .with_body(format!(
+            r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[
+                {{"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"{}","size":{},
+                  "artifactType":"application/vnd.dev.cosign.artifact.sig.v1+json"}},
+                {{"mediaType":"application/vnd.oci.image.manifest.v1+json",
+                  "digest":"sha256:1111111111111111111111111111111111111111111111111111111111111111","size":10,
+                  "artifactType":"application/spdx+json"}}
+            ]}}"#,
+            manifest_digest,
+            manifest.len()
+        ))
+        .create();
+    mock_signature(
+        &mut server,
+        repository,
+        &manifest_digest,
+        &payload,
+        &manifest,
+    );
+
+    // Verify with a different key
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = write_public_key(&dir, &key_pair());
+
+    let report = verify_image(&server.url(), "verify-referrer:v1", &key_path).unwrap();
+
+    assert!(!report.verified);
+    assert_eq!(report.signatures.len(), 1);
+    let check = &report.signatures[0];
+    assert_eq!(check.source, SignatureSource::Referrer);
+    assert_eq!(check.manifest_digest, manifest_digest);
+    assert!(
+        check
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("does not match the public key")
+    );
+    assert!(report.format_pretty().contains("Verified: no"));
+}
+
+#[test]
+fn test_verify_image_unsigned() {
+    let repository = "verify-unsigned";
+    let mut server = image_server(repository);
+    mock_no_referrers(&mut server, repository);
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/sha256-{}.sig", repository, IMAGE_HEX).as_str(),
+        )
+        .with_status(404)
+        .create();
+
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = write_public_key(&dir, &key_pair());
+
+    let report = verify_image(&server.url(), "verify-unsigned:v1", &key_path).unwrap();
+
+    assert!(!report.verified);
+    assert!(report.signatures.is_empty());
+    assert!(report.format_pretty().contains("No signatures found."));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["verified"], false);
+}
+
+#[test]
+fn test_verify_image_invalid_key_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let key_path = dir.path().join("cosign.pub");
+    std::fs::write(&key_path, "not a key").unwrap();
+
+    let err = verify_image("http://localhost:1", "app:v1", &key_path).unwrap_err();
+    assert!(err.contains("Invalid key"));
+
+    let err = verify_image(
+        "http://localhost:1",
+        "app:v1",
+        &dir.path().join("missing.pub"),
+    )
+    .unwrap_err();
+    assert!(err.contains("Failed to read key"));
+}
@@ -145,6 +145,8 @@ pub fn handle_registry_login(
     name: &str,
     username: Option<&str>,
     password: Option<&str>,
+    token: Option<&str>,
+    token_stdin: bool,
 ) {
     format::print(
         ctx,
@@ -154,7 +156,13 @@ pub fn handle_registry_login(
 
     let config_path = config::get_config_path();
 
-    match login_registry(&config_path, name, username, password) {
+    let result = if token.is_some() || token_stdin {
+        login_registry_with_token(&config_path, name, token)
+    } else {
+        login_registry(&config_path, name, username, password)
+    };
+
+    match result {
         Ok(_) => format::success(ctx, &format!("Stored credentials for '{}'", name)),
         Err(e) => {
             format::error(ctx, &e);
@@ -319,6 +327,190 @@ pub fn handle_cache_sync(
     }
 }
 
+/// Handle the registry du subcommand
+pub fn handle_registry_du(
+    ctx: &crate::context::AppContext,
+    name: Option<&str>,
+    top: Option<usize>,
+    format: OutputFormat,
+) {
+    use librex::usage::UsageEvent;
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        "Computing registry storage usage...",
+    );
+
+    let config_path = config::get_config_path();
+    let formatter = format::create_formatter(ctx);
+    let mut progress = None;
+
+    let result = disk_usage(&config_path, name, top, |event| match event {
+        // Keep stdout clean for JSON
+        UsageEvent::Catalog(count) if format == OutputFormat::Pretty => {
+            progress = Some(formatter.progress_bar(*count as u64, "Scanning repositories"));
+        }
+        UsageEvent::Catalog(_) => {}
+        UsageEvent::Repository { .. } => {
+            if let Some(ref pb) = progress {
+                pb.inc(1);
+            }
+        }
+        UsageEvent::Skipped { reference, error } => format::print(
+            ctx,
+            VerbosityLevel::Verbose,
+            &format!("Skipping {}: {}", reference, error),
+        ),
+    });
+    if let Some(pb) = progress {
+        pb.finish_and_clear();
+    }
+
+    match result {
+        Ok(usage) => match crate::format::format_output(&usage, format) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                format::error(ctx, &format!("formatting output: {}", e));
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Handle the registry gc-report subcommand
+pub fn handle_registry_gc_report(
+    ctx: &crate::context::AppContext,
+    name: Option<&str>,
+    delete: bool,
+    force: bool,
+    format: OutputFormat,
+) {
+    use librex::gc::GcEvent;
+
+    format::print(
+        ctx,
+        VerbosityLevel::Verbose,
+        "Scanning registry for unreferenced manifests...",
+    );
+
+    let config_path = config::get_config_path();
+    let formatter = format::create_formatter(ctx);
+    let mut progress = None;
+
+    let result = gc_report(&config_path, name, |event| match event {
+        // Keep stdout clean for JSON
+        GcEvent::Catalog(count) if format == OutputFormat::Pretty => {
+            progress = Some(formatter.progress_bar(*count as u64, "Scanning repositories"));
+        }
+        GcEvent::Repository { .. } => {
+            if let Some(ref pb) = progress {
+                pb.inc(1);
+            }
+        }
+        GcEvent::Skipped { reference, error } => format::print(
+            ctx,
+            VerbosityLevel::Verbose,
+            &format!("Skipping {}: {}", reference, error),
+        ),
+        _ => {}
+    });
+    if let Some(pb) = progress {
+        pb.finish_and_clear();
+    }
+
+    let mut display = match result {
+        Ok(display) => display,
+        Err(e) => {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+    };
+
+    let deletable = display.report.deletable().count();
+    if delete && deletable > 0 {
+        if format == OutputFormat::Pretty {
+            println!("{}\n", display.format_pretty());
+        }
+        if !force
+            && let Err(e) = confirm(&format!(
+                "Delete {} manifests from '{}'?",
+                deletable, display.registry
+            ))
+        {
+            format::error(ctx, &e);
+            std::process::exit(1);
+        }
+
+        let result = gc_delete(&config_path, &display, |event| match event {
+            GcEvent::Deleted { repository, digest } => format::print(
+                ctx,
+                VerbosityLevel::Verbose,
+                &format!("Deleted {}@{}", repository, digest),
+            ),
+            GcEvent::DeleteFailed {
+                repository,
+                digest,
+                error,
+            } => format::print(
+                ctx,
+                VerbosityLevel::Verbose,
+                &format!("Failed to delete {}@{}: {}", repository, digest, error),
+            ),
+            _ => {}
+        });
+        let deletion = match result {
+            Ok(deletion) => deletion,
+            Err(e) => {
+                format::error(ctx, &e);
+                std::process::exit(1);
+            }
+        };
+        let failed = !deletion.failed.is_empty();
+
+        match format {
+            OutputFormat::Json => {
+                display.deletion = Some(deletion);
+                match crate::format::format_output(&display, format) {
+                    Ok(output) => println!("{}", output),
+                    Err(e) => {
+                        format::error(ctx, &format!("formatting output: {}", e));
+                        std::process::exit(1);
+                    }
+                }
+            }
+            OutputFormat::Pretty => {
+                format::success(
+                    ctx,
+                    &format!("Deleted {} manifests", deletion.deleted.len()),
+                );
+                for failed in &deletion.failed {
+                    format::error(ctx, &format!("Failed to delete {}", failed));
+                }
+            }
+        }
+        if failed {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    match crate::format::format_output(&display, format) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            format::error(ctx, &format!("formatting output: {}", e));
+            std::process::exit(1);
+        }
+    }
+    if format == OutputFormat::Pretty && deletable > 0 {
+        println!("\nRun with --delete to remove {} manifests", deletable);
+    }
+}
+
 #[cfg(test)]
 #[path = "handlers_tests.rs"]
 mod tests;
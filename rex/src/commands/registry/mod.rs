@@ -81,6 +81,10 @@ pub struct RegistryCheckResult {
     pub authenticated: bool,
     /// API version if available
     pub api_version: Option<String>,
+    /// Extensions offered by the registry (Zot search, CVE, ...), if they
+    /// could be discovered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<librex::RegistryCapabilities>,
     /// Error message if check failed
     pub error: Option<String>,
 }
@@ -108,6 +112,17 @@ impl Formattable for RegistryCheckResult {
                 output.push_str(&format!("API Version: {}\n", api_version));
             }
 
+            if let Some(ref capabilities) = self.capabilities {
+                let features = capabilities.feature_names();
+                if features.is_empty() {
+                    output.push_str("Extensions: none (OCI Distribution API only)\n");
+                } else if capabilities.is_zot() {
+                    output.push_str(&format!("Extensions: {} (Zot)\n", features.join(", ")));
+                } else {
+                    output.push_str(&format!("Extensions: {}\n", features.join(", ")));
+                }
+            }
+
             // Show auth status
             if self.authenticated {
                 output.push_str(&format!("Authentication: {} Authenticated\n", "✓"));
@@ -208,6 +223,7 @@ pub(crate) fn init_registry(config_path: &PathBuf, name: &str, url: &str) -> Res
         name: name.to_string(),
         url: normalized_url,
         dockerhub_compat: false, // Default to false for most registries
+        tls: Default::default(),
     });
 
     // Set as default if this is the first registry
@@ -298,16 +314,7 @@ pub(crate) fn show_registry(config_path: &PathBuf, name: &str) -> Result<Registr
         .ok_or_else(|| format!("Registry '{}' not found", name))?;
 
     // Check if credentials are configured for this registry
-    let creds_path = config::get_credentials_path();
-    let authenticated = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(&registry.url).unwrap_or(None).is_some()
-        } else {
-            false
-        }
-    } else {
-        false
-    };
+    let authenticated = config::load_credentials(&registry.url).is_some();
 
     // Create display with default marker
     let is_default = config.registries.default.as_ref() == Some(&name.to_string());
@@ -337,12 +344,7 @@ pub(crate) fn list_registries(config_path: &PathBuf) -> Result<Vec<RegistryDispl
     let config = config::Config::load(config_path)?;
 
     // Load credential store once for all registries
-    let creds_path = config::get_credentials_path();
-    let cred_store = if creds_path.exists() {
-        librex::auth::FileCredentialStore::new(creds_path).ok()
-    } else {
-        None
-    };
+    let cred_store = config::credential_store();
 
     // Create display list with default markers and auth status
     let registries: Vec<RegistryDisplay> = config
@@ -353,11 +355,7 @@ pub(crate) fn list_registries(config_path: &PathBuf) -> Result<Vec<RegistryDispl
             let is_default = config.registries.default.as_ref() == Some(&r.name);
 
             // Check if credentials exist for this registry
-            let authenticated = if let Some(ref store) = cred_store {
-                store.get(&r.url).unwrap_or(None).is_some()
-            } else {
-                false
-            };
+            let authenticated = cred_store.get(&r.url).unwrap_or(None).is_some();
 
             RegistryDisplay {
                 name: r.name.clone(),
@@ -412,6 +410,7 @@ pub(crate) fn check_registry(
                 auth_required: false,
                 authenticated: false,
                 api_version: None,
+                capabilities: None,
                 error: Some(format!("Configuration error: {}", e)),
             };
         }
@@ -428,6 +427,7 @@ pub(crate) fn check_registry(
                 auth_required: false,
                 authenticated: false,
                 api_version: None,
+                capabilities: None,
                 error: Some(format!(
                     "Registry '{}' not found in configuration. Use 'rex registry add' to add it.",
                     name
@@ -436,25 +436,9 @@ pub(crate) fn check_registry(
         }
     };
 
-    // Check if credentials are configured for this registry
-    let creds_path = config::get_credentials_path();
-    let authenticated = if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-        store.get(&registry.url).unwrap_or(None).is_some()
-    } else {
-        false
-    };
-
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(&registry.url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(&registry.url);
+    let authenticated = credentials.is_some();
 
     // Create client and check version
     format::print(
@@ -462,7 +446,11 @@ pub(crate) fn check_registry(
         VerbosityLevel::VeryVerbose,
         &format!("Connecting to registry at: {}", registry.url),
     );
-    let client = match librex::client::Client::new(&registry.url, credentials) {
+    let client = match librex::client::Client::with_config(
+        &registry.url,
+        registry.client_config(),
+        credentials,
+    ) {
         Ok(c) => c,
         Err(e) => {
             return RegistryCheckResult {
@@ -472,21 +460,43 @@ pub(crate) fn check_registry(
                 auth_required: false,
                 authenticated: false,
                 api_version: None,
+                capabilities: None,
                 error: Some(format!("Invalid registry URL: {}", e)),
             };
         }
     };
 
     match client.check_version() {
-        Ok(version) => RegistryCheckResult {
-            name: name.to_string(),
-            url: registry.url.clone(),
-            online: true,
-            auth_required: false,
-            authenticated,
-            api_version: version.api_version,
-            error: None,
-        },
+        Ok(version) => {
+            // Extensions are optional: a failed discovery leaves them unknown
+            format::print(
+                ctx,
+                VerbosityLevel::VeryVerbose,
+                "Discovering registry extensions...",
+            );
+            let capabilities = match librex::zot::discover(&client) {
+                Ok(capabilities) => Some(capabilities),
+                Err(e) => {
+                    format::print(
+                        ctx,
+                        VerbosityLevel::Verbose,
+                        &format!("Extension discovery failed: {}", e),
+                    );
+                    None
+                }
+            };
+
+            RegistryCheckResult {
+                name: name.to_string(),
+                url: registry.url.clone(),
+                online: true,
+                auth_required: false,
+                authenticated,
+                api_version: version.api_version,
+                capabilities,
+                error: None,
+            }
+        }
         Err(e) => {
             // Check if error is authentication-related
             let error_str = format!("{}", e);
@@ -523,6 +533,7 @@ pub(crate) fn check_registry(
                 auth_required,
                 authenticated,
                 api_version: None,
+                capabilities: None,
                 error: Some(friendly_error),
             }
         }
@@ -587,15 +598,78 @@ pub(crate) fn login_registry(
     // Create credentials
     let credentials = librex::auth::Credentials::basic(&username, &password);
 
+    verify_and_store_credentials(registry, &credentials)
+}
+
+/// Read a bearer token from stdin
+fn read_token_stdin() -> Result<String, String> {
+    let mut token = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut token)
+        .map_err(|e| format!("Failed to read token from stdin: {}", e))?;
+
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        return Err("No token provided on stdin".to_string());
+    }
+
+    Ok(token)
+}
+
+/// Login to a registry with a bearer token
+///
+/// The token is read from stdin when `token` is `None`.
+pub(crate) fn login_registry_with_token(
+    config_path: &PathBuf,
+    name: &str,
+    token: Option<&str>,
+) -> Result<(), String> {
+    // Load config to verify registry exists
+    let config = config::Config::load(config_path)?;
+
+    // Find registry
+    let registry = config
+        .registries
+        .list
+        .iter()
+        .find(|r| r.name == name)
+        .ok_or_else(|| {
+            format!(
+                "Registry '{}' not found. Use 'rex registry add' to add it first.",
+                name
+            )
+        })?;
+
+    let token = match token {
+        Some(token) if !token.trim().is_empty() => token.trim().to_string(),
+        Some(_) => return Err("Token cannot be empty".to_string()),
+        None => read_token_stdin()?,
+    };
+
+    let credentials = librex::auth::Credentials::bearer(token);
+
+    verify_and_store_credentials(registry, &credentials)
+}
+
+/// Verify credentials against a registry and store them
+fn verify_and_store_credentials(
+    registry: &RegistryEntry,
+    credentials: &librex::auth::Credentials,
+) -> Result<(), String> {
+    let registry_url = registry.url.as_str();
+
     // Verify credentials by attempting to authenticate with the registry
     println!("Verifying credentials...");
-    let client = librex::client::Client::new(&registry.url, Some(credentials.clone()))
-        .map_err(|e| format!("Invalid registry URL: {}", e))?;
+    let client = librex::client::Client::with_config(
+        registry_url,
+        registry.client_config(),
+        Some(credentials.clone()),
+    )
+    .map_err(|e| format!("Failed to create registry client: {}", e))?;
 
     client.check_version().map_err(|e| {
         let error_str = format!("{}", e);
         if error_str.contains("Authentication") || error_str.contains("401") {
-            "Authentication failed. Please check your username and password.".to_string()
+            "Authentication failed. Please check your credentials.".to_string()
         } else if error_str.contains("403") || error_str.contains("Forbidden") {
             "Access forbidden. Your credentials may not have the required permissions.".to_string()
         } else {
@@ -609,7 +683,7 @@ pub(crate) fn login_registry(
         .map_err(|e| format!("Failed to initialize credential store: {}", e))?;
 
     store
-        .store(&registry.url, &credentials)
+        .store(registry_url, credentials)
         .map_err(|e| format!("Failed to store credentials: {}", e))?;
 
     Ok(())
@@ -952,21 +1026,14 @@ fn sync_single_registry(
     let cache_path_ref = cache_dir.as_path();
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(registry_url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(registry_url);
 
-    // Build Rex with cache and credentials
+    // Build Rex with cache, credentials and TLS settings
+    let client_config = config::load_client_config(registry_url);
     let mut builder = librex::Rex::builder()
         .registry_url(registry_url)
-        .with_cache(cache_path_ref);
+        .with_cache(cache_path_ref)
+        .with_client_config(client_config.clone());
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -1026,7 +1093,8 @@ fn sync_single_registry(
                 // Each thread gets its own Rex instance
                 let mut builder = librex::Rex::builder()
                     .registry_url(&registry_url_str)
-                    .with_cache(cache_dir_clone.clone());
+                    .with_cache(cache_dir_clone.clone())
+                    .with_client_config(client_config.clone());
 
                 if let Some(ref creds) = credentials_clone {
                     builder = builder.with_credentials(creds.clone());
@@ -1136,6 +1204,333 @@ fn sync_single_registry(
     Ok(stats)
 }
 
+/// Find a configured registry by name, or the default registry
+pub(crate) fn find_registry<'a>(
+    cfg: &'a config::Config,
+    name: Option<&str>,
+) -> Result<&'a RegistryEntry, String> {
+    if let Some(name) = name {
+        return cfg
+            .registries
+            .list
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| format!("Registry '{}' not found", name));
+    }
+
+    let default_name = cfg
+        .registries
+        .default
+        .as_ref()
+        .ok_or_else(|| no_default_registry_error(&cfg.registries.list))?;
+    cfg.registries
+        .list
+        .iter()
+        .find(|r| r.name == *default_name)
+        .ok_or_else(|| format!("Default registry '{}' not found", default_name))
+}
+
+/// Connect to a configured registry, without a cache: reports read what the
+/// registry holds now
+fn connect_uncached(registry: &RegistryEntry) -> Result<librex::Rex, String> {
+    let mut builder = librex::Rex::builder()
+        .registry_url(&registry.url)
+        .with_client_config(registry.client_config());
+
+    if let Some(creds) = config::load_credentials(&registry.url) {
+        builder = builder.with_credentials(creds);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to connect to registry: {}", e))
+}
+
+/// Repository row of the disk usage table
+#[derive(Debug, Tabled)]
+pub struct RepositoryUsageRow {
+    #[tabled(rename = "REPOSITORY")]
+    pub name: String,
+    #[tabled(rename = "TAGS")]
+    pub tags: usize,
+    #[tabled(rename = "UNIQUE")]
+    pub unique: String,
+    #[tabled(rename = "SHARED")]
+    pub shared: String,
+    #[tabled(rename = "TOTAL")]
+    pub total: String,
+    #[tabled(rename = "APPARENT")]
+    pub apparent: String,
+}
+
+impl From<&librex::usage::RepositoryUsage> for RepositoryUsageRow {
+    fn from(usage: &librex::usage::RepositoryUsage) -> Self {
+        use librex::format::format_size;
+
+        Self {
+            name: usage.name.clone(),
+            tags: usage.tags.len(),
+            unique: format_size(usage.unique_bytes),
+            shared: format_size(usage.shared_bytes),
+            total: format_size(usage.total_bytes),
+            apparent: format_size(usage.apparent_bytes),
+        }
+    }
+}
+
+/// Deduplicated storage usage of a registry
+#[derive(Debug, Serialize)]
+pub struct DiskUsageDisplay {
+    /// Registry name
+    pub registry: String,
+    /// Registry URL
+    pub url: String,
+    /// Number of repositories scanned (the report may list fewer with `--top`)
+    pub repository_count: usize,
+    /// Registry-wide numbers and per-repository usage
+    #[serde(flatten)]
+    pub usage: librex::usage::UsageReport,
+}
+
+impl Formattable for DiskUsageDisplay {
+    fn format_pretty(&self) -> String {
+        use librex::format::format_size;
+        use tabled::{Table, settings::Style};
+
+        let usage = &self.usage;
+        let mut output = format!("Storage usage for '{}' ({})\n\n", self.registry, self.url);
+        output.push_str(&format!(
+            "  Stored:    {} in {} blobs\n",
+            format_size(usage.total_bytes),
+            usage.blobs
+        ));
+        output.push_str(&format!(
+            "  Apparent:  {} (sum of tag sizes)\n",
+            format_size(usage.apparent_bytes)
+        ));
+        output.push_str(&format!(
+            "  Saved:     {} by sharing blobs\n",
+            format_size(usage.saved_bytes())
+        ));
+        output.push_str(&format!(
+            "  Shared:    {} used by several repositories\n",
+            format_size(usage.shared_bytes)
+        ));
+
+        if !usage.repositories.is_empty() {
+            let rows: Vec<RepositoryUsageRow> = usage
+                .repositories
+                .iter()
+                .map(RepositoryUsageRow::from)
+                .collect();
+            output.push('\n');
+            output.push_str(&Table::new(&rows).with(Style::empty()).to_string());
+            output.push('\n');
+        }
+        if usage.repositories.len() < self.repository_count {
+            output.push_str(&format!(
+                "\nShowing top {} of {} repositories by unique size\n",
+                usage.repositories.len(),
+                self.repository_count
+            ));
+        }
+        if !usage.skipped.is_empty() {
+            output.push_str(&format!(
+                "\nSkipped {} unreadable repositories or tags:\n",
+                usage.skipped.len()
+            ));
+            for skipped in &usage.skipped {
+                output.push_str(&format!("  {}\n", skipped));
+            }
+        }
+
+        output.trim_end().to_string()
+    }
+}
+
+/// Compute the deduplicated storage usage of a registry
+///
+/// Every tag is read from the registry and each blob is counted once, so
+/// layers shared between tags and repositories are not double-counted.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to the configuration file
+/// * `name` - Registry name (the default registry if `None`)
+/// * `top` - Keep only the N repositories with the largest unique usage
+/// * `on_event` - Called as the scan progresses
+pub(crate) fn disk_usage<F>(
+    config_path: &PathBuf,
+    name: Option<&str>,
+    top: Option<usize>,
+    on_event: F,
+) -> Result<DiskUsageDisplay, String>
+where
+    F: FnMut(&librex::usage::UsageEvent),
+{
+    let cfg = config::Config::load(config_path)?;
+    let registry = find_registry(&cfg, name)?;
+    let rex = connect_uncached(registry)?;
+
+    let mut usage = rex
+        .disk_usage(on_event)
+        .map_err(|e| format!("Failed to compute disk usage: {}", e))?;
+    let repository_count = usage.repositories.len();
+    if let Some(top) = top {
+        usage.repositories.truncate(top);
+    }
+
+    Ok(DiskUsageDisplay {
+        registry: registry.name.clone(),
+        url: registry.url.clone(),
+        repository_count,
+        usage,
+    })
+}
+
+/// Garbage report for a registry, with the deletion outcome if requested
+#[derive(Debug, Serialize)]
+pub struct GcReportDisplay {
+    /// Registry name
+    pub registry: String,
+    /// Registry URL
+    pub url: String,
+    /// Scan counts and findings
+    #[serde(flatten)]
+    pub report: librex::gc::GcReport,
+    /// Deleted and failed manifests, with `--delete`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deletion: Option<librex::gc::GcDeletion>,
+}
+
+impl Formattable for GcReportDisplay {
+    fn format_pretty(&self) -> String {
+        use librex::gc::GcReason;
+
+        let report = &self.report;
+        let mut output = format!(
+            "Garbage report for '{}' ({})\n\nScanned {} manifests in {} repositories\n",
+            self.registry, self.url, report.manifests, report.repositories
+        );
+
+        for (reason, title, detail) in [
+            (
+                GcReason::DanglingReferrer,
+                "Referrers whose subject is gone",
+                "subject",
+            ),
+            (
+                GcReason::MissingChildren,
+                "Indexes with missing children",
+                "missing",
+            ),
+            (GcReason::Untagged, "Untagged manifests", "found via"),
+        ] {
+            let findings: Vec<_> = report.by_reason(reason).collect();
+            if findings.is_empty() {
+                continue;
+            }
+            output.push_str(&format!("\n{} ({}):\n", title, findings.len()));
+            for finding in findings {
+                output.push_str(&format!("  {}@{}", finding.repository, finding.digest));
+                if !finding.tags.is_empty() {
+                    output.push_str(&format!(" (tags: {})", finding.tags.join(", ")));
+                }
+                if finding.live {
+                    output.push_str(" [kept: reachable from a tag]");
+                }
+                output.push('\n');
+                let linked = match reason {
+                    GcReason::Untagged => &finding.found_via,
+                    _ => &finding.missing,
+                };
+                for digest in linked {
+                    output.push_str(&format!("    {}: {}\n", detail, digest));
+                }
+            }
+        }
+
+        if report.findings.is_empty() {
+            output.push_str("\nNothing to clean up\n");
+        }
+        if let Some(ref deletion) = self.deletion {
+            output.push_str(&format!("\nDeleted {} manifests\n", deletion.deleted.len()));
+            if !deletion.failed.is_empty() {
+                output.push_str(&format!("Failed to delete {}:\n", deletion.failed.len()));
+                for failed in &deletion.failed {
+                    output.push_str(&format!("  {}\n", failed));
+                }
+            }
+        }
+        if !report.skipped.is_empty() {
+            output.push_str(&format!(
+                "\nSkipped {} unreadable repositories or manifests:\n",
+                report.skipped.len()
+            ));
+            for skipped in &report.skipped {
+                output.push_str(&format!("  {}\n", skipped));
+            }
+        }
+
+        output.trim_end().to_string()
+    }
+}
+
+/// Find untagged manifests and broken links in a registry
+///
+/// # Arguments
+///
+/// * `config_path` - Path to the configuration file
+/// * `name` - Registry name (the default registry if `None`)
+/// * `on_event` - Called as the scan progresses
+pub(crate) fn gc_report<F>(
+    config_path: &PathBuf,
+    name: Option<&str>,
+    on_event: F,
+) -> Result<GcReportDisplay, String>
+where
+    F: FnMut(&librex::gc::GcEvent),
+{
+    let cfg = config::Config::load(config_path)?;
+    let registry = find_registry(&cfg, name)?;
+    let rex = connect_uncached(registry)?;
+
+    let report = rex
+        .gc_report(on_event)
+        .map_err(|e| format!("Failed to scan registry: {}", e))?;
+
+    Ok(GcReportDisplay {
+        registry: registry.name.clone(),
+        url: registry.url.clone(),
+        report,
+        deletion: None,
+    })
+}
+
+/// Delete the findings of a garbage report by digest
+///
+/// Findings a regular tag reaches are left alone.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to the configuration file
+/// * `display` - Report made by [`gc_report`]
+/// * `on_event` - Called for each deletion
+pub(crate) fn gc_delete<F>(
+    config_path: &PathBuf,
+    display: &GcReportDisplay,
+    on_event: F,
+) -> Result<librex::gc::GcDeletion, String>
+where
+    F: FnMut(&librex::gc::GcEvent),
+{
+    let cfg = config::Config::load(config_path)?;
+    let registry = find_registry(&cfg, Some(&display.registry))?;
+    let mut rex = connect_uncached(registry)?;
+
+    Ok(rex.gc_delete(&display.report, on_event))
+}
+
 #[cfg(test)]
 #[path = "tests.rs"]
 mod tests;
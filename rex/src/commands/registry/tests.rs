@@ -1,4 +1,5 @@
 use super::*;
+use crate::test_support::sha256;
 
 // Tests for registry init command
 #[test]
@@ -28,6 +29,7 @@ fn test_init_registry_to_existing_registries() {
         name: "existing".to_string(),
         url: "http://example.com".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -49,6 +51,7 @@ fn test_init_registry_duplicate_name_fails() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -112,6 +115,7 @@ fn test_init_second_registry_does_not_change_default() {
         name: "first".to_string(),
         url: "http://example.com".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -148,11 +152,13 @@ fn test_list_registries_with_entries() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.registries.list.push(RegistryEntry {
         name: "dockerhub".to_string(),
         url: "https://registry-1.docker.io".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -277,11 +283,13 @@ fn test_remove_registry_existing() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.registries.list.push(RegistryEntry {
         name: "dockerhub".to_string(),
         url: "https://registry-1.docker.io".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -303,6 +311,7 @@ fn test_remove_registry_nonexistent() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -322,6 +331,7 @@ fn test_remove_last_registry() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -344,11 +354,13 @@ fn test_remove_default_registry_clears_default() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.registries.list.push(RegistryEntry {
         name: "dockerhub".to_string(),
         url: "https://registry-1.docker.io".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -385,11 +397,13 @@ fn test_use_registry_existing() {
         name: "dockerhub".to_string(),
         url: "https://registry-1.docker.io".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.registries.list.push(RegistryEntry {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -410,6 +424,7 @@ fn test_use_registry_nonexistent() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -428,6 +443,7 @@ fn test_use_registry_when_none_set() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -462,6 +478,7 @@ fn test_show_registry_existing() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -482,6 +499,7 @@ fn test_show_registry_nonexistent() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -501,6 +519,7 @@ fn test_show_registry_with_default_marker() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
     config.save(&config_path).unwrap();
 
@@ -566,6 +585,7 @@ fn test_registry_check_result_creation() {
         auth_required: false,
         authenticated: false,
         api_version: Some("registry/2.0".to_string()),
+        capabilities: None,
         error: None,
     };
 
@@ -586,6 +606,7 @@ fn test_registry_check_result_with_error() {
         auth_required: false,
         authenticated: false,
         api_version: None,
+        capabilities: None,
         error: Some("Connection refused".to_string()),
     };
 
@@ -603,6 +624,7 @@ fn test_registry_check_result_pretty_format_online() {
         auth_required: false,
         authenticated: false,
         api_version: Some("registry/2.0".to_string()),
+        capabilities: None,
         error: None,
     };
 
@@ -623,6 +645,7 @@ fn test_registry_check_result_pretty_format_offline() {
         auth_required: false,
         authenticated: false,
         api_version: None,
+        capabilities: None,
         error: Some("Connection refused".to_string()),
     };
 
@@ -641,6 +664,7 @@ fn test_registry_check_result_pretty_format_auth_required() {
         auth_required: true,
         authenticated: false,
         api_version: None,
+        capabilities: None,
         error: Some("Authentication required".to_string()),
     };
 
@@ -658,6 +682,7 @@ fn test_registry_check_result_pretty_format_authenticated() {
         auth_required: false,
         authenticated: true,
         api_version: Some("registry/2.0".to_string()),
+        capabilities: None,
         error: None,
     };
 
@@ -674,6 +699,7 @@ fn test_registry_check_result_pretty_format_auth_required_not_authenticated() {
         auth_required: true,
         authenticated: false,
         api_version: Some("registry/2.0".to_string()),
+        capabilities: None,
         error: None,
     };
 
@@ -690,6 +716,7 @@ fn test_registry_check_result_serialization() {
         auth_required: false,
         authenticated: false,
         api_version: Some("registry/2.0".to_string()),
+        capabilities: None,
         error: None,
     };
 
@@ -700,3 +727,366 @@ fn test_registry_check_result_serialization() {
     assert!(json.contains("\"authenticated\":false"));
     assert!(json.contains("\"api_version\":\"registry/2.0\""));
 }
+
+// Tests for registry login --token
+#[test]
+fn test_login_registry_with_token_registry_not_found() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let config = config::Config::default();
+    config.save(&config_path).unwrap();
+
+    let result = login_registry_with_token(&config_path, "missing", Some("token"));
+    assert!(result.is_err());
+    assert!(result.unwrap_err().contains("not found"));
+}
+
+#[test]
+fn test_login_registry_with_token_empty_token() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let mut config = config::Config::default();
+    config.registries.list.push(RegistryEntry {
+        name: "local".to_string(),
+        url: "http://localhost:5000".to_string(),
+        dockerhub_compat: false,
+        tls: Default::default(),
+    });
+    config.save(&config_path).unwrap();
+
+    let result = login_registry_with_token(&config_path, "local", Some("  "));
+    assert_eq!(result.unwrap_err(), "Token cannot be empty");
+}
+
+fn check_config_with(url: &str) -> (tempfile::TempDir, PathBuf) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let mut config = config::Config::default();
+    config.registries.list.push(RegistryEntry {
+        name: "zot".to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    });
+    config.save(&config_path).unwrap();
+
+    (temp_dir, config_path)
+}
+
+#[test]
+fn test_check_registry_detects_zot_capabilities() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(200)
+        .with_body(
+            r#"{"distSpecVersion":"1.1.0","extensions":[{"name":"_zot","endpoints":["/v2/_zot/ext/search","/v2/_zot/ext/userprefs"]}]}"#,
+        )
+        .create();
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+
+    let ctx = crate::context::AppContext::build(
+        crate::format::ColorChoice::Never,
+        crate::context::VerbosityLevel::Normal,
+    );
+    let result = check_registry(&ctx, &config_path, "zot");
+
+    assert!(result.online);
+    let capabilities = result.capabilities.as_ref().unwrap();
+    assert!(capabilities.is_zot());
+    assert!(capabilities.search);
+    assert!(!capabilities.mgmt);
+    assert!(
+        result
+            .format_pretty()
+            .contains("Extensions: search, cve, userprefs (Zot)")
+    );
+}
+
+#[test]
+fn test_check_registry_plain_distribution() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(404)
+        .create();
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+
+    let ctx = crate::context::AppContext::build(
+        crate::format::ColorChoice::Never,
+        crate::context::VerbosityLevel::Normal,
+    );
+    let result = check_registry(&ctx, &config_path, "zot");
+
+    assert!(result.online);
+    assert_eq!(
+        result.capabilities,
+        Some(librex::RegistryCapabilities::default())
+    );
+    assert!(
+        result
+            .format_pretty()
+            .contains("Extensions: none (OCI Distribution API only)")
+    );
+}
+
+#[test]
+fn test_check_registry_discovery_failure_stays_online() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(403)
+        .create();
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+
+    let ctx = crate::context::AppContext::build(
+        crate::format::ColorChoice::Never,
+        crate::context::VerbosityLevel::Normal,
+    );
+    let result = check_registry(&ctx, &config_path, "zot");
+
+    assert!(result.online);
+    assert!(result.capabilities.is_none());
+    assert!(!result.format_pretty().contains("Extensions"));
+}
+
+/// Serves a registry where `app` and `web` share a 100 byte base layer.
+fn serve_usage_registry(server: &mut mockito::Server) {
+    let manifest = |config: char, layer: char, size: u64| {
+        format!(
+            r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","config":{{"mediaType":"application/vnd.oci.image.config.v1+json","digest":"sha256:{}","size":10}},"layers":[{{"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"sha256:{}","size":100}},{{"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"sha256:{}","size":{}}}]}}"#,
+            config.to_string().repeat(64),
+            "b".repeat(64),
+            layer.to_string().repeat(64),
+            size
+        )
+    };
+    server
+        .mock("GET", "/v2/_catalog")
+        .with_status(200)
+        .with_body(r#"{"repositories":["app","web"]}"#)
+        .create();
+    for (repository, tags) in [("app", r#"["1.0","1.1"]"#), ("web", r#"["latest"]"#)] {
+        server
+            .mock("GET", format!("/v2/{}/tags/list", repository).as_str())
+            .with_status(200)
+            .with_body(format!(r#"{{"name":"{}","tags":{}}}"#, repository, tags))
+            .create();
+    }
+    for (path, body) in [
+        ("/v2/app/manifests/1.0", manifest('c', '1', 20)),
+        ("/v2/app/manifests/1.1", manifest('c', '2', 30)),
+        ("/v2/web/manifests/latest", manifest('d', '3', 5000)),
+    ] {
+        server
+            .mock("GET", path)
+            .with_status(200)
+            .with_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+            .with_body(body)
+            .create();
+    }
+}
+
+#[test]
+fn test_disk_usage_counts_shared_layers_once() {
+    let mut server = mockito::Server::new();
+    serve_usage_registry(&mut server);
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+    let mut scanned = Vec::new();
+
+    let result = disk_usage(&config_path, Some("zot"), None, |event| {
+        if let librex::usage::UsageEvent::Repository { name, .. } = event {
+            scanned.push(name.clone());
+        }
+    })
+    .unwrap();
+
+    assert_eq!(scanned, vec!["app", "web"]);
+    assert_eq!(result.repository_count, 2);
+    let usage = &result.usage;
+    // Sorted by unique bytes: web has the large layer
+    assert_eq!(usage.repositories[0].name, "web");
+    let app = &usage.repositories[1];
+    assert_eq!(app.tags.len(), 2);
+    // The config and base layer are shared by both tags, the base layer
+    // with web as well
+    assert_eq!(app.shared_bytes, 100);
+    assert_eq!(app.tags[0].unique_bytes, app.tags[0].total_bytes - 110);
+    assert_eq!(app.apparent_bytes - app.total_bytes, 110);
+    assert_eq!(usage.shared_bytes, 100);
+    assert_eq!(usage.saved_bytes(), 210);
+    assert!(usage.skipped.is_empty());
+
+    let pretty = result.format_pretty();
+    assert!(pretty.contains("Storage usage for 'zot'"));
+    assert!(pretty.contains("REPOSITORY"));
+    assert!(!pretty.contains("Showing top"));
+}
+
+#[test]
+fn test_disk_usage_top_and_json() {
+    let mut server = mockito::Server::new();
+    serve_usage_registry(&mut server);
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+
+    let result = disk_usage(&config_path, None, Some(1), |_| {});
+    // No default registry configured
+    assert!(
+        result
+            .unwrap_err()
+            .contains("No default registry configured")
+    );
+
+    let result = disk_usage(&config_path, Some("zot"), Some(1), |_| {}).unwrap();
+
+    assert_eq!(result.usage.repositories.len(), 1);
+    assert!(
+        result
+            .format_pretty()
+            .contains("Showing top 1 of 2 repositories")
+    );
+    let json: serde_json::Value = serde_json::from_str(
+        &crate::format::format_output(&result, crate::format::OutputFormat::Json).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["repository_count"], 2);
+    assert_eq!(json["shared_bytes"], 100);
+    assert_eq!(json["repositories"][0]["name"], "web");
+    assert_eq!(json["repositories"][0]["tags"][0]["tag"], "latest");
+}
+
+/// Serves a registry where `app:1.0` is an index with a missing child and a
+/// cosign signature refers to a deleted image; returns the two digests.
+fn serve_gc_registry(server: &mut mockito::Server) -> (String, String) {
+    let index = format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[{{"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"sha256:{}","size":100}}]}}"#,
+        "a".repeat(64)
+    );
+    let signature = format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","config":{{"mediaType":"application/vnd.oci.image.config.v1+json","digest":"sha256:{}","size":10}},"layers":[]}}"#,
+        "c".repeat(64)
+    );
+    let signature_tag = format!("sha256-{}.sig", "d".repeat(64));
+
+    server
+        .mock("GET", "/v2/_catalog")
+        .with_status(200)
+        .with_body(r#"{"repositories":["app"]}"#)
+        .create();
+    server
+        .mock("GET", "/v2/app/tags/list")
+        .with_status(200)
+        .with_body(format!(
+            r#"{{"name":"app","tags":["1.0","{}"]}}"#,
+            signature_tag
+        ))
+        .create();
+    for (reference, body, media_type) in [
+        ("1.0", &index, "application/vnd.oci.image.index.v1+json"),
+        (
+            signature_tag.as_str(),
+            &signature,
+            "application/vnd.oci.image.manifest.v1+json",
+        ),
+    ] {
+        server
+            .mock("GET", format!("/v2/app/manifests/{}", reference).as_str())
+            .with_status(200)
+            .with_header("Content-Type", media_type)
+            .with_body(body)
+            .create();
+    }
+    for missing in ["a", "d"] {
+        server
+            .mock(
+                "GET",
+                format!("/v2/app/manifests/sha256:{}", missing.repeat(64)).as_str(),
+            )
+            .with_status(404)
+            .create();
+    }
+    server
+        .mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/v2/app/referrers/".to_string()),
+        )
+        .with_status(200)
+        .with_body(r#"{"schemaVersion":2,"manifests":[]}"#)
+        .create();
+
+    (sha256(index.as_bytes()), sha256(signature.as_bytes()))
+}
+
+#[test]
+fn test_gc_report_lists_broken_manifests() {
+    let mut server = mockito::Server::new();
+    let (index, signature) = serve_gc_registry(&mut server);
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+
+    let display = gc_report(&config_path, Some("zot"), |_| {}).unwrap();
+
+    assert_eq!(display.report.manifests, 2);
+    assert_eq!(display.report.findings.len(), 2);
+    // Referrers are listed (and deleted) first
+    assert_eq!(display.report.findings[0].digest, signature);
+    assert_eq!(display.report.findings[1].digest, index);
+    assert!(display.deletion.is_none());
+
+    let pretty = display.format_pretty();
+    assert!(pretty.contains("Referrers whose subject is gone (1):"));
+    assert!(pretty.contains("Indexes with missing children (1):"));
+    assert!(pretty.contains(&format!(
+        "app@{} (tags: 1.0) [kept: reachable from a tag]",
+        index
+    )));
+    assert!(pretty.contains(&format!("missing: sha256:{}", "a".repeat(64))));
+    assert!(!pretty.contains("Untagged manifests"));
+
+    let json: serde_json::Value = serde_json::from_str(
+        &crate::format::format_output(&display, crate::format::OutputFormat::Json).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(json["findings"][0]["reason"], "dangling_referrer");
+    assert_eq!(json["findings"][1]["reason"], "missing_children");
+    assert_eq!(json["findings"][1]["live"], true);
+    assert!(json.get("deletion").is_none());
+}
+
+#[test]
+fn test_gc_delete_removes_findings_by_digest() {
+    let mut server = mockito::Server::new();
+    let (index, signature) = serve_gc_registry(&mut server);
+    let delete = |server: &mut mockito::Server, digest: &str| {
+        server
+            .mock("DELETE", format!("/v2/app/manifests/{}", digest).as_str())
+            .with_status(202)
+            .create()
+    };
+    let signature_delete = delete(&mut server, &signature);
+    let index_delete = delete(&mut server, &index).expect(0);
+    let (_temp_dir, config_path) = check_config_with(&server.url());
+    let mut display = gc_report(&config_path, Some("zot"), |_| {}).unwrap();
+
+    let deletion = gc_delete(&config_path, &display, |_| {}).unwrap();
+
+    signature_delete.assert();
+    assert_eq!(deletion.deleted, vec![format!("app@{}", signature)]);
+    assert!(deletion.failed.is_empty());
+    display.deletion = Some(deletion);
+    assert!(display.format_pretty().contains("Deleted 1 manifests"));
+
+    // The index is tagged: its missing child is reported, the tag survives
+    index_delete.assert();
+    let after = gc_report(&config_path, Some("zot"), |_| {}).unwrap();
+    assert!(
+        after
+            .report
+            .by_reason(librex::gc::GcReason::MissingChildren)
+            .any(|finding| finding.digest == index && finding.tags == ["1.0"])
+    );
+}
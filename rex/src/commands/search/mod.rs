@@ -1,7 +1,6 @@
 use crate::config;
 use crate::context::VerbosityLevel;
 use crate::format::{self, Formattable};
-use librex::auth::CredentialStore;
 use serde::Serialize;
 
 pub mod handlers;
@@ -35,6 +34,9 @@ pub struct TagResults {
 #[derive(Debug, Serialize)]
 pub struct ImageResult {
     pub name: String,
+    /// Server-side metadata (Zot search extension only)
+    #[serde(flatten)]
+    pub details: Option<ResultDetails>,
 }
 
 /// Single tag search result
@@ -43,6 +45,69 @@ pub struct TagResult {
     pub image: String,
     pub tag: String,
     pub reference: String,
+    /// Server-side metadata (Zot search extension only)
+    #[serde(flatten)]
+    pub details: Option<ResultDetails>,
+}
+
+/// Metadata Zot's search extension returns with each result
+#[derive(Debug, Default, Serialize)]
+pub struct ResultDetails {
+    /// Size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Last update time (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<String>,
+    /// Vendor(s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vendor: Option<String>,
+}
+
+impl ResultDetails {
+    /// Formats the details as a parenthesized suffix, empty if there are none
+    fn suffix(details: &Option<ResultDetails>) -> String {
+        let Some(details) = details else {
+            return String::new();
+        };
+
+        let mut parts = Vec::new();
+        if let Some(size) = details.size {
+            parts.push(librex::format::format_size(size));
+        }
+        if let Some(last_updated) = &details.last_updated {
+            parts.push(format!("updated {}", last_updated));
+        }
+        if let Some(vendor) = &details.vendor {
+            parts.push(vendor.clone());
+        }
+
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", parts.join(", "))
+        }
+    }
+}
+
+impl From<&librex::zot::RepoSummary> for ResultDetails {
+    fn from(repo: &librex::zot::RepoSummary) -> Self {
+        Self {
+            size: repo.size,
+            last_updated: repo.last_updated.clone(),
+            vendor: (!repo.vendors.is_empty()).then(|| repo.vendors.join(", ")),
+        }
+    }
+}
+
+impl From<&librex::zot::ImageSummary> for ResultDetails {
+    fn from(image: &librex::zot::ImageSummary) -> Self {
+        Self {
+            size: image.size,
+            last_updated: image.last_updated.clone(),
+            vendor: image.vendor.clone().filter(|v| !v.is_empty()),
+        }
+    }
 }
 
 impl Formattable for SearchResults {
@@ -53,7 +118,11 @@ impl Formattable for SearchResults {
         if !self.images.results.is_empty() {
             output.push_str("Images:\n");
             for result in &self.images.results {
-                output.push_str(&format!("  {}\n", result.name));
+                output.push_str(&format!(
+                    "  {}{}\n",
+                    result.name,
+                    ResultDetails::suffix(&result.details)
+                ));
             }
         }
 
@@ -64,7 +133,11 @@ impl Formattable for SearchResults {
             }
             output.push_str("Tags:\n");
             for result in &self.tags.results {
-                output.push_str(&format!("  {}\n", result.reference));
+                output.push_str(&format!(
+                    "  {}{}\n",
+                    result.reference,
+                    ResultDetails::suffix(&result.details)
+                ));
             }
         }
 
@@ -107,21 +180,13 @@ pub fn search(
     let cache_dir = config::get_registry_cache_dir(&registry.url)?;
 
     // Load credentials if available
-    let creds_path = config::get_credentials_path();
-    let credentials = if creds_path.exists() {
-        if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-            store.get(&registry.url).ok().flatten()
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let credentials = config::load_credentials(&registry.url);
 
     // Build Rex client
     let mut builder = librex::Rex::builder()
         .registry_url(&registry.url)
-        .with_cache(cache_dir.as_path());
+        .with_cache(cache_dir.as_path())
+        .with_client_config(registry.client_config());
 
     if let Some(creds) = credentials {
         builder = builder.with_credentials(creds);
@@ -133,6 +198,24 @@ pub fn search(
 
     let formatter = crate::format::create_formatter(ctx);
 
+    // Zot answers the query server-side, with sizes and dates; other
+    // registries use the fuzzy path
+    let spinner = formatter.spinner("Searching...");
+    let found = rex.search_summaries(query, limit);
+    spinner.finish_and_clear();
+    match found {
+        Ok(Some(found)) => {
+            format::print(
+                ctx,
+                VerbosityLevel::Verbose,
+                "Using the registry's search extension",
+            );
+            return Ok(zot_results(query, found, limit));
+        }
+        Ok(None) => {}
+        Err(e) => return Err(format!("Registry search failed: {}", e)),
+    }
+
     // Search images (repositories)
     let spinner = formatter.spinner("Searching repositories...");
     let image_results_res = rex.search_repositories(query);
@@ -176,7 +259,10 @@ pub fn search(
         total_results: image_results.len(),
         results: image_results
             .into_iter()
-            .map(|result| ImageResult { name: result.value })
+            .map(|result| ImageResult {
+                name: result.value,
+                details: None,
+            })
             .collect(),
     };
 
@@ -198,6 +284,7 @@ pub fn search(
                     reference: result.value,
                     image,
                     tag,
+                    details: None,
                 }
             })
             .collect(),
@@ -209,3 +296,46 @@ pub fn search(
         tags,
     })
 }
+
+/// Convert results of Zot's search extension, keeping the server's order
+fn zot_results(
+    query: &str,
+    found: librex::zot::GlobalSearchResult,
+    limit: Option<usize>,
+) -> SearchResults {
+    let limit = limit.unwrap_or(usize::MAX);
+
+    let images: Vec<ImageResult> = found
+        .repos
+        .iter()
+        .take(limit)
+        .map(|repo| ImageResult {
+            name: repo.name.clone(),
+            details: Some(ResultDetails::from(repo)),
+        })
+        .collect();
+
+    let tags: Vec<TagResult> = found
+        .images
+        .iter()
+        .take(limit)
+        .map(|image| TagResult {
+            image: image.repo_name.clone(),
+            tag: image.tag.clone(),
+            reference: image.reference(),
+            details: Some(ResultDetails::from(image)),
+        })
+        .collect();
+
+    SearchResults {
+        query: query.to_string(),
+        images: ImageResults {
+            total_results: images.len(),
+            results: images,
+        },
+        tags: TagResults {
+            total_results: tags.len(),
+            results: tags,
+        },
+    }
+}
@@ -8,6 +8,7 @@ fn test_search_results_serialization() {
             total_results: 1,
             results: vec![ImageResult {
                 name: "alpine".to_string(),
+                details: None,
             }],
         },
         tags: TagResults {
@@ -16,6 +17,7 @@ fn test_search_results_serialization() {
                 image: "alpine".to_string(),
                 tag: "latest".to_string(),
                 reference: "alpine:latest".to_string(),
+                details: None,
             }],
         },
     };
@@ -30,6 +32,7 @@ fn test_search_results_serialization() {
 fn test_image_result_creation() {
     let result = ImageResult {
         name: "nginx".to_string(),
+        details: None,
     };
     assert_eq!(result.name, "nginx");
 }
@@ -40,6 +43,7 @@ fn test_tag_result_creation() {
         image: "nginx".to_string(),
         tag: "1.21".to_string(),
         reference: "nginx:1.21".to_string(),
+        details: None,
     };
     assert_eq!(result.image, "nginx");
     assert_eq!(result.tag, "1.21");
@@ -55,9 +59,11 @@ fn test_search_results_format_pretty_with_both() {
             results: vec![
                 ImageResult {
                     name: "alpine".to_string(),
+                    details: None,
                 },
                 ImageResult {
                     name: "nginx".to_string(),
+                    details: None,
                 },
             ],
         },
@@ -68,11 +74,13 @@ fn test_search_results_format_pretty_with_both() {
                     image: "alpine".to_string(),
                     tag: "latest".to_string(),
                     reference: "alpine:latest".to_string(),
+                    details: None,
                 },
                 TagResult {
                     image: "nginx".to_string(),
                     tag: "1.21".to_string(),
                     reference: "nginx:1.21".to_string(),
+                    details: None,
                 },
             ],
         },
@@ -95,6 +103,7 @@ fn test_search_results_format_pretty_images_only() {
             total_results: 1,
             results: vec![ImageResult {
                 name: "alpine".to_string(),
+                details: None,
             }],
         },
         tags: TagResults {
@@ -123,6 +132,7 @@ fn test_search_results_format_pretty_tags_only() {
                 image: "alpine".to_string(),
                 tag: "latest".to_string(),
                 reference: "alpine:latest".to_string(),
+                details: None,
             }],
         },
     };
@@ -160,9 +170,11 @@ fn test_image_results_structure() {
         results: vec![
             ImageResult {
                 name: "image1".to_string(),
+                details: None,
             },
             ImageResult {
                 name: "image2".to_string(),
+                details: None,
             },
         ],
     };
@@ -182,11 +194,13 @@ fn test_tag_results_structure() {
                 image: "alpine".to_string(),
                 tag: "3.14".to_string(),
                 reference: "alpine:3.14".to_string(),
+                details: None,
             },
             TagResult {
                 image: "alpine".to_string(),
                 tag: "latest".to_string(),
                 reference: "alpine:latest".to_string(),
+                details: None,
             },
         ],
     };
@@ -196,3 +210,187 @@ fn test_tag_results_structure() {
     assert_eq!(results.results[0].reference, "alpine:3.14");
     assert_eq!(results.results[1].reference, "alpine:latest");
 }
+
+fn search_context(url: &str) -> crate::context::AppContext {
+    let mut ctx = crate::context::AppContext::build(
+        crate::format::ColorChoice::Never,
+        crate::context::VerbosityLevel::Normal,
+    );
+    ctx.config.registries.list = vec![crate::config::RegistryEntry {
+        name: "zot".to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    }];
+    ctx.config.registries.default = Some("zot".to_string());
+    ctx
+}
+
+/// Catalog of the fallback tests; both serve the same one, since the
+/// catalog is cached per registry URL and mockito reuses ports
+const FALLBACK_CATALOG: &str = r#"{"repositories":["zfallback/alpine","zfallback/nginx"]}"#;
+
+const ZOT_DISCOVERY: &str = r#"{"distSpecVersion":"1.1.0","extensions":[{"name":"_zot","endpoints":["/v2/_zot/ext/search"]}]}"#;
+
+#[test]
+fn test_search_uses_zot_search_extension() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(200)
+        .with_body(ZOT_DISCOVERY)
+        .create();
+    // The limit is sent to Zot rather than applied afterwards
+    let global_mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Regex(
+            "GlobalSearch.*requestedPage%3A\\+%7B\\+limit%3A\\+1%2C".to_string(),
+        ))
+        .with_status(200)
+        .with_body(
+            r#"{"data":{"GlobalSearch":{"Images":[],"Repos":[
+                {"Name":"zsearch/alpine","Size":"3623807","LastUpdated":"2024-05-01T10:00:00Z","Vendors":["Alpine Linux"]}
+            ]}}}"#,
+        )
+        .create();
+    let images_mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Regex(
+            "ImageList.*requestedPage%3A\\+%7B\\+limit%3A\\+1%2C".to_string(),
+        ))
+        .with_status(200)
+        .with_body(
+            r#"{"data":{"ImageList":{"Results":[
+                {"RepoName":"zsearch/alpine","Tag":"3.20","Digest":"sha256:aaaa","Size":"3623807"}
+            ]}}}"#,
+        )
+        .expect(1)
+        .create();
+    // The fuzzy path must not be used
+    let catalog_mock = server
+        .mock("GET", "/v2/_catalog")
+        .match_query(mockito::Matcher::Any)
+        .expect(0)
+        .create();
+
+    let ctx = search_context(&server.url());
+    let results = search(&ctx, "alp", Some(1)).unwrap();
+
+    global_mock.assert();
+    images_mock.assert();
+    catalog_mock.assert();
+    assert_eq!(results.images.total_results, 1);
+    let repo = &results.images.results[0];
+    assert_eq!(repo.name, "zsearch/alpine");
+    let details = repo.details.as_ref().unwrap();
+    assert_eq!(details.size, Some(3623807));
+    assert_eq!(details.vendor.as_deref(), Some("Alpine Linux"));
+
+    assert_eq!(results.tags.total_results, 1);
+    assert_eq!(results.tags.results[0].reference, "zsearch/alpine:3.20");
+
+    let output = results.format_pretty();
+    let expected = format!(
+        "zsearch/alpine  ({}, updated 2024-05-01T10:00:00Z, Alpine Linux)",
+        librex::format::format_size(3623807)
+    );
+    assert!(output.contains(&expected));
+
+    let json = serde_json::to_value(&results).unwrap();
+    assert_eq!(json["images"]["results"][0]["size"], 3623807);
+    assert!(json["tags"]["results"][0].get("vendor").is_none());
+}
+
+#[test]
+fn test_search_falls_back_without_search_extension() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(404)
+        .create();
+    let search_mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Any)
+        .expect(0)
+        .create();
+    let _catalog_mock = server
+        .mock("GET", "/v2/_catalog")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(FALLBACK_CATALOG)
+        .create();
+
+    let ctx = search_context(&server.url());
+    let results = search(&ctx, "alpine", None).unwrap();
+
+    search_mock.assert();
+    let names: Vec<&str> = results
+        .images
+        .results
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert!(names.contains(&"zfallback/alpine"));
+    assert!(!names.contains(&"zfallback/nginx"));
+    assert!(results.images.results[0].details.is_none());
+}
+
+#[test]
+fn test_search_falls_back_once_when_search_endpoint_is_missing() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(200)
+        .with_body(ZOT_DISCOVERY)
+        .create();
+    // Advertised but not served: asked once, then the catalog is used
+    let search_mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .expect(1)
+        .create();
+    let _catalog_mock = server
+        .mock("GET", "/v2/_catalog")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(FALLBACK_CATALOG)
+        .create();
+
+    let ctx = search_context(&server.url());
+    let results = search(&ctx, "alpine", None).unwrap();
+
+    search_mock.assert();
+    assert_eq!(results.images.results[0].name, "zfallback/alpine");
+    assert!(results.images.results[0].details.is_none());
+}
+
+#[test]
+fn test_search_reports_search_extension_errors() {
+    let mut server = mockito::Server::new();
+    let _v2_mock = server.mock("GET", "/v2/").with_status(200).create();
+    let _discover_mock = server
+        .mock("GET", "/v2/_oci/ext/discover")
+        .with_status(200)
+        .with_body(ZOT_DISCOVERY)
+        .create();
+    let _search_mock = server
+        .mock("GET", "/v2/_zot/ext/search")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"errors":[{"message":"query too complex"}],"data":null}"#)
+        .create();
+    let catalog_mock = server
+        .mock("GET", "/v2/_catalog")
+        .match_query(mockito::Matcher::Any)
+        .expect(0)
+        .create();
+
+    let ctx = search_context(&server.url());
+    let error = search(&ctx, "alpine", None).unwrap_err();
+
+    catalog_mock.assert();
+    assert!(error.contains("query too complex"));
+}
@@ -128,7 +128,7 @@ pub struct RegistriesConfig {
 }
 
 /// A single registry entry
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct RegistryEntry {
     /// Registry name
     pub name: String,
@@ -138,6 +138,82 @@ pub struct RegistryEntry {
     /// Default: false (works with Zot, GHCR, and most registries)
     #[serde(default)]
     pub dockerhub_compat: bool,
+    /// TLS settings for this registry
+    #[serde(default, skip_serializing_if = "RegistryTls::is_empty")]
+    pub tls: RegistryTls,
+}
+
+/// TLS settings for a registry entry
+///
+/// Stored as a `[registries.list.tls]` table:
+///
+/// ```toml
+/// [[registries.list]]
+/// name = "internal"
+/// url = "https://zot.internal:5000"
+///
+/// [registries.list.tls]
+/// ca_cert = "~/.config/rex/certs/internal-ca.pem"
+/// client_cert = "~/.config/rex/certs/client.pem"
+/// client_key = "~/.config/rex/certs/client-key.pem"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct RegistryTls {
+    /// Extra root CA certificates (PEM file or directory of PEM files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<String>,
+    /// Client certificate for mutual TLS (may also contain the key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+    /// Client private key for mutual TLS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+    /// Skip server certificate verification (insecure)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure_skip_verify: bool,
+}
+
+impl RegistryTls {
+    /// Returns true if no TLS settings are configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl RegistryEntry {
+    /// Build the librex client configuration for this registry
+    ///
+    /// Paths starting with `~/` are expanded to the home directory. Transient
+    /// failures such as rate limits are retried with the default retry policy.
+    pub fn client_config(&self) -> librex::client::ClientConfig {
+        let mut config = librex::client::ClientConfig::new()
+            .with_retry_policy(librex::client::RetryPolicy::default())
+            .with_dockerhub_compat(self.dockerhub_compat)
+            .with_insecure_skip_verify(self.tls.insecure_skip_verify);
+
+        if let Some(ref ca_cert) = self.tls.ca_cert {
+            config = config.with_ca_cert(expand_home(ca_cert));
+        }
+        if let Some(ref client_cert) = self.tls.client_cert {
+            config = config.with_client_cert(
+                expand_home(client_cert),
+                self.tls.client_key.as_deref().map(expand_home),
+            );
+        } else if let Some(ref client_key) = self.tls.client_key {
+            // Let librex report the missing certificate
+            config.client_key = Some(expand_home(client_key));
+        }
+
+        config
+    }
+}
+
+/// Expand a leading `~/` to the user's home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
 }
 
 impl Config {
@@ -325,6 +401,99 @@ pub fn get_credentials_path() -> PathBuf {
     }
 }
 
+/// Get the Docker config path
+///
+/// Honors `$DOCKER_CONFIG` (a directory), defaulting to `~/.docker/config.json`.
+pub fn get_docker_config_path() -> PathBuf {
+    if let Ok(docker_config) = env::var("DOCKER_CONFIG") {
+        return PathBuf::from(docker_config).join("config.json");
+    }
+
+    if let Some(home_dir) = dirs::home_dir() {
+        home_dir.join(".docker").join("config.json")
+    } else {
+        PathBuf::from("config.json")
+    }
+}
+
+/// Get the Podman auth file path
+///
+/// Honors `$REGISTRY_AUTH_FILE`, then `$XDG_RUNTIME_DIR/containers/auth.json`
+/// if it exists, defaulting to `~/.config/containers/auth.json`.
+pub fn get_podman_auth_path() -> PathBuf {
+    if let Ok(auth_file) = env::var("REGISTRY_AUTH_FILE") {
+        return PathBuf::from(auth_file);
+    }
+
+    if let Ok(runtime_dir) = env::var("XDG_RUNTIME_DIR") {
+        let path = PathBuf::from(runtime_dir)
+            .join("containers")
+            .join("auth.json");
+        if path.exists() {
+            return path;
+        }
+    }
+
+    if let Some(config_dir) = dirs::config_dir() {
+        config_dir.join("containers").join("auth.json")
+    } else {
+        PathBuf::from("auth.json")
+    }
+}
+
+/// Build the credential store chain
+///
+/// Credentials are looked up in rex's own credentials file first, then in the
+/// Docker config, then in the Podman auth file. Sources that cannot be loaded
+/// are skipped. Writes go to rex's credentials file.
+pub fn credential_store() -> librex::auth::ChainedCredentialStore {
+    use librex::auth::{ChainedCredentialStore, DockerConfigStore, FileCredentialStore};
+
+    let mut chain = ChainedCredentialStore::new();
+
+    if let Ok(store) = FileCredentialStore::new(get_credentials_path()) {
+        chain = chain.with_store(store);
+    }
+    if let Ok(store) = DockerConfigStore::new(get_docker_config_path()) {
+        chain = chain.with_store(store);
+    }
+    if let Ok(store) = DockerConfigStore::new(get_podman_auth_path()) {
+        chain = chain.with_store(store);
+    }
+
+    chain
+}
+
+/// Load credentials for a registry
+///
+/// Consults the credential store chain (see [`credential_store`]) and returns
+/// the first credentials found.
+pub fn load_credentials(registry_url: &str) -> Option<librex::Credentials> {
+    use librex::auth::CredentialStore;
+
+    credential_store().get(registry_url).ok().flatten()
+}
+
+/// Load the client configuration for a registry
+///
+/// Looks up the registry entry with a matching URL in the config file and
+/// returns its client configuration. Unknown registries get the settings of
+/// an entry without TLS options.
+pub fn load_client_config(registry_url: &str) -> librex::client::ClientConfig {
+    let url = registry_url.trim_end_matches('/');
+
+    Config::load(&get_config_path())
+        .ok()
+        .and_then(|cfg| {
+            cfg.registries
+                .list
+                .into_iter()
+                .find(|r| r.url.trim_end_matches('/') == url)
+        })
+        .unwrap_or_default()
+        .client_config()
+}
+
 /// Get the default cache directory
 ///
 /// Returns the default cache directory for rex.
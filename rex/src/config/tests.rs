@@ -212,10 +212,11 @@ fn test_registry_entry_creation() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     };
     assert_eq!(entry.name, "local");
     assert_eq!(entry.url, "http://localhost:5000");
-    assert_eq!(entry.dockerhub_compat, false);
+    assert!(!entry.dockerhub_compat);
 }
 
 #[test]
@@ -226,11 +227,13 @@ fn test_config_with_registries_serialization() {
         name: "dockerhub".to_string(),
         url: "https://registry-1.docker.io".to_string(),
         dockerhub_compat: true,
+        tls: Default::default(),
     });
     config.registries.list.push(RegistryEntry {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     });
 
     let toml_str = toml::to_string(&config).unwrap();
@@ -286,16 +289,19 @@ fn test_registry_entry_equality() {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     };
     let entry2 = RegistryEntry {
         name: "local".to_string(),
         url: "http://localhost:5000".to_string(),
         dockerhub_compat: false,
+        tls: Default::default(),
     };
     let entry3 = RegistryEntry {
         name: "remote".to_string(),
         url: "http://example.com".to_string(),
         dockerhub_compat: true,
+        tls: Default::default(),
     };
 
     assert_eq!(entry1, entry2);
@@ -311,7 +317,7 @@ url = "https://zothub.io"
 "#;
     let config: Config = toml::from_str(toml_str).unwrap();
     assert_eq!(config.registries.list.len(), 1);
-    assert_eq!(config.registries.list[0].dockerhub_compat, false);
+    assert!(!config.registries.list[0].dockerhub_compat);
 }
 
 #[test]
@@ -324,7 +330,7 @@ dockerhub_compat = true
 "#;
     let config: Config = toml::from_str(toml_str).unwrap();
     assert_eq!(config.registries.list.len(), 1);
-    assert_eq!(config.registries.list[0].dockerhub_compat, true);
+    assert!(config.registries.list[0].dockerhub_compat);
 }
 
 #[test]
@@ -337,7 +343,7 @@ dockerhub_compat = false
 "#;
     let config: Config = toml::from_str(toml_str).unwrap();
     assert_eq!(config.registries.list.len(), 1);
-    assert_eq!(config.registries.list[0].dockerhub_compat, false);
+    assert!(!config.registries.list[0].dockerhub_compat);
 }
 
 #[test]
@@ -365,13 +371,200 @@ url = "https://ghcr.io"
 
     // Zot: explicitly false
     assert_eq!(config.registries.list[0].name, "zot");
-    assert_eq!(config.registries.list[0].dockerhub_compat, false);
+    assert!(!config.registries.list[0].dockerhub_compat);
 
     // Docker Hub: explicitly true
     assert_eq!(config.registries.list[1].name, "dockerhub");
-    assert_eq!(config.registries.list[1].dockerhub_compat, true);
+    assert!(config.registries.list[1].dockerhub_compat);
 
     // GHCR: defaults to false
     assert_eq!(config.registries.list[2].name, "ghcr");
-    assert_eq!(config.registries.list[2].dockerhub_compat, false);
+    assert!(!config.registries.list[2].dockerhub_compat);
+}
+
+#[test]
+fn test_get_docker_config_path_uses_env_var() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    unsafe {
+        env::set_var("DOCKER_CONFIG", temp_dir.path());
+    }
+    let result = get_docker_config_path();
+    unsafe {
+        env::remove_var("DOCKER_CONFIG");
+    }
+
+    assert_eq!(result, temp_dir.path().join("config.json"));
+}
+
+#[test]
+fn test_get_podman_auth_path_uses_env_var() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let auth_path = temp_dir.path().join("auth.json");
+
+    unsafe {
+        env::set_var("REGISTRY_AUTH_FILE", &auth_path);
+    }
+    let result = get_podman_auth_path();
+    unsafe {
+        env::remove_var("REGISTRY_AUTH_FILE");
+    }
+
+    assert_eq!(result, auth_path);
+}
+
+#[test]
+fn test_load_credentials_falls_back_to_docker_and_podman() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let docker_dir = temp_dir.path().join("docker");
+    fs::create_dir_all(&docker_dir).unwrap();
+    // dXNlcjpwYXNz = base64("user:pass")
+    fs::write(
+        docker_dir.join("config.json"),
+        r#"{"auths":{"docker.example.com":{"auth":"dXNlcjpwYXNz"}}}"#,
+    )
+    .unwrap();
+    let podman_auth = temp_dir.path().join("auth.json");
+    fs::write(
+        &podman_auth,
+        r#"{"auths":{"podman.example.com":{"username":"puser","password":"ppass"}}}"#,
+    )
+    .unwrap();
+
+    unsafe {
+        env::set_var("REX_CREDENTIALS", temp_dir.path().join("credentials.toml"));
+        env::set_var("DOCKER_CONFIG", &docker_dir);
+        env::set_var("REGISTRY_AUTH_FILE", &podman_auth);
+    }
+    let docker_creds = load_credentials("https://docker.example.com");
+    let podman_creds = load_credentials("podman.example.com");
+    let missing = load_credentials("missing.example.com");
+    unsafe {
+        env::remove_var("REX_CREDENTIALS");
+        env::remove_var("DOCKER_CONFIG");
+        env::remove_var("REGISTRY_AUTH_FILE");
+    }
+
+    assert_eq!(
+        docker_creds,
+        Some(librex::Credentials::basic("user", "pass"))
+    );
+    assert_eq!(
+        podman_creds,
+        Some(librex::Credentials::basic("puser", "ppass"))
+    );
+    assert_eq!(missing, None);
+}
+
+#[test]
+fn test_registry_entry_tls_defaults_to_empty() {
+    let toml_str = r#"
+[[registries.list]]
+name = "zot"
+url = "https://zothub.io"
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    assert!(config.registries.list[0].tls.is_empty());
+}
+
+#[test]
+fn test_registry_entry_tls_deserialization() {
+    let toml_str = r#"
+[[registries.list]]
+name = "internal"
+url = "https://zot.internal:5000"
+
+[registries.list.tls]
+ca_cert = "/etc/rex/ca.pem"
+client_cert = "/etc/rex/client.pem"
+client_key = "/etc/rex/client-key.pem"
+insecure_skip_verify = true
+"#;
+    let config: Config = toml::from_str(toml_str).unwrap();
+    let tls = &config.registries.list[0].tls;
+    assert_eq!(tls.ca_cert.as_deref(), Some("/etc/rex/ca.pem"));
+    assert_eq!(tls.client_cert.as_deref(), Some("/etc/rex/client.pem"));
+    assert_eq!(tls.client_key.as_deref(), Some("/etc/rex/client-key.pem"));
+    assert!(tls.insecure_skip_verify);
+}
+
+#[test]
+fn test_registry_entry_tls_serialization_round_trip() {
+    let mut config = Config::default();
+    config.registries.list.push(RegistryEntry {
+        name: "plain".to_string(),
+        url: "https://plain.example.com".to_string(),
+        ..Default::default()
+    });
+    config.registries.list.push(RegistryEntry {
+        name: "internal".to_string(),
+        url: "https://zot.internal:5000".to_string(),
+        tls: RegistryTls {
+            ca_cert: Some("/etc/rex/ca.pem".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    let toml_str = toml::to_string_pretty(&config).unwrap();
+    assert_eq!(toml_str.matches("[registries.list.tls]").count(), 1);
+    assert!(!toml_str.contains("insecure_skip_verify"));
+
+    let parsed: Config = toml::from_str(&toml_str).unwrap();
+    assert_eq!(parsed.registries.list, config.registries.list);
+}
+
+#[test]
+fn test_registry_entry_client_config() {
+    let entry = RegistryEntry {
+        name: "internal".to_string(),
+        url: "https://zot.internal:5000".to_string(),
+        dockerhub_compat: true,
+        tls: RegistryTls {
+            ca_cert: Some("/etc/rex/ca.pem".to_string()),
+            client_cert: Some("/etc/rex/client.pem".to_string()),
+            client_key: Some("/etc/rex/client-key.pem".to_string()),
+            insecure_skip_verify: true,
+        },
+    };
+
+    let client_config = entry.client_config();
+    assert!(client_config.dockerhub_compat);
+    assert_eq!(
+        client_config.ca_certs,
+        vec![PathBuf::from("/etc/rex/ca.pem")]
+    );
+    assert_eq!(
+        client_config.client_cert,
+        Some(PathBuf::from("/etc/rex/client.pem"))
+    );
+    assert_eq!(
+        client_config.client_key,
+        Some(PathBuf::from("/etc/rex/client-key.pem"))
+    );
+    assert!(client_config.insecure_skip_verify);
+    assert_eq!(
+        client_config.retry_policy,
+        librex::client::RetryPolicy::default()
+    );
+}
+
+#[test]
+fn test_registry_entry_client_config_expands_home() {
+    let entry = RegistryEntry {
+        name: "internal".to_string(),
+        url: "https://zot.internal:5000".to_string(),
+        tls: RegistryTls {
+            ca_cert: Some("~/certs/ca.pem".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client_config = entry.client_config();
+    if let Some(home) = dirs::home_dir() {
+        assert_eq!(client_config.ca_certs, vec![home.join("certs/ca.pem")]);
+    }
+    assert!(client_config.client_cert.is_none());
+    assert!(!client_config.insecure_skip_verify);
 }
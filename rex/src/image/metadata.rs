@@ -7,6 +7,7 @@
 use std::path::Path;
 use std::str::FromStr;
 
+use librex::client::ClientConfig;
 use librex::{Credentials, Rex};
 use rayon::prelude::*;
 
@@ -36,6 +37,7 @@ pub struct TagMetadataFetcher {
     registry_url: String,
     cache_dir: std::path::PathBuf,
     credentials: Option<Credentials>,
+    client_config: ClientConfig,
     concurrency: usize,
 }
 
@@ -48,16 +50,20 @@ impl TagMetadataFetcher {
     /// * `cache_dir` - Cache directory for storing fetched data
     /// * `credentials` - Optional credentials for authentication
     /// * `concurrency` - Maximum number of parallel connections
+    ///
+    /// TLS settings are taken from the matching registry entry in the config file.
     pub fn new(
         registry_url: String,
         cache_dir: &Path,
         credentials: Option<Credentials>,
         concurrency: usize,
     ) -> Self {
+        let client_config = crate::config::load_client_config(&registry_url);
         Self {
             registry_url,
             cache_dir: cache_dir.to_path_buf(),
             credentials,
+            client_config,
             concurrency,
         }
     }
@@ -88,7 +94,8 @@ impl TagMetadataFetcher {
         // Build Rex client with cache and credentials
         let mut builder = Rex::builder()
             .registry_url(&self.registry_url)
-            .with_cache(&self.cache_dir);
+            .with_cache(&self.cache_dir)
+            .with_client_config(self.client_config.clone());
 
         if let Some(ref creds) = self.credentials {
             builder = builder.with_credentials(creds.clone());
@@ -118,6 +125,7 @@ impl TagMetadataFetcher {
         let registry_url = self.registry_url.clone();
         let cache_dir = self.cache_dir.clone();
         let credentials = self.credentials.clone();
+        let client_config = &self.client_config;
         let repository = repository.to_string();
 
         // Fetch metadata for all tags in parallel
@@ -130,6 +138,7 @@ impl TagMetadataFetcher {
                         tag,
                         &cache_dir,
                         credentials.clone(),
+                        client_config,
                     )
                 })
                 .collect()
@@ -159,6 +168,7 @@ impl TagMetadataFetcher {
 /// * `tag` - The tag name
 /// * `cache_dir` - Cache directory path
 /// * `credentials` - Optional credentials
+/// * `client_config` - HTTP client configuration (TLS settings)
 ///
 /// # Returns
 ///
@@ -169,11 +179,13 @@ fn fetch_single_tag_metadata(
     tag: &str,
     cache_dir: &Path,
     credentials: Option<Credentials>,
+    client_config: &ClientConfig,
 ) -> Option<TagInfo> {
     // Create per-thread Rex instance with cache and credentials
     let mut builder = Rex::builder()
         .registry_url(registry_url)
-        .with_cache(cache_dir);
+        .with_cache(cache_dir)
+        .with_client_config(client_config.clone());
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -324,6 +336,7 @@ pub struct RepositoryMetadataFetcher {
     registry_url: String,
     cache_dir: std::path::PathBuf,
     credentials: Option<Credentials>,
+    client_config: ClientConfig,
     concurrency: usize,
 }
 
@@ -336,16 +349,20 @@ impl RepositoryMetadataFetcher {
     /// * `cache_dir` - Cache directory for storing fetched data
     /// * `credentials` - Optional credentials for authentication
     /// * `concurrency` - Maximum number of parallel connections
+    ///
+    /// TLS settings are taken from the matching registry entry in the config file.
     pub fn new(
         registry_url: String,
         cache_dir: &Path,
         credentials: Option<Credentials>,
         concurrency: usize,
     ) -> Self {
+        let client_config = crate::config::load_client_config(&registry_url);
         Self {
             registry_url,
             cache_dir: cache_dir.to_path_buf(),
             credentials,
+            client_config,
             concurrency,
         }
     }
@@ -383,7 +400,8 @@ impl RepositoryMetadataFetcher {
         // Build Rex client with cache and credentials
         let mut builder = Rex::builder()
             .registry_url(&self.registry_url)
-            .with_cache(&self.cache_dir);
+            .with_cache(&self.cache_dir)
+            .with_client_config(self.client_config.clone());
 
         if let Some(ref creds) = self.credentials {
             builder = builder.with_credentials(creds.clone());
@@ -413,6 +431,7 @@ impl RepositoryMetadataFetcher {
         let registry_url = self.registry_url.clone();
         let cache_dir = self.cache_dir.clone();
         let credentials = self.credentials.clone();
+        let client_config = &self.client_config;
 
         // Fetch metadata for all repositories in parallel
         let results: Vec<RepositoryItem> = pool.install(|| {
@@ -424,6 +443,7 @@ impl RepositoryMetadataFetcher {
                         repo,
                         &cache_dir,
                         credentials.clone(),
+                        client_config,
                     );
 
                     // Invoke progress callback if provided
@@ -451,6 +471,7 @@ impl RepositoryMetadataFetcher {
 /// * `repository` - The repository name
 /// * `cache_dir` - Cache directory path
 /// * `credentials` - Optional credentials
+/// * `client_config` - HTTP client configuration (TLS settings)
 ///
 /// # Returns
 ///
@@ -460,11 +481,13 @@ fn fetch_single_repository_metadata(
     repository: &str,
     cache_dir: &Path,
     credentials: Option<Credentials>,
+    client_config: &ClientConfig,
 ) -> RepositoryItem {
     // Create per-thread Rex instance with cache and credentials
     let mut builder = Rex::builder()
         .registry_url(registry_url)
-        .with_cache(cache_dir);
+        .with_cache(cache_dir)
+        .with_client_config(client_config.clone());
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
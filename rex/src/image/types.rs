@@ -17,6 +17,9 @@ pub struct RepositoryItem {
     pub tag_count: usize,
 
     /// Total size of the most recent tag (in bytes, formatted for display)
+    ///
+    /// Layers shared with other tags or repositories are included; see
+    /// `rex registry du` for deduplicated usage.
     #[tabled(rename = "SIZE")]
     pub total_size_display: String,
 
@@ -5,6 +5,8 @@ mod config;
 mod context;
 mod format;
 mod image;
+#[cfg(test)]
+mod test_support;
 mod tui;
 
 /// Rex - Container Registry Explorer
@@ -126,6 +128,171 @@ enum ImageCommands {
         #[arg(long)]
         raw_config: bool,
     },
+    /// List the files in an image or one of its layers
+    Files {
+        /// Image reference (name:tag or name@digest)
+        reference: String,
+        /// Only list this layer (numbered from 1, as in `image inspect`)
+        #[arg(long)]
+        layer: Option<usize>,
+        /// Only list entries at or below this path (e.g., /etc)
+        #[arg(long)]
+        path: Option<String>,
+        /// Platform to list (for multi-arch images)
+        #[arg(long)]
+        platform: Option<String>,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// Compare two images: config, layers and optionally files
+    Diff {
+        /// Image to compare from (name:tag or name@digest)
+        reference: String,
+        /// Image to compare to; defaults to REFERENCE, to compare two platforms
+        #[arg(required_unless_present = "other_platform")]
+        other: Option<String>,
+        /// Platform of the first image (and of the second, unless --other-platform)
+        #[arg(long)]
+        platform: Option<String>,
+        /// Platform of the second image
+        #[arg(long)]
+        other_platform: Option<String>,
+        /// Also compare the merged filesystems (reads every layer)
+        #[arg(long)]
+        files: bool,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// Print a file of an image to stdout, without pulling it
+    Cat {
+        /// Image reference (name:tag or name@digest)
+        reference: String,
+        /// Path of the file in the image (e.g., /etc/os-release)
+        path: String,
+        /// Platform to read from (for multi-arch images)
+        #[arg(long)]
+        platform: Option<String>,
+    },
+    /// Extract a file or directory of an image to a local directory
+    Extract {
+        /// Image reference (name:tag or name@digest)
+        reference: String,
+        /// Path in the image (e.g., /etc/nginx)
+        path: String,
+        /// Directory to extract into; entries keep their image path below it
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+        /// Platform to extract from (for multi-arch images)
+        #[arg(long)]
+        platform: Option<String>,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// List signatures, SBOMs and attestations attached to an image
+    Referrers {
+        /// Image reference (name:tag or name@digest)
+        reference: String,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+        /// Only list referrers of this artifact type (e.g., application/spdx+json)
+        #[arg(long)]
+        artifact_type: Option<String>,
+    },
+    /// Show vulnerabilities found by the registry's CVE scanner (Zot)
+    Cves {
+        /// Image reference (name:tag or name@digest)
+        #[arg(required_unless_present = "cve")]
+        reference: Option<String>,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+        /// Only list vulnerabilities at or above this severity (critical, high, medium, low)
+        #[arg(long)]
+        severity: Option<String>,
+        /// Exit with status 1 if a vulnerability at or above this severity is found
+        #[arg(long, value_name = "SEVERITY")]
+        fail_on: Option<String>,
+        /// List the images affected by this CVE instead (e.g., CVE-2024-1234)
+        #[arg(long, conflicts_with_all = ["reference", "severity", "fail_on"])]
+        cve: Option<String>,
+    },
+    /// Verify an image's cosign signatures against a public key
+    Verify {
+        /// Image reference (name:tag or name@digest)
+        reference: String,
+        /// Public key file (e.g., cosign.pub)
+        #[arg(long)]
+        key: std::path::PathBuf,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// List the packages in an image's SBOMs (SPDX, CycloneDX, in-toto)
+    Sbom {
+        /// Image reference (name:tag or name@digest); the repository with --all-tags
+        reference: String,
+        /// Only show packages matching NAME or NAME@VERSION (e.g., log4j-core@2.14)
+        #[arg(long)]
+        package: Option<String>,
+        /// Search every tag of the repository for --package
+        #[arg(long, requires = "package", conflicts_with = "raw")]
+        all_tags: bool,
+        /// Print the SBOM documents as stored
+        #[arg(long)]
+        raw: bool,
+        /// Output format: pretty, json, yaml
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// Add a tag to an existing image without pulling its layers
+    Tag {
+        /// Source image reference (name:tag or name@digest)
+        source: String,
+        /// New tag in the same repository (e.g., prod)
+        new_tag: String,
+    },
+    /// Copy an image to another repository or registry
+    #[command(visible_alias = "cp")]
+    Copy {
+        /// Source image reference (e.g., alpine:3.19 or ghcr.io/org/app:v1)
+        source: String,
+        /// Destination image reference (e.g., mirror/alpine:3.19)
+        destination: String,
+        /// Copy only this platform of a multi-platform image (e.g., linux/amd64)
+        #[arg(long, conflicts_with = "all_platforms")]
+        platform: Option<String>,
+        /// Copy every platform of a multi-platform image
+        #[arg(long)]
+        all_platforms: bool,
+    },
+    /// Save an image to an OCI layout directory or tarball, without a docker daemon
+    Save {
+        /// Image reference (e.g., alpine:3.19 or ghcr.io/org/app:v1)
+        reference: String,
+        /// Layout directory, or tarball path ending in .tar
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+        /// Archive format: oci, docker-archive (a tarball for `docker load`)
+        #[arg(short, long, default_value = "oci")]
+        format: String,
+        /// Save only this platform of a multi-platform image (e.g., linux/amd64)
+        #[arg(long)]
+        platform: Option<String>,
+    },
+    /// Push an image from an OCI layout directory or tarball (the inverse of save)
+    Load {
+        /// Layout directory, or tarball (OCI layout or `docker save` output)
+        source: std::path::PathBuf,
+        /// Image reference to push to (e.g., alpine:3.19 or mirror.local/app:v1)
+        reference: String,
+        /// Image to load when the source holds several (e.g., alpine:3.19)
+        #[arg(long)]
+        name: Option<String>,
+    },
     /// Remove an image or all tags from a repository
     #[command(visible_alias = "rm")]
     Remove {
@@ -222,12 +389,43 @@ enum RegistryCommands {
         /// Password (will prompt if not provided)
         #[arg(short, long)]
         password: Option<String>,
+        /// Bearer token to store instead of a username and password
+        #[arg(long, conflicts_with_all = ["username", "password", "token_stdin"])]
+        token: Option<String>,
+        /// Read the bearer token from stdin
+        #[arg(long, conflicts_with_all = ["username", "password"])]
+        token_stdin: bool,
     },
     /// Logout from a registry
     Logout {
         /// Registry name
         name: String,
     },
+    /// Show storage usage with shared layers counted once
+    Du {
+        /// Registry name (optional, uses default if omitted)
+        name: Option<String>,
+        /// Only list the N repositories with the most unique data
+        #[arg(long)]
+        top: Option<usize>,
+        /// Output format: pretty, json
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
+    /// Report untagged manifests, broken indexes and dangling referrers
+    GcReport {
+        /// Registry name (optional, uses default if omitted)
+        name: Option<String>,
+        /// Delete the reported manifests no tag reaches, by digest
+        #[arg(long)]
+        delete: bool,
+        /// Skip confirmation prompt
+        #[arg(long, requires = "delete")]
+        force: bool,
+        /// Output format: pretty, json
+        #[arg(short, long, default_value = "pretty")]
+        format: String,
+    },
     /// Manage registry cache
     Cache {
         #[command(subcommand)]
@@ -335,17 +533,40 @@ fn main() {
                 name,
                 username,
                 password,
+                token,
+                token_stdin,
             } => {
                 commands::registry::handlers::handle_registry_login(
                     &ctx,
                     &name,
                     username.as_deref(),
                     password.as_deref(),
+                    token.as_deref(),
+                    token_stdin,
                 );
             }
             RegistryCommands::Logout { name } => {
                 commands::registry::handlers::handle_registry_logout(&ctx, &name);
             }
+            RegistryCommands::Du { name, top, format } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::registry::handlers::handle_registry_du(&ctx, name.as_deref(), top, fmt);
+            }
+            RegistryCommands::GcReport {
+                name,
+                delete,
+                force,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::registry::handlers::handle_registry_gc_report(
+                    &ctx,
+                    name.as_deref(),
+                    delete,
+                    force,
+                    fmt,
+                );
+            }
             RegistryCommands::Cache { command } => match command {
                 CacheCommands::Stats { name, format } => {
                     let fmt = format::OutputFormat::from(format.as_str());
@@ -414,6 +635,71 @@ fn main() {
                 let fmt = format::OutputFormat::from(format.as_str());
                 commands::image::handle_image_details(&ctx, reference.as_str(), fmt);
             }
+            ImageCommands::Files {
+                reference,
+                layer,
+                path,
+                platform,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_files(
+                    &ctx,
+                    reference.as_str(),
+                    platform.as_deref(),
+                    layer,
+                    path.as_deref(),
+                    fmt,
+                );
+            }
+            ImageCommands::Diff {
+                reference,
+                other,
+                platform,
+                other_platform,
+                files,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_diff(
+                    &ctx,
+                    reference.as_str(),
+                    other.as_deref(),
+                    platform.as_deref(),
+                    other_platform.as_deref(),
+                    files,
+                    fmt,
+                );
+            }
+            ImageCommands::Cat {
+                reference,
+                path,
+                platform,
+            } => {
+                commands::image::handle_image_cat(
+                    &ctx,
+                    reference.as_str(),
+                    path.as_str(),
+                    platform.as_deref(),
+                );
+            }
+            ImageCommands::Extract {
+                reference,
+                path,
+                output,
+                platform,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_extract(
+                    &ctx,
+                    reference.as_str(),
+                    path.as_str(),
+                    &output,
+                    platform.as_deref(),
+                    fmt,
+                );
+            }
             ImageCommands::Inspect {
                 reference,
                 format,
@@ -431,6 +717,104 @@ fn main() {
                     raw_config,
                 );
             }
+            ImageCommands::Referrers {
+                reference,
+                format,
+                artifact_type,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_referrers(
+                    &ctx,
+                    reference.as_str(),
+                    fmt,
+                    artifact_type.as_deref(),
+                );
+            }
+            ImageCommands::Cves {
+                reference,
+                format,
+                severity,
+                fail_on,
+                cve,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_cves(
+                    &ctx,
+                    reference.as_deref(),
+                    fmt,
+                    severity.as_deref(),
+                    fail_on.as_deref(),
+                    cve.as_deref(),
+                );
+            }
+            ImageCommands::Verify {
+                reference,
+                key,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_verify(&ctx, reference.as_str(), &key, fmt);
+            }
+            ImageCommands::Sbom {
+                reference,
+                package,
+                all_tags,
+                raw,
+                format,
+            } => {
+                let fmt = format::OutputFormat::from(format.as_str());
+                commands::image::handle_image_sbom(
+                    &ctx,
+                    reference.as_str(),
+                    package.as_deref(),
+                    all_tags,
+                    raw,
+                    fmt,
+                );
+            }
+            ImageCommands::Tag { source, new_tag } => {
+                commands::image::handle_image_tag(&ctx, source.as_str(), new_tag.as_str());
+            }
+            ImageCommands::Copy {
+                source,
+                destination,
+                platform,
+                all_platforms,
+            } => {
+                commands::image::handle_image_copy(
+                    &ctx,
+                    source.as_str(),
+                    destination.as_str(),
+                    platform.as_deref(),
+                    all_platforms,
+                );
+            }
+            ImageCommands::Save {
+                reference,
+                output,
+                format,
+                platform,
+            } => {
+                commands::image::handle_image_save(
+                    &ctx,
+                    reference.as_str(),
+                    &output,
+                    format.as_str(),
+                    platform.as_deref(),
+                );
+            }
+            ImageCommands::Load {
+                source,
+                reference,
+                name,
+            } => {
+                commands::image::handle_image_load(
+                    &ctx,
+                    &source,
+                    reference.as_str(),
+                    name.as_deref(),
+                );
+            }
             ImageCommands::Remove {
                 reference,
                 force,
@@ -0,0 +1,156 @@
+//! Helpers shared by the unit tests: gzip layers and configs built in memory,
+//! and images served from mockito.
+
+use std::io::Write;
+
+pub(crate) fn sha256(bytes: &[u8]) -> String {
+    let hash = ring::digest::digest(&ring::digest::SHA256, bytes);
+    let hex: String = hash.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
+/// Build a gzip layer from (path, content) pairs; a path ending in "/" is a
+/// directory and content starting with "->" makes a symbolic link
+pub(crate) fn layer(entries: &[(&str, &str)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(Vec::new());
+    for (path, content) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(0);
+        if path.ends_with('/') {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_mode(0o755);
+            builder
+                .append_data(&mut header, path, std::io::empty())
+                .unwrap();
+        } else if let Some(target) = content.strip_prefix("->") {
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_mode(0o777);
+            builder.append_link(&mut header, path, target).unwrap();
+        } else {
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_mode(0o644);
+            header.set_size(content.len() as u64);
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+    }
+    let tar = builder.into_inner().unwrap();
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(&tar).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// An image config for `architecture`, running as `user` with VERSION set
+pub(crate) fn config(architecture: &str, user: &str, version: &str) -> String {
+    format!(
+        r#"{{"architecture":"{}","os":"linux","rootfs":{{"type":"layers","diff_ids":[]}},
+            "config":{{"User":"{}","Env":["PATH=/usr/bin","VERSION={}"],
+                       "Entrypoint":["/app/server"],"ExposedPorts":{{"8080/tcp":{{}}}}}}}}"#,
+        architecture, user, version
+    )
+}
+
+/// Serve an image manifest referencing `config` and `layers`, without the
+/// blobs; returns the manifest
+pub(crate) fn mock_manifest(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    reference: &str,
+    config: &str,
+    layers: &[Vec<u8>],
+) -> String {
+    let descriptors: Vec<String> = layers
+        .iter()
+        .map(|layer| {
+            format!(
+                r#"{{"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","size":{},"digest":"{}"}}"#,
+                layer.len(),
+                sha256(layer)
+            )
+        })
+        .collect();
+    let manifest = format!(
+        r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json",
+            "config":{{"mediaType":"application/vnd.oci.image.config.v1+json","size":{},"digest":"{}"}},
+            "layers":[{}]}}"#,
+        config.len(),
+        sha256(config.as_bytes()),
+        descriptors.join(",")
+    );
+
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/manifests/{}", repository, reference).as_str(),
+        )
+        .with_status(200)
+        .with_header("Content-Type", "application/vnd.oci.image.manifest.v1+json")
+        .with_header("Docker-Content-Digest", &sha256(manifest.as_bytes()))
+        .with_body(&manifest)
+        .create();
+    manifest
+}
+
+/// Serve a blob of `repository`
+pub(crate) fn mock_blob(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    blob: &[u8],
+) -> mockito::Mock {
+    server
+        .mock(
+            "GET",
+            format!("/v2/{}/blobs/{}", repository, sha256(blob)).as_str(),
+        )
+        .with_status(200)
+        .with_body(blob)
+        .create()
+}
+
+/// Serve an image manifest with its config and layers; returns the manifest
+pub(crate) fn mock_image(
+    server: &mut mockito::ServerGuard,
+    repository: &str,
+    reference: &str,
+    config: &str,
+    layers: &[Vec<u8>],
+) -> String {
+    let manifest = mock_manifest(server, repository, reference, config, layers);
+    mock_blob(server, repository, config.as_bytes());
+    for layer in layers {
+        mock_blob(server, repository, layer);
+    }
+    manifest
+}
+
+/// Serve a two-layer alpine-like image as `v1`; returns the mock of the base
+/// layer's blob too, to check whether it was fetched
+pub(crate) fn alpine_server(repository: &str) -> (mockito::ServerGuard, mockito::Mock) {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/v2/").with_status(200).create();
+
+    let base = layer(&[
+        ("etc/", ""),
+        ("etc/os-release", "->../usr/lib/os-release"),
+        ("etc/motd", "Welcome!\n"),
+        ("usr/lib/", ""),
+        ("usr/lib/os-release", "ID=alpine\nVERSION_ID=3.19.1\n"),
+    ]);
+    let top = layer(&[
+        ("etc/.wh.motd", ""),
+        ("app/", ""),
+        ("app/config.yaml", "port: 8080\n"),
+    ]);
+    mock_manifest(
+        &mut server,
+        repository,
+        "v1",
+        "{}",
+        &[base.clone(), top.clone()],
+    );
+    let base = mock_blob(&mut server, repository, &base);
+    mock_blob(&mut server, repository, &top);
+    (server, base)
+}
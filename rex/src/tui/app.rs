@@ -8,7 +8,6 @@ use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender, channel};
 
 use librex::Credentials;
-use librex::auth::CredentialStore;
 
 use super::Result;
 use super::banner::{BannerManager, BannerType};
@@ -143,16 +142,7 @@ impl App {
         let cache_dir = crate::config::get_registry_cache_dir(&registry)?;
 
         // Load credentials if available
-        let creds_path = crate::config::get_credentials_path();
-        let credentials = if creds_path.exists() {
-            if let Ok(store) = librex::auth::FileCredentialStore::new(creds_path) {
-                store.get(&registry).ok().flatten()
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let credentials = crate::config::load_credentials(&registry);
 
         // Get theme from config
         let theme = match ctx.config.tui.theme.as_str() {
@@ -269,7 +269,7 @@ fn test_handle_tags_loaded_error() {
     let error = Err("Not found".into());
     app.handle_message(Message::TagsLoaded("alpine".to_string(), error));
 
-    assert!(app.tags.get("alpine").is_none());
+    assert!(!app.tags.contains_key("alpine"));
 }
 
 #[test]
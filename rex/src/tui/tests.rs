@@ -4,8 +4,8 @@ use super::*;
 
 #[test]
 fn test_tui_module_compiles() {
-    // Verify the tui module structure compiles correctly
-    assert!(true);
+    // Verify the tui module structure compiles correctly; reaching this
+    // point is the assertion.
 }
 
 // Tests for terminal initialization and cleanup
@@ -56,7 +56,8 @@ pub fn fetch_repositories(
     // Build a Rex client to fetch the repository list
     let mut builder = Rex::builder()
         .registry_url(&registry_url)
-        .with_cache(&cache_dir_owned);
+        .with_cache(&cache_dir_owned)
+        .with_client_config(crate::config::load_client_config(&registry_url));
 
     if let Some(ref creds) = credentials {
         builder = builder.with_credentials(creds.clone());
@@ -225,7 +226,8 @@ pub fn fetch_manifest_and_config(
     // Build Rex client with cache and credentials
     let mut builder = Rex::builder()
         .registry_url(&registry_url)
-        .with_cache(cache_dir);
+        .with_cache(cache_dir)
+        .with_client_config(crate::config::load_client_config(&registry_url));
 
     if let Some(creds) = credentials {
         builder = builder.with_credentials(creds);